@@ -85,20 +85,24 @@ CREATE TABLE
     );
 
 -- insert roles
+-- seeds can run again on a database created by the old compose init script
 insert into
     roles (id, name)
 VALUES
-    (0, 'admin');
+    (0, 'admin')
+ON CONFLICT DO NOTHING;
 
 insert into
     roles (id, name)
 VALUES
-    (1, 'user');
+    (1, 'user')
+ON CONFLICT DO NOTHING;
 
 insert into
     roles (id, name)
 VALUES
-    (2, 'anonymous');
+    (2, 'anonymous')
+ON CONFLICT DO NOTHING;
 
 -- Insert anonymous user into users
 insert into
@@ -120,7 +124,8 @@ values
         'admin@a.a',
         true,
         CURRENT_TIMESTAMP AT TIME ZONE 'Europe/Berlin'
-    );
+    )
+ON CONFLICT DO NOTHING;
 
 -- Insert admin user into users
 insert into
@@ -142,24 +147,38 @@ values
         'admin@a.a',
         true,
         CURRENT_TIMESTAMP AT TIME ZONE 'Europe/Berlin'
-    );
+    )
+ON CONFLICT DO NOTHING;
 
 -- associate admin user and roles
+-- users_roles has no unique pair, so existing ones are skipped by hand
 -- Give admin status
 insert into
     users_roles (rol_id, user_id)
-VALUES
-    (0, 2);
+SELECT
+    0, 2
+WHERE
+    NOT EXISTS (
+        SELECT 1 FROM users_roles WHERE rol_id = 0 AND user_id = 2
+    );
 
 -- Give user status
 insert into
     users_roles (rol_id, user_id)
-VALUES
-    (1, 2);
+SELECT
+    1, 2
+WHERE
+    NOT EXISTS (
+        SELECT 1 FROM users_roles WHERE rol_id = 1 AND user_id = 2
+    );
 
 -- associate anonymous user
 -- Give anonymous status
 insert into
     users_roles (rol_id, user_id)
-VALUES
-    (2, 1);
\ No newline at end of file
+SELECT
+    2, 1
+WHERE
+    NOT EXISTS (
+        SELECT 1 FROM users_roles WHERE rol_id = 2 AND user_id = 1
+    );
\ No newline at end of file
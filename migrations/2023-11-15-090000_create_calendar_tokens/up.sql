@@ -0,0 +1,14 @@
+-- Your SQL goes here
+-- create table calendar_tokens
+CREATE TABLE
+    IF NOT EXISTS calendar_tokens (
+        "id" INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+        "baby_id" INTEGER not null,
+        "user_id" INTEGER not null,
+        "token" uuid not null,
+        "created_at" TIMESTAMP not null,
+        UNIQUE (token),
+        UNIQUE (baby_id, user_id),
+        CONSTRAINT fk_baby_calendar_tokens FOREIGN KEY (baby_id) REFERENCES babies (id) ON DELETE CASCADE ON UPDATE CASCADE,
+        CONSTRAINT fk_user_calendar_tokens FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE ON UPDATE CASCADE
+    );
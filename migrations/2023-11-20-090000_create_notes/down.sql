@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+drop INDEX idx_notes_search;
+drop TABLE notes;
@@ -0,0 +1,19 @@
+-- Your SQL goes here
+-- create table notes
+CREATE TABLE
+    IF NOT EXISTS notes (
+        "id" INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+        "baby_id" INTEGER not null,
+        "category" VARCHAR(32) not null,
+        "date" TIMESTAMP not null,
+        "value" REAL,
+        "unit" VARCHAR(16),
+        "description" TEXT,
+        CONSTRAINT fk_baby_notes FOREIGN KEY (baby_id) REFERENCES babies (id) ON DELETE CASCADE ON UPDATE CASCADE
+    );
+
+-- full text search over category and description
+CREATE INDEX
+    IF NOT EXISTS idx_notes_search ON notes USING GIN (
+        to_tsvector('simple', category || ' ' || coalesce(description, ''))
+    );
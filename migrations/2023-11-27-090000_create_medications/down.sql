@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+drop TABLE medication_doses;
+drop TABLE medications;
@@ -0,0 +1,24 @@
+-- Your SQL goes here
+-- create table medications
+CREATE TABLE
+    IF NOT EXISTS medications (
+        "id" INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+        "baby_id" INTEGER not null,
+        "name" VARCHAR(64) not null,
+        "dose" REAL not null,
+        "unit" VARCHAR(16) not null,
+        "interval_minutes" INTEGER,
+        "daily_time" TIME,
+        "created_at" TIMESTAMP not null,
+        CONSTRAINT fk_baby_medications FOREIGN KEY (baby_id) REFERENCES babies (id) ON DELETE CASCADE ON UPDATE CASCADE
+    );
+
+-- create table medication_doses
+CREATE TABLE
+    IF NOT EXISTS medication_doses (
+        "id" INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+        "medication_id" INTEGER not null,
+        "date" TIMESTAMP not null,
+        "dose" REAL not null,
+        CONSTRAINT fk_medication_medication_doses FOREIGN KEY (medication_id) REFERENCES medications (id) ON DELETE CASCADE ON UPDATE CASCADE
+    );
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+drop TABLE booklet_entries;
+drop TABLE health_schedule;
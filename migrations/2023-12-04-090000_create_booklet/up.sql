@@ -0,0 +1,51 @@
+-- Your SQL goes here
+-- create table health_schedule, reference schedule keyed on age
+CREATE TABLE
+    IF NOT EXISTS health_schedule (
+        "id" INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+        "kind" VARCHAR(16) not null,
+        "name" VARCHAR(64) not null,
+        "age_months" SMALLINT not null,
+        "tolerance_months" SMALLINT not null,
+        UNIQUE (kind, name)
+    );
+
+-- create table booklet_entries, milestones and vaccinations
+CREATE TABLE
+    IF NOT EXISTS booklet_entries (
+        "id" INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+        "baby_id" INTEGER not null,
+        "schedule_id" INTEGER,
+        "kind" VARCHAR(16) not null,
+        "name" VARCHAR(64) not null,
+        "date" DATE not null,
+        "notes" TEXT,
+        CONSTRAINT fk_baby_booklet_entries FOREIGN KEY (baby_id) REFERENCES babies (id) ON DELETE CASCADE ON UPDATE CASCADE,
+        CONSTRAINT fk_schedule_booklet_entries FOREIGN KEY (schedule_id) REFERENCES health_schedule (id) ON DELETE SET NULL ON UPDATE CASCADE
+    );
+
+-- default reference schedule, admins can adapt it to each country
+INSERT INTO
+    health_schedule (kind, name, age_months, tolerance_months)
+VALUES
+    ('milestone', 'Social smile', 2, 2),
+    ('milestone', 'Holds head up', 3, 2),
+    ('milestone', 'Rolls over', 5, 2),
+    ('milestone', 'Sits without support', 7, 2),
+    ('milestone', 'First tooth', 7, 6),
+    ('milestone', 'Crawls', 9, 3),
+    ('milestone', 'First words', 12, 6),
+    ('milestone', 'First steps', 12, 6),
+    ('vaccination', 'Hepatitis B (1st dose)', 0, 1),
+    ('vaccination', 'Hepatitis B (2nd dose)', 2, 1),
+    ('vaccination', 'DTaP, Hib, polio (1st dose)', 2, 1),
+    ('vaccination', 'Pneumococcal (1st dose)', 2, 1),
+    ('vaccination', 'Rotavirus (1st dose)', 2, 1),
+    ('vaccination', 'DTaP, Hib, polio (2nd dose)', 4, 1),
+    ('vaccination', 'Pneumococcal (2nd dose)', 4, 1),
+    ('vaccination', 'Rotavirus (2nd dose)', 4, 1),
+    ('vaccination', 'DTaP, Hib, polio (3rd dose)', 6, 1),
+    ('vaccination', 'Hepatitis B (3rd dose)', 6, 2),
+    ('vaccination', 'MMR (1st dose)', 12, 3),
+    ('vaccination', 'Varicella (1st dose)', 12, 3),
+    ('vaccination', 'Pneumococcal (booster)', 12, 3);
@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE meals
+DROP COLUMN milk_bag_id;
+drop TABLE milk_bags;
+drop TABLE pumping_sessions;
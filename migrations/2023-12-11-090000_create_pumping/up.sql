@@ -0,0 +1,33 @@
+-- Your SQL goes here
+-- create table pumping_sessions
+CREATE TABLE
+    IF NOT EXISTS pumping_sessions (
+        "id" INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+        "baby_id" INTEGER not null,
+        "date" TIMESTAMP not null,
+        "side" VARCHAR(8) not null,
+        "duration" SMALLINT not null,
+        "volume" SMALLINT not null,
+        CONSTRAINT fk_baby_pumping_sessions FOREIGN KEY (baby_id) REFERENCES babies (id) ON DELETE CASCADE ON UPDATE CASCADE
+    );
+
+-- create table milk_bags
+CREATE TABLE
+    IF NOT EXISTS milk_bags (
+        "id" INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+        "baby_id" INTEGER not null,
+        "pumping_id" INTEGER,
+        "volume" SMALLINT not null,
+        "remaining" SMALLINT not null,
+        "pumped_at" TIMESTAMP not null,
+        "location" VARCHAR(16) not null,
+        "expires_at" TIMESTAMP not null,
+        CHECK (remaining >= 0),
+        CONSTRAINT fk_baby_milk_bags FOREIGN KEY (baby_id) REFERENCES babies (id) ON DELETE CASCADE ON UPDATE CASCADE,
+        CONSTRAINT fk_pumping_milk_bags FOREIGN KEY (pumping_id) REFERENCES pumping_sessions (id) ON DELETE SET NULL ON UPDATE CASCADE
+    );
+
+-- bottle feeds can consume from a bag
+ALTER TABLE meals
+ADD COLUMN "milk_bag_id" INTEGER,
+ADD CONSTRAINT fk_milk_bag_meals FOREIGN KEY (milk_bag_id) REFERENCES milk_bags (id) ON DELETE SET NULL ON UPDATE CASCADE;
@@ -0,0 +1,35 @@
+-- Your SQL goes here
+-- create table webhooks
+CREATE TABLE
+    IF NOT EXISTS webhooks (
+        "id" INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+        "baby_id" INTEGER not null,
+        "user_id" INTEGER not null,
+        "url" TEXT not null,
+        "secret" VARCHAR(64) not null,
+        "active" BOOLEAN not null DEFAULT TRUE,
+        "created_at" TIMESTAMP not null,
+        CONSTRAINT fk_baby_webhooks FOREIGN KEY (baby_id) REFERENCES babies (id) ON DELETE CASCADE ON UPDATE CASCADE,
+        CONSTRAINT fk_user_webhooks FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE ON UPDATE CASCADE
+    );
+
+-- create table webhook_deliveries, it works as queue and delivery log
+CREATE TABLE
+    IF NOT EXISTS webhook_deliveries (
+        "id" INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+        "webhook_id" INTEGER not null,
+        "event" VARCHAR(16) not null,
+        "payload" TEXT not null,
+        "status" VARCHAR(16) not null DEFAULT 'pending',
+        "attempts" SMALLINT not null DEFAULT 0,
+        "next_attempt_at" TIMESTAMP not null,
+        "response_code" SMALLINT,
+        "error" TEXT,
+        "created_at" TIMESTAMP not null,
+        "delivered_at" TIMESTAMP,
+        CONSTRAINT fk_webhook_webhook_deliveries FOREIGN KEY (webhook_id) REFERENCES webhooks (id) ON DELETE CASCADE ON UPDATE CASCADE
+    );
+
+CREATE INDEX IF NOT EXISTS webhook_deliveries_pending_idx ON webhook_deliveries (next_attempt_at)
+WHERE
+    status = 'pending';
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+drop TABLE notifications;
+drop TABLE reminder_rules;
@@ -0,0 +1,39 @@
+-- Your SQL goes here
+-- create table reminder_rules
+CREATE TABLE
+    IF NOT EXISTS reminder_rules (
+        "id" INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+        "baby_id" INTEGER not null,
+        "user_id" INTEGER not null,
+        "kind" VARCHAR(16) not null,
+        "threshold_minutes" INTEGER,
+        "medication_id" INTEGER,
+        "due_time" TIME,
+        "channels" VARCHAR(32) not null DEFAULT 'app',
+        "active" BOOLEAN not null DEFAULT TRUE,
+        "last_triggered_at" TIMESTAMP,
+        "created_at" TIMESTAMP not null,
+        CONSTRAINT fk_baby_reminder_rules FOREIGN KEY (baby_id) REFERENCES babies (id) ON DELETE CASCADE ON UPDATE CASCADE,
+        CONSTRAINT fk_user_reminder_rules FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE ON UPDATE CASCADE,
+        CONSTRAINT fk_medication_reminder_rules FOREIGN KEY (medication_id) REFERENCES medications (id) ON DELETE CASCADE ON UPDATE CASCADE
+    );
+
+-- create table notifications
+CREATE TABLE
+    IF NOT EXISTS notifications (
+        "id" INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+        "user_id" INTEGER not null,
+        "baby_id" INTEGER not null,
+        "rule_id" INTEGER,
+        "title" VARCHAR(128) not null,
+        "message" TEXT not null,
+        "created_at" TIMESTAMP not null,
+        "read_at" TIMESTAMP,
+        CONSTRAINT fk_user_notifications FOREIGN KEY (user_id) REFERENCES users (id) ON DELETE CASCADE ON UPDATE CASCADE,
+        CONSTRAINT fk_baby_notifications FOREIGN KEY (baby_id) REFERENCES babies (id) ON DELETE CASCADE ON UPDATE CASCADE,
+        CONSTRAINT fk_rule_notifications FOREIGN KEY (rule_id) REFERENCES reminder_rules (id) ON DELETE SET NULL ON UPDATE CASCADE
+    );
+
+CREATE INDEX IF NOT EXISTS notifications_unread_idx ON notifications (user_id)
+WHERE
+    read_at IS NULL;
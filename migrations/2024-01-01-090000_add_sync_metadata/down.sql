@@ -0,0 +1,10 @@
+-- This file should undo anything in `up.sql`
+drop TABLE deleted_records;
+ALTER TABLE meals
+DROP CONSTRAINT meals_client_id_unique,
+DROP COLUMN client_id,
+DROP COLUMN updated_at;
+ALTER TABLE dreams
+DROP CONSTRAINT dreams_client_id_unique,
+DROP COLUMN client_id,
+DROP COLUMN updated_at;
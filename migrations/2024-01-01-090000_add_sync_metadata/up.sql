@@ -0,0 +1,29 @@
+-- Your SQL goes here
+-- records created offline carry an id generated by the client
+ALTER TABLE meals
+ADD COLUMN "client_id" UUID,
+ADD COLUMN "updated_at" TIMESTAMP not null DEFAULT CURRENT_TIMESTAMP,
+ADD CONSTRAINT meals_client_id_unique UNIQUE (baby_id, client_id);
+
+ALTER TABLE dreams
+ADD COLUMN "client_id" UUID,
+ADD COLUMN "updated_at" TIMESTAMP not null DEFAULT CURRENT_TIMESTAMP,
+ADD CONSTRAINT dreams_client_id_unique UNIQUE (baby_id, client_id);
+
+CREATE INDEX IF NOT EXISTS meals_updated_at_idx ON meals (baby_id, updated_at);
+
+CREATE INDEX IF NOT EXISTS dreams_updated_at_idx ON dreams (baby_id, updated_at);
+
+-- create table deleted_records, tombstones sent to syncing clients
+CREATE TABLE
+    IF NOT EXISTS deleted_records (
+        "id" INTEGER PRIMARY KEY GENERATED ALWAYS AS IDENTITY,
+        "baby_id" INTEGER not null,
+        "record_type" VARCHAR(16) not null,
+        "record_id" INTEGER not null,
+        "client_id" UUID,
+        "deleted_at" TIMESTAMP not null,
+        CONSTRAINT fk_baby_deleted_records FOREIGN KEY (baby_id) REFERENCES babies (id) ON DELETE CASCADE ON UPDATE CASCADE
+    );
+
+CREATE INDEX IF NOT EXISTS deleted_records_baby_idx ON deleted_records (baby_id, deleted_at);
@@ -0,0 +1,9 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE babies
+DROP COLUMN version;
+ALTER TABLE meals
+DROP COLUMN version;
+ALTER TABLE dreams
+DROP COLUMN version;
+ALTER TABLE weights
+DROP COLUMN version;
@@ -0,0 +1,13 @@
+-- Your SQL goes here
+-- version is increased on every update, it is sent as ETag
+ALTER TABLE babies
+ADD COLUMN "version" INTEGER not null DEFAULT 1;
+
+ALTER TABLE meals
+ADD COLUMN "version" INTEGER not null DEFAULT 1;
+
+ALTER TABLE dreams
+ADD COLUMN "version" INTEGER not null DEFAULT 1;
+
+ALTER TABLE weights
+ADD COLUMN "version" INTEGER not null DEFAULT 1;
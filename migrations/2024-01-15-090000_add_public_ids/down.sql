@@ -0,0 +1,34 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE deleted_records
+DROP COLUMN record_id;
+ALTER TABLE deleted_records
+ADD COLUMN "record_id" INTEGER not null DEFAULT 0;
+ALTER TABLE deleted_records
+ALTER COLUMN record_id DROP DEFAULT;
+
+ALTER TABLE notifications
+DROP COLUMN public_id;
+ALTER TABLE reminder_rules
+DROP COLUMN public_id;
+ALTER TABLE webhook_deliveries
+DROP COLUMN public_id;
+ALTER TABLE webhooks
+DROP COLUMN public_id;
+ALTER TABLE milk_bags
+DROP COLUMN public_id;
+ALTER TABLE pumping_sessions
+DROP COLUMN public_id;
+ALTER TABLE booklet_entries
+DROP COLUMN public_id;
+ALTER TABLE medication_doses
+DROP COLUMN public_id;
+ALTER TABLE medications
+DROP COLUMN public_id;
+ALTER TABLE notes
+DROP COLUMN public_id;
+ALTER TABLE weights
+DROP COLUMN public_id;
+ALTER TABLE dreams
+DROP COLUMN public_id;
+ALTER TABLE meals
+DROP COLUMN public_id;
@@ -0,0 +1,62 @@
+-- Your SQL goes here
+-- records are addressed by public_id, integer ids never leave the database
+-- references between records keep their integer foreign keys
+ALTER TABLE meals
+ADD COLUMN "public_id" UUID not null DEFAULT gen_random_uuid(),
+ADD CONSTRAINT meals_public_id_unique UNIQUE (public_id);
+
+ALTER TABLE dreams
+ADD COLUMN "public_id" UUID not null DEFAULT gen_random_uuid(),
+ADD CONSTRAINT dreams_public_id_unique UNIQUE (public_id);
+
+ALTER TABLE weights
+ADD COLUMN "public_id" UUID not null DEFAULT gen_random_uuid(),
+ADD CONSTRAINT weights_public_id_unique UNIQUE (public_id);
+
+ALTER TABLE notes
+ADD COLUMN "public_id" UUID not null DEFAULT gen_random_uuid(),
+ADD CONSTRAINT notes_public_id_unique UNIQUE (public_id);
+
+ALTER TABLE medications
+ADD COLUMN "public_id" UUID not null DEFAULT gen_random_uuid(),
+ADD CONSTRAINT medications_public_id_unique UNIQUE (public_id);
+
+ALTER TABLE medication_doses
+ADD COLUMN "public_id" UUID not null DEFAULT gen_random_uuid(),
+ADD CONSTRAINT medication_doses_public_id_unique UNIQUE (public_id);
+
+ALTER TABLE booklet_entries
+ADD COLUMN "public_id" UUID not null DEFAULT gen_random_uuid(),
+ADD CONSTRAINT booklet_entries_public_id_unique UNIQUE (public_id);
+
+ALTER TABLE pumping_sessions
+ADD COLUMN "public_id" UUID not null DEFAULT gen_random_uuid(),
+ADD CONSTRAINT pumping_sessions_public_id_unique UNIQUE (public_id);
+
+ALTER TABLE milk_bags
+ADD COLUMN "public_id" UUID not null DEFAULT gen_random_uuid(),
+ADD CONSTRAINT milk_bags_public_id_unique UNIQUE (public_id);
+
+ALTER TABLE webhooks
+ADD COLUMN "public_id" UUID not null DEFAULT gen_random_uuid(),
+ADD CONSTRAINT webhooks_public_id_unique UNIQUE (public_id);
+
+ALTER TABLE webhook_deliveries
+ADD COLUMN "public_id" UUID not null DEFAULT gen_random_uuid(),
+ADD CONSTRAINT webhook_deliveries_public_id_unique UNIQUE (public_id);
+
+ALTER TABLE reminder_rules
+ADD COLUMN "public_id" UUID not null DEFAULT gen_random_uuid(),
+ADD CONSTRAINT reminder_rules_public_id_unique UNIQUE (public_id);
+
+ALTER TABLE notifications
+ADD COLUMN "public_id" UUID not null DEFAULT gen_random_uuid(),
+ADD CONSTRAINT notifications_public_id_unique UNIQUE (public_id);
+
+-- deleted records never had a public id, old tombstones get a random one and are still matched by client_id
+ALTER TABLE deleted_records
+DROP COLUMN record_id;
+ALTER TABLE deleted_records
+ADD COLUMN "record_id" UUID not null DEFAULT gen_random_uuid();
+ALTER TABLE deleted_records
+ALTER COLUMN record_id DROP DEFAULT;
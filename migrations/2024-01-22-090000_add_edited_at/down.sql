@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE meals
+DROP COLUMN "edited_at";
+
+ALTER TABLE dreams
+DROP COLUMN "edited_at";
@@ -0,0 +1,18 @@
+-- Your SQL goes here
+-- edited_at is when the change was made, on the client for offline changes,
+-- updated_at stays the time it reached the server and drives sync cursors
+ALTER TABLE meals
+ADD COLUMN "edited_at" TIMESTAMP;
+
+UPDATE meals SET edited_at = updated_at;
+
+ALTER TABLE meals
+ALTER COLUMN "edited_at" SET NOT NULL;
+
+ALTER TABLE dreams
+ADD COLUMN "edited_at" TIMESTAMP;
+
+UPDATE dreams SET edited_at = updated_at;
+
+ALTER TABLE dreams
+ALTER COLUMN "edited_at" SET NOT NULL;
@@ -1,34 +1,67 @@
-use axum::{extract::MatchedPath, Router};
+use axum::{extract::MatchedPath, middleware, Extension, Router};
 
 use axum_session::{SessionLayer, SessionRedisPool, SessionStore};
-use axum_session_auth::AuthSessionLayer;
+use axum_session_auth::{AuthSession, AuthSessionLayer};
 use controller::{baby_controller::route_baby, user_controller::route_user};
 use hyper::Request;
 use redis::Client;
-use tower_http::trace::TraceLayer;
+use tower_http::{
+    request_id::{MakeRequestUuid, PropagateRequestIdLayer, RequestId, SetRequestIdLayer},
+    trace::TraceLayer,
+};
 use tracing::{error, info_span};
 
 use crate::{
     configuration::{
         app_settings::{auth_config, private_cookies_session, session_config},
-        settings::Setting,
+        config::config,
     },
     connection::connection_redis::poll,
-    controller::{self, admin_controller::route_admin},
+    controller::{
+        self, admin_controller::route_admin, calendar_controller::route_calendar_feed,
+        health_controller::route_health, metrics_controller::route_metrics,
+        notification_controller::route_notification, openapi_controller::route_openapi,
+        route_table::Routes,
+    },
     model::session_model::CurrentUser,
-    utils::app::error_404,
+    utils::{
+        app::error_404,
+        idempotency::idempotent_request,
+        metrics::track_metrics,
+        version::{deprecated_version, versioned_errors, ApiVersion},
+    },
 };
 
 /// Create app object with routes and layers.
 /// Session layer must be on top of session auth layer.
 pub fn create_router() -> Router {
-    Router::new().nest(
-        "/api",
-        Router::new()
-            .merge(route_user())
-            .merge(route_baby())
-            .merge(route_admin()),
-    )
+    route_versions().into_router()
+}
+
+/// API routes under each version prefix, the table lists what they serve.
+pub fn route_versions() -> Routes {
+    Routes::new()
+        .nest(ApiVersion::V1.prefix(), route_api(ApiVersion::V1))
+        .nest(ApiVersion::V2.prefix(), route_api(ApiVersion::V2))
+        // Unversioned paths are v1, apps released before versioning still call them.
+        .nest("/api", route_api(ApiVersion::V1))
+}
+
+/// Every API route, v2 sends timestamps and durations as numbers and RFC 3339
+/// and requires `If-Match` to change versioned records.
+fn route_api(version: ApiVersion) -> Routes {
+    let routes = Routes::new()
+        .merge(route_user())
+        .merge(route_baby(version))
+        .merge(route_admin())
+        .merge(route_calendar_feed())
+        .merge(route_notification(version))
+        // Handlers and extractors like `IfMatch` can tell which version was called.
+        .layer(Extension(version));
+    match version {
+        ApiVersion::V1 => routes.layer(middleware::from_fn(deprecated_version)),
+        ApiVersion::V2 => routes,
+    }
 }
 
 pub async fn expand_router_layer(app: Router) -> Router {
@@ -43,25 +76,54 @@ pub async fn expand_router_layer(app: Router) -> Router {
                 .extensions()
                 .get::<MatchedPath>()
                 .map(MatchedPath::as_str);
+            let request_id = request
+                .extensions()
+                .get::<RequestId>()
+                .and_then(|id| id.header_value().to_str().ok());
+            // Session layers are outside, the user is already loaded.
+            let user_id = request
+                .extensions()
+                .get::<AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>>()
+                .and_then(|auth| auth.current_user.as_ref())
+                .filter(|user| !user.anonymous())
+                .map(CurrentUser::id);
 
             info_span!(
                 "http_request",
                 method = ?request.method(),
                 matched_path,
-                some_other_field = tracing::field::Empty,
+                request_id,
+                user_id,
+                // Recorded once baby permissions are checked.
+                baby_id = tracing::field::Empty,
             )
         }),
     )
+    // Inside session layers, it needs the current user.
+    .layer(middleware::from_fn(idempotent_request))
     .layer(
         AuthSessionLayer::<CurrentUser, i64, SessionRedisPool, redis::Client>::new(Some(poll))
             .with_config(auth_config()),
     )
     .layer(SessionLayer::new(session_store))
+    // Generate an `X-Request-Id` when the client didn't send one, and echo it back.
+    .layer(PropagateRequestIdLayer::x_request_id())
+    .layer(SetRequestIdLayer::x_request_id(MakeRequestUuid))
     .fallback(error_404)
+    // After the fallback so unknown routes are counted as `unmatched`, latency includes loading
+    // the session.
+    .layer(middleware::from_fn(track_metrics))
+    // v1 error bodies, outside the layers above so their errors are converted too.
+    .layer(middleware::from_fn(versioned_errors))
+    // Merged after the layers, probes must answer even when sessions can't be loaded.
+    .merge(route_health())
+    .merge(route_metrics())
+    // Public, the document only describes the API.
+    .merge(route_openapi())
 }
 
 async fn create_session_store() -> (Client, SessionStore<SessionRedisPool>) {
-    let config = if Setting::Branch.get().eq("local") {
+    let config = if config().branch.eq("local") {
         session_config()
     } else {
         private_cookies_session()
@@ -0,0 +1,218 @@
+// Copyright 2023 Jaime Alvarez Fernandez
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+
+//     http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::env;
+
+use clap::{Parser, Subcommand, ValueEnum};
+use nighty_night::{
+    configuration::config::init_config,
+    data::user_dto::NewUserDto,
+    repository::baby_repository::select_baby_from_unique_id,
+    response::{
+        error::ApiError,
+        response::{MsgResponse, RecordResponse},
+    },
+    service::{
+        admin_service::get_baby_export_service,
+        role_service::seed_roles_service,
+        user_service::{delete_old_users_service, patch_password_service, post_new_admin_service},
+    },
+    set_environment,
+    utils::app::refresh_anonymous_user,
+};
+use serde::Serialize;
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+const PASSWORD_VARIABLE: &str = "ADMIN_PASSWORD";
+
+/// Operational tasks, run against the same database and Redis as the server.
+#[derive(Parser)]
+#[command(name = "nighty_night-admin", version)]
+struct Cli {
+    /// Output for humans or one JSON document.
+    #[arg(long, value_enum, default_value_t = Format::Human, global = true)]
+    format: Format,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Human,
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a user with admin and user roles, password read as `reset-password` does.
+    CreateAdmin {
+        #[arg(long)]
+        username: String,
+        #[arg(long)]
+        email: Option<String>,
+    },
+    /// Set a new password for an existing user, read from `ADMIN_PASSWORD` or prompted for.
+    ResetPassword {
+        #[arg(long)]
+        username: String,
+    },
+    /// Insert the admin, user and anonymous roles if missing.
+    SeedRoles,
+    /// Delete inactive users not updated for `limits.delete_account_days`.
+    PurgeUsers,
+    /// Export a baby with all its meals, dreams and weights, records only in JSON output.
+    ExportBaby {
+        /// Public baby id.
+        baby_id: Uuid,
+    },
+    /// Read the anonymous user from the database and cache it in Redis again.
+    CacheAnonymous,
+}
+
+/// What a command produced, `data` is only printed as JSON.
+enum Output {
+    Message(String),
+    Record { summary: String, data: Value },
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+    set_environment();
+    if let Err(error) = init_config() {
+        eprintln!("{error}");
+        std::process::exit(1);
+    }
+    match run(cli.command).await {
+        Ok(output) => print_output(output, cli.format),
+        Err(error) => {
+            let (status, message) = error.get_error();
+            match cli.format {
+                Format::Human => eprintln!("Error: {message}"),
+                Format::Json => eprintln!(
+                    "{}",
+                    json!({"error": {"status": status.as_u16(), "detail": message}})
+                ),
+            }
+            std::process::exit(1);
+        }
+    }
+}
+
+async fn run(command: Command) -> Result<Output, ApiError> {
+    match command {
+        Command::CreateAdmin { username, email } => {
+            let new_user = NewUserDto {
+                username,
+                password: read_password(),
+                email,
+                name: None,
+                surname: None,
+            };
+            let response = post_new_admin_service(new_user).await?;
+            let summary = format!(
+                "Admin user {} created with id {}.",
+                response.data.attributes.username, response.data.id
+            );
+            Ok(record(&summary, response))
+        }
+        Command::ResetPassword { username } => {
+            let password = read_password();
+            Ok(message(patch_password_service(&username, &password).await?))
+        }
+        Command::SeedRoles => Ok(message(seed_roles_service().await?)),
+        Command::PurgeUsers => Ok(message(delete_old_users_service().await?)),
+        Command::ExportBaby { baby_id } => {
+            let id = select_baby_from_unique_id(baby_id)?;
+            let response = get_baby_export_service(id).await?;
+            let summary = format!(
+                "{}: {} meals, {} dreams and {} weights.",
+                response.data.baby.attributes.name,
+                response.data.meals.len(),
+                response.data.dreams.len(),
+                response.data.weights.len()
+            );
+            Ok(record(&summary, response))
+        }
+        Command::CacheAnonymous => {
+            refresh_anonymous_user().await?;
+            Ok(Output::Message(String::from("Anonymous user cached.")))
+        }
+    }
+}
+
+/// Never an argument, other users can read those from the process list.
+fn read_password() -> String {
+    if let Ok(password) = env::var(PASSWORD_VARIABLE) {
+        return password;
+    }
+    match rpassword::prompt_password("Password: ") {
+        Ok(password) => password,
+        Err(error) => {
+            eprintln!("Error: {error}");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn message(response: MsgResponse) -> Output {
+    Output::Message(response.get_response().1)
+}
+
+fn record<T: Serialize>(summary: &str, response: RecordResponse<T>) -> Output {
+    Output::Record {
+        summary: summary.to_string(),
+        data: json!(response.data),
+    }
+}
+
+fn print_output(output: Output, format: Format) {
+    match (output, format) {
+        (Output::Message(message), Format::Human) => println!("{message}"),
+        (Output::Message(message), Format::Json) => println!("{}", json!({ "message": message })),
+        (Output::Record { summary, .. }, Format::Human) => println!("{summary}"),
+        (Output::Record { data, .. }, Format::Json) => println!("{}", json!({ "data": data })),
+    }
+}
+
+#[cfg(test)]
+mod test_admin_cli {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn test_cli_definition() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn test_parse_commands() {
+        let cli = Cli::try_parse_from([
+            "nighty_night-admin",
+            "reset-password",
+            "--username",
+            "admin",
+            "--format",
+            "json",
+        ])
+        .unwrap();
+        assert!(matches!(cli.format, Format::Json));
+        assert!(matches!(cli.command, Command::ResetPassword { .. }));
+        assert!(Cli::try_parse_from(["nighty_night-admin", "export-baby", "1"]).is_err());
+        let password = ["create-admin", "--username", "ops", "--password", "secret"];
+        assert!(Cli::try_parse_from(["nighty_night-admin"].into_iter().chain(password)).is_err());
+    }
+}
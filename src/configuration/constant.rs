@@ -1,9 +1,6 @@
-const LAST_DAYS_DEFAULT: u32 = 7;
-const WEIGHT_LAST_DAYS_DEFAULT: u32 = 30;
-const DEFAULT_RECORDS_PER_PAGE: u32 = 100;
-const MAX_PAGINATION_THRESHOLD: u32 = 365;
-const DELETE_ACCOUNT: u32 = 180;
-const DEFAULT_ANONYMOUS_ID: u32 = 1;
+use super::config::config;
+
+/// Values are read from `[limits]` in the configuration.
 pub enum GlobalCte {
     /// How many days, by default, get from the db.
     LastDaysCte,
@@ -12,19 +9,66 @@ pub enum GlobalCte {
     /// Max records allowed in pagination.
     MaxPaginationThreshold,
     WeightLastDaysDefault,
+    /// How many days, by default, go into a report.
+    ReportLastDaysDefault,
+    /// How many days, by default, go into a calendar feed.
+    CalendarLastDaysDefault,
+    /// Minutes after a scheduled dose before it is considered overdue.
+    MedicationOverdueMinutes,
+    /// Months ahead a scheduled milestone or vaccination is reported as upcoming.
+    BookletUpcomingMonths,
+    /// Hours breast milk keeps at room temperature, in the fridge and in the freezer.
+    MilkRoomHours,
+    MilkFridgeHours,
+    MilkFreezerHours,
+    /// Hours before expiry a milk bag is flagged.
+    MilkExpiryWarningHours,
+    /// Attempts before a webhook delivery is given up.
+    WebhookMaxAttempts,
+    /// First retry delay, doubled on every failed attempt.
+    WebhookRetrySeconds,
+    /// Seconds between webhook queue polls.
+    WebhookPollSeconds,
+    /// Seconds between reminder rules evaluations.
+    ReminderPollSeconds,
+    /// Seconds between checks that an event stream's user can still see the baby.
+    EventsAccessCheckSeconds,
+    /// Seconds a response is kept to be replayed for the same idempotency key.
+    IdempotencyKeySeconds,
+    /// Seconds a key stays locked while its first request is running.
+    IdempotencyLockSeconds,
+    /// Milliseconds a dependency has to answer a readiness check.
+    HealthCheckTimeoutMillis,
     DeleteAccount,
-    DefaultAnonymousID
+    DefaultAnonymousID,
 }
 
 impl GlobalCte {
     pub fn get(&self) -> u32 {
+        let limits = &config().limits;
         match self {
-            GlobalCte::LastDaysCte => LAST_DAYS_DEFAULT,
-            GlobalCte::RecordsPerPage => DEFAULT_RECORDS_PER_PAGE,
-            GlobalCte::WeightLastDaysDefault => WEIGHT_LAST_DAYS_DEFAULT,
-            GlobalCte::MaxPaginationThreshold => MAX_PAGINATION_THRESHOLD,
-            GlobalCte::DeleteAccount => DELETE_ACCOUNT,
-            GlobalCte::DefaultAnonymousID => DEFAULT_ANONYMOUS_ID,
+            GlobalCte::LastDaysCte => limits.last_days,
+            GlobalCte::RecordsPerPage => limits.records_per_page,
+            GlobalCte::WeightLastDaysDefault => limits.weight_last_days,
+            GlobalCte::ReportLastDaysDefault => limits.report_last_days,
+            GlobalCte::CalendarLastDaysDefault => limits.calendar_last_days,
+            GlobalCte::MedicationOverdueMinutes => limits.medication_overdue_minutes,
+            GlobalCte::BookletUpcomingMonths => limits.booklet_upcoming_months,
+            GlobalCte::MilkRoomHours => limits.milk_room_hours,
+            GlobalCte::MilkFridgeHours => limits.milk_fridge_hours,
+            GlobalCte::MilkFreezerHours => limits.milk_freezer_hours,
+            GlobalCte::MilkExpiryWarningHours => limits.milk_expiry_warning_hours,
+            GlobalCte::WebhookMaxAttempts => limits.webhook_max_attempts,
+            GlobalCte::WebhookRetrySeconds => limits.webhook_retry_seconds,
+            GlobalCte::WebhookPollSeconds => limits.webhook_poll_seconds,
+            GlobalCte::ReminderPollSeconds => limits.reminder_poll_seconds,
+            GlobalCte::EventsAccessCheckSeconds => limits.events_access_check_seconds,
+            GlobalCte::IdempotencyKeySeconds => limits.idempotency_key_seconds,
+            GlobalCte::IdempotencyLockSeconds => limits.idempotency_lock_seconds,
+            GlobalCte::HealthCheckTimeoutMillis => limits.health_check_timeout_millis,
+            GlobalCte::MaxPaginationThreshold => limits.max_pagination_threshold,
+            GlobalCte::DeleteAccount => limits.delete_account_days,
+            GlobalCte::DefaultAnonymousID => limits.default_anonymous_id,
         }
     }
 }
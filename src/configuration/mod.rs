@@ -1,3 +1,3 @@
-pub mod settings;
+pub mod config;
 pub mod constant;
 pub mod app_settings;
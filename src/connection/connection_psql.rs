@@ -1,10 +1,25 @@
 use diesel::pg::PgConnection;
 use diesel::prelude::*;
 
-use crate::configuration::settings::Setting;
+use crate::configuration::config::config;
 
 pub fn establish_connection() -> PgConnection {
-    let database_url = Setting::DatabaseUrl.get();
+    let database_url = config().database.url();
     PgConnection::establish(&database_url)
         .unwrap_or_else(|_| panic!("Error connecting to {}", database_url))
-}
\ No newline at end of file
+}
+
+/// Connection errors are returned, for callers that must keep going without a database.
+pub fn try_establish_connection() -> ConnectionResult<PgConnection> {
+    let database_url = config().database.url();
+    PgConnection::establish(&database_url)
+}
+
+/// Like `try_establish_connection`, but gives up after `connect_timeout` seconds.
+pub fn try_establish_connection_within(connect_timeout: u64) -> ConnectionResult<PgConnection> {
+    let database_url = format!(
+        "{}?connect_timeout={connect_timeout}",
+        config().database.url()
+    );
+    PgConnection::establish(&database_url)
+}
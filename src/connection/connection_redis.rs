@@ -1,8 +1,8 @@
 use redis::Client;
 
-use crate::configuration::settings::Setting;
+use crate::configuration::config::config;
 
 pub async fn poll() -> Client {
-    let address = Setting::RedisHost.get();
+    let address = config().redis.url();
     Client::open(address).expect("Can't connect to redis")
 }
@@ -1,16 +1,24 @@
-use axum::{extract::Query, response::IntoResponse, routing::get, Json, Router};
+use axum::{extract::Query, response::IntoResponse, Json};
 use axum_session::SessionRedisPool;
 use axum_session_auth::AuthSession;
 
 use crate::{
     data::{
+        booklet_dto::InputScheduleItemDto,
         query_dto::{IdDto, Pagination},
         role_dto::UpdateRole,
     },
     model::session_model::CurrentUser,
+    response::response::{
+        AdminBabyPage, AdminUserPage, BabyRecord, MessageBody, RolesRecord, ScheduleItemRecord,
+        ScheduleRecord, StatsRecord,
+    },
     service::{
         admin_service::{get_roles_service, get_stats_of_tables_service},
         baby_service::{get_all_babies_service, get_baby_by_id_service},
+        booklet_service::{
+            delete_schedule_item_service, get_schedule_service, post_schedule_item_service,
+        },
         role_service::{
             add_rol_to_user_service, delete_rol_to_user_service, get_role_by_name_service,
         },
@@ -23,11 +31,14 @@ use crate::{
     },
 };
 
-pub(crate) fn route_admin() -> Router {
-    let routes: Router = Router::new()
+use super::openapi_controller::PageQuery;
+use super::route_table::{get, Routes};
+
+pub(crate) fn route_admin() -> Routes {
+    let routes: Routes = Routes::new()
         .nest(
             "/baby",
-            Router::new()
+            Routes::new()
                 .route("/", get(get_all_babies))
                 .route("/baby_id", get(get_baby_by_id)),
         )
@@ -38,13 +49,26 @@ pub(crate) fn route_admin() -> Router {
                 .patch(patch_activate_user),
         )
         .route("/stats", get(get_stats_of_tables))
+        .route(
+            "/schedule",
+            get(get_schedule)
+                .post(post_schedule_item)
+                .delete(delete_schedule_item),
+        )
         .route(
             "/roles",
             get(get_roles).put(put_user_role).delete(delete_user_role),
         );
-    Router::new().nest("/admin", routes)
+    Routes::new().nest("/admin", routes)
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/admin/baby",
+    tag = "admin",
+    params(PageQuery),
+    responses((status = 200, description = "Every baby.", body = AdminBabyPage))
+)]
 async fn get_all_babies(
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
     page: Option<Query<Pagination>>,
@@ -54,6 +78,13 @@ async fn get_all_babies(
     get_all_babies_service(pagination).await
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/admin/user",
+    tag = "admin",
+    params(PageQuery),
+    responses((status = 200, description = "Every user.", body = AdminUserPage))
+)]
 async fn get_all_users(
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
     page: Option<Query<Pagination>>,
@@ -63,6 +94,16 @@ async fn get_all_users(
     get_all_users_service(pagination).await
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/admin/user",
+    operation_id = "delete_admin_user",
+    tag = "admin",
+    params(
+        ("entry" = Option<i32>, Query, description = "User id, expired inactive users if missing")
+    ),
+    responses((status = 200, description = "User or inactive users deleted.", body = MessageBody))
+)]
 async fn delete_user(
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
     user_id: Option<Query<IdDto>>,
@@ -75,6 +116,13 @@ async fn delete_user(
     }
 }
 
+#[utoipa::path(
+    patch,
+    path = "/api/admin/user",
+    tag = "admin",
+    params(IdDto),
+    responses((status = 200, description = "User activated.", body = MessageBody))
+)]
 async fn patch_activate_user(
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
     user_id: Query<IdDto>,
@@ -83,6 +131,20 @@ async fn patch_activate_user(
     delete_active_user_service(user_id.id(), true).await
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/admin/baby/baby_id",
+    tag = "admin",
+    params(IdDto),
+    responses(
+        (
+            status = 200,
+            description = "Baby found.",
+            body = BabyRecord,
+            headers(("ETag" = String, description = "Record version"))
+        )
+    )
+)]
 async fn get_baby_by_id(
     baby_id: Query<IdDto>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
@@ -91,6 +153,12 @@ async fn get_baby_by_id(
     get_baby_by_id_service(baby_id.id()).await
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/admin/stats",
+    tag = "admin",
+    responses((status = 200, description = "Rows per table.", body = StatsRecord))
+)]
 async fn get_stats_of_tables(
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
 ) -> impl IntoResponse {
@@ -98,6 +166,14 @@ async fn get_stats_of_tables(
     get_stats_of_tables_service().await
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/admin/roles",
+    tag = "admin",
+    responses(
+        (status = 200, description = "Roles and how many users have them.", body = RolesRecord)
+    )
+)]
 async fn get_roles(
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
 ) -> impl IntoResponse {
@@ -105,6 +181,13 @@ async fn get_roles(
     get_roles_service().await
 }
 
+#[utoipa::path(
+    put,
+    path = "/api/admin/roles",
+    tag = "admin",
+    request_body = UpdateRole,
+    responses((status = 200, description = "Role granted.", body = MessageBody))
+)]
 async fn put_user_role(
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
     Json(user_role): Json<UpdateRole>,
@@ -115,6 +198,13 @@ async fn put_user_role(
     add_rol_to_user_service(user, rol).await
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/admin/roles",
+    tag = "admin",
+    request_body = UpdateRole,
+    responses((status = 200, description = "Role revoked.", body = MessageBody))
+)]
 async fn delete_user_role(
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
     Json(user_role): Json<UpdateRole>,
@@ -124,3 +214,52 @@ async fn delete_user_role(
     let user = get_user_id_from_username(&user_role.username).await?;
     delete_rol_to_user_service(user, rol).await
 }
+
+#[utoipa::path(
+    get,
+    path = "/api/admin/schedule",
+    tag = "admin",
+    responses(
+        (
+            status = 200,
+            description = "Reference schedule of milestones and vaccinations.",
+            body = ScheduleRecord
+        )
+    )
+)]
+async fn get_schedule(
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse {
+    current_user_is_admin(auth)?;
+    get_schedule_service().await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/admin/schedule",
+    tag = "admin",
+    request_body = InputScheduleItemDto,
+    responses((status = 201, description = "Schedule item created.", body = ScheduleItemRecord))
+)]
+async fn post_schedule_item(
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    Json(new_item): Json<InputScheduleItemDto>,
+) -> impl IntoResponse {
+    current_user_is_admin(auth)?;
+    post_schedule_item_service(new_item).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/admin/schedule",
+    tag = "admin",
+    params(IdDto),
+    responses((status = 200, description = "Schedule item deleted.", body = MessageBody))
+)]
+async fn delete_schedule_item(
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    item_id: Query<IdDto>,
+) -> impl IntoResponse {
+    current_user_is_admin(auth)?;
+    delete_schedule_item_service(item_id.id()).await
+}
@@ -1,39 +1,62 @@
 use axum::{
     extract::{Path, Query},
     response::IntoResponse,
-    routing::{get, patch, post},
-    Json, Router,
+    Json,
 };
 use axum_session::SessionRedisPool;
 use axum_session_auth::AuthSession;
+use serde::Serialize;
 
 use crate::{
     data::{
         baby_dto::InputBabyDto,
+        common_structure::{DashboardDto, DashboardDtoV2},
         query_dto::{Pagination, Username},
     },
-    model::session_model::CurrentUser,
+    model::{dashboard_model::BabyDashboard, session_model::CurrentUser},
+    response::response::{BabyPage, BabyRecord, DashboardRecord, MessageBody},
     service::{
         baby_service::{
             delete_baby_service, get_babies_for_user_service, get_baby_by_id_service,
             patch_baby_service, post_new_baby_service, post_share_baby_with_user_service,
             transfer_baby_service,
         },
+        dashboard_service::get_household_dashboard_service,
         session_service::{check_user_permissions, login_required, update_user_session},
         user_service::get_user_id_from_username,
     },
+    utils::{etag::IfMatch, version::ApiVersion},
 };
 
 use super::{
-    dream_controller::route_dream, meal_controller::route_meal, weight_controller::route_weight,
+    booklet_controller::route_booklet,
+    calendar_controller::route_calendar,
+    dream_controller::route_dream,
+    event_controller::route_event,
+    meal_controller::route_meal,
+    medication_controller::route_medication,
+    note_controller::route_note,
+    openapi_controller::{BabyPath, PageQuery},
+    pumping_controller::route_pumping,
+    reminder_controller::route_reminder,
+    report_controller::route_report,
+    route_table::{get, patch, post, Routes},
+    sync_controller::route_sync,
+    webhook_controller::route_webhook,
+    weight_controller::route_weight,
 };
 
-pub(crate) fn route_baby() -> Router {
-    let routes: Router = Router::new()
+pub(crate) fn route_baby(version: ApiVersion) -> Routes {
+    let dashboard = match version {
+        ApiVersion::V1 => get(get_household_dashboard::<DashboardDto>),
+        ApiVersion::V2 => get(get_household_dashboard::<DashboardDtoV2>),
+    };
+    let routes: Routes = Routes::new()
         .route("/", get(get_babies_for_user).post(post_new_baby))
+        .route("/dashboard", dashboard)
         .nest(
             "/:baby_id",
-            Router::new()
+            Routes::new()
                 .route(
                     "/",
                     get(get_baby_by_unique_id)
@@ -42,13 +65,30 @@ pub(crate) fn route_baby() -> Router {
                 )
                 .route("/share", post(post_share_baby_with_user))
                 .route("/transfer", patch(patch_transfer_owner))
-                .merge(route_meal())
-                .merge(route_dream())
-                .merge(route_weight()),
+                .merge(route_meal(version))
+                .merge(route_dream(version))
+                .merge(route_weight())
+                .merge(route_booklet())
+                .merge(route_note(version))
+                .merge(route_medication(version))
+                .merge(route_pumping(version))
+                .merge(route_report())
+                .merge(route_calendar())
+                .merge(route_event())
+                .merge(route_webhook(version))
+                .merge(route_reminder(version))
+                .merge(route_sync(version)),
         );
-    Router::new().nest("/baby", routes)
+    Routes::new().nest("/baby", routes)
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/baby",
+    tag = "babies",
+    request_body = InputBabyDto,
+    responses((status = 201, description = "Baby created.", body = BabyRecord))
+)]
 async fn post_new_baby(
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
     Json(new_baby): Json<InputBabyDto>,
@@ -64,6 +104,20 @@ async fn post_new_baby(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}",
+    tag = "babies",
+    params(BabyPath),
+    responses(
+        (
+            status = 200,
+            description = "Baby found.",
+            body = BabyRecord,
+            headers(("ETag" = String, description = "Record version"))
+        )
+    )
+)]
 async fn get_baby_by_unique_id(
     Path(baby_unique_id): Path<String>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
@@ -72,28 +126,73 @@ async fn get_baby_by_unique_id(
     get_baby_by_id_service(baby_id).await
 }
 
+#[utoipa::path(
+    patch,
+    path = "/api/baby/{baby_id}",
+    tag = "babies",
+    params(
+        BabyPath,
+        (
+            "If-Match" = Option<String>,
+            Header,
+            description = "Last `ETag` read, 412 if outdated, 428 if missing in v2"
+        )
+    ),
+    request_body = InputBabyDto,
+    responses(
+        (
+            status = 200,
+            description = "Baby updated.",
+            body = BabyRecord,
+            headers(("ETag" = String, description = "Record version"))
+        )
+    )
+)]
 async fn patch_baby(
     Path(baby_unique_id): Path<String>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    if_match: IfMatch,
     Json(update): Json<InputBabyDto>,
 ) -> impl IntoResponse {
     let baby_id = check_user_permissions(auth, &baby_unique_id)?;
-    patch_baby_service(baby_id, update).await
+    patch_baby_service(baby_id, update, if_match).await
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/baby/{baby_id}",
+    tag = "babies",
+    params(
+        BabyPath,
+        (
+            "If-Match" = Option<String>,
+            Header,
+            description = "Last `ETag` read, 412 if outdated, 428 if missing in v2"
+        )
+    ),
+    responses((status = 200, description = "Baby deleted.", body = MessageBody))
+)]
 async fn delete_baby(
     Path(baby_unique_id): Path<String>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    if_match: IfMatch,
 ) -> impl IntoResponse {
     let user_binding: i32 = auth.id.try_into().unwrap();
     let baby_id = check_user_permissions(auth.clone(), &baby_unique_id)?;
-    let message = delete_baby_service(baby_id, user_binding).await;
+    let message = delete_baby_service(baby_id, user_binding, if_match).await;
     if message.is_ok() {
         update_user_session(auth).await?;
     }
     message
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/baby",
+    tag = "babies",
+    params(PageQuery),
+    responses((status = 200, description = "Babies of the current user.", body = BabyPage))
+)]
 async fn get_babies_for_user(
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
     page: Option<Query<Pagination>>,
@@ -104,6 +203,31 @@ async fn get_babies_for_user(
     get_babies_for_user_service(id, pagination).await
 }
 
+/// Last meal, last dream and sleeping status for every baby of current user.
+#[utoipa::path(
+    get,
+    path = "/api/baby/dashboard",
+    tag = "babies",
+    responses((status = 200, description = "One entry per baby.", body = DashboardRecord))
+)]
+async fn get_household_dashboard<D>(
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse
+where
+    D: From<BabyDashboard> + Serialize,
+{
+    login_required(auth.clone())?;
+    let babies = auth.current_user.unwrap().baby_unique_id();
+    get_household_dashboard_service::<D>(babies).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/baby/{baby_id}/share",
+    tag = "babies",
+    params(BabyPath, Username),
+    responses((status = 200, description = "Baby shared with the user.", body = MessageBody))
+)]
 async fn post_share_baby_with_user(
     Path(baby_unique_id): Path<String>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
@@ -114,6 +238,13 @@ async fn post_share_baby_with_user(
     post_share_baby_with_user_service(baby_id, user).await
 }
 
+#[utoipa::path(
+    patch,
+    path = "/api/baby/{baby_id}/transfer",
+    tag = "babies",
+    params(BabyPath, Username),
+    responses((status = 200, description = "User is the new owner.", body = MessageBody))
+)]
 async fn patch_transfer_owner(
     Path(baby_unique_id): Path<String>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
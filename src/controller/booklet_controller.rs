@@ -0,0 +1,147 @@
+use axum::{
+    extract::{Path, Query},
+    response::IntoResponse,
+    Extension, Json,
+};
+use axum_session::SessionRedisPool;
+use axum_session_auth::AuthSession;
+use uuid::Uuid;
+
+use crate::{
+    data::{booklet_dto::InputBookletEntryDto, query_dto::Pagination},
+    model::{booklet_model::BookletKind, session_model::CurrentUser},
+    response::response::{BookletEntryPage, BookletEntryRecord, BookletStatusRecord, MessageBody},
+    service::{
+        booklet_service::{
+            delete_booklet_entry_service, get_booklet_entries_service,
+            get_booklet_entry_id_service, get_booklet_status_service, patch_booklet_entry_service,
+            post_booklet_entry_service,
+        },
+        session_service::check_user_permissions,
+    },
+};
+
+use super::openapi_controller::{BabyPath, PageQuery, RecordPath};
+use super::route_table::{get, Routes};
+
+/// Milestones and vaccinations share handlers, kind is injected per route.
+pub(super) fn route_booklet() -> Routes {
+    Routes::new()
+        .nest("/milestones", route_entries(BookletKind::Milestone))
+        .nest("/vaccinations", route_entries(BookletKind::Vaccination))
+        .route("/booklet", get(get_booklet_status))
+}
+
+fn route_entries(kind: BookletKind) -> Routes {
+    Routes::new()
+        .route("/", get(get_entries).post(post_entry))
+        .route(
+            "/:record",
+            get(get_entry_id).patch(patch_entry).delete(delete_entry),
+        )
+        .layer(Extension(kind))
+}
+
+/// Upcoming, due and overdue items of the reference schedule.
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/booklet",
+    tag = "booklet",
+    params(BabyPath),
+    responses(
+        (status = 200, description = "Status of every schedule item.", body = BookletStatusRecord)
+    )
+)]
+async fn get_booklet_status(
+    Path(baby_unique_id): Path<String>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse {
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    get_booklet_status_service(baby_id).await
+}
+
+/// Milestones or vaccinations, depending on the path.
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/{booklet}",
+    tag = "booklet",
+    params(BabyPath, PageQuery),
+    responses((status = 200, description = "Entries of the baby.", body = BookletEntryPage))
+)]
+async fn get_entries(
+    Path(baby_unique_id): Path<String>,
+    Extension(kind): Extension<BookletKind>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    page: Option<Query<Pagination>>,
+) -> impl IntoResponse {
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    get_booklet_entries_service(kind, baby_id, page.unwrap_or_default().0).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/baby/{baby_id}/{booklet}",
+    tag = "booklet",
+    params(BabyPath),
+    request_body = InputBookletEntryDto,
+    responses((status = 201, description = "Entry created.", body = BookletEntryRecord))
+)]
+async fn post_entry(
+    Path(baby_unique_id): Path<String>,
+    Extension(kind): Extension<BookletKind>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    Json(new_entry): Json<InputBookletEntryDto>,
+) -> impl IntoResponse {
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    post_booklet_entry_service(new_entry, kind, baby_id).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/{booklet}/{record}",
+    tag = "booklet",
+    params(RecordPath),
+    responses((status = 200, description = "Entry found.", body = BookletEntryRecord))
+)]
+async fn get_entry_id(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    Extension(kind): Extension<BookletKind>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse {
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    get_booklet_entry_id_service(kind, record, baby_id).await
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/baby/{baby_id}/{booklet}/{record}",
+    tag = "booklet",
+    params(RecordPath),
+    request_body = InputBookletEntryDto,
+    responses((status = 200, description = "Entry updated.", body = BookletEntryRecord))
+)]
+async fn patch_entry(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    Extension(kind): Extension<BookletKind>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    Json(entry): Json<InputBookletEntryDto>,
+) -> impl IntoResponse {
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    patch_booklet_entry_service(entry, kind, record, baby_id).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/baby/{baby_id}/{booklet}/{record}",
+    tag = "booklet",
+    params(RecordPath),
+    responses((status = 200, description = "Entry deleted.", body = MessageBody))
+)]
+async fn delete_entry(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    Extension(kind): Extension<BookletKind>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse {
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    delete_booklet_entry_service(kind, record, baby_id).await
+}
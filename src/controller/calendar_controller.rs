@@ -0,0 +1,103 @@
+use axum::{
+    extract::{Path, Query},
+    response::IntoResponse,
+};
+use axum_session::SessionRedisPool;
+use axum_session_auth::AuthSession;
+
+use crate::{
+    configuration::constant::GlobalCte,
+    data::query_dto::{DateRangeDto, LastDaysDto},
+    model::session_model::CurrentUser,
+    response::response::{CalendarRecord, MessageBody},
+    service::{
+        calendar_service::{
+            check_calendar_token, delete_calendar_token_service,
+            get_calendar_feed_last_days_service, get_calendar_feed_range_service,
+            post_calendar_token_service,
+        },
+        session_service::check_user_permissions,
+    },
+};
+
+use super::openapi_controller::{BabyPath, RangeQuery};
+use super::route_table::{get, post, Routes};
+
+pub(super) fn route_calendar() -> Routes {
+    Routes::new().nest(
+        "/calendar",
+        Routes::new().route("/", post(post_calendar_token).delete(delete_calendar_token)),
+    )
+}
+
+/// Feed is public, calendar apps can't share a session so the token acts as credential.
+pub(crate) fn route_calendar_feed() -> Routes {
+    Routes::new().route("/calendar/:token/feed.ics", get(get_calendar_feed))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/baby/{baby_id}/calendar",
+    tag = "calendar",
+    params(BabyPath),
+    responses((status = 201, description = "Token and feed url.", body = CalendarRecord))
+)]
+async fn post_calendar_token(
+    Path(baby_unique_id): Path<String>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse {
+    let user_id: i32 = auth.id.try_into().unwrap();
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    post_calendar_token_service(baby_id, user_id).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/baby/{baby_id}/calendar",
+    tag = "calendar",
+    params(BabyPath),
+    responses((status = 200, description = "Token revoked.", body = MessageBody))
+)]
+async fn delete_calendar_token(
+    Path(baby_unique_id): Path<String>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse {
+    let user_id: i32 = auth.id.try_into().unwrap();
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    delete_calendar_token_service(baby_id, user_id).await
+}
+
+/// If there are no parameters, it will try to get last 30 days.
+#[utoipa::path(
+    get,
+    path = "/api/calendar/{token}/feed.ics",
+    tag = "calendar",
+    params(("token" = String, Path, description = "Calendar token"), RangeQuery),
+    responses(
+        (
+            status = 200,
+            description = "iCalendar feed.",
+            content_type = "text/calendar",
+            body = String
+        )
+    ),
+    security(())
+)]
+async fn get_calendar_feed(
+    Path(token): Path<String>,
+    range: Option<Query<DateRangeDto>>,
+    last_days: Option<Query<LastDaysDto>>,
+) -> impl IntoResponse {
+    let baby_id = check_calendar_token(&token)?;
+    if range.is_some() {
+        let dates = range.unwrap();
+        get_calendar_feed_range_service(baby_id, dates.from()?, dates.to()?).await
+    } else {
+        let last = last_days
+            .unwrap_or(Query(LastDaysDto::new(
+                GlobalCte::CalendarLastDaysDefault.get(),
+            )))
+            .days();
+        get_calendar_feed_last_days_service(baby_id, last).await
+    }
+}
@@ -1,18 +1,21 @@
 use axum::{
     extract::{Path, Query},
     response::IntoResponse,
-    routing::get,
-    Json, Router,
+    Json,
 };
 use axum_session::SessionRedisPool;
 use axum_session_auth::AuthSession;
+use serde::Serialize;
+use uuid::Uuid;
 
 use crate::{
     data::{
-        dream_dto::InputDreamDto,
-        query_dto::{AllRecords, DateDto, DateRangeDto, LastDaysDto, Pagination},
+        common_structure::{DreamDto, DreamDtoV2},
+        dream_dto::{DreamSummaryDto, DreamSummaryDtoV2, InputDreamDto},
+        query_dto::{AllRecords, DateDto, DateRangeDto, LastDaysDto, ListQuery, Pagination},
     },
-    model::session_model::CurrentUser,
+    model::{dream_model::Dream, session_model::CurrentUser, summary_model::DreamSummary},
+    response::response::{DreamPage, DreamRecord, DreamSummaryPage, MessageBody},
     service::{
         dream_service::{
             delete_dream_service, get_dream_id_service, get_dreams_all_service,
@@ -25,22 +28,45 @@ use crate::{
         },
         session_service::check_user_permissions,
     },
+    utils::{etag::IfMatch, version::ApiVersion},
 };
 
-pub(super) fn route_dream() -> Router {
-    Router::new().nest(
-        "/dreams",
-        Router::new()
-            .route("/", get(get_dreams).post(post_dream))
-            .route(
-                "/:record",
-                get(get_dream_id).patch(patch_dream).delete(delete_dream),
-            )
-            .route("/summary", get(get_dream_summary)),
-    )
+use super::openapi_controller::{BabyPath, DayQuery, PageQuery, RangeQuery, RecordPath, SortQuery};
+use super::route_table::{get, Routes};
+
+pub(super) fn route_dream(version: ApiVersion) -> Routes {
+    let routes = match version {
+        ApiVersion::V1 => route_versioned::<DreamDto, DreamSummaryDto>(),
+        ApiVersion::V2 => route_versioned::<DreamDtoV2, DreamSummaryDtoV2>(),
+    };
+    Routes::new().nest("/dreams", routes)
+}
+
+/// Same handlers in every version, `D` and `S` are the dream and summary sent.
+fn route_versioned<D, S>() -> Routes
+where
+    D: From<Dream> + Serialize + Send + 'static,
+    S: From<DreamSummary> + Serialize + Send + 'static,
+{
+    Routes::new()
+        .route("/", get(get_dreams::<D>).post(post_dream::<D>))
+        .route(
+            "/:record",
+            get(get_dream_id::<D>)
+                .patch(patch_dream::<D>)
+                .delete(delete_dream),
+        )
+        .route("/summary", get(get_dream_summary::<S>))
 }
 
-async fn get_dreams(
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/dreams",
+    tag = "dreams",
+    params(BabyPath, DayQuery, RangeQuery, PageQuery, SortQuery),
+    responses((status = 200, description = "Dreams of the baby.", body = DreamPage))
+)]
+async fn get_dreams<D>(
     Path(baby_unique_id): Path<String>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
     all_records: Option<Query<AllRecords>>,
@@ -48,51 +74,122 @@ async fn get_dreams(
     page: Option<Query<Pagination>>,
     range: Option<Query<DateRangeDto>>,
     last_days: Option<Query<LastDaysDto>>,
-) -> impl IntoResponse {
+    list: ListQuery,
+) -> impl IntoResponse
+where
+    D: From<Dream> + Serialize,
+{
     let baby_id = check_user_permissions(auth, &baby_unique_id)?;
     let pagination = page.unwrap_or_default().0;
     if all_records.is_some() && all_records.unwrap().all() {
-        get_dreams_all_service(baby_id, pagination).await
+        get_dreams_all_service::<D>(baby_id, list, pagination).await
     } else if date.is_some() {
         let day = date.unwrap().date()?;
-        get_dreams_by_range_date_service(baby_id, day, day, pagination).await
+        get_dreams_by_range_date_service::<D>(baby_id, day, day, list, pagination).await
     } else if range.is_some() {
         let dates = range.unwrap();
-        get_dreams_by_range_date_service(baby_id, dates.from()?, dates.to()?, pagination).await
+        get_dreams_by_range_date_service::<D>(baby_id, dates.from()?, dates.to()?, list, pagination)
+            .await
     } else {
         let last = last_days.unwrap_or_default().days();
-        get_dreams_by_last_days_service(baby_id, last, pagination).await
+        get_dreams_by_last_days_service::<D>(baby_id, last, list, pagination).await
     }
 }
 
-async fn post_dream(
+#[utoipa::path(
+    post,
+    path = "/api/baby/{baby_id}/dreams",
+    tag = "dreams",
+    params(BabyPath),
+    request_body = InputDreamDto,
+    responses(
+        (status = 201, description = "Dream created.", body = DreamRecord),
+        (
+            status = 200,
+            description = "Dream with the same `client_id` was already saved.",
+            body = DreamRecord
+        )
+    )
+)]
+async fn post_dream<D>(
     Path(baby_unique_id): Path<String>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
     Json(new_dream): Json<InputDreamDto>,
-) -> impl IntoResponse {
+) -> impl IntoResponse
+where
+    D: From<Dream> + Serialize,
+{
     let baby_id = check_user_permissions(auth, &baby_unique_id)?;
-    post_dream_service(new_dream, baby_id).await
+    post_dream_service::<D>(new_dream, baby_id).await
 }
 
-async fn patch_dream(
-    Path((baby_unique_id, record)): Path<(String, i32)>,
+#[utoipa::path(
+    patch,
+    path = "/api/baby/{baby_id}/dreams/{record}",
+    tag = "dreams",
+    params(
+        RecordPath,
+        (
+            "If-Match" = Option<String>,
+            Header,
+            description = "Last `ETag` read, 412 if outdated, 428 if missing in v2"
+        )
+    ),
+    request_body = InputDreamDto,
+    responses(
+        (
+            status = 200,
+            description = "Dream updated.",
+            body = DreamRecord,
+            headers(("ETag" = String, description = "Record version"))
+        )
+    )
+)]
+async fn patch_dream<D>(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    if_match: IfMatch,
     Json(dream): Json<InputDreamDto>,
-) -> impl IntoResponse {
+) -> impl IntoResponse
+where
+    D: From<Dream> + Serialize,
+{
     let baby_id = check_user_permissions(auth, &baby_unique_id)?;
-    patch_dream_service(dream, record, baby_id).await
+    patch_dream_service::<D>(dream, record, baby_id, if_match).await
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/baby/{baby_id}/dreams/{record}",
+    tag = "dreams",
+    params(
+        RecordPath,
+        (
+            "If-Match" = Option<String>,
+            Header,
+            description = "Last `ETag` read, 412 if outdated, 428 if missing in v2"
+        )
+    ),
+    responses((status = 200, description = "Dream deleted.", body = MessageBody))
+)]
 async fn delete_dream(
-    Path((baby_unique_id, record)): Path<(String, i32)>,
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    if_match: IfMatch,
 ) -> impl IntoResponse {
     let baby_id = check_user_permissions(auth, &baby_unique_id)?;
-    delete_dream_service(record, baby_id).await
+    delete_dream_service(record, baby_id, if_match).await
 }
 
 /// Obtain summary records, if there are no parameters, it will try to get last 7 days.
-async fn get_dream_summary(
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/dreams/summary",
+    tag = "dreams",
+    params(BabyPath, DayQuery, RangeQuery, PageQuery),
+    responses((status = 200, description = "One summary per day.", body = DreamSummaryPage))
+)]
+async fn get_dream_summary<S>(
     Path(baby_unique_id): Path<String>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
     all_records: Option<Query<AllRecords>>,
@@ -100,13 +197,16 @@ async fn get_dream_summary(
     date: Option<Query<DateDto>>,
     range: Option<Query<DateRangeDto>>,
     last_days: Option<Query<LastDaysDto>>,
-) -> impl IntoResponse {
+) -> impl IntoResponse
+where
+    S: From<DreamSummary> + Serialize,
+{
     let baby_id = check_user_permissions(auth, &baby_unique_id)?;
     let pagination = page.unwrap_or_default().0;
     if all_records.is_some() && all_records.unwrap().all() {
-        get_dreams_summary_all_service(baby_id, pagination).await
+        get_dreams_summary_all_service::<S>(baby_id, pagination).await
     } else if date.is_some() {
-        get_dreams_summary_range_service(
+        get_dreams_summary_range_service::<S>(
             baby_id,
             date.as_ref().unwrap().date()?,
             date.unwrap().date()?,
@@ -115,10 +215,15 @@ async fn get_dream_summary(
         .await
     } else if range.is_some() {
         let range_date = range.unwrap();
-        get_dreams_summary_range_service(baby_id, range_date.from()?, range_date.to()?, pagination)
-            .await
+        get_dreams_summary_range_service::<S>(
+            baby_id,
+            range_date.from()?,
+            range_date.to()?,
+            pagination,
+        )
+        .await
     } else {
-        get_dreams_summary_last_days_service(
+        get_dreams_summary_last_days_service::<S>(
             baby_id,
             last_days.unwrap_or_default().days(),
             pagination,
@@ -127,10 +232,27 @@ async fn get_dream_summary(
     }
 }
 
-async fn get_dream_id(
-    Path((baby_unique_id, record)): Path<(String, i32)>,
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/dreams/{record}",
+    tag = "dreams",
+    params(RecordPath),
+    responses(
+        (
+            status = 200,
+            description = "Dream found.",
+            body = DreamRecord,
+            headers(("ETag" = String, description = "Record version"))
+        )
+    )
+)]
+async fn get_dream_id<D>(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
-) -> impl IntoResponse {
+) -> impl IntoResponse
+where
+    D: From<Dream> + Serialize,
+{
     let baby_id: i32 = check_user_permissions(auth, &baby_unique_id)?;
-    get_dream_id_service(record, baby_id).await
+    get_dream_id_service::<D>(record, baby_id).await
 }
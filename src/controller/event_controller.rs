@@ -0,0 +1,40 @@
+use axum::{extract::Path, response::IntoResponse};
+use axum_session::SessionRedisPool;
+use axum_session_auth::AuthSession;
+
+use crate::{
+    model::session_model::CurrentUser,
+    service::{event_service::get_baby_events_service, session_service::check_user_permissions},
+};
+
+use super::openapi_controller::BabyPath;
+use super::route_table::{get, Routes};
+
+pub(super) fn route_event() -> Routes {
+    Routes::new().route("/events", get(get_baby_events))
+}
+
+/// Server-sent events with every meal, dream and weight change.
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/events",
+    tag = "events",
+    params(BabyPath),
+    responses(
+        (
+            status = 200,
+            description = "Stream of `created`, `updated` and `deleted` events, \
+                ended when the session loses access to the baby.",
+            content_type = "text/event-stream",
+            body = String
+        )
+    )
+)]
+async fn get_baby_events(
+    Path(baby_unique_id): Path<String>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse {
+    let user_id: i32 = auth.id.try_into()?;
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    get_baby_events_service(baby_id, user_id).await
+}
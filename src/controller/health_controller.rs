@@ -0,0 +1,22 @@
+use axum::{response::IntoResponse, routing::get, Router};
+
+use crate::service::health_service::{get_liveness_service, get_readiness_service};
+
+/// Probes for the orchestrator, they don't need a session.
+pub(crate) fn route_health() -> Router {
+    Router::new().nest(
+        "/health",
+        Router::new()
+            .route("/live", get(get_live))
+            .route("/ready", get(get_ready)),
+    )
+}
+
+async fn get_live() -> impl IntoResponse {
+    get_liveness_service().await
+}
+
+/// Checks PostgreSQL and Redis, 503 while any of them is down.
+async fn get_ready() -> impl IntoResponse {
+    get_readiness_service().await
+}
@@ -1,18 +1,21 @@
 use axum::{
     extract::{Path, Query},
     response::IntoResponse,
-    routing::get,
-    Json, Router,
+    Json,
 };
 use axum_session::SessionRedisPool;
 use axum_session_auth::AuthSession;
+use serde::Serialize;
+use uuid::Uuid;
 
 use crate::{
     data::{
-        meal_dto::InputMealDto,
-        query_dto::{AllRecords, DateDto, DateRangeDto, LastDaysDto, Pagination},
+        common_structure::{MealDto, MealDtoV2},
+        meal_dto::{InputMealDto, MealSummaryDto, MealSummaryDtoV2},
+        query_dto::{AllRecords, DateDto, DateRangeDto, LastDaysDto, ListQuery, Pagination},
     },
-    model::session_model::CurrentUser,
+    model::{meals_model::Meal, session_model::CurrentUser, summary_model::MealSummary},
+    response::response::{MealPage, MealRecord, MealSummaryPage, MessageBody},
     service::{
         meal_service::{
             delete_meal_service, get_meal_id_service, get_meals_all_service,
@@ -25,22 +28,45 @@ use crate::{
         },
         session_service::check_user_permissions,
     },
+    utils::{etag::IfMatch, version::ApiVersion},
 };
 
-pub(super) fn route_meal() -> Router {
-    Router::new().nest(
-        "/meals",
-        Router::new()
-            .route("/", get(get_meals).post(post_meal))
-            .route(
-                "/:record",
-                get(get_meal_id).patch(patch_meal).delete(delete_meal),
-            )
-            .route("/summary", get(get_meal_summary)),
-    )
+use super::openapi_controller::{BabyPath, DayQuery, PageQuery, RangeQuery, RecordPath, SortQuery};
+use super::route_table::{get, Routes};
+
+pub(super) fn route_meal(version: ApiVersion) -> Routes {
+    let routes = match version {
+        ApiVersion::V1 => route_versioned::<MealDto, MealSummaryDto>(),
+        ApiVersion::V2 => route_versioned::<MealDtoV2, MealSummaryDtoV2>(),
+    };
+    Routes::new().nest("/meals", routes)
+}
+
+/// Same handlers in every version, `D` and `S` are the meal and summary sent.
+fn route_versioned<D, S>() -> Routes
+where
+    D: From<Meal> + Serialize + Send + 'static,
+    S: From<MealSummary> + Serialize + Send + 'static,
+{
+    Routes::new()
+        .route("/", get(get_meals::<D>).post(post_meal::<D>))
+        .route(
+            "/:record",
+            get(get_meal_id::<D>)
+                .patch(patch_meal::<D>)
+                .delete(delete_meal),
+        )
+        .route("/summary", get(get_meal_summary::<S>))
 }
 
-async fn get_meals(
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/meals",
+    tag = "meals",
+    params(BabyPath, DayQuery, RangeQuery, PageQuery, SortQuery),
+    responses((status = 200, description = "Meals of the baby.", body = MealPage))
+)]
+async fn get_meals<D>(
     Path(baby_unique_id): Path<String>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
     all_records: Option<Query<AllRecords>>,
@@ -48,51 +74,121 @@ async fn get_meals(
     page: Option<Query<Pagination>>,
     range: Option<Query<DateRangeDto>>,
     last_days: Option<Query<LastDaysDto>>,
-) -> impl IntoResponse {
+    list: ListQuery,
+) -> impl IntoResponse
+where
+    D: From<Meal> + Serialize,
+{
     let baby_id = check_user_permissions(auth, &baby_unique_id)?;
     let pagination = page.unwrap_or_default().0;
     if all_records.is_some() && all_records.unwrap().all() {
-        get_meals_all_service(baby_id, pagination).await
+        get_meals_all_service::<D>(baby_id, list, pagination).await
     } else if date.is_some() {
         let day = date.unwrap().date()?;
-        get_meals_by_range_service(baby_id, day, day, pagination).await
+        get_meals_by_range_service::<D>(baby_id, day, day, list, pagination).await
     } else if range.is_some() {
         let dates = range.unwrap();
-        get_meals_by_range_service(baby_id, dates.from()?, dates.to()?, pagination).await
+        get_meals_by_range_service::<D>(baby_id, dates.from()?, dates.to()?, list, pagination).await
     } else {
         let last = last_days.unwrap_or_default().days();
-        get_meals_by_last_days_service(baby_id, last, pagination).await
+        get_meals_by_last_days_service::<D>(baby_id, last, list, pagination).await
     }
 }
 
-async fn post_meal(
+#[utoipa::path(
+    post,
+    path = "/api/baby/{baby_id}/meals",
+    tag = "meals",
+    params(BabyPath),
+    request_body = InputMealDto,
+    responses(
+        (status = 201, description = "Meal created.", body = MealRecord),
+        (
+            status = 200,
+            description = "Meal with the same `client_id` was already saved.",
+            body = MealRecord
+        )
+    )
+)]
+async fn post_meal<D>(
     Path(baby_unique_id): Path<String>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
     Json(new_meal): Json<InputMealDto>,
-) -> impl IntoResponse {
+) -> impl IntoResponse
+where
+    D: From<Meal> + Serialize,
+{
     let baby_id = check_user_permissions(auth, &baby_unique_id)?;
-    post_meal_service(new_meal, baby_id).await
+    post_meal_service::<D>(new_meal, baby_id).await
 }
 
-async fn patch_meal(
-    Path((baby_unique_id, record)): Path<(String, i32)>,
+#[utoipa::path(
+    patch,
+    path = "/api/baby/{baby_id}/meals/{record}",
+    tag = "meals",
+    params(
+        RecordPath,
+        (
+            "If-Match" = Option<String>,
+            Header,
+            description = "Last `ETag` read, 412 if outdated, 428 if missing in v2"
+        )
+    ),
+    request_body = InputMealDto,
+    responses(
+        (
+            status = 200,
+            description = "Meal updated.",
+            body = MealRecord,
+            headers(("ETag" = String, description = "Record version"))
+        )
+    )
+)]
+async fn patch_meal<D>(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    if_match: IfMatch,
     Json(meal): Json<InputMealDto>,
-) -> impl IntoResponse {
+) -> impl IntoResponse
+where
+    D: From<Meal> + Serialize,
+{
     let baby_id = check_user_permissions(auth, &baby_unique_id)?;
-    patch_meal_service(meal, record, baby_id).await
+    patch_meal_service::<D>(meal, record, baby_id, if_match).await
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/baby/{baby_id}/meals/{record}",
+    tag = "meals",
+    params(
+        RecordPath,
+        (
+            "If-Match" = Option<String>,
+            Header,
+            description = "Last `ETag` read, 412 if outdated, 428 if missing in v2"
+        )
+    ),
+    responses((status = 200, description = "Meal deleted.", body = MessageBody))
+)]
 async fn delete_meal(
-    Path((baby_unique_id, record)): Path<(String, i32)>,
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    if_match: IfMatch,
 ) -> impl IntoResponse {
     let baby_id = check_user_permissions(auth, &baby_unique_id)?;
-    delete_meal_service(record, baby_id).await
+    delete_meal_service(record, baby_id, if_match).await
 }
 
 /// Obtain summary records, if there are no parameters, it will try to get last 7 days.
-async fn get_meal_summary(
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/meals/summary",
+    tag = "meals",
+    params(BabyPath, DayQuery, RangeQuery, PageQuery),
+    responses((status = 200, description = "One summary per day.", body = MealSummaryPage))
+)]
+async fn get_meal_summary<S>(
     Path(baby_unique_id): Path<String>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
     page: Option<Query<Pagination>>,
@@ -100,13 +196,16 @@ async fn get_meal_summary(
     date: Option<Query<DateDto>>,
     range: Option<Query<DateRangeDto>>,
     last_days: Option<Query<LastDaysDto>>,
-) -> impl IntoResponse {
+) -> impl IntoResponse
+where
+    S: From<MealSummary> + Serialize,
+{
     let baby_id = check_user_permissions(auth, &baby_unique_id)?;
     let pagination = page.unwrap_or_default().0;
     if all_records.is_some() && all_records.unwrap().all() {
-        get_meals_summary_all_service(baby_id, pagination).await
+        get_meals_summary_all_service::<S>(baby_id, pagination).await
     } else if date.is_some() {
-        get_meals_summary_range_service(
+        get_meals_summary_range_service::<S>(
             baby_id,
             date.as_ref().unwrap().date()?,
             date.unwrap().date()?,
@@ -115,10 +214,15 @@ async fn get_meal_summary(
         .await
     } else if range.is_some() {
         let range_date = range.unwrap();
-        get_meals_summary_range_service(baby_id, range_date.from()?, range_date.to()?, pagination)
-            .await
+        get_meals_summary_range_service::<S>(
+            baby_id,
+            range_date.from()?,
+            range_date.to()?,
+            pagination,
+        )
+        .await
     } else {
-        get_meals_summary_last_days_service(
+        get_meals_summary_last_days_service::<S>(
             baby_id,
             last_days.unwrap_or_default().days(),
             pagination,
@@ -127,10 +231,27 @@ async fn get_meal_summary(
     }
 }
 
-async fn get_meal_id(
-    Path((baby_unique_id, record)): Path<(String, i32)>,
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/meals/{record}",
+    tag = "meals",
+    params(RecordPath),
+    responses(
+        (
+            status = 200,
+            description = "Meal found.",
+            body = MealRecord,
+            headers(("ETag" = String, description = "Record version"))
+        )
+    )
+)]
+async fn get_meal_id<D>(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
-) -> impl IntoResponse {
+) -> impl IntoResponse
+where
+    D: From<Meal> + Serialize,
+{
     let baby_id: i32 = check_user_permissions(auth, &baby_unique_id)?;
-    get_meal_id_service(record, baby_id).await
+    get_meal_id_service::<D>(record, baby_id).await
 }
@@ -0,0 +1,233 @@
+use axum::{
+    extract::{Path, Query},
+    response::IntoResponse,
+    Json,
+};
+use axum_session::SessionRedisPool;
+use axum_session_auth::AuthSession;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    data::{
+        common_structure::{DoseDto, DoseDtoV2, MedicationStatusDto, MedicationStatusDtoV2},
+        medication_dto::{InputDoseDto, InputMedicationDto},
+        query_dto::{DateRangeDto, LastDaysDto, Pagination},
+    },
+    model::{
+        medication_model::{MedicationDose, MedicationStatus},
+        session_model::CurrentUser,
+    },
+    response::response::{
+        DosePage, DoseRecord, MedicationPage, MedicationRecord, MedicationStatusRecord, MessageBody,
+    },
+    service::{
+        medication_service::{
+            delete_dose_service, delete_medication_service, get_doses_by_last_days_service,
+            get_doses_by_range_service, get_medication_id_service, get_medications_due_service,
+            get_medications_service, patch_medication_service, post_dose_service,
+            post_medication_service,
+        },
+        session_service::check_user_permissions,
+    },
+    utils::version::ApiVersion,
+};
+
+use super::openapi_controller::{BabyPath, PageQuery, RangeQuery, RecordPath};
+use super::route_table::{delete, get, Routes};
+
+pub(super) fn route_medication(version: ApiVersion) -> Routes {
+    let routes = match version {
+        ApiVersion::V1 => route_versioned::<DoseDto, MedicationStatusDto>(),
+        ApiVersion::V2 => route_versioned::<DoseDtoV2, MedicationStatusDtoV2>(),
+    };
+    Routes::new().nest("/medications", routes)
+}
+
+/// `D` is the dose and `S` the status sent, medications are the same in every version.
+fn route_versioned<D, S>() -> Routes
+where
+    D: From<MedicationDose> + Serialize + Send + 'static,
+    S: From<MedicationStatus> + Serialize + Send + 'static,
+{
+    Routes::new()
+        .route("/", get(get_medications).post(post_medication))
+        .route("/due", get(get_medications_due::<S>))
+        .route(
+            "/:record",
+            get(get_medication_id)
+                .patch(patch_medication)
+                .delete(delete_medication),
+        )
+        .route("/:record/doses", get(get_doses::<D>).post(post_dose::<D>))
+        .route("/:record/doses/:dose", delete(delete_dose))
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/medications",
+    tag = "medications",
+    params(BabyPath, PageQuery),
+    responses((status = 200, description = "Medications of the baby.", body = MedicationPage))
+)]
+async fn get_medications(
+    Path(baby_unique_id): Path<String>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    page: Option<Query<Pagination>>,
+) -> impl IntoResponse {
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    get_medications_service(baby_id, page.unwrap_or_default().0).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/baby/{baby_id}/medications",
+    tag = "medications",
+    params(BabyPath),
+    request_body = InputMedicationDto,
+    responses((status = 201, description = "Medication created.", body = MedicationRecord))
+)]
+async fn post_medication(
+    Path(baby_unique_id): Path<String>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    Json(new_medication): Json<InputMedicationDto>,
+) -> impl IntoResponse {
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    post_medication_service(new_medication, baby_id).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/medications/due",
+    tag = "medications",
+    params(BabyPath),
+    responses(
+        (
+            status = 200,
+            description = "Last and next dose of every medication.",
+            body = MedicationStatusRecord
+        )
+    )
+)]
+async fn get_medications_due<S>(
+    Path(baby_unique_id): Path<String>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse
+where
+    S: From<MedicationStatus> + Serialize,
+{
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    get_medications_due_service::<S>(baby_id).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/medications/{record}",
+    tag = "medications",
+    params(RecordPath),
+    responses((status = 200, description = "Medication found.", body = MedicationRecord))
+)]
+async fn get_medication_id(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse {
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    get_medication_id_service(record, baby_id).await
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/baby/{baby_id}/medications/{record}",
+    tag = "medications",
+    params(RecordPath),
+    request_body = InputMedicationDto,
+    responses((status = 200, description = "Medication updated.", body = MedicationRecord))
+)]
+async fn patch_medication(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    Json(medication): Json<InputMedicationDto>,
+) -> impl IntoResponse {
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    patch_medication_service(medication, record, baby_id).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/baby/{baby_id}/medications/{record}",
+    tag = "medications",
+    params(RecordPath),
+    responses((status = 200, description = "Medication deleted.", body = MessageBody))
+)]
+async fn delete_medication(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse {
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    delete_medication_service(record, baby_id).await
+}
+
+/// If there are no parameters, it will try to get last 7 days.
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/medications/{record}/doses",
+    tag = "medications",
+    params(RecordPath, RangeQuery, PageQuery),
+    responses((status = 200, description = "Doses of the medication.", body = DosePage))
+)]
+async fn get_doses<D>(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    page: Option<Query<Pagination>>,
+    range: Option<Query<DateRangeDto>>,
+    last_days: Option<Query<LastDaysDto>>,
+) -> impl IntoResponse
+where
+    D: From<MedicationDose> + Serialize,
+{
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    let pagination = page.unwrap_or_default().0;
+    if range.is_some() {
+        let dates = range.unwrap();
+        get_doses_by_range_service::<D>(record, baby_id, dates.from()?, dates.to()?, pagination)
+            .await
+    } else {
+        let last = last_days.unwrap_or_default().days();
+        get_doses_by_last_days_service::<D>(record, baby_id, last, pagination).await
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/baby/{baby_id}/medications/{record}/doses",
+    tag = "medications",
+    params(RecordPath),
+    request_body = InputDoseDto,
+    responses((status = 201, description = "Dose given.", body = DoseRecord))
+)]
+async fn post_dose<D>(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    Json(new_dose): Json<InputDoseDto>,
+) -> impl IntoResponse
+where
+    D: From<MedicationDose> + Serialize,
+{
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    post_dose_service::<D>(new_dose, record, baby_id).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/baby/{baby_id}/medications/{record}/doses/{dose}",
+    tag = "medications",
+    params(RecordPath, ("dose" = Uuid, Path, description = "Dose id")),
+    responses((status = 200, description = "Dose deleted.", body = MessageBody))
+)]
+async fn delete_dose(
+    Path((baby_unique_id, record, dose)): Path<(String, Uuid, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse {
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    delete_dose_service(record, dose, baby_id).await
+}
@@ -0,0 +1,15 @@
+use axum::{http::header, response::IntoResponse, routing::get, Router};
+
+use crate::service::metrics_service::get_metrics_service;
+
+/// Prometheus scrape target, outside `/api` and without a session.
+pub(crate) fn route_metrics() -> Router {
+    Router::new().route("/metrics", get(get_metrics))
+}
+
+async fn get_metrics() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        get_metrics_service().await,
+    )
+}
@@ -3,4 +3,19 @@ pub mod baby_controller;
 pub(self) mod meal_controller;
 pub(self) mod dream_controller;
 pub(self) mod weight_controller;
+pub(self) mod note_controller;
+pub(self) mod medication_controller;
+pub(self) mod booklet_controller;
+pub(self) mod pumping_controller;
+pub(self) mod event_controller;
+pub(self) mod webhook_controller;
+pub(self) mod reminder_controller;
+pub(self) mod sync_controller;
+pub(self) mod report_controller;
+pub mod calendar_controller;
 pub mod admin_controller;
+pub mod notification_controller;
+pub mod health_controller;
+pub mod metrics_controller;
+pub mod openapi_controller;
+pub(crate) mod route_table;
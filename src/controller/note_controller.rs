@@ -0,0 +1,165 @@
+use axum::{
+    extract::{Path, Query},
+    response::IntoResponse,
+    Json,
+};
+use axum_session::SessionRedisPool;
+use axum_session_auth::AuthSession;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    data::{
+        common_structure::{NoteDto, NoteDtoV2},
+        note_dto::{InputNoteDto, NoteFilterDto},
+        query_dto::{AllRecords, DateDto, DateRangeDto, LastDaysDto, Pagination},
+    },
+    model::{note_model::Note, session_model::CurrentUser},
+    response::response::{MessageBody, NotePage, NoteRecord},
+    service::{
+        note_service::{
+            delete_note_service, get_note_id_service, get_notes_all_service,
+            get_notes_by_last_days_service, get_notes_by_range_service, patch_note_service,
+            post_note_service,
+        },
+        session_service::check_user_permissions,
+    },
+    utils::version::ApiVersion,
+};
+
+use super::openapi_controller::{BabyPath, DayQuery, PageQuery, RangeQuery, RecordPath};
+use super::route_table::{get, Routes};
+
+pub(super) fn route_note(version: ApiVersion) -> Routes {
+    let routes = match version {
+        ApiVersion::V1 => route_versioned::<NoteDto>(),
+        ApiVersion::V2 => route_versioned::<NoteDtoV2>(),
+    };
+    Routes::new().nest("/notes", routes)
+}
+
+fn route_versioned<D>() -> Routes
+where
+    D: From<Note> + Serialize + Send + 'static,
+{
+    Routes::new()
+        .route("/", get(get_notes::<D>).post(post_note::<D>))
+        .route(
+            "/:record",
+            get(get_note_id::<D>)
+                .patch(patch_note::<D>)
+                .delete(delete_note),
+        )
+}
+
+/// Same date queries as meals, `search` and `category` narrow down any of them.
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/notes",
+    tag = "notes",
+    params(BabyPath, DayQuery, RangeQuery, PageQuery, NoteFilterDto),
+    responses((status = 200, description = "Notes of the baby.", body = NotePage))
+)]
+async fn get_notes<D>(
+    Path(baby_unique_id): Path<String>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    filter: Option<Query<NoteFilterDto>>,
+    all_records: Option<Query<AllRecords>>,
+    date: Option<Query<DateDto>>,
+    page: Option<Query<Pagination>>,
+    range: Option<Query<DateRangeDto>>,
+    last_days: Option<Query<LastDaysDto>>,
+) -> impl IntoResponse
+where
+    D: From<Note> + Serialize,
+{
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    let pagination = page.unwrap_or_default().0;
+    let filter = filter.unwrap_or_default().0;
+    if all_records.is_some() && all_records.unwrap().all() {
+        get_notes_all_service::<D>(baby_id, filter, pagination).await
+    } else if date.is_some() {
+        let day = date.unwrap().date()?;
+        get_notes_by_range_service::<D>(baby_id, day, day, filter, pagination).await
+    } else if range.is_some() {
+        let dates = range.unwrap();
+        get_notes_by_range_service::<D>(baby_id, dates.from()?, dates.to()?, filter, pagination)
+            .await
+    } else {
+        let last = last_days.unwrap_or_default().days();
+        get_notes_by_last_days_service::<D>(baby_id, last, filter, pagination).await
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/baby/{baby_id}/notes",
+    tag = "notes",
+    params(BabyPath),
+    request_body = InputNoteDto,
+    responses((status = 201, description = "Note created.", body = NoteRecord))
+)]
+async fn post_note<D>(
+    Path(baby_unique_id): Path<String>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    Json(new_note): Json<InputNoteDto>,
+) -> impl IntoResponse
+where
+    D: From<Note> + Serialize,
+{
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    post_note_service::<D>(new_note, baby_id).await
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/baby/{baby_id}/notes/{record}",
+    tag = "notes",
+    params(RecordPath),
+    request_body = InputNoteDto,
+    responses((status = 200, description = "Note updated.", body = NoteRecord))
+)]
+async fn patch_note<D>(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    Json(note): Json<InputNoteDto>,
+) -> impl IntoResponse
+where
+    D: From<Note> + Serialize,
+{
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    patch_note_service::<D>(note, record, baby_id).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/baby/{baby_id}/notes/{record}",
+    tag = "notes",
+    params(RecordPath),
+    responses((status = 200, description = "Note deleted.", body = MessageBody))
+)]
+async fn delete_note(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse {
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    delete_note_service(record, baby_id).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/notes/{record}",
+    tag = "notes",
+    params(RecordPath),
+    responses((status = 200, description = "Note found.", body = NoteRecord))
+)]
+async fn get_note_id<D>(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse
+where
+    D: From<Note> + Serialize,
+{
+    let baby_id: i32 = check_user_permissions(auth, &baby_unique_id)?;
+    get_note_id_service::<D>(record, baby_id).await
+}
@@ -0,0 +1,135 @@
+use axum::{
+    extract::{Path, Query},
+    response::IntoResponse,
+    Json,
+};
+use axum_session::SessionRedisPool;
+use axum_session_auth::AuthSession;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    data::{
+        common_structure::{NotificationDto, NotificationDtoV2},
+        notification_dto::{InputNotificationDto, UnreadDto},
+        query_dto::Pagination,
+    },
+    model::{notification_model::Notification, session_model::CurrentUser},
+    response::response::{MessageBody, NotificationPage, NotificationRecord},
+    service::{
+        notification_service::{
+            delete_notification_service, get_notifications_service, patch_notification_service,
+            post_read_all_notifications_service,
+        },
+        session_service::login_required,
+    },
+    utils::version::ApiVersion,
+};
+
+use super::openapi_controller::PageQuery;
+use super::route_table::{get, patch, post, Routes};
+
+/// In-app notifications of the current user, from every baby.
+pub(crate) fn route_notification(version: ApiVersion) -> Routes {
+    match version {
+        ApiVersion::V1 => route_versioned::<NotificationDto>(),
+        ApiVersion::V2 => route_versioned::<NotificationDtoV2>(),
+    }
+}
+
+fn route_versioned<D>() -> Routes
+where
+    D: From<(Notification, Uuid)> + Serialize + Send + 'static,
+{
+    Routes::new().nest(
+        "/notifications",
+        Routes::new()
+            .route("/", get(get_notifications::<D>))
+            .route("/read", post(post_read_all_notifications))
+            .route(
+                "/:record",
+                patch(patch_notification::<D>).delete(delete_notification),
+            ),
+    )
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/notifications",
+    tag = "notifications",
+    params(UnreadDto, PageQuery),
+    responses(
+        (
+            status = 200,
+            description = "Notifications of the current user.",
+            body = NotificationPage
+        )
+    )
+)]
+async fn get_notifications<D>(
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    unread: Option<Query<UnreadDto>>,
+    page: Option<Query<Pagination>>,
+) -> impl IntoResponse
+where
+    D: From<(Notification, Uuid)> + Serialize,
+{
+    let user_id: i32 = auth.id.try_into().unwrap();
+    login_required(auth)?;
+    let only_unread = unread.unwrap_or_default().unread();
+    let pagination = page.unwrap_or_default().0;
+    get_notifications_service::<D>(user_id, only_unread, pagination).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/notifications/read",
+    tag = "notifications",
+    responses(
+        (status = 200, description = "Every notification marked as read.", body = MessageBody)
+    )
+)]
+async fn post_read_all_notifications(
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse {
+    let user_id: i32 = auth.id.try_into().unwrap();
+    login_required(auth)?;
+    post_read_all_notifications_service(user_id).await
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/notifications/{record}",
+    tag = "notifications",
+    params(("record" = Uuid, Path, description = "Notification id")),
+    request_body = InputNotificationDto,
+    responses((status = 200, description = "Notification updated.", body = NotificationRecord))
+)]
+async fn patch_notification<D>(
+    Path(record): Path<Uuid>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    Json(notification): Json<InputNotificationDto>,
+) -> impl IntoResponse
+where
+    D: From<(Notification, Uuid)> + Serialize,
+{
+    let user_id: i32 = auth.id.try_into().unwrap();
+    login_required(auth)?;
+    patch_notification_service::<D>(notification, record, user_id).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/notifications/{record}",
+    tag = "notifications",
+    params(("record" = Uuid, Path, description = "Notification id")),
+    responses((status = 200, description = "Notification deleted.", body = MessageBody))
+)]
+async fn delete_notification(
+    Path(record): Path<Uuid>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse {
+    let user_id: i32 = auth.id.try_into().unwrap();
+    login_required(auth)?;
+    delete_notification_service(record, user_id).await
+}
@@ -0,0 +1,513 @@
+use axum::Router;
+#[cfg(not(feature = "swagger-ui"))]
+use axum::{response::IntoResponse, routing::get, Json};
+use utoipa::{
+    openapi::{
+        security::{ApiKey, ApiKeyValue, SecurityScheme},
+        Content, OneOfBuilder, OpenApi as OpenApiDocument, Ref, ResponseBuilder,
+    },
+    IntoParams, Modify, OpenApi,
+};
+#[cfg(feature = "swagger-ui")]
+use utoipa_swagger_ui::SwaggerUi;
+use uuid::Uuid;
+
+use crate::{
+    data::{
+        admin_dto::{AdminBabyData, AdminUserData},
+        baby_dto::{BabyData, InputBabyDto},
+        booklet_dto::{
+            BookletEntryData, InputBookletEntryDto, InputScheduleItemDto, ScheduleItemData,
+            ScheduleStatusData,
+        },
+        calendar_dto::CalendarData,
+        common_structure::MealDto,
+        dashboard_dto::{DashboardData, DashboardDataV2},
+        dream_dto::{DreamData, DreamDataV2, DreamSummaryDto, DreamSummaryDtoV2, InputDreamDto},
+        meal_dto::{InputMealDto, MealData, MealDataV2, MealSummaryDto, MealSummaryDtoV2},
+        medication_dto::{
+            DoseData, DoseDataV2, InputDoseDto, InputMedicationDto, MedicationData,
+            MedicationStatusData, MedicationStatusDataV2,
+        },
+        note_dto::{InputNoteDto, NoteData, NoteDataV2},
+        notification_dto::{InputNotificationDto, NotificationData, NotificationDataV2},
+        pumping_dto::{
+            InputMilkBagDto, InputPumpingDto, MilkBagData, MilkBagDataV2, MilkStashData,
+            PumpingData, PumpingDataV2,
+        },
+        reminder_dto::{InputReminderDto, ReminderData, ReminderDataV2},
+        role_dto::{RoleData, UpdateRole},
+        session_dto::UserSessionData,
+        sync_dto::{
+            DeletedRecordData, DeletedRecordDataV2, InputSyncDto, MealChangeDto, SyncData,
+            SyncDataV2,
+        },
+        user_dto::{FindUserDto, LoginDto, NewUserDto, UpdateUserDto, UserData},
+        webhook_dto::{
+            InputWebhookDto, WebhookData, WebhookDataV2, WebhookDeliveryData, WebhookDeliveryDataV2,
+        },
+        weight_dto::{InputWeightDto, WeightData},
+    },
+    model::session_model::BabyInfo,
+    repository::admin_repository::{StatsDB, TableDescription},
+    response::{
+        error::{ErrorBody, ErrorBodyV2, ErrorSource},
+        response::{MealPage, MealRecord, MessageBody, PageInfo},
+    },
+};
+
+use super::{
+    admin_controller, baby_controller, booklet_controller, calendar_controller, dream_controller,
+    event_controller, meal_controller, medication_controller, note_controller,
+    notification_controller, pumping_controller, reminder_controller, report_controller,
+    sync_controller, user_controller, webhook_controller, weight_controller,
+};
+
+const OPENAPI_PATH: &str = "/api/openapi.json";
+
+/// Every route of `create_router`, title and version are read from the manifest.
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        user_controller::post_new_user,
+        user_controller::post_find_user,
+        user_controller::post_session_user,
+        user_controller::delete_session_user,
+        user_controller::get_session_user,
+        user_controller::get_user_by_id,
+        user_controller::patch_user,
+        user_controller::delete_user,
+        baby_controller::get_babies_for_user,
+        baby_controller::post_new_baby,
+        baby_controller::get_household_dashboard,
+        baby_controller::get_baby_by_unique_id,
+        baby_controller::patch_baby,
+        baby_controller::delete_baby,
+        baby_controller::post_share_baby_with_user,
+        baby_controller::patch_transfer_owner,
+        meal_controller::get_meals,
+        meal_controller::post_meal,
+        meal_controller::get_meal_id,
+        meal_controller::patch_meal,
+        meal_controller::delete_meal,
+        meal_controller::get_meal_summary,
+        dream_controller::get_dreams,
+        dream_controller::post_dream,
+        dream_controller::get_dream_id,
+        dream_controller::patch_dream,
+        dream_controller::delete_dream,
+        dream_controller::get_dream_summary,
+        weight_controller::get_weights,
+        weight_controller::post_weight,
+        weight_controller::get_weight_id,
+        weight_controller::patch_weight,
+        weight_controller::delete_weight,
+        note_controller::get_notes,
+        note_controller::post_note,
+        note_controller::get_note_id,
+        note_controller::patch_note,
+        note_controller::delete_note,
+        medication_controller::get_medications,
+        medication_controller::post_medication,
+        medication_controller::get_medication_id,
+        medication_controller::patch_medication,
+        medication_controller::delete_medication,
+        medication_controller::get_medications_due,
+        medication_controller::get_doses,
+        medication_controller::post_dose,
+        medication_controller::delete_dose,
+        booklet_controller::get_booklet_status,
+        booklet_controller::get_entries,
+        booklet_controller::post_entry,
+        booklet_controller::get_entry_id,
+        booklet_controller::patch_entry,
+        booklet_controller::delete_entry,
+        pumping_controller::get_pumpings,
+        pumping_controller::post_pumping,
+        pumping_controller::get_pumping_id,
+        pumping_controller::patch_pumping,
+        pumping_controller::delete_pumping,
+        pumping_controller::get_milk_bags,
+        pumping_controller::post_milk_bag,
+        pumping_controller::get_milk_bag_id,
+        pumping_controller::patch_milk_bag,
+        pumping_controller::delete_milk_bag,
+        pumping_controller::get_milk_stash,
+        reminder_controller::get_reminders,
+        reminder_controller::post_reminder,
+        reminder_controller::get_reminder_id,
+        reminder_controller::patch_reminder,
+        reminder_controller::delete_reminder,
+        webhook_controller::get_webhooks,
+        webhook_controller::post_webhook,
+        webhook_controller::get_webhook_id,
+        webhook_controller::patch_webhook,
+        webhook_controller::delete_webhook,
+        webhook_controller::get_webhook_deliveries,
+        calendar_controller::post_calendar_token,
+        calendar_controller::delete_calendar_token,
+        calendar_controller::get_calendar_feed,
+        event_controller::get_baby_events,
+        report_controller::get_report,
+        sync_controller::post_sync,
+        notification_controller::get_notifications,
+        notification_controller::post_read_all_notifications,
+        notification_controller::patch_notification,
+        notification_controller::delete_notification,
+        admin_controller::get_all_babies,
+        admin_controller::get_baby_by_id,
+        admin_controller::get_all_users,
+        admin_controller::delete_user,
+        admin_controller::patch_activate_user,
+        admin_controller::get_stats_of_tables,
+        admin_controller::get_schedule,
+        admin_controller::post_schedule_item,
+        admin_controller::delete_schedule_item,
+        admin_controller::get_roles,
+        admin_controller::put_user_role,
+        admin_controller::delete_user_role,
+    ),
+    components(schemas(
+        NewUserDto,
+        FindUserDto,
+        LoginDto,
+        UpdateUserDto,
+        InputBabyDto,
+        InputMealDto,
+        InputDreamDto,
+        InputWeightDto,
+        InputNoteDto,
+        InputMedicationDto,
+        InputDoseDto,
+        InputBookletEntryDto,
+        InputScheduleItemDto,
+        InputPumpingDto,
+        InputMilkBagDto,
+        InputReminderDto,
+        InputWebhookDto,
+        InputNotificationDto,
+        InputSyncDto,
+        UpdateRole,
+        UserSessionData,
+        BabyInfo,
+        UserData,
+        BabyData,
+        DashboardData,
+        MealData,
+        MealSummaryDto,
+        DreamData,
+        DreamSummaryDto,
+        WeightData,
+        NoteData,
+        MedicationData,
+        DoseData,
+        MedicationStatusData,
+        BookletEntryData,
+        ScheduleItemData,
+        ScheduleStatusData,
+        PumpingData,
+        MilkBagData,
+        MilkStashData,
+        CalendarData,
+        ReminderData,
+        NotificationData,
+        WebhookData,
+        WebhookDeliveryData,
+        SyncData,
+        DeletedRecordData,
+        RoleData,
+        AdminUserData,
+        AdminBabyData,
+        StatsDB,
+        TableDescription,
+        PageInfo,
+        MessageBody,
+        ErrorBody,
+        ErrorSource,
+        // Sent by `/api/v2` instead of the schema without suffix.
+        ErrorBodyV2,
+        DashboardDataV2,
+        MealDataV2,
+        MealSummaryDtoV2,
+        DreamDataV2,
+        DreamSummaryDtoV2,
+        NoteDataV2,
+        DoseDataV2,
+        MedicationStatusDataV2,
+        PumpingDataV2,
+        MilkBagDataV2,
+        ReminderDataV2,
+        NotificationDataV2,
+        WebhookDataV2,
+        WebhookDeliveryDataV2,
+        SyncDataV2,
+        DeletedRecordDataV2,
+        // Generic structs register all their aliases from any of them.
+        MealChangeDto,
+        MealDto,
+        MealRecord,
+        MealPage,
+    )),
+    info(description = "Paths are documented under `/api`, the same as `/api/v1`. \
+        `/api/v2` sends the `V2` schemas: RFC 3339 timestamps and durations in seconds. \
+        Inputs accept `YYYY-mm-dd HH:MM` or RFC 3339 in every version."),
+    modifiers(&SessionCookie, &ErrorResponses, &BookletPaths),
+    security(("session" = [])),
+    tags(
+        (name = "auth", description = "Registration, session and profile of the current user."),
+        (name = "babies", description = "Babies of the current user, shared or owned."),
+        (name = "meals"),
+        (name = "dreams"),
+        (name = "weights"),
+        (name = "notes"),
+        (name = "medications", description = "Medications and the doses given."),
+        (name = "booklet", description = "Milestones and vaccinations of a baby."),
+        (name = "pumping"),
+        (name = "milk", description = "Stored milk bags."),
+        (name = "reminders"),
+        (name = "webhooks"),
+        (name = "calendar", description = "iCalendar feed, read with a token."),
+        (name = "events", description = "Server-sent events of a baby."),
+        (name = "reports"),
+        (name = "sync", description = "Offline clients pushing and pulling changes."),
+        (name = "notifications"),
+        (name = "admin", description = "Only for users with the admin role."),
+    )
+)]
+pub struct ApiDoc;
+
+/// Baby resources, `baby_id` is the public id of the baby.
+#[derive(IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct BabyPath {
+    pub baby_id: Uuid,
+}
+
+/// Single record of a baby.
+#[derive(IntoParams)]
+#[into_params(parameter_in = Path)]
+pub struct RecordPath {
+    pub baby_id: Uuid,
+    /// Record id.
+    pub record: Uuid,
+}
+
+/// Records of a single day or all of them, they take precedence over [RangeQuery].
+#[derive(IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct DayQuery {
+    /// Every record, ignoring any other date.
+    pub all: Option<bool>,
+    /// `YYYY-mm-dd` or `today`.
+    pub date: Option<String>,
+}
+
+/// Records between two dates or from the last days, the default of each endpoint if missing.
+#[derive(IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct RangeQuery {
+    /// `YYYY-mm-dd`.
+    pub from: Option<String>,
+    /// `YYYY-mm-dd`, today if missing.
+    pub to: Option<String>,
+    pub last_days: Option<u32>,
+}
+
+/// Page number, or a `cursor` from a previous `page_info` which takes precedence.
+#[derive(IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct PageQuery {
+    pub page: Option<i32>,
+    pub per_page: Option<i32>,
+    pub cursor: Option<String>,
+}
+
+/// Order of a list, filters like `quantity_gte` are listed in the README.
+#[derive(IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct SortQuery {
+    /// Field name, descending with a leading `-`.
+    pub sort: Option<String>,
+}
+
+/// Session cookie, the anonymous user gets one too.
+struct SessionCookie;
+
+impl Modify for SessionCookie {
+    fn modify(&self, openapi: &mut OpenApiDocument) {
+        if let Some(components) = openapi.components.as_mut() {
+            components.add_security_scheme(
+                "session",
+                SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::new("session"))),
+            );
+        }
+    }
+}
+
+/// Any operation can fail with an [ErrorBody], or [ErrorBodyV2] in `/api/v2`, documented once
+/// as the default response.
+struct ErrorResponses;
+
+impl Modify for ErrorResponses {
+    fn modify(&self, openapi: &mut OpenApiDocument) {
+        let error = ResponseBuilder::new()
+            .description("Request failed, `errors` tells why.")
+            .content(
+                "application/json",
+                Content::new(
+                    OneOfBuilder::new()
+                        .item(Ref::from_schema_name("ErrorBody"))
+                        .item(Ref::from_schema_name("ErrorBodyV2")),
+                ),
+            )
+            .build();
+        let operations = openapi
+            .paths
+            .paths
+            .values_mut()
+            .flat_map(|item| item.operations.values_mut());
+        for operation in operations {
+            operation
+                .responses
+                .responses
+                .entry(String::from("default"))
+                .or_insert_with(|| error.clone().into());
+        }
+    }
+}
+
+/// Booklet handlers serve milestones and vaccinations, their `{booklet}` paths are
+/// documented once per kind.
+struct BookletPaths;
+
+impl Modify for BookletPaths {
+    fn modify(&self, openapi: &mut OpenApiDocument) {
+        let templates: Vec<String> = openapi
+            .paths
+            .paths
+            .keys()
+            .filter(|path| path.contains("{booklet}"))
+            .cloned()
+            .collect();
+        for template in templates {
+            let item = openapi.paths.paths.remove(&template).unwrap();
+            for booklet in ["milestones", "vaccinations"] {
+                let mut item = item.clone();
+                for operation in item.operations.values_mut() {
+                    operation.operation_id = operation
+                        .operation_id
+                        .as_ref()
+                        .map(|id| format!("{id}_{booklet}"));
+                }
+                openapi
+                    .paths
+                    .paths
+                    .insert(template.replace("{booklet}", booklet), item);
+            }
+        }
+    }
+}
+
+/// OpenAPI document, served by Swagger UI at `/api/docs` when built with `swagger-ui`.
+#[cfg(not(feature = "swagger-ui"))]
+pub(crate) fn route_openapi() -> Router {
+    Router::new().route(OPENAPI_PATH, get(get_openapi))
+}
+
+#[cfg(feature = "swagger-ui")]
+pub(crate) fn route_openapi() -> Router {
+    Router::new().merge(SwaggerUi::new("/api/docs").url(OPENAPI_PATH, ApiDoc::openapi()))
+}
+
+#[cfg(not(feature = "swagger-ui"))]
+async fn get_openapi() -> impl IntoResponse {
+    Json(ApiDoc::openapi())
+}
+
+#[cfg(test)]
+mod test_openapi {
+    use std::collections::BTreeSet;
+
+    use serde_json::Value;
+
+    use crate::{app::route_versions, utils::version::ApiVersion};
+
+    use super::*;
+
+    /// Method and path of every route in the table, `:param` converted to `{param}`.
+    ///
+    /// Versions share their paths, they are documented once under `/api`.
+    fn router_operations() -> BTreeSet<(String, String)> {
+        route_versions()
+            .table()
+            .iter()
+            .map(|(method, path)| {
+                let path = [ApiVersion::V1, ApiVersion::V2]
+                    .iter()
+                    .find_map(|version| path.strip_prefix(version.prefix()))
+                    .map_or(path.to_string(), |rest| format!("/api{rest}"));
+                let path = path
+                    .split('/')
+                    .map(|segment| match segment.strip_prefix(':') {
+                        Some(param) => format!("{{{param}}}"),
+                        None => segment.to_string(),
+                    })
+                    .collect::<Vec<_>>()
+                    .join("/");
+                (method.as_str().to_lowercase(), path)
+            })
+            .collect()
+    }
+
+    /// Method and path of every operation in the document.
+    fn documented_operations() -> BTreeSet<(String, String)> {
+        let document = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let paths = document["paths"].as_object().unwrap();
+        paths
+            .iter()
+            .flat_map(|(path, item)| {
+                item.as_object()
+                    .unwrap()
+                    .keys()
+                    .filter(|key| OPERATIONS.contains(&key.as_str()))
+                    .map(move |method| (method.clone(), path.clone()))
+            })
+            .collect()
+    }
+
+    const OPERATIONS: [&str; 5] = ["get", "post", "put", "patch", "delete"];
+
+    #[test]
+    fn test_every_route_documented() {
+        let routes = router_operations();
+        let documented = documented_operations();
+        assert!(!routes.is_empty());
+        let missing: Vec<_> = routes.difference(&documented).collect();
+        let unknown: Vec<_> = documented.difference(&routes).collect();
+        assert!(missing.is_empty(), "Routes not documented: {missing:?}");
+        assert!(
+            unknown.is_empty(),
+            "Documented operations without route: {unknown:?}"
+        );
+    }
+
+    #[test]
+    fn test_schema_references() {
+        let document = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let schemas = &document["components"]["schemas"];
+        let mut pending = vec![&document];
+        while let Some(value) = pending.pop() {
+            match value {
+                Value::Object(map) => {
+                    if let Some(Value::String(reference)) = map.get("$ref") {
+                        let name = reference.trim_start_matches("#/components/schemas/");
+                        assert!(schemas.get(name).is_some(), "Missing schema {reference}");
+                    }
+                    pending.extend(map.values());
+                }
+                Value::Array(values) => pending.extend(values),
+                _ => (),
+            }
+        }
+    }
+}
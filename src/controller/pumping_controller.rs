@@ -0,0 +1,299 @@
+use axum::{
+    extract::{Path, Query},
+    response::IntoResponse,
+    Json,
+};
+use axum_session::SessionRedisPool;
+use axum_session_auth::AuthSession;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    data::{
+        common_structure::{MilkBagDto, MilkBagDtoV2, PumpingDto, PumpingDtoV2},
+        pumping_dto::{AvailableBagsDto, InputMilkBagDto, InputPumpingDto},
+        query_dto::{AllRecords, DateDto, DateRangeDto, LastDaysDto, Pagination},
+    },
+    model::{
+        pumping_model::{MilkBag, Pumping},
+        session_model::CurrentUser,
+    },
+    response::response::{
+        MessageBody, MilkBagPage, MilkBagRecord, MilkStashRecord, PumpingPage, PumpingRecord,
+    },
+    service::{
+        milk_bag_service::{
+            delete_milk_bag_service, get_milk_bag_id_service, get_milk_bags_service,
+            get_milk_stash_service, patch_milk_bag_service, post_milk_bag_service,
+        },
+        pumping_service::{
+            delete_pumping_service, get_pumping_id_service, get_pumpings_all_service,
+            get_pumpings_by_last_days_service, get_pumpings_by_range_service,
+            patch_pumping_service, post_pumping_service,
+        },
+        session_service::check_user_permissions,
+    },
+    utils::version::ApiVersion,
+};
+
+use super::openapi_controller::{BabyPath, DayQuery, PageQuery, RangeQuery, RecordPath};
+use super::route_table::{get, Routes};
+
+pub(super) fn route_pumping(version: ApiVersion) -> Routes {
+    match version {
+        ApiVersion::V1 => route_versioned::<PumpingDto, MilkBagDto>(),
+        ApiVersion::V2 => route_versioned::<PumpingDtoV2, MilkBagDtoV2>(),
+    }
+}
+
+/// `D` is the pumping session and `B` the milk bag sent.
+fn route_versioned<D, B>() -> Routes
+where
+    D: From<Pumping> + Serialize + Send + 'static,
+    B: From<MilkBag> + Serialize + Send + 'static,
+{
+    Routes::new()
+        .nest(
+            "/pumping",
+            Routes::new()
+                .route("/", get(get_pumpings::<D>).post(post_pumping::<D>))
+                .route(
+                    "/:record",
+                    get(get_pumping_id::<D>)
+                        .patch(patch_pumping::<D>)
+                        .delete(delete_pumping),
+                ),
+        )
+        .nest(
+            "/milk",
+            Routes::new()
+                .route("/bags", get(get_milk_bags::<B>).post(post_milk_bag::<B>))
+                .route(
+                    "/bags/:record",
+                    get(get_milk_bag_id::<B>)
+                        .patch(patch_milk_bag::<B>)
+                        .delete(delete_milk_bag),
+                )
+                .route("/stash", get(get_milk_stash)),
+        )
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/pumping",
+    tag = "pumping",
+    params(BabyPath, DayQuery, RangeQuery, PageQuery),
+    responses((status = 200, description = "Pumping sessions of the baby.", body = PumpingPage))
+)]
+async fn get_pumpings<D>(
+    Path(baby_unique_id): Path<String>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    all_records: Option<Query<AllRecords>>,
+    date: Option<Query<DateDto>>,
+    page: Option<Query<Pagination>>,
+    range: Option<Query<DateRangeDto>>,
+    last_days: Option<Query<LastDaysDto>>,
+) -> impl IntoResponse
+where
+    D: From<Pumping> + Serialize,
+{
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    let pagination = page.unwrap_or_default().0;
+    if all_records.is_some() && all_records.unwrap().all() {
+        get_pumpings_all_service::<D>(baby_id, pagination).await
+    } else if date.is_some() {
+        let day = date.unwrap().date()?;
+        get_pumpings_by_range_service::<D>(baby_id, day, day, pagination).await
+    } else if range.is_some() {
+        let dates = range.unwrap();
+        get_pumpings_by_range_service::<D>(baby_id, dates.from()?, dates.to()?, pagination).await
+    } else {
+        let last = last_days.unwrap_or_default().days();
+        get_pumpings_by_last_days_service::<D>(baby_id, last, pagination).await
+    }
+}
+
+/// With `store` set a milk bag is created from the pumped volume.
+#[utoipa::path(
+    post,
+    path = "/api/baby/{baby_id}/pumping",
+    tag = "pumping",
+    params(BabyPath),
+    request_body = InputPumpingDto,
+    responses((status = 201, description = "Pumping session created.", body = PumpingRecord))
+)]
+async fn post_pumping<D>(
+    Path(baby_unique_id): Path<String>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    Json(new_pumping): Json<InputPumpingDto>,
+) -> impl IntoResponse
+where
+    D: From<Pumping> + Serialize,
+{
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    post_pumping_service::<D>(new_pumping, baby_id).await
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/baby/{baby_id}/pumping/{record}",
+    tag = "pumping",
+    params(RecordPath),
+    request_body = InputPumpingDto,
+    responses((status = 200, description = "Pumping session updated.", body = PumpingRecord))
+)]
+async fn patch_pumping<D>(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    Json(pumping): Json<InputPumpingDto>,
+) -> impl IntoResponse
+where
+    D: From<Pumping> + Serialize,
+{
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    patch_pumping_service::<D>(pumping, record, baby_id).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/baby/{baby_id}/pumping/{record}",
+    tag = "pumping",
+    params(RecordPath),
+    responses((status = 200, description = "Pumping session deleted.", body = MessageBody))
+)]
+async fn delete_pumping(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse {
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    delete_pumping_service(record, baby_id).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/pumping/{record}",
+    tag = "pumping",
+    params(RecordPath),
+    responses((status = 200, description = "Pumping session found.", body = PumpingRecord))
+)]
+async fn get_pumping_id<D>(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse
+where
+    D: From<Pumping> + Serialize,
+{
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    get_pumping_id_service::<D>(record, baby_id).await
+}
+
+/// Bags sorted by expiry, `available=true` skips empty and expired ones.
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/milk/bags",
+    tag = "milk",
+    params(BabyPath, AvailableBagsDto, PageQuery),
+    responses((status = 200, description = "Milk bags of the baby.", body = MilkBagPage))
+)]
+async fn get_milk_bags<B>(
+    Path(baby_unique_id): Path<String>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    available: Option<Query<AvailableBagsDto>>,
+    page: Option<Query<Pagination>>,
+) -> impl IntoResponse
+where
+    B: From<MilkBag> + Serialize,
+{
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    let pagination = page.unwrap_or_default().0;
+    let available = available.unwrap_or_default().available();
+    get_milk_bags_service::<B>(baby_id, available, pagination).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/baby/{baby_id}/milk/bags",
+    tag = "milk",
+    params(BabyPath),
+    request_body = InputMilkBagDto,
+    responses((status = 201, description = "Milk bag created.", body = MilkBagRecord))
+)]
+async fn post_milk_bag<B>(
+    Path(baby_unique_id): Path<String>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    Json(new_bag): Json<InputMilkBagDto>,
+) -> impl IntoResponse
+where
+    B: From<MilkBag> + Serialize,
+{
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    post_milk_bag_service::<B>(new_bag, baby_id).await
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/baby/{baby_id}/milk/bags/{record}",
+    tag = "milk",
+    params(RecordPath),
+    request_body = InputMilkBagDto,
+    responses((status = 200, description = "Milk bag updated.", body = MilkBagRecord))
+)]
+async fn patch_milk_bag<B>(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    Json(bag): Json<InputMilkBagDto>,
+) -> impl IntoResponse
+where
+    B: From<MilkBag> + Serialize,
+{
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    patch_milk_bag_service::<B>(bag, record, baby_id).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/baby/{baby_id}/milk/bags/{record}",
+    tag = "milk",
+    params(RecordPath),
+    responses((status = 200, description = "Milk bag deleted.", body = MessageBody))
+)]
+async fn delete_milk_bag(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse {
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    delete_milk_bag_service(record, baby_id).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/milk/bags/{record}",
+    tag = "milk",
+    params(RecordPath),
+    responses((status = 200, description = "Milk bag found.", body = MilkBagRecord))
+)]
+async fn get_milk_bag_id<B>(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse
+where
+    B: From<MilkBag> + Serialize,
+{
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    get_milk_bag_id_service::<B>(record, baby_id).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/milk/stash",
+    tag = "milk",
+    params(BabyPath),
+    responses((status = 200, description = "Stored milk by location.", body = MilkStashRecord))
+)]
+async fn get_milk_stash(
+    Path(baby_unique_id): Path<String>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse {
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    get_milk_stash_service(baby_id).await
+}
@@ -0,0 +1,152 @@
+use axum::{
+    extract::{Path, Query},
+    response::IntoResponse,
+    Json,
+};
+use axum_session::SessionRedisPool;
+use axum_session_auth::AuthSession;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    data::{
+        common_structure::{ReminderDto, ReminderDtoV2},
+        query_dto::Pagination,
+        reminder_dto::InputReminderDto,
+    },
+    model::{reminder_model::ReminderRule, session_model::CurrentUser},
+    response::response::{MessageBody, ReminderPage, ReminderRecord},
+    service::{
+        reminder_service::{
+            delete_reminder_service, get_reminder_id_service, get_reminders_service,
+            patch_reminder_service, post_reminder_service,
+        },
+        session_service::check_user_permissions,
+    },
+    utils::version::ApiVersion,
+};
+
+use super::openapi_controller::{BabyPath, PageQuery, RecordPath};
+use super::route_table::{get, Routes};
+
+pub(super) fn route_reminder(version: ApiVersion) -> Routes {
+    match version {
+        ApiVersion::V1 => route_versioned::<ReminderDto>(),
+        ApiVersion::V2 => route_versioned::<ReminderDtoV2>(),
+    }
+}
+
+fn route_versioned<D>() -> Routes
+where
+    D: From<ReminderRule> + Serialize + Send + 'static,
+{
+    Routes::new().nest(
+        "/reminders",
+        Routes::new()
+            .route("/", get(get_reminders::<D>).post(post_reminder::<D>))
+            .route(
+                "/:record",
+                get(get_reminder_id::<D>)
+                    .patch(patch_reminder::<D>)
+                    .delete(delete_reminder),
+            ),
+    )
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/reminders",
+    tag = "reminders",
+    params(BabyPath, PageQuery),
+    responses((status = 200, description = "Reminders of the baby.", body = ReminderPage))
+)]
+async fn get_reminders<D>(
+    Path(baby_unique_id): Path<String>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    page: Option<Query<Pagination>>,
+) -> impl IntoResponse
+where
+    D: From<ReminderRule> + Serialize,
+{
+    let user_id: i32 = auth.id.try_into().unwrap();
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    let pagination = page.unwrap_or_default().0;
+    get_reminders_service::<D>(baby_id, user_id, pagination).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/baby/{baby_id}/reminders",
+    tag = "reminders",
+    params(BabyPath),
+    request_body = InputReminderDto,
+    responses((status = 201, description = "Reminder created.", body = ReminderRecord))
+)]
+async fn post_reminder<D>(
+    Path(baby_unique_id): Path<String>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    Json(new_rule): Json<InputReminderDto>,
+) -> impl IntoResponse
+where
+    D: From<ReminderRule> + Serialize,
+{
+    let user_id: i32 = auth.id.try_into().unwrap();
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    post_reminder_service::<D>(new_rule, baby_id, user_id).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/reminders/{record}",
+    tag = "reminders",
+    params(RecordPath),
+    responses((status = 200, description = "Reminder found.", body = ReminderRecord))
+)]
+async fn get_reminder_id<D>(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse
+where
+    D: From<ReminderRule> + Serialize,
+{
+    let user_id: i32 = auth.id.try_into().unwrap();
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    get_reminder_id_service::<D>(record, baby_id, user_id).await
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/baby/{baby_id}/reminders/{record}",
+    tag = "reminders",
+    params(RecordPath),
+    request_body = InputReminderDto,
+    responses((status = 200, description = "Reminder updated.", body = ReminderRecord))
+)]
+async fn patch_reminder<D>(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    Json(rule): Json<InputReminderDto>,
+) -> impl IntoResponse
+where
+    D: From<ReminderRule> + Serialize,
+{
+    let user_id: i32 = auth.id.try_into().unwrap();
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    patch_reminder_service::<D>(rule, record, baby_id, user_id).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/baby/{baby_id}/reminders/{record}",
+    tag = "reminders",
+    params(RecordPath),
+    responses((status = 200, description = "Reminder deleted.", body = MessageBody))
+)]
+async fn delete_reminder(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse {
+    let user_id: i32 = auth.id.try_into().unwrap();
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    delete_reminder_service(record, baby_id, user_id).await
+}
@@ -0,0 +1,62 @@
+use axum::{
+    extract::{Path, Query},
+    response::IntoResponse,
+};
+use axum_session::SessionRedisPool;
+use axum_session_auth::AuthSession;
+
+use crate::{
+    configuration::constant::GlobalCte,
+    data::{
+        query_dto::{DateRangeDto, LastDaysDto},
+        report_dto::ReportFormatDto,
+    },
+    model::session_model::CurrentUser,
+    service::{
+        report_service::{get_report_last_days_service, get_report_range_service},
+        session_service::check_user_permissions,
+    },
+};
+
+use super::openapi_controller::{BabyPath, RangeQuery};
+use super::route_table::{get, Routes};
+
+pub(super) fn route_report() -> Routes {
+    Routes::new().nest("/report", Routes::new().route("/", get(get_report)))
+}
+
+/// Printable report, html by default. If there are no parameters, it will try to get last 30 days.
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/report",
+    tag = "reports",
+    params(BabyPath, ReportFormatDto, RangeQuery),
+    responses(
+        (
+            status = 200,
+            description = "Report as html or pdf.",
+            content(("text/html" = String), ("application/pdf" = String))
+        )
+    )
+)]
+async fn get_report(
+    Path(baby_unique_id): Path<String>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    format: Option<Query<ReportFormatDto>>,
+    range: Option<Query<DateRangeDto>>,
+    last_days: Option<Query<LastDaysDto>>,
+) -> impl IntoResponse {
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    let format = format.unwrap_or_default().format()?;
+    if range.is_some() {
+        let dates = range.unwrap();
+        get_report_range_service(baby_id, dates.from()?, dates.to()?, format).await
+    } else {
+        let last = last_days
+            .unwrap_or(Query(LastDaysDto::new(
+                GlobalCte::ReportLastDaysDefault.get(),
+            )))
+            .days();
+        get_report_last_days_service(baby_id, last, format).await
+    }
+}
@@ -0,0 +1,161 @@
+use std::convert::Infallible;
+
+use axum::{
+    body::Body,
+    handler::Handler,
+    response::IntoResponse,
+    routing::{self, MethodRouter, Route},
+    Router,
+};
+use hyper::{Method, Request};
+use tower::{Layer, Service};
+
+/// Router that keeps the method and path of every route it serves, so they can be checked
+/// against the OpenAPI document.
+pub struct Routes {
+    router: Router,
+    table: Vec<(Method, String)>,
+}
+
+impl Routes {
+    pub fn new() -> Self {
+        Self {
+            router: Router::new(),
+            table: Vec::new(),
+        }
+    }
+
+    pub fn route(mut self, path: &str, methods: Methods) -> Self {
+        self.table.extend(
+            methods
+                .methods
+                .into_iter()
+                .map(|method| (method, path.to_string())),
+        );
+        self.router = self.router.route(path, methods.router);
+        self
+    }
+
+    /// Paths of `routes` go under `path`, their `/` is `path` itself.
+    pub fn nest(mut self, path: &str, routes: Routes) -> Self {
+        self.table.extend(
+            routes
+                .table
+                .into_iter()
+                .map(|(method, route)| match route.as_str() {
+                    "/" => (method, path.to_string()),
+                    _ => (method, format!("{path}{route}")),
+                }),
+        );
+        self.router = self.router.nest(path, routes.router);
+        self
+    }
+
+    pub fn merge(mut self, routes: Routes) -> Self {
+        self.table.extend(routes.table);
+        self.router = self.router.merge(routes.router);
+        self
+    }
+
+    pub fn layer<L>(mut self, layer: L) -> Self
+    where
+        L: Layer<Route> + Clone + Send + 'static,
+        L::Service: Service<Request<Body>> + Clone + Send + 'static,
+        <L::Service as Service<Request<Body>>>::Response: IntoResponse + 'static,
+        <L::Service as Service<Request<Body>>>::Error: Into<Infallible> + 'static,
+        <L::Service as Service<Request<Body>>>::Future: Send + 'static,
+    {
+        self.router = self.router.layer(layer);
+        self
+    }
+
+    /// Method and path of every route, parameters written as `:param`.
+    pub fn table(&self) -> &[(Method, String)] {
+        &self.table
+    }
+
+    pub fn into_router(self) -> Router {
+        self.router
+    }
+}
+
+impl Default for Routes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Handlers of one path, along with the methods they answer.
+pub struct Methods {
+    methods: Vec<Method>,
+    router: MethodRouter,
+}
+
+/// Same as the functions and `MethodRouter` methods of `axum::routing`, recording the method.
+macro_rules! method_routes {
+    ($($name:ident => $method:ident),+ $(,)?) => {
+        $(
+            // Some methods only follow others so far, like `put`.
+            #[allow(dead_code)]
+            pub fn $name<H, T>(handler: H) -> Methods
+            where
+                H: Handler<T, (), Body>,
+                T: 'static,
+            {
+                Methods {
+                    methods: vec![Method::$method],
+                    router: routing::$name(handler),
+                }
+            }
+        )+
+
+        impl Methods {
+            $(
+                pub fn $name<H, T>(mut self, handler: H) -> Self
+                where
+                    H: Handler<T, (), Body>,
+                    T: 'static,
+                {
+                    self.methods.push(Method::$method);
+                    self.router = self.router.$name(handler);
+                    self
+                }
+            )+
+        }
+    };
+}
+
+method_routes!(
+    get => GET,
+    post => POST,
+    put => PUT,
+    patch => PATCH,
+    delete => DELETE,
+);
+
+#[cfg(test)]
+mod test_route_table {
+    use super::*;
+
+    async fn handler() {}
+
+    #[test]
+    fn test_nested_table() {
+        let records = Routes::new()
+            .route("/", get(handler).post(handler))
+            .route("/:record", delete(handler));
+        let routes = Routes::new()
+            .route("/dashboard", get(handler))
+            .nest("/meals", records);
+        let table = Routes::new().nest("/baby", routes);
+        assert_eq!(
+            table.table(),
+            [
+                (Method::GET, String::from("/baby/dashboard")),
+                (Method::GET, String::from("/baby/meals")),
+                (Method::POST, String::from("/baby/meals")),
+                (Method::DELETE, String::from("/baby/meals/:record")),
+            ]
+        );
+    }
+}
@@ -0,0 +1,43 @@
+use axum::{extract::Path, response::IntoResponse, Json};
+use axum_session::SessionRedisPool;
+use axum_session_auth::AuthSession;
+use serde::Serialize;
+
+use crate::{
+    data::sync_dto::{InputSyncDto, SyncData, SyncDataV2},
+    model::{session_model::CurrentUser, sync_model::SyncChanges},
+    response::response::SyncRecord,
+    service::{session_service::check_user_permissions, sync_service::post_sync_service},
+    utils::version::ApiVersion,
+};
+
+use super::openapi_controller::BabyPath;
+use super::route_table::{post, Routes};
+
+pub(super) fn route_sync(version: ApiVersion) -> Routes {
+    match version {
+        ApiVersion::V1 => Routes::new().route("/sync", post(post_sync::<SyncData>)),
+        ApiVersion::V2 => Routes::new().route("/sync", post(post_sync::<SyncDataV2>)),
+    }
+}
+
+/// Offline clients push queued meals and dreams and pull server changes.
+#[utoipa::path(
+    post,
+    path = "/api/baby/{baby_id}/sync",
+    tag = "sync",
+    params(BabyPath),
+    request_body = InputSyncDto,
+    responses((status = 200, description = "Server changes since `cursor`.", body = SyncRecord))
+)]
+async fn post_sync<S>(
+    Path(baby_unique_id): Path<String>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    Json(changes): Json<InputSyncDto>,
+) -> impl IntoResponse
+where
+    S: From<SyncChanges> + Serialize,
+{
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    post_sync_service::<S>(changes, baby_id).await
+}
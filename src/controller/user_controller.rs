@@ -1,6 +1,7 @@
 use crate::{
     data::user_dto::{FindUserDto, LoginDto, NewUserDto, UpdateUserDto},
     model::session_model::CurrentUser,
+    response::response::{MessageBody, SessionRecord, UserRecord},
     service::{
         session_service::{
             get_current_user_service, login_required, login_session, logout_user_session,
@@ -12,16 +13,14 @@ use crate::{
         },
     },
 };
-use axum::{
-    response::IntoResponse,
-    routing::{get, post},
-    Json, Router,
-};
+use axum::{response::IntoResponse, Json};
 use axum_session::SessionRedisPool;
 use axum_session_auth::AuthSession;
 
-pub(crate) fn route_user() -> Router {
-    let routes = Router::new()
+use super::route_table::{get, post, Routes};
+
+pub(crate) fn route_user() -> Routes {
+    let routes = Routes::new()
         .route("/register", post(post_new_user))
         .route("/user", post(post_find_user))
         .route(
@@ -34,9 +33,16 @@ pub(crate) fn route_user() -> Router {
             "/profile",
             get(get_user_by_id).patch(patch_user).delete(delete_user),
         );
-    Router::new().nest("/auth", routes)
+    Routes::new().nest("/auth", routes)
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/auth/register",
+    tag = "auth",
+    request_body = NewUserDto,
+    responses((status = 201, description = "User created and logged in.", body = SessionRecord))
+)]
 async fn post_new_user(
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
     Json(new_user): Json<NewUserDto>,
@@ -50,10 +56,31 @@ async fn post_new_user(
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/auth/user",
+    tag = "auth",
+    request_body = FindUserDto,
+    responses((status = 200, description = "User found.", body = UserRecord))
+)]
 async fn post_find_user(Json(data): Json<FindUserDto>) -> impl IntoResponse {
     post_find_user_service(data).await
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/auth/session",
+    tag = "auth",
+    request_body = LoginDto,
+    responses(
+        (
+            status = 200,
+            description = "Logged in,
+            the session cookie is set.",
+            body = SessionRecord
+        )
+    )
+)]
 async fn post_session_user(
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
     Json(login): Json<LoginDto>,
@@ -67,6 +94,12 @@ async fn post_session_user(
     }
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/auth/session",
+    tag = "auth",
+    responses((status = 200, description = "Logged out.", body = MessageBody))
+)]
 async fn delete_session_user(
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
 ) -> impl IntoResponse {
@@ -78,12 +111,31 @@ async fn delete_session_user(
     }
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/auth/session",
+    tag = "auth",
+    responses(
+        (
+            status = 200,
+            description = "Current user,
+            anonymous if not logged in.",
+            body = SessionRecord
+        )
+    )
+)]
 async fn get_session_user(
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
 ) -> impl IntoResponse {
     get_current_user_service(auth)
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/auth/profile",
+    tag = "auth",
+    responses((status = 200, description = "Profile of the current user.", body = UserRecord))
+)]
 async fn get_user_by_id(
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
 ) -> impl IntoResponse {
@@ -92,6 +144,13 @@ async fn get_user_by_id(
     get_user_by_id_service(binding_id).await
 }
 
+#[utoipa::path(
+    patch,
+    path = "/api/auth/profile",
+    tag = "auth",
+    request_body = UpdateUserDto,
+    responses((status = 200, description = "Profile updated.", body = UserRecord))
+)]
 async fn patch_user(
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
     Json(profile): Json<UpdateUserDto>,
@@ -101,6 +160,12 @@ async fn patch_user(
     patch_user_service(binding_id, profile).await
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/auth/profile",
+    tag = "auth",
+    responses((status = 200, description = "User deactivated and logged out.", body = MessageBody))
+)]
 async fn delete_user(
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
 ) -> impl IntoResponse {
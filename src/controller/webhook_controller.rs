@@ -0,0 +1,182 @@
+use axum::{
+    extract::{Path, Query},
+    response::IntoResponse,
+    Json,
+};
+use axum_session::SessionRedisPool;
+use axum_session_auth::AuthSession;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    data::{
+        common_structure::{WebhookDeliveryDto, WebhookDeliveryDtoV2, WebhookDto, WebhookDtoV2},
+        query_dto::Pagination,
+        webhook_dto::InputWebhookDto,
+    },
+    model::{
+        session_model::CurrentUser,
+        webhook_model::{Webhook, WebhookDelivery},
+    },
+    response::response::{MessageBody, WebhookDeliveryPage, WebhookPage, WebhookRecord},
+    service::{
+        session_service::check_user_permissions,
+        webhook_service::{
+            delete_webhook_service, get_webhook_deliveries_service, get_webhook_id_service,
+            get_webhooks_service, patch_webhook_service, post_webhook_service,
+        },
+    },
+    utils::version::ApiVersion,
+};
+
+use super::openapi_controller::{BabyPath, PageQuery, RecordPath};
+use super::route_table::{get, Routes};
+
+pub(super) fn route_webhook(version: ApiVersion) -> Routes {
+    match version {
+        ApiVersion::V1 => route_versioned::<WebhookDto, WebhookDeliveryDto>(),
+        ApiVersion::V2 => route_versioned::<WebhookDtoV2, WebhookDeliveryDtoV2>(),
+    }
+}
+
+/// `W` is the webhook and `D` the delivery sent.
+fn route_versioned<W, D>() -> Routes
+where
+    W: From<Webhook> + From<(Webhook, Option<String>)> + Serialize + Send + 'static,
+    D: From<WebhookDelivery> + Serialize + Send + 'static,
+{
+    Routes::new().nest(
+        "/webhooks",
+        Routes::new()
+            .route("/", get(get_webhooks::<W>).post(post_webhook::<W>))
+            .route(
+                "/:record",
+                get(get_webhook_id::<W>)
+                    .patch(patch_webhook::<W>)
+                    .delete(delete_webhook),
+            )
+            .route("/:record/deliveries", get(get_webhook_deliveries::<D>)),
+    )
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/webhooks",
+    tag = "webhooks",
+    params(BabyPath, PageQuery),
+    responses((status = 200, description = "Webhooks of the baby.", body = WebhookPage))
+)]
+async fn get_webhooks<W>(
+    Path(baby_unique_id): Path<String>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    page: Option<Query<Pagination>>,
+) -> impl IntoResponse
+where
+    W: From<Webhook> + Serialize,
+{
+    let user_id: i32 = auth.id.try_into().unwrap();
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    let pagination = page.unwrap_or_default().0;
+    get_webhooks_service::<W>(baby_id, user_id, pagination).await
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/baby/{baby_id}/webhooks",
+    tag = "webhooks",
+    params(BabyPath),
+    request_body = InputWebhookDto,
+    responses((status = 201, description = "Webhook created.", body = WebhookRecord))
+)]
+async fn post_webhook<W>(
+    Path(baby_unique_id): Path<String>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    Json(new_webhook): Json<InputWebhookDto>,
+) -> impl IntoResponse
+where
+    W: From<(Webhook, Option<String>)> + Serialize,
+{
+    let user_id: i32 = auth.id.try_into().unwrap();
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    post_webhook_service::<W>(new_webhook, baby_id, user_id).await
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/webhooks/{record}",
+    tag = "webhooks",
+    params(RecordPath),
+    responses((status = 200, description = "Webhook found.", body = WebhookRecord))
+)]
+async fn get_webhook_id<W>(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse
+where
+    W: From<Webhook> + Serialize,
+{
+    let user_id: i32 = auth.id.try_into().unwrap();
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    get_webhook_id_service::<W>(record, baby_id, user_id).await
+}
+
+#[utoipa::path(
+    patch,
+    path = "/api/baby/{baby_id}/webhooks/{record}",
+    tag = "webhooks",
+    params(RecordPath),
+    request_body = InputWebhookDto,
+    responses((status = 200, description = "Webhook updated.", body = WebhookRecord))
+)]
+async fn patch_webhook<W>(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    Json(webhook): Json<InputWebhookDto>,
+) -> impl IntoResponse
+where
+    W: From<Webhook> + Serialize,
+{
+    let user_id: i32 = auth.id.try_into().unwrap();
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    patch_webhook_service::<W>(webhook, record, baby_id, user_id).await
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/baby/{baby_id}/webhooks/{record}",
+    tag = "webhooks",
+    params(RecordPath),
+    responses((status = 200, description = "Webhook deleted.", body = MessageBody))
+)]
+async fn delete_webhook(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+) -> impl IntoResponse {
+    let user_id: i32 = auth.id.try_into().unwrap();
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    delete_webhook_service(record, baby_id, user_id).await
+}
+
+/// Delivery log, newest first.
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/webhooks/{record}/deliveries",
+    tag = "webhooks",
+    params(RecordPath, PageQuery),
+    responses(
+        (status = 200, description = "Deliveries of the webhook.", body = WebhookDeliveryPage)
+    )
+)]
+async fn get_webhook_deliveries<D>(
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    page: Option<Query<Pagination>>,
+) -> impl IntoResponse
+where
+    D: From<WebhookDelivery> + Serialize,
+{
+    let user_id: i32 = auth.id.try_into().unwrap();
+    let baby_id = check_user_permissions(auth, &baby_unique_id)?;
+    let pagination = page.unwrap_or_default().0;
+    get_webhook_deliveries_service::<D>(record, baby_id, user_id, pagination).await
+}
@@ -1,19 +1,20 @@
 use axum::{
     extract::{Path, Query},
     response::IntoResponse,
-    routing::get,
-    Json, Router,
+    Json,
 };
 use axum_session::SessionRedisPool;
 use axum_session_auth::AuthSession;
+use uuid::Uuid;
 
 use crate::{
     configuration::constant::GlobalCte,
     data::{
-        query_dto::{AllRecords, DateDto, DateRangeDto, LastDaysDto, Pagination},
+        query_dto::{AllRecords, DateDto, DateRangeDto, LastDaysDto, ListQuery, Pagination},
         weight_dto::InputWeightDto,
     },
     model::session_model::CurrentUser,
+    response::response::{MessageBody, WeightPage, WeightRecord},
     service::{
         session_service::check_user_permissions,
         weight_service::{
@@ -22,19 +23,17 @@ use crate::{
             post_weight_service,
         },
     },
+    utils::etag::IfMatch,
 };
 
-pub(super) fn route_weight() -> Router {
-    Router::new().nest(
+use super::openapi_controller::{BabyPath, DayQuery, PageQuery, RangeQuery, RecordPath, SortQuery};
+use super::route_table::{get, Routes};
+
+pub(super) fn route_weight() -> Routes {
+    Routes::new().nest(
         "/weights",
-        Router::new()
-            .route(
-                "/",
-                get(get_weights)
-                    .post(post_weight)
-                    .patch(patch_weight)
-                    .delete(delete_weight),
-            )
+        Routes::new()
+            .route("/", get(get_weights).post(post_weight))
             .route(
                 "/:record",
                 get(get_weight_id).patch(patch_weight).delete(delete_weight),
@@ -42,6 +41,13 @@ pub(super) fn route_weight() -> Router {
     )
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/weights",
+    tag = "weights",
+    params(BabyPath, DayQuery, RangeQuery, PageQuery, SortQuery),
+    responses((status = 200, description = "Weights of the baby.", body = WeightPage))
+)]
 async fn get_weights(
     Path(baby_unique_id): Path<String>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
@@ -50,27 +56,36 @@ async fn get_weights(
     page: Option<Query<Pagination>>,
     range: Option<Query<DateRangeDto>>,
     last_days: Option<Query<LastDaysDto>>,
+    list: ListQuery,
 ) -> impl IntoResponse {
     let baby_id = check_user_permissions(auth, &baby_unique_id)?;
     let pagination = page.unwrap_or_default().0;
     if all_records.is_some() && all_records.unwrap().all() {
-        get_weights_all_service(baby_id, pagination).await
+        get_weights_all_service(baby_id, list, pagination).await
     } else if date.is_some() {
         let day = date.unwrap().date()?;
-        get_weight_range_service(baby_id, day, day, pagination).await
+        get_weight_range_service(baby_id, day, day, list, pagination).await
     } else if range.is_some() {
         let dates = range.unwrap();
-        get_weight_range_service(baby_id, dates.from()?, dates.to()?, pagination).await
+        get_weight_range_service(baby_id, dates.from()?, dates.to()?, list, pagination).await
     } else {
         let last = last_days
             .unwrap_or(axum::extract::Query(LastDaysDto::new(
                 GlobalCte::WeightLastDaysDefault.get(),
             )))
             .days();
-        get_weights_by_last_days(baby_id, last, pagination).await
+        get_weights_by_last_days(baby_id, last, list, pagination).await
     }
 }
 
+#[utoipa::path(
+    post,
+    path = "/api/baby/{baby_id}/weights",
+    tag = "weights",
+    params(BabyPath),
+    request_body = InputWeightDto,
+    responses((status = 201, description = "Weight created.", body = WeightRecord))
+)]
 async fn post_weight(
     Path(baby_unique_id): Path<String>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
@@ -80,25 +95,77 @@ async fn post_weight(
     post_weight_service(new_measure, baby_id).await
 }
 
+#[utoipa::path(
+    patch,
+    path = "/api/baby/{baby_id}/weights/{record}",
+    tag = "weights",
+    params(
+        RecordPath,
+        (
+            "If-Match" = Option<String>,
+            Header,
+            description = "Last `ETag` read, 412 if outdated, 428 if missing in v2"
+        )
+    ),
+    request_body = InputWeightDto,
+    responses(
+        (
+            status = 200,
+            description = "Weight updated.",
+            body = WeightRecord,
+            headers(("ETag" = String, description = "Record version"))
+        )
+    )
+)]
 async fn patch_weight(
-    Path((baby_unique_id, record)): Path<(String, i32)>,
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    if_match: IfMatch,
     Json(measure): Json<InputWeightDto>,
 ) -> impl IntoResponse {
     let baby_id = check_user_permissions(auth, &baby_unique_id)?;
-    patch_weight_service(measure, record, baby_id).await
+    patch_weight_service(measure, record, baby_id, if_match).await
 }
 
+#[utoipa::path(
+    delete,
+    path = "/api/baby/{baby_id}/weights/{record}",
+    tag = "weights",
+    params(
+        RecordPath,
+        (
+            "If-Match" = Option<String>,
+            Header,
+            description = "Last `ETag` read, 412 if outdated, 428 if missing in v2"
+        )
+    ),
+    responses((status = 200, description = "Weight deleted.", body = MessageBody))
+)]
 async fn delete_weight(
-    Path((baby_unique_id, record)): Path<(String, i32)>,
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    if_match: IfMatch,
 ) -> impl IntoResponse {
     let baby_id = check_user_permissions(auth, &baby_unique_id)?;
-    delete_weight_service(record, baby_id).await
+    delete_weight_service(record, baby_id, if_match).await
 }
 
+#[utoipa::path(
+    get,
+    path = "/api/baby/{baby_id}/weights/{record}",
+    tag = "weights",
+    params(RecordPath),
+    responses(
+        (
+            status = 200,
+            description = "Weight found.",
+            body = WeightRecord,
+            headers(("ETag" = String, description = "Record version"))
+        )
+    )
+)]
 async fn get_weight_id(
-    Path((baby_unique_id, record)): Path<(String, i32)>,
+    Path((baby_unique_id, record)): Path<(String, Uuid)>,
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
 ) -> impl IntoResponse {
     let baby_id = check_user_permissions(auth, &baby_unique_id)?;
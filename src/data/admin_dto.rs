@@ -1,7 +1,10 @@
 use chrono::NaiveDateTime;
 use serde::Serialize;
+use utoipa::ToSchema;
 
-#[derive(Serialize)]
+use super::common_structure::{BabyDto, DreamDto, MealDto, WeightDto};
+
+#[derive(Serialize, ToSchema)]
 pub struct AdminUserData {
     pub username: String,
     pub email: Option<String>,
@@ -11,9 +14,18 @@ pub struct AdminUserData {
     pub updated_at: Option<NaiveDateTime>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct AdminBabyData {
     pub name: String,
     pub belongs_to: i32,
     pub added_on: NaiveDateTime,
 }
+
+/// Baby with every meal, dream and weight, for the admin CLI export.
+#[derive(Serialize)]
+pub struct BabyExportData {
+    pub baby: BabyDto,
+    pub meals: Vec<MealDto>,
+    pub dreams: Vec<DreamDto>,
+    pub weights: Vec<WeightDto>,
+}
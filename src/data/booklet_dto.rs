@@ -0,0 +1,44 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Deserialize, ToSchema)]
+pub struct InputBookletEntryDto {
+    pub schedule_id: Option<i32>,
+    pub name: Option<String>,
+    pub date: Option<String>,
+    pub notes: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct InputScheduleItemDto {
+    pub kind: String,
+    pub name: String,
+    pub age_months: i16,
+    pub tolerance_months: Option<i16>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct BookletEntryData {
+    pub schedule_id: Option<i32>,
+    pub name: String,
+    pub date: String,
+    pub notes: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ScheduleItemData {
+    pub kind: String,
+    pub name: String,
+    pub age_months: i16,
+    pub tolerance_months: i16,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ScheduleStatusData {
+    pub kind: String,
+    pub name: String,
+    pub age_months: i16,
+    pub due_date: String,
+    pub status: String,
+    pub done_date: Option<String>,
+}
@@ -0,0 +1,9 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Serialize, Debug, ToSchema)]
+pub struct CalendarData {
+    pub token: Uuid,
+    pub url: String,
+}
@@ -1,13 +1,27 @@
 use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
 
 use super::{
     admin_dto::{AdminBabyData, AdminUserData},
     baby_dto::BabyData,
-    dream_dto::DreamData,
-    meal_dto::MealData,
+    booklet_dto::{BookletEntryData, ScheduleItemData, ScheduleStatusData},
+    calendar_dto::CalendarData,
+    dashboard_dto::{DashboardData, DashboardDataV2},
+    dream_dto::{DreamData, DreamDataV2},
+    meal_dto::{MealData, MealDataV2},
+    medication_dto::{
+        DoseData, DoseDataV2, MedicationData, MedicationStatusData, MedicationStatusDataV2,
+    },
+    note_dto::{NoteData, NoteDataV2},
+    notification_dto::{NotificationData, NotificationDataV2},
+    pumping_dto::{MilkBagData, MilkBagDataV2, MilkStashData, PumpingData, PumpingDataV2},
+    reminder_dto::{ReminderData, ReminderDataV2},
     role_dto::RoleData,
     session_dto::UserSessionData,
+    sync_dto::{DeletedRecordData, DeletedRecordDataV2},
     user_dto::UserData,
+    webhook_dto::{WebhookData, WebhookDataV2, WebhookDeliveryData, WebhookDeliveryDataV2},
     weight_dto::WeightData,
 };
 
@@ -18,10 +32,28 @@ pub enum DataType {
     Meal,
     Weight,
     Role,
+    Calendar,
+    Note,
+    Medication,
+    Dose,
+    MedicationStatus,
+    Milestone,
+    Vaccination,
+    Schedule,
+    Booklet,
+    Pumping,
+    MilkBag,
+    MilkStash,
+    Dashboard,
+    Webhook,
+    WebhookDelivery,
+    Reminder,
+    Notification,
+    DeletedRecord,
 }
 
 impl DataType {
-    fn get(self) -> &'static str {
+    pub fn get(self) -> &'static str {
         match self {
             DataType::User => "user",
             DataType::Baby => "baby",
@@ -29,25 +61,89 @@ impl DataType {
             DataType::Meal => "meal",
             DataType::Weight => "weight",
             DataType::Role => "role",
+            DataType::Calendar => "calendar",
+            DataType::Note => "note",
+            DataType::Medication => "medication",
+            DataType::Dose => "dose",
+            DataType::MedicationStatus => "medication_status",
+            DataType::Milestone => "milestone",
+            DataType::Vaccination => "vaccination",
+            DataType::Schedule => "schedule",
+            DataType::Booklet => "booklet",
+            DataType::Pumping => "pumping",
+            DataType::MilkBag => "milk_bag",
+            DataType::MilkStash => "milk_stash",
+            DataType::Dashboard => "dashboard",
+            DataType::Webhook => "webhook",
+            DataType::WebhookDelivery => "webhook_delivery",
+            DataType::Reminder => "reminder",
+            DataType::Notification => "notification",
+            DataType::DeletedRecord => "deleted_record",
         }
     }
 }
 
-#[derive(Serialize, Debug)]
-pub struct BasicDataStruct<T>
+/// Baby records are identified by their public id, `I` is `i32` for everything else.
+///
+/// Every DTO is an alias, they are the schemas documented in the OpenAPI document.
+#[derive(Serialize, Debug, ToSchema)]
+#[aliases(
+    SessionDto = BasicDataStruct<UserSessionData, i32>,
+    UserDto = BasicDataStruct<UserData, i32>,
+    BabyDto = BasicDataStruct<BabyData, Uuid>,
+    DreamDto = BasicDataStruct<DreamData, Uuid>,
+    DreamDtoV2 = BasicDataStruct<DreamDataV2, Uuid>,
+    MealDto = BasicDataStruct<MealData, Uuid>,
+    MealDtoV2 = BasicDataStruct<MealDataV2, Uuid>,
+    WeightDto = BasicDataStruct<WeightData, Uuid>,
+    RoleDto = BasicDataStruct<RoleData, i32>,
+    AdminUserDto = BasicDataStruct<AdminUserData, i32>,
+    AdminBabyDto = BasicDataStruct<AdminBabyData, i32>,
+    CalendarDto = BasicDataStruct<CalendarData, Uuid>,
+    NoteDto = BasicDataStruct<NoteData, Uuid>,
+    NoteDtoV2 = BasicDataStruct<NoteDataV2, Uuid>,
+    MedicationDto = BasicDataStruct<MedicationData, Uuid>,
+    DoseDto = BasicDataStruct<DoseData, Uuid>,
+    DoseDtoV2 = BasicDataStruct<DoseDataV2, Uuid>,
+    MedicationStatusDto = BasicDataStruct<MedicationStatusData, Uuid>,
+    MedicationStatusDtoV2 = BasicDataStruct<MedicationStatusDataV2, Uuid>,
+    BookletEntryDto = BasicDataStruct<BookletEntryData, Uuid>,
+    ScheduleItemDto = BasicDataStruct<ScheduleItemData, i32>,
+    ScheduleStatusDto = BasicDataStruct<ScheduleStatusData, i32>,
+    PumpingDto = BasicDataStruct<PumpingData, Uuid>,
+    PumpingDtoV2 = BasicDataStruct<PumpingDataV2, Uuid>,
+    MilkBagDto = BasicDataStruct<MilkBagData, Uuid>,
+    MilkBagDtoV2 = BasicDataStruct<MilkBagDataV2, Uuid>,
+    MilkStashDto = BasicDataStruct<MilkStashData, Uuid>,
+    DashboardDto = BasicDataStruct<DashboardData, Uuid>,
+    DashboardDtoV2 = BasicDataStruct<DashboardDataV2, Uuid>,
+    WebhookDto = BasicDataStruct<WebhookData, Uuid>,
+    WebhookDtoV2 = BasicDataStruct<WebhookDataV2, Uuid>,
+    WebhookDeliveryDto = BasicDataStruct<WebhookDeliveryData, Uuid>,
+    WebhookDeliveryDtoV2 = BasicDataStruct<WebhookDeliveryDataV2, Uuid>,
+    ReminderDto = BasicDataStruct<ReminderData, Uuid>,
+    ReminderDtoV2 = BasicDataStruct<ReminderDataV2, Uuid>,
+    NotificationDto = BasicDataStruct<NotificationData, Uuid>,
+    NotificationDtoV2 = BasicDataStruct<NotificationDataV2, Uuid>,
+    DeletedRecordDto = BasicDataStruct<DeletedRecordData, Uuid>,
+    DeletedRecordDtoV2 = BasicDataStruct<DeletedRecordDataV2, Uuid>,
+)]
+pub struct BasicDataStruct<T, I = i32>
 where
     T: Serialize,
+    I: Serialize,
 {
-    pub id: i32,
+    pub id: I,
     r#type: &'static str,
     pub attributes: T,
 }
 
-impl<T> BasicDataStruct<T>
+impl<T, I> BasicDataStruct<T, I>
 where
     T: Serialize,
+    I: Serialize,
 {
-    pub fn new(id: i32, r#type: DataType, attributes: T) -> Self {
+    pub fn new(id: I, r#type: DataType, attributes: T) -> Self {
         Self {
             id,
             r#type: r#type.get(),
@@ -55,13 +151,3 @@ where
         }
     }
 }
-
-pub type SessionDto = BasicDataStruct<UserSessionData>;
-pub type UserDto = BasicDataStruct<UserData>;
-pub type BabyDto = BasicDataStruct<BabyData>;
-pub type DreamDto = BasicDataStruct<DreamData>;
-pub type MealDto = BasicDataStruct<MealData>;
-pub type WeightDto = BasicDataStruct<WeightData>;
-pub type RoleDto = BasicDataStruct<RoleData>;
-pub type AdminUserDto = BasicDataStruct<AdminUserData>;
-pub type AdminBabyDto = BasicDataStruct<AdminBabyData>;
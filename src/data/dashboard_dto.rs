@@ -0,0 +1,27 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::common_structure::{DreamDto, DreamDtoV2, MealDto, MealDtoV2};
+
+#[derive(Serialize, ToSchema)]
+pub struct DashboardData {
+    pub unique_id: Uuid,
+    pub name: String,
+    pub last_meal: Option<MealDto>,
+    pub since_last_meal: Option<String>,
+    pub last_dream: Option<DreamDto>,
+    pub since_last_dream: Option<String>,
+    pub sleeping: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DashboardDataV2 {
+    pub unique_id: Uuid,
+    pub name: String,
+    pub last_meal: Option<MealDtoV2>,
+    pub since_last_meal_seconds: Option<i64>,
+    pub last_dream: Option<DreamDtoV2>,
+    pub since_last_dream_seconds: Option<i64>,
+    pub sleeping: bool,
+}
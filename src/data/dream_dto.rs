@@ -1,22 +1,89 @@
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
 
-#[derive(Deserialize)]
+use crate::response::error::ApiError;
+
+use super::query_dto::{ListQuery, Sort};
+
+#[derive(Deserialize, ToSchema)]
 pub struct InputDreamDto {
     pub from_date: Option<String>,
     pub to_date: Option<String>,
+    /// Generated by the client, a replayed record returns the one already saved.
+    pub client_id: Option<Uuid>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct DreamSummaryDto {
     pub date: String,
     pub summary: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct DreamData {
     pub from_date: String,
     pub from_time: String,
     pub to_date: String,
     pub to_time: String,
     pub elapsed: String,
+    pub client_id: Option<Uuid>,
+}
+
+/// Dream in `/api/v2`, a dream still running has no end and lasts 0 seconds.
+#[derive(Serialize, ToSchema)]
+pub struct DreamDataV2 {
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub duration_seconds: i64,
+    pub client_id: Option<Uuid>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DreamSummaryDtoV2 {
+    pub date: NaiveDate,
+    pub total_seconds: i64,
+}
+
+/// Optional filters and order for dream lists, durations in minutes.
+#[derive(Default)]
+pub struct DreamFilterDto {
+    duration_gte: Option<i32>,
+    duration_lte: Option<i32>,
+    open: Option<bool>,
+    sort: Option<Sort>,
+}
+
+impl DreamFilterDto {
+    pub fn duration_gte(&self) -> Option<i32> {
+        self.duration_gte
+    }
+
+    pub fn duration_lte(&self) -> Option<i32> {
+        self.duration_lte
+    }
+
+    /// Dreams without an end yet, or finished ones if `false`.
+    pub fn open(&self) -> Option<bool> {
+        self.open
+    }
+
+    pub fn sort(&self) -> Option<&Sort> {
+        self.sort.as_ref()
+    }
+}
+
+impl TryFrom<&ListQuery> for DreamFilterDto {
+    type Error = ApiError;
+
+    fn try_from(list: &ListQuery) -> Result<Self, Self::Error> {
+        list.supports(&["date"], &["duration_gte", "duration_lte", "open"])?;
+        Ok(Self {
+            duration_gte: list.filter("duration_gte")?,
+            duration_lte: list.filter("duration_lte")?,
+            open: list.filter("open")?,
+            sort: list.sort().cloned(),
+        })
+    }
 }
@@ -0,0 +1,11 @@
+use serde::Serialize;
+
+/// Message published to a baby's channel, `data` is the record as returned by the API.
+#[derive(Serialize)]
+pub struct EventData<'a, T>
+where
+    T: Serialize,
+{
+    pub event: &'static str,
+    pub data: &'a T,
+}
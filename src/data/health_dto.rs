@@ -0,0 +1,29 @@
+use serde::Serialize;
+
+#[derive(Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Up,
+    Down,
+}
+
+#[derive(Serialize)]
+pub struct HealthDto {
+    pub status: HealthStatus,
+}
+
+/// One dependency check, `latency_ms` is how long it took to answer or fail.
+#[derive(Serialize)]
+pub struct DependencyDto {
+    pub status: HealthStatus,
+    pub latency_ms: u128,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct ReadinessDto {
+    pub status: HealthStatus,
+    pub postgres: DependencyDto,
+    pub redis: DependencyDto,
+}
@@ -1,13 +1,25 @@
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
 
-#[derive(Deserialize)]
+use crate::response::error::ApiError;
+
+use super::query_dto::{ListQuery, Sort};
+
+#[derive(Deserialize, ToSchema)]
 pub struct InputMealDto {
     pub date: Option<String>,
     pub quantity: Option<i16>,
     pub to_time: Option<String>,
+    /// Bottle feed from a milk bag, `quantity` is required and taken from the bag. Changing it
+    /// later takes the difference from the bag or gives it back.
+    pub milk_bag_id: Option<Uuid>,
+    /// Generated by the client, a replayed record returns the one already saved.
+    pub client_id: Option<Uuid>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct MealSummaryDto {
     pub date: String,
     pub total_feedings: u8,
@@ -15,10 +27,96 @@ pub struct MealSummaryDto {
     pub formula: i16,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct MealData {
     pub date: String,
     pub start_time: String,
     pub quantity: i16,
     pub elapsed: String,
+    pub milk_bag_id: Option<Uuid>,
+    pub client_id: Option<Uuid>,
+}
+
+/// Meal in `/api/v2`, a feeding still running has no end and lasts 0 seconds.
+#[derive(Serialize, ToSchema)]
+pub struct MealDataV2 {
+    pub started_at: DateTime<Utc>,
+    pub ended_at: Option<DateTime<Utc>>,
+    pub duration_seconds: i64,
+    /// Millilitres of formula or expressed milk, missing for nursing.
+    pub quantity: Option<i16>,
+    pub milk_bag_id: Option<Uuid>,
+    pub client_id: Option<Uuid>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MealSummaryDtoV2 {
+    pub date: NaiveDate,
+    pub total_feedings: u8,
+    pub nursing_seconds: i64,
+    /// Millilitres.
+    pub formula: i16,
+}
+
+/// Optional filters and order for meal lists, durations in minutes.
+#[derive(Default)]
+pub struct MealFilterDto {
+    quantity_gte: Option<i16>,
+    quantity_lte: Option<i16>,
+    duration_gte: Option<i32>,
+    duration_lte: Option<i32>,
+    open: Option<bool>,
+    sort: Option<Sort>,
+}
+
+impl MealFilterDto {
+    pub fn quantity_gte(&self) -> Option<i16> {
+        self.quantity_gte
+    }
+
+    pub fn quantity_lte(&self) -> Option<i16> {
+        self.quantity_lte
+    }
+
+    pub fn duration_gte(&self) -> Option<i32> {
+        self.duration_gte
+    }
+
+    pub fn duration_lte(&self) -> Option<i32> {
+        self.duration_lte
+    }
+
+    /// Feedings still running, or finished ones if `false`.
+    pub fn open(&self) -> Option<bool> {
+        self.open
+    }
+
+    pub fn sort(&self) -> Option<&Sort> {
+        self.sort.as_ref()
+    }
+}
+
+impl TryFrom<&ListQuery> for MealFilterDto {
+    type Error = ApiError;
+
+    fn try_from(list: &ListQuery) -> Result<Self, Self::Error> {
+        list.supports(
+            &["date", "quantity"],
+            &[
+                "quantity_gte",
+                "quantity_lte",
+                "duration_gte",
+                "duration_lte",
+                "open",
+            ],
+        )?;
+        Ok(Self {
+            quantity_gte: list.filter("quantity_gte")?,
+            quantity_lte: list.filter("quantity_lte")?,
+            duration_gte: list.filter("duration_gte")?,
+            duration_lte: list.filter("duration_lte")?,
+            open: list.filter("open")?,
+            sort: list.sort().cloned(),
+        })
+    }
 }
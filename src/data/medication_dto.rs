@@ -0,0 +1,65 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Deserialize, ToSchema)]
+pub struct InputMedicationDto {
+    pub name: Option<String>,
+    pub dose: Option<f32>,
+    pub unit: Option<String>,
+    pub interval_minutes: Option<i32>,
+    pub daily_time: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct InputDoseDto {
+    pub date: Option<String>,
+    pub dose: Option<f32>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MedicationData {
+    pub name: String,
+    pub dose: f32,
+    pub unit: String,
+    pub interval_minutes: Option<i32>,
+    pub daily_time: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DoseData {
+    pub medication_id: Uuid,
+    pub date: String,
+    pub time: String,
+    pub dose: f32,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MedicationStatusData {
+    pub name: String,
+    pub dose: f32,
+    pub unit: String,
+    pub last_dose: Option<String>,
+    pub next_dose: String,
+    pub due: bool,
+    pub overdue: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DoseDataV2 {
+    pub medication_id: Uuid,
+    pub date: DateTime<Utc>,
+    pub dose: f32,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MedicationStatusDataV2 {
+    pub name: String,
+    pub dose: f32,
+    pub unit: String,
+    pub last_dose: Option<DateTime<Utc>>,
+    pub next_dose: DateTime<Utc>,
+    pub due: bool,
+    pub overdue: bool,
+}
@@ -9,3 +9,16 @@ pub mod user_dto;
 pub mod weight_dto;
 pub mod role_dto;
 pub mod common_structure;
+pub mod report_dto;
+pub mod calendar_dto;
+pub mod note_dto;
+pub mod medication_dto;
+pub mod booklet_dto;
+pub mod pumping_dto;
+pub mod dashboard_dto;
+pub mod event_dto;
+pub mod webhook_dto;
+pub mod reminder_dto;
+pub mod notification_dto;
+pub mod sync_dto;
+pub mod health_dto;
@@ -0,0 +1,77 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Deserialize, ToSchema)]
+pub struct InputNoteDto {
+    pub date: Option<String>,
+    pub category: Option<String>,
+    pub value: Option<f32>,
+    pub unit: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct NoteData {
+    pub date: String,
+    pub time: String,
+    pub category: String,
+    pub value: Option<f32>,
+    pub unit: Option<String>,
+    pub description: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct NoteDataV2 {
+    pub date: DateTime<Utc>,
+    pub category: String,
+    pub value: Option<f32>,
+    pub unit: Option<String>,
+    pub description: Option<String>,
+}
+
+/// Optional filters, they can be combined with any date query.
+#[derive(Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct NoteFilterDto {
+    search: Option<String>,
+    category: Option<String>,
+}
+
+impl NoteFilterDto {
+    pub fn search(&self) -> Option<String> {
+        non_empty(&self.search)
+    }
+
+    pub fn category(&self) -> Option<String> {
+        non_empty(&self.category).map(|category| category.to_lowercase())
+    }
+}
+
+fn non_empty(value: &Option<String>) -> Option<String> {
+    match value.as_deref().map(str::trim) {
+        Some("") | None => None,
+        Some(value) => Some(value.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod test_note_dto {
+    use super::*;
+
+    #[test]
+    fn test_note_filter() {
+        let empty = NoteFilterDto {
+            search: Some("  ".to_string()),
+            category: None,
+        };
+        assert_eq!(empty.search(), None);
+        assert_eq!(empty.category(), None);
+        let filter = NoteFilterDto {
+            search: Some(" rash ".to_string()),
+            category: Some("Symptom".to_string()),
+        };
+        assert_eq!(filter.search(), Some("rash".to_string()));
+        assert_eq!(filter.category(), Some("symptom".to_string()));
+    }
+}
@@ -0,0 +1,41 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+#[derive(Deserialize, ToSchema)]
+pub struct InputNotificationDto {
+    pub read: Option<bool>,
+}
+
+#[derive(Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct UnreadDto {
+    unread: Option<bool>,
+}
+
+impl UnreadDto {
+    pub fn unread(&self) -> bool {
+        self.unread.unwrap_or(false)
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct NotificationData {
+    pub baby_unique_id: Uuid,
+    pub rule_id: Option<Uuid>,
+    pub title: String,
+    pub message: String,
+    pub created_at: String,
+    pub read: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct NotificationDataV2 {
+    pub baby_unique_id: Uuid,
+    pub rule_id: Option<Uuid>,
+    pub title: String,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+    pub read: bool,
+}
@@ -0,0 +1,89 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+#[derive(Deserialize, ToSchema)]
+pub struct InputPumpingDto {
+    pub date: Option<String>,
+    pub side: Option<String>,
+    pub duration: Option<i16>,
+    pub volume: Option<i16>,
+    /// Store pumped milk in a new bag at this location.
+    pub store: Option<String>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct InputMilkBagDto {
+    pub volume: Option<i16>,
+    pub remaining: Option<i16>,
+    pub pumped_at: Option<String>,
+    pub location: Option<String>,
+    pub expires_at: Option<String>,
+}
+
+#[derive(Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AvailableBagsDto {
+    available: Option<bool>,
+}
+
+impl AvailableBagsDto {
+    pub fn available(&self) -> bool {
+        self.available.unwrap_or(false)
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct PumpingData {
+    pub date: String,
+    pub time: String,
+    pub side: String,
+    pub duration: i16,
+    pub volume: i16,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MilkBagData {
+    pub pumping_id: Option<Uuid>,
+    pub volume: i16,
+    pub remaining: i16,
+    pub pumped_at: String,
+    pub location: String,
+    pub expires_at: String,
+    pub expiring: bool,
+    pub expired: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct PumpingDataV2 {
+    pub date: DateTime<Utc>,
+    pub side: String,
+    /// Minutes, as sent when the session was logged.
+    pub duration: i16,
+    pub volume: i16,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MilkBagDataV2 {
+    pub pumping_id: Option<Uuid>,
+    pub volume: i16,
+    pub remaining: i16,
+    pub pumped_at: DateTime<Utc>,
+    pub location: String,
+    pub expires_at: DateTime<Utc>,
+    pub expiring: bool,
+    pub expired: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct MilkStashData {
+    pub bags: usize,
+    pub total_volume: i32,
+    pub room_volume: i32,
+    pub fridge_volume: i32,
+    pub freezer_volume: i32,
+    pub expiring_volume: i32,
+    pub expiring_bags: Vec<Uuid>,
+    pub expired_volume: i32,
+}
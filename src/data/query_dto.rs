@@ -1,13 +1,25 @@
+use std::{collections::HashMap, fmt::Display, str::FromStr};
+
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Query},
+    http::request::Parts,
+};
 use chrono::NaiveDate;
 use serde::Deserialize;
+use utoipa::IntoParams;
 
 use crate::{
     configuration::constant::GlobalCte,
-    response::error::ApiError,
-    utils::datetime::{convert_to_date, format_date, today},
+    response::error::{ApiError, FieldError},
+    utils::{
+        cursor::{Cursor, CursorScope},
+        datetime::{convert_to_date, format_date, today},
+    },
 };
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct IdDto {
     entry: i32,
 }
@@ -27,7 +39,7 @@ impl DateDto {
     pub fn date(&self) -> Result<NaiveDate, ApiError> {
         match self.date.as_str() {
             "today" => Ok(today()),
-            _ => parse_date(&self.date),
+            _ => parse_date("date", &self.date),
         }
     }
 }
@@ -70,7 +82,7 @@ pub struct DateRangeDto {
 
 impl DateRangeDto {
     pub fn from(&self) -> Result<NaiveDate, ApiError> {
-        parse_date(&self.from)
+        parse_date("from", &self.from)
     }
 
     pub fn to(&self) -> Result<NaiveDate, ApiError> {
@@ -78,7 +90,7 @@ impl DateRangeDto {
         // If there is no value, it will return today's date.
         let value = match &self.to {
             Some(value) => {
-                let to_date = parse_date(&value);
+                let to_date = parse_date("to", &value);
                 Self::compare_dates(&self, to_date)
             }
             None => Ok(today()),
@@ -110,14 +122,15 @@ impl AllRecords {
     }
 }
 
-fn parse_date(date: &str) -> Result<NaiveDate, ApiError> {
-    match convert_to_date(date) {
-        Ok(date) => Ok(date),
-        Err(error) => Err(error.into()),
-    }
+/// `parameter` is the query parameter holding the date.
+fn parse_date(parameter: &str, date: &str) -> Result<NaiveDate, ApiError> {
+    convert_to_date(date).map_err(|_| {
+        FieldError::parameter(parameter, "invalid_format", "Date must be YYYY-mm-dd.").into()
+    })
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct Username {
     username: Option<String>,
 }
@@ -131,10 +144,13 @@ impl Username {
     }
 }
 
+/// Page number, or a `cursor` from a previous `page_info` which takes precedence.
 #[derive(Deserialize, Debug)]
 pub struct Pagination {
+    #[serde(default = "first_page")]
     pub page: i32,
     pub per_page: Option<i32>,
+    pub cursor: Option<String>,
 }
 
 impl Default for Pagination {
@@ -142,11 +158,27 @@ impl Default for Pagination {
         Self {
             page: 1,
             per_page: Some(GlobalCte::RecordsPerPage.get().try_into().unwrap()),
+            cursor: None,
         }
     }
 }
 
+fn first_page() -> i32 {
+    1
+}
+
 impl Pagination {
+    /// Cursor sent for the list of `scope`, cursors of other lists fail.
+    pub fn cursor<I>(&self, scope: &CursorScope) -> Result<Option<Cursor<I>>, ApiError>
+    where
+        I: Copy + Display + FromStr,
+    {
+        self.cursor
+            .as_deref()
+            .map(|cursor| Cursor::decode(cursor, scope))
+            .transpose()
+    }
+
     pub fn page(&self) -> i64 {
         self.page.abs().into()
     }
@@ -167,6 +199,168 @@ impl Pagination {
     }
 }
 
+/// Parameters every list endpoint reads for itself, never taken as filters.
+const RESERVED_PARAMS: [&str; 9] = [
+    "all",
+    "date",
+    "from",
+    "to",
+    "last_days",
+    "page",
+    "per_page",
+    "cursor",
+    "sort",
+];
+
+/// `sort=-field`, `field_gte=value` like filters and `fields[type]=a,b` sparse fieldsets, for
+/// list endpoints. Each endpoint tells which sorts and filters it supports.
+#[derive(Default, Debug)]
+pub struct ListQuery {
+    sort: Option<Sort>,
+    filters: HashMap<String, String>,
+    fields: HashMap<String, Vec<String>>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Sort {
+    field: String,
+    descending: bool,
+}
+
+impl Sort {
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    pub fn descending(&self) -> bool {
+        self.descending
+    }
+}
+
+impl ListQuery {
+    pub fn from_params(params: HashMap<String, String>) -> Self {
+        let mut list = Self::default();
+        for (key, value) in params {
+            if key.eq("sort") {
+                let field = value.trim();
+                list.sort = Some(Sort {
+                    field: field.trim_start_matches('-').to_string(),
+                    descending: field.starts_with('-'),
+                });
+            } else if let Some(data_type) = key
+                .strip_prefix("fields[")
+                .and_then(|key| key.strip_suffix(']'))
+            {
+                let fields = value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|field| !field.is_empty())
+                    .map(String::from)
+                    .collect();
+                list.fields.insert(data_type.to_string(), fields);
+            } else if !RESERVED_PARAMS.contains(&key.as_str()) {
+                list.filters.insert(key, value);
+            }
+        }
+        list
+    }
+
+    pub fn sort(&self) -> Option<&Sort> {
+        self.sort.as_ref()
+    }
+
+    /// Filter value, `None` if it wasn't sent.
+    pub fn filter<T: FromStr>(&self, key: &str) -> Result<Option<T>, ApiError> {
+        match self.filters.get(key) {
+            Some(value) => value.trim().parse().map(Some).map_err(|_| {
+                FieldError::parameter(key, "invalid", format!("Invalid value for {key}.")).into()
+            }),
+            None => Ok(None),
+        }
+    }
+
+    /// Sort, filters and dates of the list, sparse fieldsets don't change which records are paged.
+    pub fn scope(&self, range: Option<(NaiveDate, NaiveDate)>) -> CursorScope {
+        let mut parts: Vec<String> = self
+            .filters
+            .iter()
+            .map(|(key, value)| format!("{key}={}", value.trim()))
+            .collect();
+        parts.sort();
+        // Date order is the default, sending it or not lists the same records.
+        match &self.sort {
+            Some(sort) if sort.descending() => parts.push(format!("sort=-{}", sort.field())),
+            Some(sort) if sort.field().ne("date") => parts.push(format!("sort={}", sort.field())),
+            _ => {}
+        }
+        if let Some((from, to)) = range {
+            parts.push(format!("range={from}..{to}"));
+        }
+        CursorScope::new(parts)
+    }
+
+    /// Attributes requested for records of `data_type`, all of them if `None`.
+    pub fn fields(&self, data_type: &str) -> Option<Vec<String>> {
+        self.fields.get(data_type).cloned()
+    }
+
+    /// Unsupported sort or filters fail, instead of being silently ignored.
+    ///
+    /// Every unsupported parameter is reported at once.
+    pub fn supports(&self, sorts: &[&str], filters: &[&str]) -> Result<(), ApiError> {
+        let mut errors = Vec::new();
+        if let Some(sort) = &self.sort {
+            if !sorts.contains(&sort.field()) {
+                errors.push(FieldError::parameter(
+                    "sort",
+                    "not_allowed",
+                    format!(
+                        "Can't sort by {}, use one of: {}.",
+                        sort.field(),
+                        sorts.join(", ")
+                    ),
+                ));
+            }
+        }
+        let mut unknown: Vec<&str> = self
+            .filters
+            .keys()
+            .map(String::as_str)
+            .filter(|key| !filters.contains(key))
+            .collect();
+        unknown.sort();
+        for key in unknown {
+            errors.push(FieldError::parameter(
+                key,
+                "unknown",
+                format!("Unknown filter, use any of: {}.", filters.join(", ")),
+            ));
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ApiError::Invalid(errors))
+        }
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ListQuery
+where
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        match Query::<HashMap<String, String>>::try_from_uri(&parts.uri) {
+            Ok(Query(params)) => Ok(Self::from_params(params)),
+            Err(_) => Err(ApiError::Generic400Error(String::from(
+                "Invalid query string.",
+            ))),
+        }
+    }
+}
+
 #[cfg(test)]
 mod test_query {
     use super::*;
@@ -213,4 +407,66 @@ mod test_query {
         };
         assert_eq!(date.date().unwrap(), today());
     }
+
+    #[test]
+    fn test_list_query() {
+        let params = HashMap::from([
+            ("sort".to_string(), "-quantity".to_string()),
+            ("quantity_gte".to_string(), "90".to_string()),
+            ("open".to_string(), "true".to_string()),
+            ("fields[meal]".to_string(), "date, quantity,".to_string()),
+            ("last_days".to_string(), "7".to_string()),
+            ("page".to_string(), "2".to_string()),
+        ]);
+        let list = ListQuery::from_params(params);
+        let sort = list.sort().unwrap();
+        assert_eq!((sort.field(), sort.descending()), ("quantity", true));
+        assert_eq!(list.filter::<i16>("quantity_gte").unwrap(), Some(90));
+        assert_eq!(list.filter::<bool>("open").unwrap(), Some(true));
+        assert_eq!(list.filter::<i16>("quantity_lte").unwrap(), None);
+        assert!(list.filter::<i16>("open").is_err());
+        assert_eq!(
+            list.fields("meal"),
+            Some(vec!["date".to_string(), "quantity".to_string()])
+        );
+        assert_eq!(list.fields("dream"), None);
+        assert!(list
+            .supports(&["date", "quantity"], &["quantity_gte", "open"])
+            .is_ok());
+        assert!(list.supports(&["date"], &["quantity_gte", "open"]).is_err());
+        assert!(list.supports(&["date", "quantity"], &["open"]).is_err());
+        match list.supports(&["date"], &[]) {
+            Err(ApiError::Invalid(errors)) => assert_eq!(errors.len(), 3),
+            _ => panic!("Sort and both filters should be reported."),
+        }
+    }
+
+    #[test]
+    fn test_list_scope() {
+        let list = |params: &[(&str, &str)]| {
+            ListQuery::from_params(
+                params
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), value.to_string()))
+                    .collect(),
+            )
+        };
+        let from = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let to = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+        let filtered = list(&[("quantity_gte", "90"), ("fields[meal]", "date")]);
+        assert_eq!(
+            filtered.scope(Some((from, to))),
+            list(&[("quantity_gte", "90")]).scope(Some((from, to)))
+        );
+        assert_ne!(filtered.scope(Some((from, to))), filtered.scope(None));
+        assert_ne!(
+            filtered.scope(None),
+            list(&[("quantity_gte", "60")]).scope(None)
+        );
+        assert_eq!(list(&[("sort", "date")]).scope(None), list(&[]).scope(None));
+        assert_ne!(
+            list(&[("sort", "date")]).scope(None),
+            list(&[("sort", "-date")]).scope(None)
+        );
+    }
 }
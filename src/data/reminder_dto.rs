@@ -0,0 +1,43 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+#[derive(Deserialize, ToSchema)]
+pub struct InputReminderDto {
+    pub kind: Option<String>,
+    pub threshold_minutes: Option<i32>,
+    pub medication_id: Option<Uuid>,
+    pub due_time: Option<String>,
+    pub channels: Option<Vec<String>>,
+    pub active: Option<bool>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ReminderData {
+    pub kind: String,
+    pub threshold_minutes: Option<i32>,
+    pub medication_id: Option<Uuid>,
+    pub due_time: Option<String>,
+    pub channels: Vec<String>,
+    pub active: bool,
+    pub last_triggered_at: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ReminderDataV2 {
+    pub kind: String,
+    pub threshold_minutes: Option<i32>,
+    pub medication_id: Option<Uuid>,
+    pub due_time: Option<String>,
+    pub channels: Vec<String>,
+    pub active: bool,
+    pub last_triggered_at: Option<DateTime<Utc>>,
+}
+
+/// Webhook payload of a fired reminder.
+#[derive(Serialize)]
+pub struct ReminderAlertData {
+    pub title: String,
+    pub message: String,
+}
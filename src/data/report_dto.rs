@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+
+use crate::response::error::{ApiError, FieldError};
+
+use super::{
+    baby_dto::BabyData, dream_dto::DreamSummaryDto, meal_dto::MealSummaryDto, note_dto::NoteData,
+    weight_dto::WeightData,
+};
+
+#[derive(Deserialize, Default, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ReportFormatDto {
+    format: Option<String>,
+}
+
+impl ReportFormatDto {
+    pub fn format(&self) -> Result<ReportFormat, ApiError> {
+        match self.format.as_deref() {
+            None | Some("html") => Ok(ReportFormat::Html),
+            Some("pdf") => Ok(ReportFormat::Pdf),
+            Some(other) => Err(FieldError::parameter(
+                "format",
+                "invalid",
+                format!("Unsupported report format: {other}."),
+            )
+            .into()),
+        }
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub enum ReportFormat {
+    Html,
+    Pdf,
+}
+
+#[derive(Serialize)]
+pub struct ReportData {
+    pub baby: BabyData,
+    pub from: String,
+    pub to: String,
+    pub weights: Vec<WeightData>,
+    pub meals: Vec<MealSummaryDto>,
+    pub dreams: Vec<DreamSummaryDto>,
+    pub notes: Vec<NoteData>,
+    pub averages: ReportAveragesData,
+}
+
+#[derive(Serialize)]
+pub struct ReportAveragesData {
+    pub feedings_per_day: f32,
+    pub formula_per_day: f32,
+    pub nursing_time_per_day: String,
+    pub sleep_per_day: String,
+    pub weight_change: f32,
+}
+
+#[cfg(test)]
+mod test_report_dto {
+    use super::*;
+
+    #[test]
+    fn test_report_format() {
+        let default_format = ReportFormatDto { format: None };
+        assert_eq!(default_format.format().unwrap(), ReportFormat::Html);
+        let pdf = ReportFormatDto {
+            format: Some("pdf".to_string()),
+        };
+        assert_eq!(pdf.format().unwrap(), ReportFormat::Pdf);
+        let unknown = ReportFormatDto {
+            format: Some("docx".to_string()),
+        };
+        assert!(unknown.format().is_err());
+    }
+}
@@ -1,13 +1,14 @@
 use serde::{Serialize, Deserialize};
+use utoipa::ToSchema;
 
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct RoleData {
     pub name: String,
     pub count: i64
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct UpdateRole {
     pub username: String,
     pub role: String
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use crate::model::session_model::BabyInfo;
 
 /// This struct belongs to redis repository.
@@ -35,7 +36,7 @@ impl CurrentUserDto {
     }
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, ToSchema)]
 pub struct UserSessionData {
     pub username: String,
     pub baby_info: Vec<BabyInfo>,
@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use super::{
+    common_structure::{
+        DeletedRecordDto, DeletedRecordDtoV2, DreamDto, DreamDtoV2, MealDto, MealDtoV2,
+    },
+    dream_dto::InputDreamDto,
+    meal_dto::InputMealDto,
+};
+
+/// Change made offline, records are matched by `client_id` inside the body.
+#[derive(Deserialize, ToSchema)]
+#[aliases(
+    MealChangeDto = SyncChangeDto<InputMealDto>,
+    DreamChangeDto = SyncChangeDto<InputDreamDto>
+)]
+pub struct SyncChangeDto<T> {
+    /// When the change was made on the client, `YYYY-mm-dd HH:MM` or RFC 3339.
+    pub updated_at: String,
+    #[serde(default)]
+    pub deleted: bool,
+    #[serde(flatten)]
+    pub record: T,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct InputSyncDto {
+    pub cursor: Option<String>,
+    #[serde(default)]
+    pub meals: Vec<MealChangeDto>,
+    #[serde(default)]
+    pub dreams: Vec<DreamChangeDto>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DeletedRecordData {
+    pub record_type: String,
+    pub client_id: Option<Uuid>,
+    pub deleted_at: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DeletedRecordDataV2 {
+    pub record_type: String,
+    pub client_id: Option<Uuid>,
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// Server changes since the given cursor, `conflicts` lists client changes discarded
+/// because the server copy was newer.
+#[derive(Serialize, ToSchema)]
+pub struct SyncData {
+    pub cursor: String,
+    pub meals: Vec<MealDto>,
+    pub dreams: Vec<DreamDto>,
+    pub deleted: Vec<DeletedRecordDto>,
+    pub conflicts: Vec<Uuid>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SyncDataV2 {
+    pub cursor: String,
+    pub meals: Vec<MealDtoV2>,
+    pub dreams: Vec<DreamDtoV2>,
+    pub deleted: Vec<DeletedRecordDtoV2>,
+    pub conflicts: Vec<Uuid>,
+}
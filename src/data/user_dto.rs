@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
 use super::traits::Mandatory;
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct NewUserDto {
     pub username: String,
     pub password: String,
@@ -17,7 +18,7 @@ impl Mandatory for NewUserDto {
     }
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, ToSchema)]
 pub struct UserData {
     pub username: String,
     pub email: Option<String>,
@@ -25,12 +26,12 @@ pub struct UserData {
     pub surname: Option<String>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct FindUserDto {
     pub username: String,
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct LoginDto {
     pub username: String,
     pub password: String,
@@ -42,7 +43,7 @@ impl Mandatory for LoginDto {
     }
 }
 
-#[derive(Deserialize)]
+#[derive(Deserialize, ToSchema)]
 pub struct UpdateUserDto {
     pub email: Option<String>,
     pub name: Option<String>,
@@ -0,0 +1,53 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Deserialize, ToSchema)]
+pub struct InputWebhookDto {
+    pub url: Option<String>,
+    pub active: Option<bool>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct WebhookData {
+    pub url: String,
+    pub active: bool,
+    pub created_at: String,
+    /// Only returned when the webhook is created.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct WebhookDeliveryData {
+    pub event: String,
+    pub status: String,
+    pub attempts: i16,
+    pub next_attempt_at: Option<String>,
+    pub response_code: Option<i16>,
+    pub error: Option<String>,
+    pub created_at: String,
+    pub delivered_at: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct WebhookDataV2 {
+    pub url: String,
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    /// Only returned when the webhook is created.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct WebhookDeliveryDataV2 {
+    pub event: String,
+    pub status: String,
+    pub attempts: i16,
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    pub response_code: Option<i16>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
@@ -1,13 +1,53 @@
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Deserialize)]
+use crate::response::error::ApiError;
+
+use super::query_dto::{ListQuery, Sort};
+
+#[derive(Deserialize, ToSchema)]
 pub struct InputWeightDto {
     pub date: Option<String>,
     pub value: Option<f32>,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, ToSchema)]
 pub struct WeightData {
     pub date: String,
     pub value: f32,
 }
+
+/// Optional filters and order for weight lists.
+#[derive(Default)]
+pub struct WeightFilterDto {
+    value_gte: Option<f32>,
+    value_lte: Option<f32>,
+    sort: Option<Sort>,
+}
+
+impl WeightFilterDto {
+    pub fn value_gte(&self) -> Option<f32> {
+        self.value_gte
+    }
+
+    pub fn value_lte(&self) -> Option<f32> {
+        self.value_lte
+    }
+
+    pub fn sort(&self) -> Option<&Sort> {
+        self.sort.as_ref()
+    }
+}
+
+impl TryFrom<&ListQuery> for WeightFilterDto {
+    type Error = ApiError;
+
+    fn try_from(list: &ListQuery) -> Result<Self, Self::Error> {
+        list.supports(&["date", "value"], &["value_gte", "value_lte"])?;
+        Ok(Self {
+            value_gte: list.filter("value_gte")?,
+            value_lte: list.filter("value_lte")?,
+            sort: list.sort().cloned(),
+        })
+    }
+}
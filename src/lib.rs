@@ -12,13 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use std::path::Path;
+use std::{env, path::Path};
+use anyhow::bail;
 use tracing::info;
 
 use crate::{
     app::{create_router, expand_router_layer},
-    configuration::settings::Setting,
-    utils::app::shutdown_signal,
+    configuration::config::config,
+    repository::migration_repository::{
+        revert_last_migration, run_pending_migrations, select_migration_status,
+    },
+    service::{reminder_service::run_reminder_scheduler, webhook_service::run_webhook_worker},
+    utils::{app::shutdown_signal, mailer::mailer_from_config},
 };
 
 pub mod app;
@@ -38,9 +43,10 @@ pub mod utils;
 /// Prepare environment variables.
 ///
 /// If no env variables are set, defaults to local file
-/// in path `/key/local.env`
+/// in path `/key/local.env`. Runs before the configuration is loaded,
+/// so only `BRANCH` from the environment is considered.
 pub fn set_environment() {
-    if Setting::Branch.get().eq("local") {
+    if env::var("BRANCH").unwrap_or("local".to_string()).eq("local") {
         match dotenvy::from_path(Path::new("./key/local.env")) {
             Ok(_) => (),
             Err(error) => panic!("{error}"),
@@ -48,14 +54,30 @@ pub fn set_environment() {
     }
 }
 
+/// Apply pending migrations when `run_migrations` is enabled.
+/// Must run before anything reads the database.
+pub fn run_startup_migrations() {
+    if config().database.run_migrations {
+        match run_pending_migrations() {
+            Ok(applied) => info!("Applied {} pending migrations", applied.len()),
+            Err(error) => panic!("Migrations failed: {error}"),
+        }
+    }
+}
+
 /// Launch server
 pub async fn serve_app() {
     let router = create_router();
     let app = expand_router_layer(router).await;
 
-    info!("Branch mode: {}", Setting::Branch.get());
+    info!("Branch mode: {}", config().branch);
+
+    tokio::spawn(run_webhook_worker());
+    let mailer = mailer_from_config(&config().mail)
+        .unwrap_or_else(|error| panic!("Invalid mail settings: {error}"));
+    tokio::spawn(run_reminder_scheduler(mailer));
 
-    let host = Setting::Host.get();
+    let host = config().server.host();
     // run it with hyper on localhost:3000
     info!("Start server, listening on {host}");
 
@@ -65,3 +87,27 @@ pub async fn serve_app() {
         .await
         .unwrap();
 }
+
+/// `migrate status|run|revert` command, reports to stdout.
+pub fn migrate(action: &str) -> anyhow::Result<()> {
+    match action {
+        "status" => {
+            for migration in select_migration_status()? {
+                let mark = if migration.applied { "X" } else { " " };
+                println!("[{mark}] {}", migration.name);
+            }
+        }
+        "run" => {
+            let applied = run_pending_migrations()?;
+            if applied.is_empty() {
+                println!("No pending migrations.");
+            }
+            for version in applied {
+                println!("Applied {version}");
+            }
+        }
+        "revert" => println!("Reverted {}", revert_last_migration()?),
+        _ => bail!("Unknown migrate command `{action}`, expected status, run or revert."),
+    }
+    Ok(())
+}
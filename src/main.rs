@@ -12,12 +12,29 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use nighty_night::{serve_app, set_environment, utils::{logger::setup_logger, app::set_anonymous_user}};
+use nighty_night::{configuration::config::init_config, migrate, run_startup_migrations, serve_app, set_environment, utils::{logger::{setup_logger, shutdown_logger}, app::set_anonymous_user, metrics::setup_metrics}};
 
 #[tokio::main]
 async fn main() {
     set_environment();
+    if let Err(error) = init_config() {
+        eprintln!("{error}");
+        std::process::exit(1);
+    }
     setup_logger();
+    // `nighty_night migrate status|run|revert` handles migrations and exits.
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|command| command.eq("migrate")) {
+        let action = args.get(1).map(String::as_str).unwrap_or("status");
+        if let Err(error) = migrate(action) {
+            eprintln!("{error}");
+            std::process::exit(1);
+        }
+        return;
+    }
+    setup_metrics();
+    run_startup_migrations();
     set_anonymous_user().await.expect("Redis should be working");
-    serve_app().await
+    serve_app().await;
+    shutdown_logger();
 }
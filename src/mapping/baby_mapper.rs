@@ -1,3 +1,5 @@
+use uuid::Uuid;
+
 use crate::{
     data::{
         baby_dto::BabyData,
@@ -6,13 +8,13 @@ use crate::{
     model::baby_model::Baby,
 };
 
-impl From<Baby> for BasicDataStruct<BabyData> {
+impl From<Baby> for BasicDataStruct<BabyData, Uuid> {
     fn from(baby: Baby) -> Self {
         let attributes = BabyData {
             unique_id: baby.unique_id(),
             name: baby.name(),
             birthdate: baby.formatted_birthdate(),
         };
-        BasicDataStruct::new(baby.id(), DataType::Baby, attributes)
+        BasicDataStruct::new(baby.unique_id(), DataType::Baby, attributes)
     }
 }
@@ -0,0 +1,53 @@
+use uuid::Uuid;
+
+use crate::{
+    data::{
+        booklet_dto::{BookletEntryData, ScheduleItemData, ScheduleStatusData},
+        common_structure::{BasicDataStruct, DataType},
+    },
+    model::booklet_model::{BookletEntry, BookletKind, ScheduleItem, ScheduleStatus},
+    utils::datetime::format_date,
+};
+
+impl From<BookletEntry> for BasicDataStruct<BookletEntryData, Uuid> {
+    fn from(entry: BookletEntry) -> Self {
+        let data_type = match entry.kind() {
+            Some(BookletKind::Vaccination) => DataType::Vaccination,
+            _ => DataType::Milestone,
+        };
+        let attributes = BookletEntryData {
+            schedule_id: entry.schedule_id(),
+            name: entry.name(),
+            date: entry.formatted_date(),
+            notes: entry.notes(),
+        };
+        BasicDataStruct::new(entry.public_id(), data_type, attributes)
+    }
+}
+
+impl From<ScheduleItem> for BasicDataStruct<ScheduleItemData> {
+    fn from(item: ScheduleItem) -> Self {
+        let attributes = ScheduleItemData {
+            kind: item.kind(),
+            name: item.name(),
+            age_months: item.age_months(),
+            tolerance_months: item.tolerance_months(),
+        };
+        BasicDataStruct::new(item.id(), DataType::Schedule, attributes)
+    }
+}
+
+impl From<ScheduleStatus> for BasicDataStruct<ScheduleStatusData> {
+    fn from(status: ScheduleStatus) -> Self {
+        let item = status.item();
+        let attributes = ScheduleStatusData {
+            kind: item.kind(),
+            name: item.name(),
+            age_months: item.age_months(),
+            due_date: format_date(status.due_date()),
+            status: status.state().get().to_string(),
+            done_date: status.done_date().map(format_date),
+        };
+        BasicDataStruct::new(item.id(), DataType::Booklet, attributes)
+    }
+}
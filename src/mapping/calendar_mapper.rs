@@ -0,0 +1,51 @@
+use uuid::Uuid;
+
+use crate::{
+    data::{
+        calendar_dto::CalendarData,
+        common_structure::{BasicDataStruct, DataType},
+    },
+    model::{
+        calendar_model::{CalendarEvent, CalendarToken},
+        dream_model::Dream,
+        meals_model::Meal,
+    },
+};
+
+impl From<CalendarToken> for BasicDataStruct<CalendarData, Uuid> {
+    fn from(token: CalendarToken) -> Self {
+        let attributes = CalendarData {
+            token: token.token(),
+            url: format!("/api/calendar/{}/feed.ics", token.token()),
+        };
+        BasicDataStruct::new(token.token(), DataType::Calendar, attributes)
+    }
+}
+
+/// Meals without an end time are shown as a single point in time.
+impl From<Meal> for CalendarEvent {
+    fn from(meal: Meal) -> Self {
+        let summary = match meal.quantity() {
+            Some(quantity) => format!("Meal ({quantity} ml)"),
+            None => String::from("Meal"),
+        };
+        CalendarEvent::new(
+            format!("meal-{}@nighty_night", meal.public_id()),
+            summary,
+            meal.date(),
+            Some(meal.to_time().unwrap_or(meal.date())),
+        )
+    }
+}
+
+/// Dreams without an end date are shown as ongoing events.
+impl From<Dream> for CalendarEvent {
+    fn from(dream: Dream) -> Self {
+        CalendarEvent::new(
+            format!("dream-{}@nighty_night", dream.public_id()),
+            String::from("Sleep"),
+            dream.from_date(),
+            dream.to_date(),
+        )
+    }
+}
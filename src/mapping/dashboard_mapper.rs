@@ -0,0 +1,50 @@
+use uuid::Uuid;
+
+use crate::{
+    data::{
+        common_structure::{BasicDataStruct, DataType},
+        dashboard_dto::{DashboardData, DashboardDataV2},
+    },
+    model::dashboard_model::BabyDashboard,
+    utils::datetime::format_duration,
+};
+
+impl From<BabyDashboard> for BasicDataStruct<DashboardData, Uuid> {
+    fn from(dashboard: BabyDashboard) -> Self {
+        let baby = dashboard.baby();
+        let attributes = DashboardData {
+            unique_id: baby.unique_id(),
+            name: baby.name(),
+            last_meal: dashboard.last_meal().map(|meal| meal.into()),
+            since_last_meal: dashboard
+                .since_last_meal()
+                .map(|elapsed| format_duration(elapsed.num_minutes())),
+            last_dream: dashboard.last_dream().map(|dream| dream.into()),
+            since_last_dream: dashboard
+                .since_last_dream()
+                .map(|elapsed| format_duration(elapsed.num_minutes())),
+            sleeping: dashboard.sleeping(),
+        };
+        BasicDataStruct::new(baby.unique_id(), DataType::Dashboard, attributes)
+    }
+}
+
+impl From<BabyDashboard> for BasicDataStruct<DashboardDataV2, Uuid> {
+    fn from(dashboard: BabyDashboard) -> Self {
+        let baby = dashboard.baby();
+        let attributes = DashboardDataV2 {
+            unique_id: baby.unique_id(),
+            name: baby.name(),
+            last_meal: dashboard.last_meal().map(|meal| meal.into()),
+            since_last_meal_seconds: dashboard
+                .since_last_meal()
+                .map(|elapsed| elapsed.num_seconds()),
+            last_dream: dashboard.last_dream().map(|dream| dream.into()),
+            since_last_dream_seconds: dashboard
+                .since_last_dream()
+                .map(|elapsed| elapsed.num_seconds()),
+            sleeping: dashboard.sleeping(),
+        };
+        BasicDataStruct::new(baby.unique_id(), DataType::Dashboard, attributes)
+    }
+}
@@ -1,12 +1,15 @@
+use uuid::Uuid;
+
 use crate::{
     data::{
         common_structure::{BasicDataStruct, DataType},
-        dream_dto::DreamData,
+        dream_dto::{DreamData, DreamDataV2},
     },
     model::dream_model::Dream,
+    utils::datetime::as_utc,
 };
 
-impl From<Dream> for BasicDataStruct<DreamData> {
+impl From<Dream> for BasicDataStruct<DreamData, Uuid> {
     fn from(dream: Dream) -> Self {
         let attributes = DreamData {
             from_date: dream.formatted_from_date(),
@@ -14,7 +17,20 @@ impl From<Dream> for BasicDataStruct<DreamData> {
             to_date: dream.formatted_to_date(),
             to_time: dream.formatted_to_time(),
             elapsed: dream.formatted_elapsed(),
+            client_id: dream.client_id(),
+        };
+        BasicDataStruct::new(dream.public_id(), DataType::Dream, attributes)
+    }
+}
+
+impl From<Dream> for BasicDataStruct<DreamDataV2, Uuid> {
+    fn from(dream: Dream) -> Self {
+        let attributes = DreamDataV2 {
+            started_at: as_utc(dream.from_date()),
+            ended_at: dream.to_date().map(as_utc),
+            duration_seconds: dream.elapsed().num_seconds(),
+            client_id: dream.client_id(),
         };
-        BasicDataStruct::new(dream.id(), DataType::Dream, attributes)
+        BasicDataStruct::new(dream.public_id(), DataType::Dream, attributes)
     }
 }
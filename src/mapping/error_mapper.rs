@@ -3,11 +3,11 @@ use std::num::{ParseIntError, TryFromIntError};
 use diesel::result::Error;
 use redis::RedisError;
 
-use crate::response::error::ApiError;
+use crate::response::error::{ApiError, FieldError};
 
-impl From<chrono::ParseError> for ApiError {
-    fn from(value: chrono::ParseError) -> Self {
-        ApiError::CastError(value.to_string())
+impl From<FieldError> for ApiError {
+    fn from(value: FieldError) -> Self {
+        ApiError::Invalid(vec![value])
     }
 }
 
@@ -49,9 +49,3 @@ impl From<TryFromIntError> for ApiError {
         ApiError::CastError(value.to_string())
     }
 }
-
-impl From<uuid::Error> for ApiError {
-    fn from(value: uuid::Error) -> Self {
-        ApiError::CastError(value.to_string())
-    }
-}
@@ -1,19 +1,38 @@
+use uuid::Uuid;
+
 use crate::{
     data::{
         common_structure::{BasicDataStruct, DataType},
-        meal_dto::MealData,
+        meal_dto::{MealData, MealDataV2},
     },
     model::meals_model::Meal,
+    utils::datetime::as_utc,
 };
 
-impl From<Meal> for BasicDataStruct<MealData> {
+impl From<Meal> for BasicDataStruct<MealData, Uuid> {
     fn from(meal: Meal) -> Self {
         let attributes = MealData {
             date: meal.formatted_date(),
             start_time: meal.formatted_time(),
             quantity: meal.formatted_quantity(),
             elapsed: meal.formatted_elapsed(),
+            milk_bag_id: meal.milk_bag_public_id(),
+            client_id: meal.client_id(),
+        };
+        BasicDataStruct::new(meal.public_id(), DataType::Meal, attributes)
+    }
+}
+
+impl From<Meal> for BasicDataStruct<MealDataV2, Uuid> {
+    fn from(meal: Meal) -> Self {
+        let attributes = MealDataV2 {
+            started_at: as_utc(meal.date()),
+            ended_at: meal.to_time().map(as_utc),
+            duration_seconds: meal.elapsed().num_seconds(),
+            quantity: meal.quantity(),
+            milk_bag_id: meal.milk_bag_public_id(),
+            client_id: meal.client_id(),
         };
-        BasicDataStruct::new(meal.id(), DataType::Meal, attributes)
+        BasicDataStruct::new(meal.public_id(), DataType::Meal, attributes)
     }
 }
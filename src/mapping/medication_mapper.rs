@@ -0,0 +1,88 @@
+use uuid::Uuid;
+
+use crate::{
+    data::{
+        common_structure::{BasicDataStruct, DataType},
+        medication_dto::{
+            DoseData, DoseDataV2, MedicationData, MedicationStatusData, MedicationStatusDataV2,
+        },
+    },
+    model::medication_model::{Medication, MedicationDose, MedicationStatus},
+    utils::datetime::{as_utc, format_date_time},
+};
+
+impl From<Medication> for BasicDataStruct<MedicationData, Uuid> {
+    fn from(medication: Medication) -> Self {
+        let attributes = MedicationData {
+            name: medication.name(),
+            dose: medication.dose(),
+            unit: medication.unit(),
+            interval_minutes: medication.interval_minutes(),
+            daily_time: medication.formatted_daily_time(),
+        };
+        BasicDataStruct::new(medication.public_id(), DataType::Medication, attributes)
+    }
+}
+
+impl From<MedicationDose> for BasicDataStruct<DoseData, Uuid> {
+    fn from(dose: MedicationDose) -> Self {
+        let attributes = DoseData {
+            medication_id: dose.medication_public_id().unwrap_or_default(),
+            date: dose.formatted_date(),
+            time: dose.formatted_time(),
+            dose: dose.dose(),
+        };
+        BasicDataStruct::new(dose.public_id(), DataType::Dose, attributes)
+    }
+}
+
+impl From<MedicationStatus> for BasicDataStruct<MedicationStatusData, Uuid> {
+    fn from(status: MedicationStatus) -> Self {
+        let medication = status.medication();
+        let attributes = MedicationStatusData {
+            name: medication.name(),
+            dose: medication.dose(),
+            unit: medication.unit(),
+            last_dose: status.last_dose().map(format_date_time),
+            next_dose: format_date_time(status.next_dose()),
+            due: status.due(),
+            overdue: status.overdue(),
+        };
+        BasicDataStruct::new(
+            medication.public_id(),
+            DataType::MedicationStatus,
+            attributes,
+        )
+    }
+}
+
+impl From<MedicationDose> for BasicDataStruct<DoseDataV2, Uuid> {
+    fn from(dose: MedicationDose) -> Self {
+        let attributes = DoseDataV2 {
+            medication_id: dose.medication_public_id().unwrap_or_default(),
+            date: as_utc(dose.date()),
+            dose: dose.dose(),
+        };
+        BasicDataStruct::new(dose.public_id(), DataType::Dose, attributes)
+    }
+}
+
+impl From<MedicationStatus> for BasicDataStruct<MedicationStatusDataV2, Uuid> {
+    fn from(status: MedicationStatus) -> Self {
+        let medication = status.medication();
+        let attributes = MedicationStatusDataV2 {
+            name: medication.name(),
+            dose: medication.dose(),
+            unit: medication.unit(),
+            last_dose: status.last_dose().map(as_utc),
+            next_dose: as_utc(status.next_dose()),
+            due: status.due(),
+            overdue: status.overdue(),
+        };
+        BasicDataStruct::new(
+            medication.public_id(),
+            DataType::MedicationStatus,
+            attributes,
+        )
+    }
+}
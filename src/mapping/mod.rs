@@ -8,3 +8,13 @@ pub mod summary_mapper;
 pub mod error_mapper;
 pub mod session_mapper;
 pub mod admin_mapper;
+pub mod report_mapper;
+pub mod calendar_mapper;
+pub mod note_mapper;
+pub mod medication_mapper;
+pub mod booklet_mapper;
+pub mod pumping_mapper;
+pub mod dashboard_mapper;
+pub mod webhook_mapper;
+pub mod reminder_mapper;
+pub mod sync_mapper;
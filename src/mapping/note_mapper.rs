@@ -0,0 +1,37 @@
+use uuid::Uuid;
+
+use crate::{
+    data::{
+        common_structure::{BasicDataStruct, DataType},
+        note_dto::{NoteData, NoteDataV2},
+    },
+    model::note_model::Note,
+    utils::datetime::as_utc,
+};
+
+impl From<Note> for BasicDataStruct<NoteData, Uuid> {
+    fn from(note: Note) -> Self {
+        let attributes = NoteData {
+            date: note.formatted_date(),
+            time: note.formatted_time(),
+            category: note.category(),
+            value: note.value(),
+            unit: note.unit(),
+            description: note.description(),
+        };
+        BasicDataStruct::new(note.public_id(), DataType::Note, attributes)
+    }
+}
+
+impl From<Note> for BasicDataStruct<NoteDataV2, Uuid> {
+    fn from(note: Note) -> Self {
+        let attributes = NoteDataV2 {
+            date: as_utc(note.date()),
+            category: note.category(),
+            value: note.value(),
+            unit: note.unit(),
+            description: note.description(),
+        };
+        BasicDataStruct::new(note.public_id(), DataType::Note, attributes)
+    }
+}
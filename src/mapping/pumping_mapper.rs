@@ -0,0 +1,85 @@
+use uuid::Uuid;
+
+use crate::{
+    data::{
+        common_structure::{BasicDataStruct, DataType},
+        pumping_dto::{MilkBagData, MilkBagDataV2, MilkStashData, PumpingData, PumpingDataV2},
+    },
+    model::pumping_model::{MilkBag, MilkStash, Pumping},
+    utils::datetime::{as_utc, format_date_time, now},
+};
+
+impl From<Pumping> for BasicDataStruct<PumpingData, Uuid> {
+    fn from(pumping: Pumping) -> Self {
+        let attributes = PumpingData {
+            date: pumping.formatted_date(),
+            time: pumping.formatted_time(),
+            side: pumping.side(),
+            duration: pumping.duration(),
+            volume: pumping.volume(),
+        };
+        BasicDataStruct::new(pumping.public_id(), DataType::Pumping, attributes)
+    }
+}
+
+impl From<MilkBag> for BasicDataStruct<MilkBagData, Uuid> {
+    fn from(bag: MilkBag) -> Self {
+        let now = now();
+        let attributes = MilkBagData {
+            pumping_id: bag.pumping_public_id(),
+            volume: bag.volume(),
+            remaining: bag.remaining(),
+            pumped_at: format_date_time(bag.pumped_at()),
+            location: bag.formatted_location(),
+            expires_at: format_date_time(bag.expires_at()),
+            expiring: bag.expiring(now),
+            expired: bag.expired(now),
+        };
+        BasicDataStruct::new(bag.public_id(), DataType::MilkBag, attributes)
+    }
+}
+
+impl From<Pumping> for BasicDataStruct<PumpingDataV2, Uuid> {
+    fn from(pumping: Pumping) -> Self {
+        let attributes = PumpingDataV2 {
+            date: as_utc(pumping.date()),
+            side: pumping.side(),
+            duration: pumping.duration(),
+            volume: pumping.volume(),
+        };
+        BasicDataStruct::new(pumping.public_id(), DataType::Pumping, attributes)
+    }
+}
+
+impl From<MilkBag> for BasicDataStruct<MilkBagDataV2, Uuid> {
+    fn from(bag: MilkBag) -> Self {
+        let now = now();
+        let attributes = MilkBagDataV2 {
+            pumping_id: bag.pumping_public_id(),
+            volume: bag.volume(),
+            remaining: bag.remaining(),
+            pumped_at: as_utc(bag.pumped_at()),
+            location: bag.formatted_location(),
+            expires_at: as_utc(bag.expires_at()),
+            expiring: bag.expiring(now),
+            expired: bag.expired(now),
+        };
+        BasicDataStruct::new(bag.public_id(), DataType::MilkBag, attributes)
+    }
+}
+
+impl From<MilkStash> for BasicDataStruct<MilkStashData, Uuid> {
+    fn from(stash: MilkStash) -> Self {
+        let attributes = MilkStashData {
+            bags: stash.bags(),
+            total_volume: stash.total_volume(),
+            room_volume: stash.room_volume(),
+            fridge_volume: stash.fridge_volume(),
+            freezer_volume: stash.freezer_volume(),
+            expiring_volume: stash.expiring_volume(),
+            expiring_bags: stash.expiring_bags(),
+            expired_volume: stash.expired_volume(),
+        };
+        BasicDataStruct::new(stash.baby_unique_id(), DataType::MilkStash, attributes)
+    }
+}
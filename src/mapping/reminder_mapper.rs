@@ -0,0 +1,77 @@
+use uuid::Uuid;
+
+use crate::{
+    data::{
+        common_structure::{BasicDataStruct, DataType},
+        notification_dto::{NotificationData, NotificationDataV2},
+        reminder_dto::{ReminderData, ReminderDataV2},
+    },
+    model::{notification_model::Notification, reminder_model::ReminderRule},
+    utils::datetime::{as_utc, format_date_time},
+};
+
+impl From<ReminderRule> for BasicDataStruct<ReminderData, Uuid> {
+    fn from(rule: ReminderRule) -> Self {
+        let attributes = ReminderData {
+            kind: rule.formatted_kind(),
+            threshold_minutes: rule.threshold_minutes(),
+            medication_id: rule.medication_public_id(),
+            due_time: rule.formatted_due_time(),
+            channels: rule
+                .channels()
+                .iter()
+                .map(|channel| channel.get().to_string())
+                .collect(),
+            active: rule.active(),
+            last_triggered_at: rule.last_triggered_at().map(format_date_time),
+        };
+        BasicDataStruct::new(rule.public_id(), DataType::Reminder, attributes)
+    }
+}
+
+impl From<ReminderRule> for BasicDataStruct<ReminderDataV2, Uuid> {
+    fn from(rule: ReminderRule) -> Self {
+        let attributes = ReminderDataV2 {
+            kind: rule.formatted_kind(),
+            threshold_minutes: rule.threshold_minutes(),
+            medication_id: rule.medication_public_id(),
+            due_time: rule.formatted_due_time(),
+            channels: rule
+                .channels()
+                .iter()
+                .map(|channel| channel.get().to_string())
+                .collect(),
+            active: rule.active(),
+            last_triggered_at: rule.last_triggered_at().map(as_utc),
+        };
+        BasicDataStruct::new(rule.public_id(), DataType::Reminder, attributes)
+    }
+}
+
+impl From<(Notification, Uuid)> for BasicDataStruct<NotificationData, Uuid> {
+    fn from((notification, baby_unique_id): (Notification, Uuid)) -> Self {
+        let attributes = NotificationData {
+            baby_unique_id,
+            rule_id: notification.rule_public_id(),
+            title: notification.title(),
+            message: notification.message(),
+            created_at: notification.formatted_created_at(),
+            read: notification.read(),
+        };
+        BasicDataStruct::new(notification.public_id(), DataType::Notification, attributes)
+    }
+}
+
+impl From<(Notification, Uuid)> for BasicDataStruct<NotificationDataV2, Uuid> {
+    fn from((notification, baby_unique_id): (Notification, Uuid)) -> Self {
+        let attributes = NotificationDataV2 {
+            baby_unique_id,
+            rule_id: notification.rule_public_id(),
+            title: notification.title(),
+            message: notification.message(),
+            created_at: as_utc(notification.created_at()),
+            read: notification.read(),
+        };
+        BasicDataStruct::new(notification.public_id(), DataType::Notification, attributes)
+    }
+}
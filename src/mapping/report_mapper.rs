@@ -0,0 +1,48 @@
+use crate::{
+    data::{
+        common_structure::{BabyDto, NoteDto, WeightDto},
+        report_dto::{ReportAveragesData, ReportData},
+    },
+    model::report_model::Report,
+};
+
+impl From<Report> for ReportData {
+    fn from(report: Report) -> Self {
+        let baby: BabyDto = report.baby().into();
+        let averages = ReportAveragesData {
+            feedings_per_day: report.feedings_per_day(),
+            formula_per_day: report.formula_per_day(),
+            nursing_time_per_day: report.formatted_nursing_time_per_day(),
+            sleep_per_day: report.formatted_sleep_per_day(),
+            weight_change: report.weight_change(),
+        };
+        ReportData {
+            baby: baby.attributes,
+            from: report.formatted_from(),
+            to: report.formatted_to(),
+            weights: report
+                .weights()
+                .into_iter()
+                .map(|weight| {
+                    let dto: WeightDto = weight.into();
+                    dto.attributes
+                })
+                .collect(),
+            meals: report.meals().into_iter().map(|meal| meal.into()).collect(),
+            dreams: report
+                .dreams()
+                .into_iter()
+                .map(|dream| dream.into())
+                .collect(),
+            notes: report
+                .notes()
+                .into_iter()
+                .map(|note| {
+                    let dto: NoteDto = note.into();
+                    dto.attributes
+                })
+                .collect(),
+            averages,
+        }
+    }
+}
@@ -1,5 +1,8 @@
 use crate::{
-    data::{dream_dto::DreamSummaryDto, meal_dto::MealSummaryDto},
+    data::{
+        dream_dto::{DreamSummaryDto, DreamSummaryDtoV2},
+        meal_dto::{MealSummaryDto, MealSummaryDtoV2},
+    },
     model::summary_model::{DreamSummary, MealSummary},
 };
 
@@ -22,3 +25,23 @@ impl From<MealSummary> for MealSummaryDto {
         }
     }
 }
+
+impl From<DreamSummary> for DreamSummaryDtoV2 {
+    fn from(dream: DreamSummary) -> Self {
+        DreamSummaryDtoV2 {
+            date: dream.date(),
+            total_seconds: dream.summary().num_seconds(),
+        }
+    }
+}
+
+impl From<MealSummary> for MealSummaryDtoV2 {
+    fn from(meal: MealSummary) -> Self {
+        MealSummaryDtoV2 {
+            date: meal.date(),
+            total_feedings: meal.total_feedings(),
+            nursing_seconds: meal.nursing_time().num_seconds(),
+            formula: meal.formula(),
+        }
+    }
+}
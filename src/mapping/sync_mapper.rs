@@ -0,0 +1,61 @@
+use uuid::Uuid;
+
+use crate::{
+    data::{
+        common_structure::{BasicDataStruct, DataType},
+        sync_dto::{DeletedRecordData, DeletedRecordDataV2, SyncData, SyncDataV2},
+    },
+    model::sync_model::{DeletedRecord, SyncChanges},
+    utils::datetime::{as_utc, format_date_time},
+};
+
+/// Id is the one of the deleted record, not the tombstone.
+impl From<DeletedRecord> for BasicDataStruct<DeletedRecordData, Uuid> {
+    fn from(record: DeletedRecord) -> Self {
+        let attributes = DeletedRecordData {
+            record_type: record.record_type(),
+            client_id: record.client_id(),
+            deleted_at: format_date_time(record.deleted_at()),
+        };
+        BasicDataStruct::new(record.record_id(), DataType::DeletedRecord, attributes)
+    }
+}
+
+impl From<DeletedRecord> for BasicDataStruct<DeletedRecordDataV2, Uuid> {
+    fn from(record: DeletedRecord) -> Self {
+        let attributes = DeletedRecordDataV2 {
+            record_type: record.record_type(),
+            client_id: record.client_id(),
+            deleted_at: as_utc(record.deleted_at()),
+        };
+        BasicDataStruct::new(record.record_id(), DataType::DeletedRecord, attributes)
+    }
+}
+
+impl From<SyncChanges> for SyncData {
+    fn from(changes: SyncChanges) -> Self {
+        let cursor = changes.cursor();
+        let (meals, dreams, deleted, conflicts) = changes.into_parts();
+        SyncData {
+            cursor,
+            meals: meals.into_iter().map(|meal| meal.into()).collect(),
+            dreams: dreams.into_iter().map(|dream| dream.into()).collect(),
+            deleted: deleted.into_iter().map(|record| record.into()).collect(),
+            conflicts,
+        }
+    }
+}
+
+impl From<SyncChanges> for SyncDataV2 {
+    fn from(changes: SyncChanges) -> Self {
+        let cursor = changes.cursor();
+        let (meals, dreams, deleted, conflicts) = changes.into_parts();
+        SyncDataV2 {
+            cursor,
+            meals: meals.into_iter().map(|meal| meal.into()).collect(),
+            dreams: dreams.into_iter().map(|dream| dream.into()).collect(),
+            deleted: deleted.into_iter().map(|record| record.into()).collect(),
+            conflicts,
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use uuid::Uuid;
+
+use crate::{
+    data::{
+        common_structure::{BasicDataStruct, DataType},
+        webhook_dto::{WebhookData, WebhookDataV2, WebhookDeliveryData, WebhookDeliveryDataV2},
+    },
+    model::webhook_model::{DeliveryStatus, Webhook, WebhookDelivery},
+    utils::datetime::{as_utc, format_date_time},
+};
+
+impl From<Webhook> for BasicDataStruct<WebhookData, Uuid> {
+    fn from(webhook: Webhook) -> Self {
+        (webhook, None).into()
+    }
+}
+
+/// The secret is only set when the webhook is created.
+impl From<(Webhook, Option<String>)> for BasicDataStruct<WebhookData, Uuid> {
+    fn from((webhook, secret): (Webhook, Option<String>)) -> Self {
+        let attributes = WebhookData {
+            url: webhook.url(),
+            active: webhook.active(),
+            created_at: webhook.formatted_created_at(),
+            secret,
+        };
+        BasicDataStruct::new(webhook.public_id(), DataType::Webhook, attributes)
+    }
+}
+
+impl From<Webhook> for BasicDataStruct<WebhookDataV2, Uuid> {
+    fn from(webhook: Webhook) -> Self {
+        (webhook, None).into()
+    }
+}
+
+impl From<(Webhook, Option<String>)> for BasicDataStruct<WebhookDataV2, Uuid> {
+    fn from((webhook, secret): (Webhook, Option<String>)) -> Self {
+        let attributes = WebhookDataV2 {
+            url: webhook.url(),
+            active: webhook.active(),
+            created_at: as_utc(webhook.created_at()),
+            secret,
+        };
+        BasicDataStruct::new(webhook.public_id(), DataType::Webhook, attributes)
+    }
+}
+
+impl From<WebhookDelivery> for BasicDataStruct<WebhookDeliveryData, Uuid> {
+    fn from(delivery: WebhookDelivery) -> Self {
+        let next_attempt_at = match delivery.status() {
+            Some(DeliveryStatus::Pending) => Some(format_date_time(delivery.next_attempt_at())),
+            _ => None,
+        };
+        let attributes = WebhookDeliveryData {
+            event: delivery.event(),
+            status: delivery.formatted_status(),
+            attempts: delivery.attempts(),
+            next_attempt_at,
+            response_code: delivery.response_code(),
+            error: delivery.error(),
+            created_at: format_date_time(delivery.created_at()),
+            delivered_at: delivery.delivered_at().map(format_date_time),
+        };
+        BasicDataStruct::new(delivery.public_id(), DataType::WebhookDelivery, attributes)
+    }
+}
+
+impl From<WebhookDelivery> for BasicDataStruct<WebhookDeliveryDataV2, Uuid> {
+    fn from(delivery: WebhookDelivery) -> Self {
+        let next_attempt_at = match delivery.status() {
+            Some(DeliveryStatus::Pending) => Some(as_utc(delivery.next_attempt_at())),
+            _ => None,
+        };
+        let attributes = WebhookDeliveryDataV2 {
+            event: delivery.event(),
+            status: delivery.formatted_status(),
+            attempts: delivery.attempts(),
+            next_attempt_at,
+            response_code: delivery.response_code(),
+            error: delivery.error(),
+            created_at: as_utc(delivery.created_at()),
+            delivered_at: delivery.delivered_at().map(as_utc),
+        };
+        BasicDataStruct::new(delivery.public_id(), DataType::WebhookDelivery, attributes)
+    }
+}
@@ -1,3 +1,5 @@
+use uuid::Uuid;
+
 use crate::{
     data::{
         common_structure::{BasicDataStruct, DataType},
@@ -6,12 +8,12 @@ use crate::{
     model::weight_model::Weight,
 };
 
-impl From<Weight> for BasicDataStruct<WeightData> {
+impl From<Weight> for BasicDataStruct<WeightData, Uuid> {
     fn from(value: Weight) -> Self {
         let attributes = WeightData {
             date: value.formatted_date(),
             value: value.value(),
         };
-        BasicDataStruct::new(value.id(), DataType::Weight, attributes)
+        BasicDataStruct::new(value.public_id(), DataType::Weight, attributes)
     }
 }
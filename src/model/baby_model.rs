@@ -5,7 +5,10 @@ use uuid::Uuid;
 use crate::{
     data::baby_dto::InputBabyDto,
     schema::babies,
-    utils::datetime::{convert_to_date, format_date, now},
+    utils::{
+        cursor::Keyset,
+        datetime::{convert_to_date, format_date, now},
+    },
 };
 
 #[derive(Queryable, Selectable, Identifiable, Clone)]
@@ -17,9 +20,29 @@ pub struct Baby {
     birthdate: NaiveDate,
     belongs_to: i32,
     added_on: NaiveDateTime,
+    version: i32,
 }
 
 impl Baby {
+    pub fn new(
+        id: i32,
+        unique_id: Uuid,
+        name: String,
+        birthdate: NaiveDate,
+        belongs_to: i32,
+        added_on: NaiveDateTime,
+    ) -> Self {
+        Self {
+            id,
+            unique_id,
+            name,
+            birthdate,
+            belongs_to,
+            added_on,
+            version: 1,
+        }
+    }
+
     pub fn name(&self) -> String {
         self.name.to_string()
     }
@@ -52,6 +75,10 @@ impl Baby {
         self.unique_id
     }
 
+    pub fn version(&self) -> i32 {
+        self.version
+    }
+
     pub fn update_baby(&self, new_baby_info: InputBabyDto) -> Self {
         let new_name = match new_baby_info.name {
             Some(value) => value,
@@ -69,6 +96,14 @@ impl Baby {
     }
 }
 
+impl Keyset for Baby {
+    type Id = Uuid;
+
+    fn keyset(&self) -> (NaiveDateTime, Uuid) {
+        (self.added_on, self.unique_id)
+    }
+}
+
 #[derive(Insertable)]
 #[diesel(table_name = babies)]
 pub struct InsertableBaby {
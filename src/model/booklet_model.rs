@@ -0,0 +1,328 @@
+use chrono::{Months, NaiveDate};
+use diesel::{Identifiable, Insertable, Queryable};
+use uuid::Uuid;
+
+use crate::{
+    configuration::constant::GlobalCte,
+    data::booklet_dto::InputBookletEntryDto,
+    schema::{booklet_entries, health_schedule},
+    utils::datetime::{convert_to_date, format_date},
+};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum BookletKind {
+    Milestone,
+    Vaccination,
+}
+
+impl BookletKind {
+    pub fn get(&self) -> &'static str {
+        match self {
+            BookletKind::Milestone => "milestone",
+            BookletKind::Vaccination => "vaccination",
+        }
+    }
+
+    pub fn parse(kind: &str) -> Option<Self> {
+        match kind.trim().to_lowercase().as_str() {
+            "milestone" => Some(BookletKind::Milestone),
+            "vaccination" => Some(BookletKind::Vaccination),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Queryable, Identifiable, Clone)]
+#[diesel(table_name = health_schedule)]
+pub struct ScheduleItem {
+    id: i32,
+    kind: String,
+    name: String,
+    age_months: i16,
+    tolerance_months: i16,
+}
+
+impl ScheduleItem {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn kind(&self) -> String {
+        self.kind.to_string()
+    }
+
+    pub fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    pub fn age_months(&self) -> i16 {
+        self.age_months
+    }
+
+    pub fn tolerance_months(&self) -> i16 {
+        self.tolerance_months
+    }
+
+    pub fn due_date(&self, birthdate: NaiveDate) -> NaiveDate {
+        add_months(birthdate, self.age_months)
+    }
+
+    /// Compare reference age with the baby's one.
+    ///
+    /// Due once the age is reached and overdue when tolerance is over.
+    pub fn status(
+        &self,
+        birthdate: NaiveDate,
+        today: NaiveDate,
+        done_date: Option<NaiveDate>,
+    ) -> ScheduleStatus {
+        let due_date = self.due_date(birthdate);
+        let upcoming_months: i16 = GlobalCte::BookletUpcomingMonths.get().try_into().unwrap();
+        let state = if done_date.is_some() {
+            ScheduleState::Done
+        } else if today.ge(&add_months(due_date, self.tolerance_months)) {
+            ScheduleState::Overdue
+        } else if today.ge(&due_date) {
+            ScheduleState::Due
+        } else if add_months(today, upcoming_months).ge(&due_date) {
+            ScheduleState::Upcoming
+        } else {
+            ScheduleState::Scheduled
+        };
+        ScheduleStatus {
+            item: self.clone(),
+            due_date,
+            state,
+            done_date,
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = health_schedule)]
+pub struct InsertableScheduleItem {
+    kind: String,
+    name: String,
+    age_months: i16,
+    tolerance_months: i16,
+}
+
+impl InsertableScheduleItem {
+    pub fn new(kind: BookletKind, name: String, age_months: i16, tolerance_months: i16) -> Self {
+        Self {
+            kind: kind.get().to_string(),
+            name,
+            age_months,
+            tolerance_months,
+        }
+    }
+}
+
+#[derive(Queryable, Identifiable, Clone)]
+#[diesel(table_name = booklet_entries)]
+pub struct BookletEntry {
+    id: i32,
+    baby_id: i32,
+    schedule_id: Option<i32>,
+    kind: String,
+    name: String,
+    date: NaiveDate,
+    notes: Option<String>,
+    public_id: Uuid,
+}
+
+impl BookletEntry {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn public_id(&self) -> Uuid {
+        self.public_id
+    }
+
+    pub fn baby_id(&self) -> i32 {
+        self.baby_id
+    }
+
+    pub fn schedule_id(&self) -> Option<i32> {
+        self.schedule_id
+    }
+
+    pub fn kind(&self) -> Option<BookletKind> {
+        BookletKind::parse(&self.kind)
+    }
+
+    pub fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    pub fn formatted_date(&self) -> String {
+        format_date(self.date)
+    }
+
+    pub fn notes(&self) -> Option<String> {
+        self.notes.to_owned()
+    }
+
+    /// Update with new fields.
+    ///
+    /// If new date gives a parsing error, defaults to older value.
+    pub fn update_entry(&self, new_entry: InputBookletEntryDto) -> Self {
+        let new_name = match new_entry.name {
+            Some(value) if !value.trim().is_empty() => value.trim().to_string(),
+            _ => self.name(),
+        };
+        let new_date = match new_entry.date {
+            Some(value) => convert_to_date(&value).unwrap_or(self.date),
+            None => self.date,
+        };
+        let new_notes = match new_entry.notes {
+            Some(value) if value.trim().is_empty() => None,
+            Some(value) => Some(value),
+            None => self.notes(),
+        };
+        Self {
+            name: new_name,
+            date: new_date,
+            notes: new_notes,
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = booklet_entries)]
+pub struct InsertableBookletEntry {
+    baby_id: i32,
+    schedule_id: Option<i32>,
+    kind: String,
+    name: String,
+    date: NaiveDate,
+    notes: Option<String>,
+}
+
+impl InsertableBookletEntry {
+    pub fn new(
+        baby_id: i32,
+        schedule_id: Option<i32>,
+        kind: BookletKind,
+        name: String,
+        date: NaiveDate,
+        notes: Option<String>,
+    ) -> Self {
+        Self {
+            baby_id,
+            schedule_id,
+            kind: kind.get().to_string(),
+            name,
+            date,
+            notes: notes.filter(|value| !value.trim().is_empty()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ScheduleState {
+    Done,
+    Overdue,
+    Due,
+    Upcoming,
+    Scheduled,
+}
+
+impl ScheduleState {
+    pub fn get(&self) -> &'static str {
+        match self {
+            ScheduleState::Done => "done",
+            ScheduleState::Overdue => "overdue",
+            ScheduleState::Due => "due",
+            ScheduleState::Upcoming => "upcoming",
+            ScheduleState::Scheduled => "scheduled",
+        }
+    }
+}
+
+pub struct ScheduleStatus {
+    item: ScheduleItem,
+    due_date: NaiveDate,
+    state: ScheduleState,
+    done_date: Option<NaiveDate>,
+}
+
+impl ScheduleStatus {
+    pub fn item(&self) -> &ScheduleItem {
+        &self.item
+    }
+
+    pub fn due_date(&self) -> NaiveDate {
+        self.due_date
+    }
+
+    pub fn state(&self) -> ScheduleState {
+        self.state
+    }
+
+    pub fn done_date(&self) -> Option<NaiveDate> {
+        self.done_date
+    }
+}
+
+fn add_months(date: NaiveDate, months: i16) -> NaiveDate {
+    date.checked_add_months(Months::new(months.max(0).unsigned_abs().into()))
+        .unwrap_or(date)
+}
+
+#[cfg(test)]
+mod test_booklet {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn item(age_months: i16, tolerance_months: i16) -> ScheduleItem {
+        ScheduleItem {
+            id: 1,
+            kind: "vaccination".to_string(),
+            name: "MMR (1st dose)".to_string(),
+            age_months,
+            tolerance_months,
+        }
+    }
+
+    #[test]
+    fn test_booklet_kind() {
+        assert_eq!(
+            BookletKind::parse("Milestone"),
+            Some(BookletKind::Milestone)
+        );
+        assert_eq!(
+            BookletKind::parse("vaccination"),
+            Some(BookletKind::Vaccination)
+        );
+        assert_eq!(BookletKind::parse("other"), None);
+    }
+
+    #[test]
+    fn test_schedule_status() {
+        let birthdate = date(2023, 1, 15);
+        let mmr = item(12, 3);
+        assert_eq!(mmr.due_date(birthdate), date(2024, 1, 15));
+        let cases = [
+            (date(2023, 6, 1), ScheduleState::Scheduled),
+            (date(2023, 12, 1), ScheduleState::Upcoming),
+            (date(2024, 2, 1), ScheduleState::Due),
+            (date(2024, 4, 15), ScheduleState::Overdue),
+        ];
+        for (today, state) in cases {
+            assert_eq!(mmr.status(birthdate, today, None).state(), state);
+        }
+        let done = mmr.status(birthdate, date(2024, 6, 1), Some(date(2024, 1, 20)));
+        assert_eq!(done.state(), ScheduleState::Done);
+        assert_eq!(done.done_date(), Some(date(2024, 1, 20)));
+    }
+}
@@ -0,0 +1,116 @@
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Insertable, Queryable};
+use uuid::Uuid;
+
+use crate::{schema::calendar_tokens, utils::datetime::now};
+
+#[derive(Queryable, Identifiable, Clone)]
+#[diesel(table_name = calendar_tokens)]
+pub struct CalendarToken {
+    id: i32,
+    baby_id: i32,
+    user_id: i32,
+    token: Uuid,
+    created_at: NaiveDateTime,
+}
+
+impl CalendarToken {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn baby_id(&self) -> i32 {
+        self.baby_id
+    }
+
+    pub fn user_id(&self) -> i32 {
+        self.user_id
+    }
+
+    pub fn token(&self) -> Uuid {
+        self.token
+    }
+
+    pub fn created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = calendar_tokens)]
+pub struct InsertableCalendarToken {
+    baby_id: i32,
+    user_id: i32,
+    token: Uuid,
+    created_at: NaiveDateTime,
+}
+
+impl InsertableCalendarToken {
+    pub fn new(baby_id: i32, user_id: i32) -> Self {
+        Self {
+            baby_id,
+            user_id,
+            token: Uuid::new_v4(),
+            created_at: now(),
+        }
+    }
+
+    pub fn baby_id(&self) -> i32 {
+        self.baby_id
+    }
+
+    pub fn user_id(&self) -> i32 {
+        self.user_id
+    }
+}
+
+/// A single entry in an iCalendar feed.
+#[derive(Clone, Debug)]
+pub struct CalendarEvent {
+    uid: String,
+    summary: String,
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+    ongoing: bool,
+}
+
+impl CalendarEvent {
+    /// If there is no end date, event is still going on and ends now.
+    pub fn new(
+        uid: String,
+        summary: String,
+        start: NaiveDateTime,
+        end: Option<NaiveDateTime>,
+    ) -> Self {
+        Self {
+            uid,
+            summary,
+            start,
+            end: end.unwrap_or(now()),
+            ongoing: end.is_none(),
+        }
+    }
+
+    pub fn uid(&self) -> &str {
+        &self.uid
+    }
+
+    pub fn summary(&self) -> String {
+        match self.ongoing {
+            true => format!("{} (ongoing)", self.summary),
+            false => self.summary.to_string(),
+        }
+    }
+
+    pub fn start(&self) -> NaiveDateTime {
+        self.start
+    }
+
+    pub fn end(&self) -> NaiveDateTime {
+        self.end
+    }
+
+    pub fn ongoing(&self) -> bool {
+        self.ongoing
+    }
+}
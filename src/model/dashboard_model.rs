@@ -0,0 +1,131 @@
+use chrono::{Duration, NaiveDateTime};
+
+use super::{baby_model::Baby, dream_model::Dream, meals_model::Meal};
+
+/// Latest activity of a baby, built from the household batched queries.
+pub struct BabyDashboard {
+    baby: Baby,
+    last_meal: Option<Meal>,
+    last_dream: Option<Dream>,
+    now: NaiveDateTime,
+}
+
+impl BabyDashboard {
+    pub fn new(
+        baby: Baby,
+        last_meal: Option<Meal>,
+        last_dream: Option<Dream>,
+        now: NaiveDateTime,
+    ) -> Self {
+        Self {
+            baby,
+            last_meal,
+            last_dream,
+            now,
+        }
+    }
+
+    /// Pair every baby with its own records, babies without activity get empty ones.
+    pub fn from_batch(
+        babies: Vec<Baby>,
+        meals: Vec<Meal>,
+        dreams: Vec<Dream>,
+        now: NaiveDateTime,
+    ) -> Vec<Self> {
+        babies
+            .into_iter()
+            .map(|baby| {
+                let meal = meals
+                    .iter()
+                    .find(|meal| meal.baby_id().eq(&baby.id()))
+                    .cloned();
+                let dream = dreams
+                    .iter()
+                    .find(|dream| dream.baby_id().eq(&baby.id()))
+                    .cloned();
+                Self::new(baby, meal, dream, now)
+            })
+            .collect()
+    }
+
+    pub fn baby(&self) -> &Baby {
+        &self.baby
+    }
+
+    pub fn last_meal(&self) -> Option<Meal> {
+        self.last_meal.to_owned()
+    }
+
+    pub fn last_dream(&self) -> Option<Dream> {
+        self.last_dream.to_owned()
+    }
+
+    /// Time since the last meal started.
+    pub fn since_last_meal(&self) -> Option<Duration> {
+        self.last_meal.as_ref().map(|meal| self.now - meal.date())
+    }
+
+    /// Ongoing dream, the only timer that stays open until it's finished.
+    pub fn active_dream(&self) -> Option<&Dream> {
+        self.last_dream
+            .as_ref()
+            .filter(|dream| dream.to_date().is_none())
+    }
+
+    /// Time asleep when a dream is ongoing, time awake otherwise.
+    pub fn since_last_dream(&self) -> Option<Duration> {
+        self.last_dream.as_ref().map(|dream| match dream.to_date() {
+            Some(to_date) => self.now - to_date,
+            None => self.now - dream.from_date(),
+        })
+    }
+
+    pub fn sleeping(&self) -> bool {
+        self.active_dream().is_some()
+    }
+}
+
+#[cfg(test)]
+mod test_dashboard {
+    use chrono::NaiveDate;
+    use uuid::Uuid;
+
+    use super::*;
+
+    fn at(hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2023, 6, 1)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn baby(id: i32, name: &str) -> Baby {
+        Baby::new(
+            id,
+            Uuid::new_v4(),
+            name.to_string(),
+            at(0, 0).date(),
+            1,
+            at(0, 0),
+        )
+    }
+
+    #[test]
+    fn test_from_batch() {
+        let babies = vec![baby(1, "Alice"), baby(2, "Bob")];
+        let meals = vec![Meal::new(10, 2, at(9, 0), Some(90), None, None)];
+        let dreams = vec![
+            Dream::new(20, 1, at(8, 0), Some(at(9, 30))),
+            Dream::new(21, 2, at(10, 0), None),
+        ];
+        let dashboard = BabyDashboard::from_batch(babies, meals, dreams, at(10, 30));
+        let alice = &dashboard[0];
+        assert!(alice.last_meal().is_none());
+        assert!(!alice.sleeping());
+        assert_eq!(alice.since_last_dream(), Some(Duration::minutes(60)));
+        let bob = &dashboard[1];
+        assert_eq!(bob.since_last_meal(), Some(Duration::minutes(90)));
+        assert!(bob.sleeping());
+        assert_eq!(bob.since_last_dream(), Some(Duration::minutes(30)));
+    }
+}
@@ -1,12 +1,16 @@
 use chrono::{Duration, NaiveDateTime};
 use diesel::{Identifiable, Insertable, Queryable};
+use uuid::Uuid;
 
 use crate::{
     data::dream_dto::InputDreamDto,
     schema::dreams,
-    utils::datetime::{
-        convert_to_date_time, format_date, format_duration, format_time, now,
-        parse_string_to_optional_date,
+    utils::{
+        cursor::Keyset,
+        datetime::{
+            convert_to_date_time, format_date, format_duration, format_time, now,
+            parse_string_to_optional_date,
+        },
     },
 };
 
@@ -17,6 +21,11 @@ pub struct Dream {
     baby_id: i32,
     from_date: NaiveDateTime,
     to_date: Option<NaiveDateTime>,
+    client_id: Option<Uuid>,
+    updated_at: NaiveDateTime,
+    version: i32,
+    public_id: Uuid,
+    edited_at: NaiveDateTime,
 }
 
 impl Dream {
@@ -31,6 +40,11 @@ impl Dream {
             baby_id,
             from_date,
             to_date,
+            client_id: None,
+            updated_at: from_date,
+            version: 1,
+            public_id: Uuid::new_v4(),
+            edited_at: from_date,
         }
     }
 
@@ -91,6 +105,32 @@ impl Dream {
         self.id
     }
 
+    pub fn public_id(&self) -> Uuid {
+        self.public_id
+    }
+
+    pub fn client_id(&self) -> Option<Uuid> {
+        self.client_id
+    }
+
+    pub fn updated_at(&self) -> NaiveDateTime {
+        self.updated_at
+    }
+
+    pub fn version(&self) -> i32 {
+        self.version
+    }
+
+    /// When the last change was made, on the client for offline changes.
+    pub fn edited_at(&self) -> NaiveDateTime {
+        self.edited_at
+    }
+
+    /// Change made offline at `edited_at`, it's compared with later changes.
+    pub fn with_edited_at(self, edited_at: NaiveDateTime) -> Self {
+        Self { edited_at, ..self }
+    }
+
     pub fn update_dream(&self, dream_record: InputDreamDto) -> Self {
         let new_from_date = match dream_record.from_date {
             Some(value) => convert_to_date_time(&value).unwrap_or(self.from_date),
@@ -103,17 +143,30 @@ impl Dream {
         Self {
             from_date: new_from_date,
             to_date: new_to_date,
+            updated_at: now(),
+            edited_at: now(),
             ..self.clone()
         }
     }
 }
 
+impl Keyset for Dream {
+    type Id = Uuid;
+
+    fn keyset(&self) -> (NaiveDateTime, Uuid) {
+        (self.from_date, self.public_id)
+    }
+}
+
 #[derive(Insertable)]
 #[diesel(table_name = dreams)]
 pub struct InsertableDream {
     baby_id: i32,
     from_date: Option<NaiveDateTime>,
     to_date: Option<NaiveDateTime>,
+    client_id: Option<Uuid>,
+    updated_at: NaiveDateTime,
+    edited_at: NaiveDateTime,
 }
 
 impl InsertableDream {
@@ -121,14 +174,22 @@ impl InsertableDream {
         baby_id: i32,
         from_date: Option<NaiveDateTime>,
         to_date: Option<NaiveDateTime>,
+        client_id: Option<Uuid>,
     ) -> Self {
         Self {
             baby_id,
             from_date,
             to_date,
+            client_id,
+            updated_at: now(),
+            edited_at: now(),
         }
     }
 
+    pub fn with_edited_at(self, edited_at: NaiveDateTime) -> Self {
+        Self { edited_at, ..self }
+    }
+
     pub fn baby_id(&self) -> i32 {
         self.baby_id
     }
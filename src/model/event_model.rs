@@ -0,0 +1,16 @@
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum EventAction {
+    Created,
+    Updated,
+    Deleted,
+}
+
+impl EventAction {
+    pub fn get(&self) -> &'static str {
+        match self {
+            EventAction::Created => "created",
+            EventAction::Updated => "updated",
+            EventAction::Deleted => "deleted",
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+
+/// Value stored in redis for an idempotency key.
+///
+/// It has no status while the first request is running.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct IdempotentResponse {
+    fingerprint: String,
+    status: Option<u16>,
+    #[serde(default)]
+    headers: Vec<(String, String)>,
+    body: Option<String>,
+    /// Detail of an error, `/api/v1` builds its own body from it.
+    #[serde(default)]
+    error_detail: Option<String>,
+}
+
+impl IdempotentResponse {
+    pub fn pending(fingerprint: &str) -> Self {
+        Self {
+            fingerprint: fingerprint.to_string(),
+            status: None,
+            headers: Vec::new(),
+            body: None,
+            error_detail: None,
+        }
+    }
+
+    /// Headers are kept in order, a name can repeat like `Link`.
+    pub fn completed(
+        fingerprint: &str,
+        status: u16,
+        headers: Vec<(String, String)>,
+        body: &str,
+        error_detail: Option<String>,
+    ) -> Self {
+        Self {
+            fingerprint: fingerprint.to_string(),
+            status: Some(status),
+            headers,
+            body: Some(body.to_string()),
+            error_detail,
+        }
+    }
+
+    pub fn is_pending(&self) -> bool {
+        self.status.is_none()
+    }
+
+    /// Same key must be replayed against the same method and path.
+    pub fn matches(&self, fingerprint: &str) -> bool {
+        self.fingerprint.eq(fingerprint)
+    }
+
+    pub fn status(&self) -> u16 {
+        self.status.unwrap_or_default()
+    }
+
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    pub fn body(&self) -> String {
+        self.body.clone().unwrap_or_default()
+    }
+
+    pub fn error_detail(&self) -> Option<&str> {
+        self.error_detail.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod test_idempotency_model {
+    use super::*;
+
+    #[test]
+    fn test_pending() {
+        let pending = IdempotentResponse::pending("POST /api/baby");
+        assert!(pending.is_pending());
+        assert!(pending.matches("POST /api/baby"));
+        assert!(!pending.matches("PATCH /api/baby"));
+    }
+
+    #[test]
+    fn test_completed_round_trip() {
+        let headers = vec![
+            ("etag".to_string(), "\"1\"".to_string()),
+            ("link".to_string(), "</a>".to_string()),
+            ("link".to_string(), "</b>".to_string()),
+        ];
+        let completed = IdempotentResponse::completed(
+            "POST /api/baby",
+            201,
+            headers.clone(),
+            "{\"data\":1}",
+            None,
+        );
+        let stored = serde_json::to_string(&completed).unwrap();
+        let replayed: IdempotentResponse = serde_json::from_str(&stored).unwrap();
+        assert!(!replayed.is_pending());
+        assert_eq!(replayed.status(), 201);
+        assert_eq!(replayed.headers(), headers.as_slice());
+        assert_eq!(replayed.body(), "{\"data\":1}");
+        assert_eq!(replayed, completed);
+    }
+
+    #[test]
+    fn test_stored_without_headers() {
+        let stored = r#"{"fingerprint":"POST /api/baby","status":201,"body":"{}"}"#;
+        let replayed: IdempotentResponse = serde_json::from_str(stored).unwrap();
+        assert!(replayed.headers().is_empty());
+        assert_eq!(replayed.error_detail(), None);
+        assert_eq!(replayed.status(), 201);
+    }
+}
@@ -1,15 +1,23 @@
+use std::collections::HashMap;
+
 use chrono::{Duration, NaiveDateTime};
 use diesel::{Identifiable, Insertable, Queryable};
+use uuid::Uuid;
 
 use crate::{
     data::meal_dto::InputMealDto,
     schema::meals,
-    utils::datetime::{
-        convert_to_date_time, format_date, format_duration, format_time,
-        parse_string_to_optional_date,
+    utils::{
+        cursor::Keyset,
+        datetime::{
+            convert_to_date_time, format_date, format_duration, format_time, now,
+            parse_string_to_optional_date,
+        },
     },
 };
 
+use super::reference_model::Reference;
+
 #[derive(Queryable, Identifiable, Clone)]
 #[diesel(table_name = meals)]
 pub struct Meal {
@@ -18,6 +26,13 @@ pub struct Meal {
     date: NaiveDateTime,
     quantity: Option<i16>,
     to_time: Option<NaiveDateTime>,
+    #[diesel(deserialize_as = Option<i32>)]
+    milk_bag: Reference,
+    client_id: Option<Uuid>,
+    updated_at: NaiveDateTime,
+    version: i32,
+    public_id: Uuid,
+    edited_at: NaiveDateTime,
 }
 
 impl Meal {
@@ -27,6 +42,7 @@ impl Meal {
         date: NaiveDateTime,
         quantity: Option<i16>,
         to_time: Option<NaiveDateTime>,
+        milk_bag_id: Option<i32>,
     ) -> Self {
         Self {
             id,
@@ -34,6 +50,12 @@ impl Meal {
             date,
             quantity,
             to_time,
+            milk_bag: milk_bag_id.into(),
+            client_id: None,
+            updated_at: date,
+            version: 1,
+            public_id: Uuid::new_v4(),
+            edited_at: date,
         }
     }
 
@@ -41,6 +63,10 @@ impl Meal {
         self.id
     }
 
+    pub fn public_id(&self) -> Uuid {
+        self.public_id
+    }
+
     pub fn baby_id(&self) -> i32 {
         self.baby_id
     }
@@ -57,6 +83,41 @@ impl Meal {
         self.to_time
     }
 
+    pub fn milk_bag_id(&self) -> Option<i32> {
+        self.milk_bag.id()
+    }
+
+    /// Public id of the bag, `None` until the repository fills it in.
+    pub fn milk_bag_public_id(&self) -> Option<Uuid> {
+        self.milk_bag.public_id()
+    }
+
+    pub fn resolve_milk_bag(&mut self, public_ids: &HashMap<i32, Uuid>) {
+        self.milk_bag.resolve(public_ids);
+    }
+
+    pub fn client_id(&self) -> Option<Uuid> {
+        self.client_id
+    }
+
+    pub fn updated_at(&self) -> NaiveDateTime {
+        self.updated_at
+    }
+
+    pub fn version(&self) -> i32 {
+        self.version
+    }
+
+    /// When the last change was made, on the client for offline changes.
+    pub fn edited_at(&self) -> NaiveDateTime {
+        self.edited_at
+    }
+
+    /// Change made offline at `edited_at`, it's compared with later changes.
+    pub fn with_edited_at(self, edited_at: NaiveDateTime) -> Self {
+        Self { edited_at, ..self }
+    }
+
     pub fn formatted_quantity(&self) -> i16 {
         match self.quantity {
             Some(q) => q,
@@ -118,11 +179,21 @@ impl Meal {
             date: new_date,
             quantity: new_quantity,
             to_time: new_to_time,
+            updated_at: now(),
+            edited_at: now(),
             ..self.clone()
         }
     }
 }
 
+impl Keyset for Meal {
+    type Id = Uuid;
+
+    fn keyset(&self) -> (NaiveDateTime, Uuid) {
+        (self.date, self.public_id)
+    }
+}
+
 #[derive(Insertable)]
 #[diesel(table_name= meals)]
 pub struct InsertableMeal {
@@ -130,6 +201,10 @@ pub struct InsertableMeal {
     date: NaiveDateTime,
     quantity: Option<i16>,
     to_time: Option<NaiveDateTime>,
+    milk_bag_id: Option<i32>,
+    client_id: Option<Uuid>,
+    updated_at: NaiveDateTime,
+    edited_at: NaiveDateTime,
 }
 
 impl InsertableMeal {
@@ -138,12 +213,30 @@ impl InsertableMeal {
         date: NaiveDateTime,
         quantity: Option<i16>,
         to_time: Option<NaiveDateTime>,
+        milk_bag_id: Option<i32>,
+        client_id: Option<Uuid>,
     ) -> Self {
         Self {
             baby_id,
             date,
             quantity,
             to_time,
+            milk_bag_id,
+            client_id,
+            updated_at: now(),
+            edited_at: now(),
         }
     }
+
+    pub fn with_edited_at(self, edited_at: NaiveDateTime) -> Self {
+        Self { edited_at, ..self }
+    }
+
+    pub fn quantity(&self) -> Option<i16> {
+        self.quantity
+    }
+
+    pub fn milk_bag_id(&self) -> Option<i32> {
+        self.milk_bag_id
+    }
 }
@@ -0,0 +1,425 @@
+use std::collections::HashMap;
+
+use chrono::{Days, Duration, NaiveDateTime, NaiveTime};
+use diesel::{Identifiable, Insertable, Queryable};
+use uuid::Uuid;
+
+use crate::{
+    configuration::constant::GlobalCte,
+    data::medication_dto::InputMedicationDto,
+    schema::{medication_doses, medications},
+    utils::datetime::{convert_to_time, format_date, format_time},
+};
+
+use super::reference_model::Reference;
+
+#[derive(Queryable, Identifiable, Clone)]
+#[diesel(table_name = medications)]
+pub struct Medication {
+    id: i32,
+    baby_id: i32,
+    name: String,
+    dose: f32,
+    unit: String,
+    interval_minutes: Option<i32>,
+    daily_time: Option<NaiveTime>,
+    created_at: NaiveDateTime,
+    public_id: Uuid,
+}
+
+impl Medication {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn public_id(&self) -> Uuid {
+        self.public_id
+    }
+
+    pub fn baby_id(&self) -> i32 {
+        self.baby_id
+    }
+
+    pub fn name(&self) -> String {
+        self.name.to_string()
+    }
+
+    pub fn dose(&self) -> f32 {
+        self.dose
+    }
+
+    pub fn unit(&self) -> String {
+        self.unit.to_string()
+    }
+
+    pub fn interval_minutes(&self) -> Option<i32> {
+        self.interval_minutes
+    }
+
+    pub fn daily_time(&self) -> Option<NaiveTime> {
+        self.daily_time
+    }
+
+    pub fn created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+
+    pub fn formatted_daily_time(&self) -> Option<String> {
+        self.daily_time.map(format_time)
+    }
+
+    pub fn has_schedule(&self) -> bool {
+        self.interval_minutes.is_some() || self.daily_time.is_some()
+    }
+
+    /// Earliest moment a new dose can be given after a previous one.
+    ///
+    /// Daily medications are given once per day, interval ones must wait the minimum interval.
+    pub fn next_allowed_after(&self, previous: NaiveDateTime) -> NaiveDateTime {
+        let mut next = previous;
+        if let Some(minutes) = self.interval_minutes {
+            next = previous + Duration::minutes(minutes.into());
+        }
+        if self.daily_time.is_some() {
+            let next_day = previous
+                .date()
+                .checked_add_days(Days::new(1))
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap();
+            next = next.max(next_day);
+        }
+        next
+    }
+
+    /// Check a dose doesn't break the minimum interval with its surrounding doses.
+    ///
+    /// On error returns the earliest date a dose is allowed.
+    pub fn check_dose(
+        &self,
+        date: NaiveDateTime,
+        previous: Option<NaiveDateTime>,
+        following: Option<NaiveDateTime>,
+    ) -> Result<(), NaiveDateTime> {
+        if let Some(previous) = previous {
+            let allowed = self.next_allowed_after(previous);
+            if date.lt(&allowed) {
+                return Err(allowed);
+            }
+        }
+        if let Some(following) = following {
+            if following.lt(&self.next_allowed_after(date)) {
+                return Err(self.next_allowed_after(following));
+            }
+        }
+        Ok(())
+    }
+
+    /// Calculate next dose from the last one given.
+    ///
+    /// Interval medications are given as needed, only daily schedules can be overdue.
+    pub fn status(&self, last_dose: Option<NaiveDateTime>, now: NaiveDateTime) -> MedicationStatus {
+        let next_dose = match (last_dose, self.daily_time) {
+            (_, Some(time)) => {
+                let scheduled = match last_dose {
+                    Some(last) if last.date().ge(&now.date()) => now
+                        .date()
+                        .checked_add_days(Days::new(1))
+                        .unwrap()
+                        .and_time(time),
+                    _ => now.date().and_time(time),
+                };
+                match last_dose {
+                    Some(last) => self.next_allowed_after(last).max(scheduled),
+                    None => scheduled,
+                }
+            }
+            (Some(last), None) => self.next_allowed_after(last),
+            (None, None) => now,
+        };
+        let grace = Duration::minutes(GlobalCte::MedicationOverdueMinutes.get().into());
+        MedicationStatus {
+            medication: self.clone(),
+            last_dose,
+            next_dose,
+            due: next_dose.le(&now),
+            overdue: self.daily_time.is_some() && (next_dose + grace).lt(&now),
+        }
+    }
+
+    /// Update with new fields.
+    ///
+    /// Interval 0 and daily time "null" remove the schedule, invalid values keep older ones.
+    pub fn update_medication(&self, new_medication: InputMedicationDto) -> Self {
+        let new_name = match new_medication.name {
+            Some(value) if !value.trim().is_empty() => value.trim().to_string(),
+            _ => self.name(),
+        };
+        let new_dose = match new_medication.dose {
+            Some(value) if value.gt(&0.0) => value,
+            _ => self.dose,
+        };
+        let new_unit = match new_medication.unit {
+            Some(value) if !value.trim().is_empty() => value.trim().to_string(),
+            _ => self.unit(),
+        };
+        let new_interval = match new_medication.interval_minutes {
+            Some(value) if value.le(&0) => None,
+            Some(value) => Some(value),
+            None => self.interval_minutes,
+        };
+        let new_daily_time = match new_medication.daily_time.as_deref() {
+            Some("null") => None,
+            Some(value) => convert_to_time(value).ok().or(self.daily_time),
+            None => self.daily_time,
+        };
+        Self {
+            name: new_name,
+            dose: new_dose,
+            unit: new_unit,
+            interval_minutes: new_interval,
+            daily_time: new_daily_time,
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = medications)]
+pub struct InsertableMedication {
+    baby_id: i32,
+    name: String,
+    dose: f32,
+    unit: String,
+    interval_minutes: Option<i32>,
+    daily_time: Option<NaiveTime>,
+    created_at: NaiveDateTime,
+}
+
+impl InsertableMedication {
+    pub fn new(
+        baby_id: i32,
+        name: String,
+        dose: f32,
+        unit: String,
+        interval_minutes: Option<i32>,
+        daily_time: Option<NaiveTime>,
+        created_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            baby_id,
+            name,
+            dose,
+            unit,
+            interval_minutes: interval_minutes.filter(|minutes| minutes.gt(&0)),
+            daily_time,
+            created_at,
+        }
+    }
+
+    pub fn has_schedule(&self) -> bool {
+        self.interval_minutes.is_some() || self.daily_time.is_some()
+    }
+}
+
+#[derive(Queryable, Identifiable, Clone)]
+#[diesel(table_name = medication_doses)]
+pub struct MedicationDose {
+    id: i32,
+    #[diesel(deserialize_as = i32)]
+    medication: Reference,
+    date: NaiveDateTime,
+    dose: f32,
+    public_id: Uuid,
+}
+
+impl MedicationDose {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn public_id(&self) -> Uuid {
+        self.public_id
+    }
+
+    /// Column is not null, the id is always there.
+    pub fn medication_id(&self) -> i32 {
+        self.medication.id().unwrap_or_default()
+    }
+
+    /// Public id of the medication, `None` until the repository fills it in.
+    pub fn medication_public_id(&self) -> Option<Uuid> {
+        self.medication.public_id()
+    }
+
+    pub fn resolve_medication(&mut self, public_ids: &HashMap<i32, Uuid>) {
+        self.medication.resolve(public_ids);
+    }
+
+    pub fn date(&self) -> NaiveDateTime {
+        self.date
+    }
+
+    pub fn dose(&self) -> f32 {
+        self.dose
+    }
+
+    pub fn formatted_date(&self) -> String {
+        format_date(self.date.date())
+    }
+
+    pub fn formatted_time(&self) -> String {
+        format_time(self.date.time())
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = medication_doses)]
+pub struct InsertableMedicationDose {
+    medication_id: i32,
+    date: NaiveDateTime,
+    dose: f32,
+}
+
+impl InsertableMedicationDose {
+    pub fn new(medication_id: i32, date: NaiveDateTime, dose: f32) -> Self {
+        Self {
+            medication_id,
+            date,
+            dose,
+        }
+    }
+
+    pub fn medication_id(&self) -> i32 {
+        self.medication_id
+    }
+
+    pub fn date(&self) -> NaiveDateTime {
+        self.date
+    }
+}
+
+pub struct MedicationStatus {
+    medication: Medication,
+    last_dose: Option<NaiveDateTime>,
+    next_dose: NaiveDateTime,
+    due: bool,
+    overdue: bool,
+}
+
+impl MedicationStatus {
+    pub fn medication(&self) -> &Medication {
+        &self.medication
+    }
+
+    pub fn last_dose(&self) -> Option<NaiveDateTime> {
+        self.last_dose
+    }
+
+    pub fn next_dose(&self) -> NaiveDateTime {
+        self.next_dose
+    }
+
+    pub fn due(&self) -> bool {
+        self.due
+    }
+
+    pub fn overdue(&self) -> bool {
+        self.overdue
+    }
+}
+
+#[cfg(test)]
+mod test_medication {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn at(day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2023, 6, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn medication(interval_minutes: Option<i32>, daily_time: Option<NaiveTime>) -> Medication {
+        Medication {
+            id: 1,
+            baby_id: 1,
+            name: "Paracetamol".to_string(),
+            dose: 2.5,
+            unit: "ml".to_string(),
+            interval_minutes,
+            daily_time,
+            created_at: at(1, 0, 0),
+            public_id: Uuid::new_v4(),
+        }
+    }
+
+    #[test]
+    fn test_check_dose_interval() {
+        let paracetamol = medication(Some(360), None);
+        assert!(paracetamol.check_dose(at(1, 8, 0), None, None).is_ok());
+        assert_eq!(
+            paracetamol.check_dose(at(1, 12, 0), Some(at(1, 8, 0)), None),
+            Err(at(1, 14, 0))
+        );
+        assert!(paracetamol
+            .check_dose(at(1, 14, 0), Some(at(1, 8, 0)), None)
+            .is_ok());
+        assert_eq!(
+            paracetamol.check_dose(at(1, 6, 0), None, Some(at(1, 8, 0))),
+            Err(at(1, 14, 0))
+        );
+    }
+
+    #[test]
+    fn test_check_dose_daily() {
+        let vitamin = medication(None, NaiveTime::from_hms_opt(9, 0, 0));
+        assert_eq!(
+            vitamin.check_dose(at(1, 20, 0), Some(at(1, 9, 0)), None),
+            Err(at(2, 0, 0))
+        );
+        assert!(vitamin
+            .check_dose(at(2, 7, 0), Some(at(1, 9, 0)), None)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_status() {
+        let paracetamol = medication(Some(360), None);
+        let status = paracetamol.status(Some(at(1, 8, 0)), at(1, 10, 0));
+        assert_eq!(status.next_dose(), at(1, 14, 0));
+        assert!(!status.due());
+        assert!(!status.overdue());
+        let status = paracetamol.status(None, at(1, 10, 0));
+        assert!(status.due());
+        assert!(!status.overdue());
+
+        let vitamin = medication(None, NaiveTime::from_hms_opt(9, 0, 0));
+        let status = vitamin.status(Some(at(1, 9, 0)), at(1, 12, 0));
+        assert_eq!(status.next_dose(), at(2, 9, 0));
+        assert!(!status.due());
+        let status = vitamin.status(Some(at(1, 9, 0)), at(2, 12, 0));
+        assert_eq!(status.next_dose(), at(2, 9, 0));
+        assert!(status.due());
+        assert!(status.overdue());
+    }
+
+    #[test]
+    fn test_update_medication() {
+        let update = InputMedicationDto {
+            name: Some(" ".to_string()),
+            dose: Some(0.0),
+            unit: None,
+            interval_minutes: Some(0),
+            daily_time: Some("08:30".to_string()),
+        };
+        let updated = medication(Some(360), None).update_medication(update);
+        assert_eq!(updated.name(), "Paracetamol");
+        assert_eq!(updated.dose(), 2.5);
+        assert_eq!(updated.interval_minutes(), None);
+        assert_eq!(updated.formatted_daily_time(), Some("08:30".to_string()));
+        assert!(updated.has_schedule());
+    }
+}
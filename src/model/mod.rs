@@ -6,4 +6,18 @@ pub mod session_model;
 pub mod meals_model;
 pub mod dream_model;
 pub mod weight_model;
-pub mod summary_model;
\ No newline at end of file
+pub mod summary_model;
+pub mod report_model;
+pub mod calendar_model;
+pub mod note_model;
+pub mod medication_model;
+pub mod booklet_model;
+pub mod pumping_model;
+pub mod dashboard_model;
+pub mod event_model;
+pub mod webhook_model;
+pub mod reminder_model;
+pub mod notification_model;
+pub mod sync_model;
+pub mod idempotency_model;
+pub mod reference_model;
@@ -0,0 +1,219 @@
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Insertable, Queryable};
+use uuid::Uuid;
+
+use crate::{
+    data::note_dto::InputNoteDto,
+    schema::notes,
+    utils::datetime::{convert_to_date_time, format_date, format_time},
+};
+
+const DEFAULT_CATEGORY: &str = "note";
+const MAX_CATEGORY_LENGTH: usize = 32;
+const MAX_UNIT_LENGTH: usize = 16;
+
+#[derive(Queryable, Identifiable, Clone)]
+#[diesel(table_name = notes)]
+pub struct Note {
+    id: i32,
+    baby_id: i32,
+    category: String,
+    date: NaiveDateTime,
+    value: Option<f32>,
+    unit: Option<String>,
+    description: Option<String>,
+    public_id: Uuid,
+}
+
+impl Note {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn public_id(&self) -> Uuid {
+        self.public_id
+    }
+
+    pub fn baby_id(&self) -> i32 {
+        self.baby_id
+    }
+
+    pub fn category(&self) -> String {
+        self.category.to_string()
+    }
+
+    pub fn date(&self) -> NaiveDateTime {
+        self.date
+    }
+
+    pub fn value(&self) -> Option<f32> {
+        self.value
+    }
+
+    pub fn unit(&self) -> Option<String> {
+        self.unit.to_owned()
+    }
+
+    pub fn description(&self) -> Option<String> {
+        self.description.to_owned()
+    }
+
+    pub fn formatted_date(&self) -> String {
+        format_date(self.date.date())
+    }
+
+    pub fn formatted_time(&self) -> String {
+        format_time(self.date.time())
+    }
+
+    /// Update with new fields.
+    ///
+    /// If new date gives a parsing error, defaults to older value.
+    /// Empty strings remove optional text fields.
+    pub fn update_note(&self, new_note: InputNoteDto) -> Self {
+        let new_date = match new_note.date {
+            Some(value) => convert_to_date_time(&value).unwrap_or(self.date),
+            None => self.date,
+        };
+        let new_category = match new_note.category {
+            Some(value) => normalize_category(Some(value)),
+            None => self.category(),
+        };
+        let new_unit = match new_note.unit {
+            Some(value) => normalize_text(Some(value), MAX_UNIT_LENGTH),
+            None => self.unit(),
+        };
+        let new_description = match new_note.description {
+            Some(value) => normalize_text(Some(value), usize::MAX),
+            None => self.description(),
+        };
+        Self {
+            category: new_category,
+            date: new_date,
+            value: new_note.value.or(self.value),
+            unit: new_unit,
+            description: new_description,
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = notes)]
+pub struct InsertableNote {
+    baby_id: i32,
+    category: String,
+    date: NaiveDateTime,
+    value: Option<f32>,
+    unit: Option<String>,
+    description: Option<String>,
+}
+
+impl InsertableNote {
+    pub fn new(
+        baby_id: i32,
+        category: Option<String>,
+        date: NaiveDateTime,
+        value: Option<f32>,
+        unit: Option<String>,
+        description: Option<String>,
+    ) -> Self {
+        Self {
+            baby_id,
+            category: normalize_category(category),
+            date,
+            value,
+            unit: normalize_text(unit, MAX_UNIT_LENGTH),
+            description: normalize_text(description, usize::MAX),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.value.is_none() && self.description.is_none()
+    }
+}
+
+/// Categories are free text, stored lowercase so they can be filtered.
+fn normalize_category(category: Option<String>) -> String {
+    normalize_text(category, MAX_CATEGORY_LENGTH)
+        .map(|value| value.to_lowercase())
+        .unwrap_or(DEFAULT_CATEGORY.to_string())
+}
+
+fn normalize_text(text: Option<String>, max_length: usize) -> Option<String> {
+    match text.as_deref().map(str::trim) {
+        Some("") | None => None,
+        Some(value) => Some(value.chars().take(max_length).collect()),
+    }
+}
+
+#[cfg(test)]
+mod test_note {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn note() -> Note {
+        Note {
+            id: 1,
+            baby_id: 1,
+            category: "temperature".to_string(),
+            date: NaiveDate::from_ymd_opt(2023, 6, 1)
+                .unwrap()
+                .and_hms_opt(8, 30, 0)
+                .unwrap(),
+            value: Some(38.2),
+            unit: Some("C".to_string()),
+            description: None,
+            public_id: Uuid::new_v4(),
+        }
+    }
+
+    #[test]
+    fn test_new_note() {
+        let note = InsertableNote::new(
+            1,
+            Some(" Symptom ".to_string()),
+            NaiveDate::from_ymd_opt(2023, 6, 1)
+                .unwrap()
+                .and_hms_opt(8, 30, 0)
+                .unwrap(),
+            None,
+            Some("".to_string()),
+            Some("Rash after bath".to_string()),
+        );
+        assert_eq!(note.category, "symptom");
+        assert_eq!(note.unit, None);
+        assert!(!note.is_empty());
+        let default_category = InsertableNote::new(1, None, note.date, None, None, None);
+        assert_eq!(default_category.category, DEFAULT_CATEGORY);
+        assert!(default_category.is_empty());
+    }
+
+    #[test]
+    fn test_update_note() {
+        let update = InputNoteDto {
+            date: Some("2023-06-01 09:00".to_string()),
+            category: None,
+            value: Some(37.5),
+            unit: None,
+            description: Some("Fussy after feed".to_string()),
+        };
+        let updated = note().update_note(update);
+        assert_eq!(updated.formatted_time(), "09:00");
+        assert_eq!(updated.category(), "temperature");
+        assert_eq!(updated.value(), Some(37.5));
+        assert_eq!(updated.unit(), Some("C".to_string()));
+        assert_eq!(updated.description(), Some("Fussy after feed".to_string()));
+        let clear_unit = InputNoteDto {
+            date: Some("invalid".to_string()),
+            category: None,
+            value: None,
+            unit: Some("".to_string()),
+            description: None,
+        };
+        let updated = note().update_note(clear_unit);
+        assert_eq!(updated.formatted_time(), "08:30");
+        assert_eq!(updated.unit(), None);
+    }
+}
@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Insertable, Queryable};
+use uuid::Uuid;
+
+use crate::{schema::notifications, utils::datetime::format_date_time};
+
+use super::reference_model::Reference;
+
+const MAX_TITLE_LENGTH: usize = 128;
+
+#[derive(Queryable, Identifiable, Clone)]
+#[diesel(table_name = notifications)]
+pub struct Notification {
+    id: i32,
+    user_id: i32,
+    baby_id: i32,
+    #[diesel(deserialize_as = Option<i32>)]
+    rule: Reference,
+    title: String,
+    message: String,
+    created_at: NaiveDateTime,
+    read_at: Option<NaiveDateTime>,
+    public_id: Uuid,
+}
+
+impl Notification {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn public_id(&self) -> Uuid {
+        self.public_id
+    }
+
+    pub fn user_id(&self) -> i32 {
+        self.user_id
+    }
+
+    pub fn baby_id(&self) -> i32 {
+        self.baby_id
+    }
+
+    pub fn rule_id(&self) -> Option<i32> {
+        self.rule.id()
+    }
+
+    /// Public id of the rule, `None` until the repository fills it in.
+    pub fn rule_public_id(&self) -> Option<Uuid> {
+        self.rule.public_id()
+    }
+
+    pub fn resolve_rule(&mut self, public_ids: &HashMap<i32, Uuid>) {
+        self.rule.resolve(public_ids);
+    }
+
+    pub fn title(&self) -> String {
+        self.title.to_string()
+    }
+
+    pub fn message(&self) -> String {
+        self.message.to_string()
+    }
+
+    pub fn created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+
+    pub fn formatted_created_at(&self) -> String {
+        format_date_time(self.created_at)
+    }
+
+    pub fn read_at(&self) -> Option<NaiveDateTime> {
+        self.read_at
+    }
+
+    pub fn read(&self) -> bool {
+        self.read_at.is_some()
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = notifications)]
+pub struct InsertableNotification {
+    user_id: i32,
+    baby_id: i32,
+    rule_id: Option<i32>,
+    title: String,
+    message: String,
+    created_at: NaiveDateTime,
+}
+
+impl InsertableNotification {
+    pub fn new(
+        user_id: i32,
+        baby_id: i32,
+        rule_id: Option<i32>,
+        title: &str,
+        message: &str,
+        created_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            user_id,
+            baby_id,
+            rule_id,
+            title: title.chars().take(MAX_TITLE_LENGTH).collect(),
+            message: message.to_string(),
+            created_at,
+        }
+    }
+}
@@ -0,0 +1,488 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDateTime};
+use diesel::{Identifiable, Insertable, Queryable};
+use uuid::Uuid;
+
+use crate::{
+    configuration::constant::GlobalCte,
+    data::pumping_dto::{InputMilkBagDto, InputPumpingDto},
+    schema::{milk_bags, pumping_sessions},
+    utils::datetime::{convert_to_date_time, format_date, format_time},
+};
+
+use super::reference_model::Reference;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum PumpingSide {
+    Left,
+    Right,
+    Both,
+}
+
+impl PumpingSide {
+    pub fn get(&self) -> &'static str {
+        match self {
+            PumpingSide::Left => "left",
+            PumpingSide::Right => "right",
+            PumpingSide::Both => "both",
+        }
+    }
+
+    pub fn parse(side: &str) -> Option<Self> {
+        match side.trim().to_lowercase().as_str() {
+            "left" => Some(PumpingSide::Left),
+            "right" => Some(PumpingSide::Right),
+            "both" => Some(PumpingSide::Both),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum StorageLocation {
+    Room,
+    Fridge,
+    Freezer,
+}
+
+impl StorageLocation {
+    pub fn get(&self) -> &'static str {
+        match self {
+            StorageLocation::Room => "room",
+            StorageLocation::Fridge => "fridge",
+            StorageLocation::Freezer => "freezer",
+        }
+    }
+
+    pub fn parse(location: &str) -> Option<Self> {
+        match location.trim().to_lowercase().as_str() {
+            "room" => Some(StorageLocation::Room),
+            "fridge" => Some(StorageLocation::Fridge),
+            "freezer" => Some(StorageLocation::Freezer),
+            _ => None,
+        }
+    }
+
+    /// How long milk keeps in this location.
+    pub fn shelf_life(&self) -> Duration {
+        let hours = match self {
+            StorageLocation::Room => GlobalCte::MilkRoomHours.get(),
+            StorageLocation::Fridge => GlobalCte::MilkFridgeHours.get(),
+            StorageLocation::Freezer => GlobalCte::MilkFreezerHours.get(),
+        };
+        Duration::hours(hours.into())
+    }
+}
+
+#[derive(Queryable, Identifiable, Clone)]
+#[diesel(table_name = pumping_sessions)]
+pub struct Pumping {
+    id: i32,
+    baby_id: i32,
+    date: NaiveDateTime,
+    side: String,
+    duration: i16,
+    volume: i16,
+    public_id: Uuid,
+}
+
+impl Pumping {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn public_id(&self) -> Uuid {
+        self.public_id
+    }
+
+    pub fn baby_id(&self) -> i32 {
+        self.baby_id
+    }
+
+    pub fn date(&self) -> NaiveDateTime {
+        self.date
+    }
+
+    pub fn side(&self) -> String {
+        self.side.to_string()
+    }
+
+    pub fn duration(&self) -> i16 {
+        self.duration
+    }
+
+    pub fn volume(&self) -> i16 {
+        self.volume
+    }
+
+    pub fn formatted_date(&self) -> String {
+        format_date(self.date.date())
+    }
+
+    pub fn formatted_time(&self) -> String {
+        format_time(self.date.time())
+    }
+
+    /// Update with new fields.
+    ///
+    /// Invalid dates or sides default to older values.
+    pub fn update_pumping(&self, new_pumping: InputPumpingDto) -> Self {
+        let new_date = match new_pumping.date {
+            Some(value) => convert_to_date_time(&value).unwrap_or(self.date),
+            None => self.date,
+        };
+        let new_side = match new_pumping.side.as_deref().and_then(PumpingSide::parse) {
+            Some(side) => side.get().to_string(),
+            None => self.side(),
+        };
+        Self {
+            date: new_date,
+            side: new_side,
+            duration: new_pumping.duration.unwrap_or(self.duration).max(0),
+            volume: new_pumping.volume.unwrap_or(self.volume).max(0),
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = pumping_sessions)]
+pub struct InsertablePumping {
+    baby_id: i32,
+    date: NaiveDateTime,
+    side: String,
+    duration: i16,
+    volume: i16,
+}
+
+impl InsertablePumping {
+    pub fn new(
+        baby_id: i32,
+        date: NaiveDateTime,
+        side: PumpingSide,
+        duration: i16,
+        volume: i16,
+    ) -> Self {
+        Self {
+            baby_id,
+            date,
+            side: side.get().to_string(),
+            duration: duration.max(0),
+            volume: volume.max(0),
+        }
+    }
+}
+
+#[derive(Queryable, Identifiable, Clone)]
+#[diesel(table_name = milk_bags)]
+pub struct MilkBag {
+    id: i32,
+    baby_id: i32,
+    #[diesel(deserialize_as = Option<i32>)]
+    pumping: Reference,
+    volume: i16,
+    remaining: i16,
+    pumped_at: NaiveDateTime,
+    location: String,
+    expires_at: NaiveDateTime,
+    public_id: Uuid,
+}
+
+impl MilkBag {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn public_id(&self) -> Uuid {
+        self.public_id
+    }
+
+    pub fn baby_id(&self) -> i32 {
+        self.baby_id
+    }
+
+    pub fn pumping_id(&self) -> Option<i32> {
+        self.pumping.id()
+    }
+
+    /// Public id of the session, `None` until the repository fills it in.
+    pub fn pumping_public_id(&self) -> Option<Uuid> {
+        self.pumping.public_id()
+    }
+
+    pub fn resolve_pumping(&mut self, public_ids: &HashMap<i32, Uuid>) {
+        self.pumping.resolve(public_ids);
+    }
+
+    pub fn volume(&self) -> i16 {
+        self.volume
+    }
+
+    pub fn remaining(&self) -> i16 {
+        self.remaining
+    }
+
+    pub fn pumped_at(&self) -> NaiveDateTime {
+        self.pumped_at
+    }
+
+    pub fn location(&self) -> Option<StorageLocation> {
+        StorageLocation::parse(&self.location)
+    }
+
+    pub fn formatted_location(&self) -> String {
+        self.location.to_string()
+    }
+
+    pub fn expires_at(&self) -> NaiveDateTime {
+        self.expires_at
+    }
+
+    pub fn expired(&self, now: NaiveDateTime) -> bool {
+        self.expires_at.le(&now)
+    }
+
+    /// Bag expires within the warning period.
+    pub fn expiring(&self, now: NaiveDateTime) -> bool {
+        let warning = Duration::hours(GlobalCte::MilkExpiryWarningHours.get().into());
+        !self.expired(now) && (self.expires_at - warning).le(&now)
+    }
+
+    /// Update with new fields.
+    ///
+    /// Moving a bag keeps the earliest expiry between the old one and the new location's.
+    pub fn update_bag(&self, new_bag: InputMilkBagDto, now: NaiveDateTime) -> Self {
+        let new_location = new_bag.location.as_deref().and_then(StorageLocation::parse);
+        let new_expires_at = match new_bag.expires_at {
+            Some(value) => convert_to_date_time(&value).unwrap_or(self.expires_at),
+            None => match new_location {
+                Some(location) if location.ne(&self.location().unwrap_or(location)) => {
+                    self.expires_at.min(now + location.shelf_life())
+                }
+                _ => self.expires_at,
+            },
+        };
+        let new_pumped_at = match new_bag.pumped_at {
+            Some(value) => convert_to_date_time(&value).unwrap_or(self.pumped_at),
+            None => self.pumped_at,
+        };
+        let new_volume = new_bag.volume.unwrap_or(self.volume).max(0);
+        Self {
+            pumped_at: new_pumped_at,
+            volume: new_volume,
+            remaining: new_bag
+                .remaining
+                .unwrap_or(self.remaining)
+                .clamp(0, new_volume),
+            location: new_location
+                .map(|location| location.get().to_string())
+                .unwrap_or(self.formatted_location()),
+            expires_at: new_expires_at,
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = milk_bags)]
+pub struct InsertableMilkBag {
+    baby_id: i32,
+    pumping_id: Option<i32>,
+    volume: i16,
+    remaining: i16,
+    pumped_at: NaiveDateTime,
+    location: String,
+    expires_at: NaiveDateTime,
+}
+
+impl InsertableMilkBag {
+    /// Expiry defaults to the location's shelf life from pumping time.
+    pub fn new(
+        baby_id: i32,
+        pumping_id: Option<i32>,
+        volume: i16,
+        pumped_at: NaiveDateTime,
+        location: StorageLocation,
+        expires_at: Option<NaiveDateTime>,
+    ) -> Self {
+        Self {
+            baby_id,
+            pumping_id,
+            volume,
+            remaining: volume,
+            pumped_at,
+            location: location.get().to_string(),
+            expires_at: expires_at.unwrap_or(pumped_at + location.shelf_life()),
+        }
+    }
+}
+
+/// Milk left in every bag of a baby.
+pub struct MilkStash {
+    baby_unique_id: Uuid,
+    bags: usize,
+    total_volume: i32,
+    room_volume: i32,
+    fridge_volume: i32,
+    freezer_volume: i32,
+    expiring_volume: i32,
+    expiring_bags: Vec<Uuid>,
+    expired_volume: i32,
+}
+
+impl MilkStash {
+    /// Expired bags only count towards `expired_volume`.
+    pub fn new(baby_unique_id: Uuid, bags: Vec<MilkBag>, now: NaiveDateTime) -> Self {
+        let mut stash = Self {
+            baby_unique_id,
+            bags: 0,
+            total_volume: 0,
+            room_volume: 0,
+            fridge_volume: 0,
+            freezer_volume: 0,
+            expiring_volume: 0,
+            expiring_bags: Vec::new(),
+            expired_volume: 0,
+        };
+        for bag in bags {
+            let remaining: i32 = bag.remaining().into();
+            if bag.expired(now) {
+                stash.expired_volume += remaining;
+                continue;
+            }
+            if bag.expiring(now) {
+                stash.expiring_volume += remaining;
+                stash.expiring_bags.push(bag.public_id());
+            }
+            match bag.location() {
+                Some(StorageLocation::Room) => stash.room_volume += remaining,
+                Some(StorageLocation::Fridge) => stash.fridge_volume += remaining,
+                Some(StorageLocation::Freezer) => stash.freezer_volume += remaining,
+                None => (),
+            }
+            stash.bags += 1;
+            stash.total_volume += remaining;
+        }
+        stash
+    }
+
+    pub fn baby_unique_id(&self) -> Uuid {
+        self.baby_unique_id
+    }
+
+    pub fn bags(&self) -> usize {
+        self.bags
+    }
+
+    pub fn total_volume(&self) -> i32 {
+        self.total_volume
+    }
+
+    pub fn room_volume(&self) -> i32 {
+        self.room_volume
+    }
+
+    pub fn fridge_volume(&self) -> i32 {
+        self.fridge_volume
+    }
+
+    pub fn freezer_volume(&self) -> i32 {
+        self.freezer_volume
+    }
+
+    pub fn expiring_volume(&self) -> i32 {
+        self.expiring_volume
+    }
+
+    pub fn expiring_bags(&self) -> Vec<Uuid> {
+        self.expiring_bags.to_vec()
+    }
+
+    pub fn expired_volume(&self) -> i32 {
+        self.expired_volume
+    }
+}
+
+#[cfg(test)]
+mod test_pumping {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn at(day: u32, hour: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2023, 6, day)
+            .unwrap()
+            .and_hms_opt(hour, 0, 0)
+            .unwrap()
+    }
+
+    fn bag(location: StorageLocation) -> MilkBag {
+        let new_bag = InsertableMilkBag::new(1, None, 120, at(1, 8), location, None);
+        MilkBag {
+            id: 1,
+            baby_id: new_bag.baby_id,
+            pumping: new_bag.pumping_id.into(),
+            volume: new_bag.volume,
+            remaining: new_bag.remaining,
+            pumped_at: new_bag.pumped_at,
+            location: new_bag.location,
+            expires_at: new_bag.expires_at,
+            public_id: Uuid::new_v4(),
+        }
+    }
+
+    #[test]
+    fn test_parse_enums() {
+        assert_eq!(PumpingSide::parse(" Left"), Some(PumpingSide::Left));
+        assert_eq!(PumpingSide::parse("middle"), None);
+        assert_eq!(
+            StorageLocation::parse("FREEZER"),
+            Some(StorageLocation::Freezer)
+        );
+    }
+
+    #[test]
+    fn test_bag_expiry() {
+        let fridge = bag(StorageLocation::Fridge);
+        assert_eq!(fridge.expires_at(), at(5, 8));
+        assert!(!fridge.expiring(at(2, 8)));
+        assert!(fridge.expiring(at(4, 9)));
+        assert!(!fridge.expired(at(4, 9)));
+        assert!(fridge.expired(at(5, 8)));
+        assert!(!fridge.expiring(at(5, 8)));
+    }
+
+    #[test]
+    fn test_move_bag() {
+        let freezer = bag(StorageLocation::Freezer);
+        let update = InputMilkBagDto {
+            volume: None,
+            remaining: Some(500),
+            pumped_at: None,
+            location: Some("fridge".to_string()),
+            expires_at: None,
+        };
+        let thawed = freezer.update_bag(update, at(10, 8));
+        assert_eq!(thawed.location(), Some(StorageLocation::Fridge));
+        assert_eq!(thawed.expires_at(), at(14, 8));
+        assert_eq!(thawed.remaining(), 120);
+    }
+
+    #[test]
+    fn test_milk_stash() {
+        let bags = vec![
+            bag(StorageLocation::Fridge),
+            bag(StorageLocation::Freezer),
+            bag(StorageLocation::Room),
+        ];
+        let fridge_bag = bags[0].public_id();
+        let stash = MilkStash::new(Uuid::new_v4(), bags, at(4, 9));
+        assert_eq!(stash.bags(), 2);
+        assert_eq!(stash.total_volume(), 240);
+        assert_eq!(stash.fridge_volume(), 120);
+        assert_eq!(stash.freezer_volume(), 120);
+        assert_eq!(stash.expiring_bags(), vec![fridge_bag]);
+        assert_eq!(stash.expired_volume(), 120);
+    }
+}
@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+/// Integer foreign key to another record, along with the public id clients know it by.
+///
+/// Models load the key column into it, their repository fills in the public id.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Reference {
+    id: Option<i32>,
+    public_id: Option<Uuid>,
+}
+
+impl Reference {
+    pub fn id(&self) -> Option<i32> {
+        self.id
+    }
+
+    pub fn public_id(&self) -> Option<Uuid> {
+        self.public_id
+    }
+
+    /// Look up the public id in `public_ids`, keyed by integer id.
+    pub fn resolve(&mut self, public_ids: &HashMap<i32, Uuid>) {
+        self.public_id = self.id.and_then(|id| public_ids.get(&id).copied());
+    }
+}
+
+impl From<Option<i32>> for Reference {
+    fn from(id: Option<i32>) -> Self {
+        Self {
+            id,
+            public_id: None,
+        }
+    }
+}
+
+impl From<i32> for Reference {
+    fn from(id: i32) -> Self {
+        Some(id).into()
+    }
+}
+
+#[cfg(test)]
+mod test_reference {
+    use super::*;
+
+    #[test]
+    fn test_resolve() {
+        let public_id = Uuid::new_v4();
+        let public_ids = HashMap::from([(7, public_id)]);
+        let mut reference = Reference::from(7);
+        assert_eq!(reference.public_id(), None);
+        reference.resolve(&public_ids);
+        assert_eq!(reference.id(), Some(7));
+        assert_eq!(reference.public_id(), Some(public_id));
+        let mut missing = Reference::from(None);
+        missing.resolve(&public_ids);
+        assert_eq!(missing.public_id(), None);
+    }
+}
@@ -0,0 +1,406 @@
+use std::collections::HashMap;
+
+use chrono::{Duration, NaiveDateTime, NaiveTime};
+use diesel::{Identifiable, Insertable, Queryable};
+use uuid::Uuid;
+
+use crate::{
+    data::reminder_dto::InputReminderDto,
+    schema::reminder_rules,
+    utils::datetime::{convert_to_time, format_duration, format_time, now},
+};
+
+use super::reference_model::Reference;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ReminderKind {
+    /// No meal logged for a while.
+    NoFeed,
+    /// Ongoing dream running longer than expected.
+    LongSleep,
+    /// Daily medication without a dose by a given time.
+    Medication,
+}
+
+impl ReminderKind {
+    pub fn get(&self) -> &'static str {
+        match self {
+            ReminderKind::NoFeed => "no_feed",
+            ReminderKind::LongSleep => "long_sleep",
+            ReminderKind::Medication => "medication",
+        }
+    }
+
+    pub fn parse(kind: &str) -> Option<Self> {
+        match kind.trim().to_lowercase().as_str() {
+            "no_feed" => Some(ReminderKind::NoFeed),
+            "long_sleep" => Some(ReminderKind::LongSleep),
+            "medication" => Some(ReminderKind::Medication),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ReminderChannel {
+    App,
+    Webhook,
+    Email,
+}
+
+impl ReminderChannel {
+    pub fn get(&self) -> &'static str {
+        match self {
+            ReminderChannel::App => "app",
+            ReminderChannel::Webhook => "webhook",
+            ReminderChannel::Email => "email",
+        }
+    }
+
+    pub fn parse(channel: &str) -> Option<Self> {
+        match channel.trim().to_lowercase().as_str() {
+            "app" => Some(ReminderChannel::App),
+            "webhook" => Some(ReminderChannel::Webhook),
+            "email" => Some(ReminderChannel::Email),
+            _ => None,
+        }
+    }
+
+    /// Parse a list of channels, `None` if any of them is unknown.
+    pub fn parse_list(channels: &[String]) -> Option<Vec<Self>> {
+        let mut parsed: Vec<Self> = Vec::new();
+        for channel in channels {
+            let channel = Self::parse(channel)?;
+            if !parsed.contains(&channel) {
+                parsed.push(channel);
+            }
+        }
+        Some(parsed)
+    }
+
+    /// Channels are stored as a comma separated list.
+    pub fn join(channels: &[Self]) -> String {
+        match channels.is_empty() {
+            true => ReminderChannel::App.get().to_string(),
+            false => channels
+                .iter()
+                .map(|channel| channel.get())
+                .collect::<Vec<&str>>()
+                .join(","),
+        }
+    }
+}
+
+/// Latest activity a rule is checked against.
+#[derive(Default)]
+pub struct RuleContext {
+    pub last_meal: Option<NaiveDateTime>,
+    pub ongoing_dream: Option<NaiveDateTime>,
+    pub medication_name: Option<String>,
+    pub last_dose: Option<NaiveDateTime>,
+}
+
+#[derive(Queryable, Identifiable, Clone)]
+#[diesel(table_name = reminder_rules)]
+pub struct ReminderRule {
+    id: i32,
+    baby_id: i32,
+    user_id: i32,
+    kind: String,
+    threshold_minutes: Option<i32>,
+    #[diesel(deserialize_as = Option<i32>)]
+    medication: Reference,
+    due_time: Option<NaiveTime>,
+    channels: String,
+    active: bool,
+    last_triggered_at: Option<NaiveDateTime>,
+    created_at: NaiveDateTime,
+    public_id: Uuid,
+}
+
+impl ReminderRule {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn public_id(&self) -> Uuid {
+        self.public_id
+    }
+
+    pub fn baby_id(&self) -> i32 {
+        self.baby_id
+    }
+
+    pub fn user_id(&self) -> i32 {
+        self.user_id
+    }
+
+    pub fn kind(&self) -> Option<ReminderKind> {
+        ReminderKind::parse(&self.kind)
+    }
+
+    pub fn formatted_kind(&self) -> String {
+        self.kind.to_string()
+    }
+
+    pub fn threshold_minutes(&self) -> Option<i32> {
+        self.threshold_minutes
+    }
+
+    pub fn medication_id(&self) -> Option<i32> {
+        self.medication.id()
+    }
+
+    /// Public id of the medication, `None` until the repository fills it in.
+    pub fn medication_public_id(&self) -> Option<Uuid> {
+        self.medication.public_id()
+    }
+
+    pub fn resolve_medication(&mut self, public_ids: &HashMap<i32, Uuid>) {
+        self.medication.resolve(public_ids);
+    }
+
+    pub fn due_time(&self) -> Option<NaiveTime> {
+        self.due_time
+    }
+
+    pub fn formatted_due_time(&self) -> Option<String> {
+        self.due_time.map(format_time)
+    }
+
+    pub fn channels(&self) -> Vec<ReminderChannel> {
+        self.channels
+            .split(',')
+            .filter_map(ReminderChannel::parse)
+            .collect()
+    }
+
+    pub fn formatted_channels(&self) -> String {
+        self.channels.to_string()
+    }
+
+    pub fn active(&self) -> bool {
+        self.active
+    }
+
+    pub fn last_triggered_at(&self) -> Option<NaiveDateTime> {
+        self.last_triggered_at
+    }
+
+    pub fn created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+
+    /// Message when the rule fires.
+    ///
+    /// A rule fires once per episode: a new meal, a new dream or a new day for medications.
+    pub fn evaluate(&self, context: &RuleContext, now: NaiveDateTime) -> Option<String> {
+        let not_triggered_since =
+            |start: NaiveDateTime| self.last_triggered_at.map_or(true, |last| last.lt(&start));
+        match self.kind()? {
+            ReminderKind::NoFeed => {
+                let last_meal = context.last_meal?;
+                let elapsed = now - last_meal;
+                (elapsed.ge(&self.threshold()?) && not_triggered_since(last_meal)).then(|| {
+                    format!(
+                        "No feed logged for {}.",
+                        format_duration(elapsed.num_minutes())
+                    )
+                })
+            }
+            ReminderKind::LongSleep => {
+                let from_date = context.ongoing_dream?;
+                let elapsed = now - from_date;
+                (elapsed.ge(&self.threshold()?) && not_triggered_since(from_date)).then(|| {
+                    format!(
+                        "Sleep running for {}.",
+                        format_duration(elapsed.num_minutes())
+                    )
+                })
+            }
+            ReminderKind::Medication => {
+                let due_time = self.due_time?;
+                let today = now.date().and_hms_opt(0, 0, 0).unwrap();
+                let given_today = context.last_dose.map_or(false, |dose| dose.ge(&today));
+                (now.time().ge(&due_time) && !given_today && not_triggered_since(today)).then(
+                    || {
+                        format!(
+                            "{} not given by {}.",
+                            context.medication_name.as_deref().unwrap_or("Medication"),
+                            format_time(due_time)
+                        )
+                    },
+                )
+            }
+        }
+    }
+
+    fn threshold(&self) -> Option<Duration> {
+        self.threshold_minutes
+            .filter(|minutes| minutes.gt(&0))
+            .map(|minutes| Duration::minutes(minutes.into()))
+    }
+
+    /// Update with new fields.
+    ///
+    /// Kind and medication can't change, invalid values default to older ones.
+    pub fn update_rule(&self, new_rule: InputReminderDto) -> Self {
+        let new_threshold = match new_rule.threshold_minutes {
+            Some(value) if value.gt(&0) => Some(value),
+            _ => self.threshold_minutes,
+        };
+        let new_due_time = match new_rule.due_time {
+            Some(value) => convert_to_time(&value).ok().or(self.due_time),
+            None => self.due_time,
+        };
+        let new_channels = match new_rule
+            .channels
+            .as_deref()
+            .and_then(ReminderChannel::parse_list)
+        {
+            Some(channels) => ReminderChannel::join(&channels),
+            None => self.formatted_channels(),
+        };
+        Self {
+            threshold_minutes: new_threshold,
+            due_time: new_due_time,
+            channels: new_channels,
+            active: new_rule.active.unwrap_or(self.active),
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = reminder_rules)]
+pub struct InsertableReminderRule {
+    baby_id: i32,
+    user_id: i32,
+    kind: String,
+    threshold_minutes: Option<i32>,
+    medication_id: Option<i32>,
+    due_time: Option<NaiveTime>,
+    channels: String,
+    created_at: NaiveDateTime,
+}
+
+impl InsertableReminderRule {
+    pub fn new(
+        baby_id: i32,
+        user_id: i32,
+        kind: ReminderKind,
+        threshold_minutes: Option<i32>,
+        medication_id: Option<i32>,
+        due_time: Option<NaiveTime>,
+        channels: Vec<ReminderChannel>,
+    ) -> Self {
+        Self {
+            baby_id,
+            user_id,
+            kind: kind.get().to_string(),
+            threshold_minutes,
+            medication_id,
+            due_time,
+            channels: ReminderChannel::join(&channels),
+            created_at: now(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_reminder {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn at(hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2023, 6, 1)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+    }
+
+    fn rule(
+        kind: ReminderKind,
+        threshold_minutes: Option<i32>,
+        due_time: Option<NaiveTime>,
+        last_triggered_at: Option<NaiveDateTime>,
+    ) -> ReminderRule {
+        ReminderRule {
+            id: 1,
+            baby_id: 1,
+            user_id: 1,
+            kind: kind.get().to_string(),
+            threshold_minutes,
+            medication: Reference::default(),
+            due_time,
+            channels: "app,email".to_string(),
+            active: true,
+            last_triggered_at,
+            created_at: at(0, 0),
+            public_id: Uuid::new_v4(),
+        }
+    }
+
+    #[test]
+    fn test_channels() {
+        let channels = vec!["App".to_string(), "email".to_string(), "app".to_string()];
+        let parsed = ReminderChannel::parse_list(&channels).unwrap();
+        assert_eq!(ReminderChannel::join(&parsed), "app,email");
+        assert!(ReminderChannel::parse_list(&["sms".to_string()]).is_none());
+        assert_eq!(ReminderChannel::join(&[]), "app");
+        assert_eq!(
+            rule(ReminderKind::NoFeed, None, None, None).channels(),
+            vec![ReminderChannel::App, ReminderChannel::Email]
+        );
+    }
+
+    #[test]
+    fn test_no_feed() {
+        let context = RuleContext {
+            last_meal: Some(at(8, 0)),
+            ..Default::default()
+        };
+        let no_feed = rule(ReminderKind::NoFeed, Some(180), None, None);
+        assert_eq!(no_feed.evaluate(&context, at(10, 0)), None);
+        assert!(no_feed.evaluate(&context, at(11, 0)).is_some());
+        let triggered = rule(ReminderKind::NoFeed, Some(180), None, Some(at(11, 0)));
+        assert_eq!(triggered.evaluate(&context, at(12, 0)), None);
+        assert_eq!(no_feed.evaluate(&RuleContext::default(), at(12, 0)), None);
+    }
+
+    #[test]
+    fn test_long_sleep() {
+        let context = RuleContext {
+            ongoing_dream: Some(at(13, 0)),
+            ..Default::default()
+        };
+        let long_sleep = rule(ReminderKind::LongSleep, Some(120), None, Some(at(9, 0)));
+        assert_eq!(
+            long_sleep.evaluate(&context, at(15, 10)),
+            Some("Sleep running for 02:10.".to_string())
+        );
+        assert_eq!(long_sleep.evaluate(&context, at(14, 0)), None);
+    }
+
+    #[test]
+    fn test_medication() {
+        let due_time = NaiveTime::from_hms_opt(10, 0, 0);
+        let context = RuleContext {
+            medication_name: Some("Vitamin D".to_string()),
+            last_dose: Some(at(9, 30) - Duration::days(1)),
+            ..Default::default()
+        };
+        let vitamin = rule(ReminderKind::Medication, None, due_time, None);
+        assert_eq!(vitamin.evaluate(&context, at(9, 0)), None);
+        assert_eq!(
+            vitamin.evaluate(&context, at(10, 5)),
+            Some("Vitamin D not given by 10:00.".to_string())
+        );
+        let given = RuleContext {
+            last_dose: Some(at(9, 30)),
+            ..context
+        };
+        assert_eq!(vitamin.evaluate(&given, at(10, 5)), None);
+    }
+}
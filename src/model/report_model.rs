@@ -0,0 +1,153 @@
+use chrono::NaiveDate;
+
+use crate::utils::datetime::{format_date, format_duration};
+
+use super::{
+    baby_model::Baby,
+    note_model::Note,
+    summary_model::{DreamSummary, MealSummary},
+    weight_model::Weight,
+};
+
+/// Everything a pediatrician needs for a given period of time.
+pub struct Report {
+    baby: Baby,
+    from: NaiveDate,
+    to: NaiveDate,
+    weights: Vec<Weight>,
+    meals: Vec<MealSummary>,
+    dreams: Vec<DreamSummary>,
+    notes: Vec<Note>,
+}
+
+impl Report {
+    pub fn new(
+        baby: Baby,
+        from: NaiveDate,
+        to: NaiveDate,
+        weights: Vec<Weight>,
+        meals: Vec<MealSummary>,
+        dreams: Vec<DreamSummary>,
+        notes: Vec<Note>,
+    ) -> Self {
+        Self {
+            baby,
+            from,
+            to,
+            weights,
+            meals,
+            dreams,
+            notes,
+        }
+    }
+
+    pub fn baby(&self) -> Baby {
+        self.baby.clone()
+    }
+
+    pub fn formatted_from(&self) -> String {
+        format_date(self.from)
+    }
+
+    pub fn formatted_to(&self) -> String {
+        format_date(self.to)
+    }
+
+    pub fn weights(&self) -> Vec<Weight> {
+        self.weights.to_owned()
+    }
+
+    pub fn meals(&self) -> Vec<MealSummary> {
+        self.meals.to_owned()
+    }
+
+    pub fn dreams(&self) -> Vec<DreamSummary> {
+        self.dreams.to_owned()
+    }
+
+    pub fn notes(&self) -> Vec<Note> {
+        self.notes.to_owned()
+    }
+
+    /// Averages are calculated over days with records, not over the whole range.
+    pub fn feedings_per_day(&self) -> f32 {
+        let total: u32 = self
+            .meals
+            .iter()
+            .map(|meal| u32::from(meal.total_feedings()))
+            .sum();
+        average(total as f32, self.meals.len())
+    }
+
+    pub fn formula_per_day(&self) -> f32 {
+        let total: i32 = self
+            .meals
+            .iter()
+            .map(|meal| i32::from(meal.formula()))
+            .sum();
+        average(total as f32, self.meals.len())
+    }
+
+    pub fn formatted_nursing_time_per_day(&self) -> String {
+        let total: i64 = self
+            .meals
+            .iter()
+            .map(|meal| meal.nursing_time().num_minutes())
+            .sum();
+        format_average_duration(total, self.meals.len())
+    }
+
+    pub fn formatted_sleep_per_day(&self) -> String {
+        let total: i64 = self
+            .dreams
+            .iter()
+            .map(|dream| dream.summary().num_minutes())
+            .sum();
+        format_average_duration(total, self.dreams.len())
+    }
+
+    /// Difference between last and first measure in the period.
+    pub fn weight_change(&self) -> f32 {
+        match (self.weights.first(), self.weights.last()) {
+            (Some(first), Some(last)) => round_one_decimal(last.value() - first.value()),
+            _ => 0.0,
+        }
+    }
+}
+
+fn average(total: f32, days: usize) -> f32 {
+    match days {
+        0 => 0.0,
+        _ => round_one_decimal(total / days as f32),
+    }
+}
+
+fn round_one_decimal(value: f32) -> f32 {
+    (value * 10.0).round() / 10.0
+}
+
+fn format_average_duration(total_minutes: i64, days: usize) -> String {
+    let minutes = match days {
+        0 => 0,
+        _ => total_minutes / days as i64,
+    };
+    format_duration(minutes)
+}
+
+#[cfg(test)]
+mod test_report {
+    use super::*;
+
+    #[test]
+    fn test_average() {
+        assert_eq!(average(10.0, 0), 0.0);
+        assert_eq!(average(10.0, 3), 3.3);
+        assert_eq!(average(9.0, 3), 3.0);
+    }
+
+    #[test]
+    fn test_average_duration() {
+        assert_eq!(format_average_duration(0, 0), "00:00");
+        assert_eq!(format_average_duration(180, 2), "01:30");
+    }
+}
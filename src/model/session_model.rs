@@ -1,9 +1,13 @@
 use axum::async_trait;
 use axum_session_auth::Authentication;
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::service::session_service::{load_user_session, read_user_from_db, save_user_session};
+use crate::{
+    service::session_service::{load_user_session, read_user_from_db, save_user_session},
+    utils::metrics::record_session_lookup,
+};
 
 use super::role_model::Rol;
 
@@ -17,7 +21,7 @@ pub struct CurrentUser {
     baby_info: Vec<BabyInfo>,
 }
 
-#[derive(Clone, Debug, Deserialize, Serialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
 pub struct BabyInfo {
     pub name: String,
     pub unique_id: Uuid,
@@ -107,8 +111,12 @@ impl Authentication<CurrentUser, i64, redis::Client> for CurrentUser {
         _pool: Option<&redis::Client>,
     ) -> Result<CurrentUser, anyhow::Error> {
         match load_user_session(user_id).await {
-            Ok(u) => Ok(u),
+            Ok(u) => {
+                record_session_lookup(true);
+                Ok(u)
+            }
             Err(_) => {
+                record_session_lookup(false);
                 let current_user = read_user_from_db(user_id.try_into().unwrap()).await?;
                 save_user_session(&current_user, None).await?;
                 Ok(current_user)
@@ -26,7 +26,7 @@ impl DreamSummary {
             .unwrap_or(Duration::minutes(0))
     }
 
-    pub fn _date(&self) -> NaiveDate {
+    pub fn date(&self) -> NaiveDate {
         self.date
     }
 
@@ -34,7 +34,7 @@ impl DreamSummary {
         format_date(self.date)
     }
 
-    pub fn _summary(&self) -> Duration {
+    pub fn summary(&self) -> Duration {
         self.summary
     }
 
@@ -81,10 +81,18 @@ impl MealSummary {
             .unwrap_or(Duration::minutes(0))
     }
 
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
     pub fn formatted_date(&self) -> String {
         format_date(self.date)
     }
 
+    pub fn nursing_time(&self) -> Duration {
+        self.nursing_time
+    }
+
     pub fn formatted_nursing_time(&self) -> String {
         format_duration(self.nursing_time.num_minutes())
     }
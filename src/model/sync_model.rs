@@ -0,0 +1,156 @@
+use chrono::NaiveDateTime;
+use diesel::{Identifiable, Insertable, Queryable};
+use uuid::Uuid;
+
+use crate::{data::common_structure::DataType, schema::deleted_records};
+
+use super::{dream_model::Dream, meals_model::Meal};
+
+const CURSOR_FORMAT: &str = "%Y-%m-%dT%H:%M:%S%.6f";
+
+/// Tombstone of a deleted meal or dream, kept so offline clients learn about it.
+#[derive(Queryable, Identifiable, Clone)]
+#[diesel(table_name = deleted_records)]
+pub struct DeletedRecord {
+    id: i32,
+    baby_id: i32,
+    record_type: String,
+    record_id: Uuid,
+    client_id: Option<Uuid>,
+    deleted_at: NaiveDateTime,
+}
+
+impl DeletedRecord {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn baby_id(&self) -> i32 {
+        self.baby_id
+    }
+
+    pub fn record_type(&self) -> String {
+        self.record_type.to_string()
+    }
+
+    pub fn record_id(&self) -> Uuid {
+        self.record_id
+    }
+
+    pub fn client_id(&self) -> Option<Uuid> {
+        self.client_id
+    }
+
+    pub fn deleted_at(&self) -> NaiveDateTime {
+        self.deleted_at
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = deleted_records)]
+pub struct InsertableDeletedRecord {
+    baby_id: i32,
+    record_type: String,
+    record_id: Uuid,
+    client_id: Option<Uuid>,
+    deleted_at: NaiveDateTime,
+}
+
+impl InsertableDeletedRecord {
+    pub fn new(
+        baby_id: i32,
+        record_type: DataType,
+        record_id: Uuid,
+        client_id: Option<Uuid>,
+        deleted_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            baby_id,
+            record_type: record_type.get().to_string(),
+            record_id,
+            client_id,
+            deleted_at,
+        }
+    }
+}
+
+/// Server side of a sync, mapped to the DTO of the requested version.
+pub struct SyncChanges {
+    cursor: NaiveDateTime,
+    meals: Vec<Meal>,
+    dreams: Vec<Dream>,
+    deleted: Vec<DeletedRecord>,
+    conflicts: Vec<Uuid>,
+}
+
+impl SyncChanges {
+    pub fn new(
+        cursor: NaiveDateTime,
+        meals: Vec<Meal>,
+        dreams: Vec<Dream>,
+        deleted: Vec<DeletedRecord>,
+        conflicts: Vec<Uuid>,
+    ) -> Self {
+        Self {
+            cursor,
+            meals,
+            dreams,
+            deleted,
+            conflicts,
+        }
+    }
+
+    pub fn cursor(&self) -> String {
+        format_cursor(self.cursor)
+    }
+
+    /// Meals, dreams, tombstones and conflicts, in that order.
+    pub fn into_parts(self) -> (Vec<Meal>, Vec<Dream>, Vec<DeletedRecord>, Vec<Uuid>) {
+        (self.meals, self.dreams, self.deleted, self.conflicts)
+    }
+}
+
+/// Cursor handed to clients, changes after it are sent on next sync.
+pub fn format_cursor(cursor: NaiveDateTime) -> String {
+    cursor.format(CURSOR_FORMAT).to_string()
+}
+
+pub fn parse_cursor(cursor: &str) -> Result<NaiveDateTime, chrono::ParseError> {
+    NaiveDateTime::parse_from_str(cursor, CURSOR_FORMAT)
+}
+
+/// Last write wins by edit time, not by when a change reached the server.
+///
+/// On a tie the client change is applied.
+pub fn server_wins(server_edited_at: NaiveDateTime, client_edited_at: NaiveDateTime) -> bool {
+    server_edited_at.gt(&client_edited_at)
+}
+
+#[cfg(test)]
+mod test_sync_model {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn at(hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_micro_opt(hour, minute, 0, 123456)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_cursor() {
+        let cursor = format_cursor(at(9, 30));
+        assert_eq!(cursor, "2024-01-01T09:30:00.123456");
+        assert_eq!(parse_cursor(&cursor).unwrap(), at(9, 30));
+        assert!(parse_cursor("2024-01-01 09:30").is_err());
+    }
+
+    #[test]
+    fn test_server_wins() {
+        assert!(server_wins(at(10, 0), at(9, 0)));
+        assert!(!server_wins(at(9, 0), at(10, 0)));
+        assert!(!server_wins(at(9, 0), at(9, 0)));
+    }
+}
@@ -2,8 +2,11 @@ use chrono::NaiveDateTime;
 use diesel::prelude::*;
 
 use crate::{
-    data::user_dto::UpdateUserDto, repository::role_repository::select_roles_names_from_user,
-    schema::users, security::security::verify_password, utils::datetime::now,
+    data::user_dto::UpdateUserDto,
+    repository::role_repository::select_roles_names_from_user,
+    schema::users,
+    security::security::verify_password,
+    utils::{cursor::Keyset, datetime::now},
 };
 
 #[derive(Queryable, Selectable, Identifiable, Clone)]
@@ -110,6 +113,15 @@ impl User {
     }
 }
 
+/// Users have no public id, admins already address them by this one.
+impl Keyset for User {
+    type Id = i32;
+
+    fn keyset(&self) -> (NaiveDateTime, i32) {
+        (self.created_at, self.id)
+    }
+}
+
 #[derive(Insertable)]
 #[diesel(table_name = users)]
 pub struct InsertableUser {
@@ -0,0 +1,336 @@
+use chrono::{Duration, NaiveDateTime};
+use diesel::{Identifiable, Insertable, Queryable};
+use uuid::Uuid;
+
+use crate::{
+    configuration::constant::GlobalCte,
+    data::webhook_dto::InputWebhookDto,
+    schema::{webhook_deliveries, webhooks},
+    utils::datetime::format_date_time,
+};
+
+#[derive(Queryable, Identifiable, Clone)]
+#[diesel(table_name = webhooks)]
+pub struct Webhook {
+    id: i32,
+    baby_id: i32,
+    user_id: i32,
+    url: String,
+    secret: String,
+    active: bool,
+    created_at: NaiveDateTime,
+    public_id: Uuid,
+}
+
+impl Webhook {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn public_id(&self) -> Uuid {
+        self.public_id
+    }
+
+    pub fn baby_id(&self) -> i32 {
+        self.baby_id
+    }
+
+    pub fn user_id(&self) -> i32 {
+        self.user_id
+    }
+
+    pub fn url(&self) -> String {
+        self.url.to_string()
+    }
+
+    pub fn secret(&self) -> String {
+        self.secret.to_string()
+    }
+
+    pub fn active(&self) -> bool {
+        self.active
+    }
+
+    pub fn created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+
+    pub fn formatted_created_at(&self) -> String {
+        format_date_time(self.created_at)
+    }
+
+    /// Update with new fields.
+    ///
+    /// Invalid urls default to older value.
+    pub fn update_webhook(&self, new_webhook: InputWebhookDto) -> Self {
+        let new_url = match new_webhook.url {
+            Some(value) if valid_url(&value) => value.trim().to_string(),
+            _ => self.url(),
+        };
+        Self {
+            url: new_url,
+            active: new_webhook.active.unwrap_or(self.active),
+            ..self.clone()
+        }
+    }
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = webhooks)]
+pub struct InsertableWebhook {
+    baby_id: i32,
+    user_id: i32,
+    url: String,
+    secret: String,
+    active: bool,
+    created_at: NaiveDateTime,
+}
+
+impl InsertableWebhook {
+    /// A random secret is generated to sign every payload.
+    pub fn new(baby_id: i32, user_id: i32, url: String, created_at: NaiveDateTime) -> Self {
+        Self {
+            baby_id,
+            user_id,
+            url: url.trim().to_string(),
+            secret: format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple()),
+            active: true,
+            created_at,
+        }
+    }
+}
+
+/// Only http and https urls can receive payloads.
+pub fn valid_url(url: &str) -> bool {
+    let url = url.trim();
+    (url.starts_with("http://") || url.starts_with("https://"))
+        && !url.contains(char::is_whitespace)
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DeliveryStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+impl DeliveryStatus {
+    pub fn get(&self) -> &'static str {
+        match self {
+            DeliveryStatus::Pending => "pending",
+            DeliveryStatus::Delivered => "delivered",
+            DeliveryStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(status: &str) -> Option<Self> {
+        match status {
+            "pending" => Some(DeliveryStatus::Pending),
+            "delivered" => Some(DeliveryStatus::Delivered),
+            "failed" => Some(DeliveryStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Queryable, Identifiable, Clone)]
+#[diesel(table_name = webhook_deliveries)]
+pub struct WebhookDelivery {
+    id: i32,
+    webhook_id: i32,
+    event: String,
+    payload: String,
+    status: String,
+    attempts: i16,
+    next_attempt_at: NaiveDateTime,
+    response_code: Option<i16>,
+    error: Option<String>,
+    created_at: NaiveDateTime,
+    delivered_at: Option<NaiveDateTime>,
+    public_id: Uuid,
+}
+
+impl WebhookDelivery {
+    pub fn id(&self) -> i32 {
+        self.id
+    }
+
+    pub fn public_id(&self) -> Uuid {
+        self.public_id
+    }
+
+    pub fn webhook_id(&self) -> i32 {
+        self.webhook_id
+    }
+
+    pub fn event(&self) -> String {
+        self.event.to_string()
+    }
+
+    pub fn payload(&self) -> String {
+        self.payload.to_string()
+    }
+
+    pub fn status(&self) -> Option<DeliveryStatus> {
+        DeliveryStatus::parse(&self.status)
+    }
+
+    pub fn formatted_status(&self) -> String {
+        self.status.to_string()
+    }
+
+    pub fn attempts(&self) -> i16 {
+        self.attempts
+    }
+
+    pub fn next_attempt_at(&self) -> NaiveDateTime {
+        self.next_attempt_at
+    }
+
+    pub fn response_code(&self) -> Option<i16> {
+        self.response_code
+    }
+
+    pub fn error(&self) -> Option<String> {
+        self.error.to_owned()
+    }
+
+    pub fn created_at(&self) -> NaiveDateTime {
+        self.created_at
+    }
+
+    pub fn delivered_at(&self) -> Option<NaiveDateTime> {
+        self.delivered_at
+    }
+
+    /// Apply the outcome of a delivery attempt, response status code or connection error.
+    ///
+    /// Failed attempts are retried with exponential backoff until attempts run out.
+    pub fn record_attempt(&self, outcome: Result<u16, String>, now: NaiveDateTime) -> Self {
+        let attempts = self.attempts.saturating_add(1);
+        let (response_code, error) = match outcome {
+            Ok(code) if (200..300).contains(&code) => (i16::try_from(code).ok(), None),
+            Ok(code) => (
+                i16::try_from(code).ok(),
+                Some(format!("Unexpected status code {code}")),
+            ),
+            Err(error) => (None, Some(error)),
+        };
+        let max_attempts: i16 = GlobalCte::WebhookMaxAttempts.get().try_into().unwrap();
+        let status = match error {
+            None => DeliveryStatus::Delivered,
+            Some(_) if attempts.ge(&max_attempts) => DeliveryStatus::Failed,
+            Some(_) => DeliveryStatus::Pending,
+        };
+        Self {
+            status: status.get().to_string(),
+            attempts,
+            next_attempt_at: match status {
+                DeliveryStatus::Pending => now + retry_delay(attempts),
+                _ => now,
+            },
+            response_code,
+            delivered_at: match status {
+                DeliveryStatus::Delivered => Some(now),
+                _ => None,
+            },
+            error,
+            ..self.clone()
+        }
+    }
+}
+
+/// Delay after a number of failed attempts, doubled every time.
+fn retry_delay(attempts: i16) -> Duration {
+    let exponent: u32 = (attempts - 1).clamp(0, 16).unsigned_abs().into();
+    Duration::seconds(i64::from(GlobalCte::WebhookRetrySeconds.get()) * 2_i64.pow(exponent))
+}
+
+#[derive(Insertable)]
+#[diesel(table_name = webhook_deliveries)]
+pub struct InsertableWebhookDelivery {
+    webhook_id: i32,
+    event: String,
+    payload: String,
+    status: String,
+    attempts: i16,
+    next_attempt_at: NaiveDateTime,
+    created_at: NaiveDateTime,
+}
+
+impl InsertableWebhookDelivery {
+    pub fn new(webhook_id: i32, event: &str, payload: &str, created_at: NaiveDateTime) -> Self {
+        Self {
+            webhook_id,
+            event: event.to_string(),
+            payload: payload.to_string(),
+            status: DeliveryStatus::Pending.get().to_string(),
+            attempts: 0,
+            next_attempt_at: created_at,
+            created_at,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_webhook {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    fn at(hour: u32, minute: u32, second: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2023, 6, 1)
+            .unwrap()
+            .and_hms_opt(hour, minute, second)
+            .unwrap()
+    }
+
+    fn delivery(attempts: i16) -> WebhookDelivery {
+        WebhookDelivery {
+            id: 1,
+            webhook_id: 1,
+            event: "created".to_string(),
+            payload: "{}".to_string(),
+            status: DeliveryStatus::Pending.get().to_string(),
+            attempts,
+            next_attempt_at: at(8, 0, 0),
+            response_code: None,
+            error: None,
+            created_at: at(8, 0, 0),
+            delivered_at: None,
+            public_id: Uuid::new_v4(),
+        }
+    }
+
+    #[test]
+    fn test_valid_url() {
+        assert!(valid_url("https://home.local/hooks/sleep"));
+        assert!(valid_url(" http://127.0.0.1:8123/api "));
+        assert!(!valid_url("ftp://home.local"));
+        assert!(!valid_url("https://home local"));
+    }
+
+    #[test]
+    fn test_record_attempt() {
+        let delivered = delivery(0).record_attempt(Ok(204), at(8, 0, 0));
+        assert_eq!(delivered.status(), Some(DeliveryStatus::Delivered));
+        assert_eq!(delivered.delivered_at(), Some(at(8, 0, 0)));
+        assert_eq!(delivered.error(), None);
+
+        let retry = delivery(0).record_attempt(Ok(500), at(8, 0, 0));
+        assert_eq!(retry.status(), Some(DeliveryStatus::Pending));
+        assert_eq!(retry.response_code(), Some(500));
+        assert_eq!(retry.next_attempt_at(), at(8, 0, 30));
+        let retry = delivery(2).record_attempt(Err("Connection refused".into()), at(8, 0, 0));
+        assert_eq!(retry.attempts(), 3);
+        assert_eq!(retry.next_attempt_at(), at(8, 2, 0));
+
+        let max_attempts: i16 = GlobalCte::WebhookMaxAttempts.get().try_into().unwrap();
+        let failed = delivery(max_attempts - 1).record_attempt(Ok(404), at(8, 0, 0));
+        assert_eq!(failed.status(), Some(DeliveryStatus::Failed));
+        assert_eq!(
+            failed.error(),
+            Some("Unexpected status code 404".to_string())
+        );
+    }
+}
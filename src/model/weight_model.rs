@@ -1,10 +1,14 @@
-use chrono::NaiveDate;
+use chrono::{NaiveDate, NaiveDateTime};
 use diesel::{Identifiable, Insertable, Queryable};
+use uuid::Uuid;
 
 use crate::{
     data::weight_dto::InputWeightDto,
     schema::weights,
-    utils::datetime::{convert_to_date, format_date},
+    utils::{
+        cursor::Keyset,
+        datetime::{convert_to_date, format_date},
+    },
 };
 
 #[derive(Queryable, Identifiable, Clone)]
@@ -14,6 +18,8 @@ pub struct Weight {
     baby_id: i32,
     date: NaiveDate,
     value: f32,
+    version: i32,
+    public_id: Uuid,
 }
 
 impl Weight {
@@ -23,6 +29,8 @@ impl Weight {
             baby_id,
             date,
             value,
+            version: 1,
+            public_id: Uuid::new_v4(),
         }
     }
 
@@ -30,6 +38,10 @@ impl Weight {
         self.id
     }
 
+    pub fn public_id(&self) -> Uuid {
+        self.public_id
+    }
+
     pub fn baby_id(&self) -> i32 {
         self.baby_id
     }
@@ -38,6 +50,10 @@ impl Weight {
         self.date
     }
 
+    pub fn version(&self) -> i32 {
+        self.version
+    }
+
     pub fn formatted_date(&self) -> String {
         format_date(self.date)
     }
@@ -64,6 +80,14 @@ impl Weight {
     }
 }
 
+impl Keyset for Weight {
+    type Id = Uuid;
+
+    fn keyset(&self) -> (NaiveDateTime, Uuid) {
+        (self.date.and_hms_opt(0, 0, 0).unwrap(), self.public_id)
+    }
+}
+
 #[derive(Insertable)]
 #[diesel(table_name = weights)]
 pub struct InsertableWeight {
@@ -1,11 +1,13 @@
 use crate::connection::connection_psql::establish_connection;
 use crate::schema::{babies, dreams, meals, roles, users, users_roles, weights};
+use crate::utils::metrics::query_timer;
 use diesel::dsl::count;
 use diesel::prelude::*;
 use diesel::result::Error;
 use serde::Serialize;
+use utoipa::ToSchema;
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct StatsDB<'a> {
     pub users: TableDescription<'a>,
     pub babies: TableDescription<'a>,
@@ -14,7 +16,7 @@ pub struct StatsDB<'a> {
     pub weights: TableDescription<'a>,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, ToSchema)]
 pub struct TableDescription<'a> {
     pub name: &'a str,
     pub value: i64,
@@ -22,6 +24,7 @@ pub struct TableDescription<'a> {
 
 pub fn select_stats_from_tables() -> Result<StatsDB<'static>, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     let users: i64 = users::table.select(users::id).count().get_result(conn)?;
     let babies: i64 = babies::table.select(babies::id).count().get_result(conn)?;
     let dreams: i64 = dreams::table.select(dreams::id).count().get_result(conn)?;
@@ -55,6 +58,22 @@ pub fn select_stats_from_tables() -> Result<StatsDB<'static>, Error> {
     Ok(result)
 }
 
+/// Dreams still running, the only timer that stays open.
+/// Dreams and meals still running, across every baby.
+pub fn count_active_timers() -> Result<i64, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let dreams: i64 = dreams::table
+        .filter(dreams::to_date.is_null())
+        .count()
+        .get_result(conn)?;
+    let meals: i64 = meals::table
+        .filter(meals::to_time.is_null())
+        .count()
+        .get_result(conn)?;
+    Ok(dreams + meals)
+}
+
 pub struct GroupedRole {
     pub id: i16,
     pub name: String,
@@ -72,6 +91,7 @@ pub struct GroupedRole {
 /// ```
 pub fn select_roles() -> Result<Vec<GroupedRole>, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     let data = roles::table
         .inner_join(users_roles::table.on(users_roles::rol_id.eq(roles::id)))
         .group_by(roles::id)
@@ -1,14 +1,18 @@
 use crate::connection::connection_psql::establish_connection;
+use crate::utils::metrics::query_timer;
+use diesel::dsl::exists;
 use diesel::prelude::*;
 use diesel::result::Error;
 
 use crate::schema::{
+    babies,
     users_babies::{self, baby_id},
     users_roles::{self, rol_id},
 };
 
 pub fn insert_rol_to_user(user: i32, rol: i16) -> Result<usize, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     let records: i64 = users_roles::table
         .filter(users_roles::rol_id.eq(rol))
         .filter(users_roles::user_id.eq(user))
@@ -25,6 +29,7 @@ pub fn insert_rol_to_user(user: i32, rol: i16) -> Result<usize, Error> {
 /// Look if there is an already association, if there is, return 1, else create a new association.
 pub fn insert_baby_to_user(user: i32, baby: i32) -> Result<usize, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     let records: i64 = users_babies::table
         .filter(users_babies::baby_id.eq(baby))
         .filter(users_babies::user_id.eq(user))
@@ -38,18 +43,39 @@ pub fn insert_baby_to_user(user: i32, baby: i32) -> Result<usize, Error> {
     }
 }
 
-pub fn delete_baby_association(baby: i32, user: i32) -> Result<usize, Error> {
+pub fn select_user_has_baby(user: i32, baby: i32) -> Result<bool, Error> {
     let conn = &mut establish_connection();
-    diesel::delete(
+    let _timer = query_timer!();
+    diesel::select(exists(
         users_babies::table
             .filter(users_babies::user_id.eq(user))
             .filter(users_babies::baby_id.eq(baby)),
+    ))
+    .get_result(conn)
+}
+
+/// Fails with `NotFound` if the baby changed since `version` was read.
+pub fn delete_baby_association(baby: i32, user: i32, version: i32) -> Result<usize, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let deleted = diesel::delete(
+        users_babies::table
+            .filter(users_babies::user_id.eq(user))
+            .filter(users_babies::baby_id.eq(baby))
+            .filter(exists(
+                babies::table.find(baby).filter(babies::version.eq(version)),
+            )),
     )
-    .execute(conn)
+    .execute(conn)?;
+    match deleted {
+        0 => Err(Error::NotFound),
+        _ => Ok(deleted),
+    }
 }
 
 pub fn delete_rol_to_user(user: i32, rol: i16) -> Result<usize, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     diesel::delete(
         users_roles::table
             .filter(users_roles::user_id.eq(user))
@@ -1,4 +1,6 @@
 use crate::connection::connection_psql::establish_connection;
+use crate::utils::metrics::query_timer;
+use diesel::pg::Pg;
 use diesel::prelude::*;
 use diesel::result::Error;
 use uuid::Uuid;
@@ -7,6 +9,7 @@ use crate::{
     data::query_dto::Pagination,
     model::baby_model::{Baby, InsertableBaby},
     schema::{babies, users_babies},
+    utils::cursor::Cursor,
 };
 
 use super::paginator::Paginate;
@@ -16,6 +19,7 @@ where
     T: Into<InsertableBaby>,
 {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     // Create baby entry in db.
     let baby = diesel::insert_into(babies::table)
         .values(new_baby.into())
@@ -37,60 +41,151 @@ where
 
 pub fn select_baby_by_id(id: i32) -> Result<Baby, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     babies::table.find(id).first(conn)
 }
 
 pub fn select_babies(pagination: Pagination) -> Result<(Vec<Baby>, i64), Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     babies::table
         .select(babies::all_columns)
+        .order((babies::added_on.asc(), babies::unique_id.asc()))
         .paginate(pagination.page())
         .per_page(pagination.per_page())
         .load_and_count_pages(conn)
 }
 
+/// Babies next to `cursor`, nearest first. Loads one more than `per_page` to tell if there are
+/// more.
+pub fn select_babies_by_cursor(cursor: &Cursor, per_page: i64) -> Result<Vec<Baby>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let query = babies::table.select(babies::all_columns).into_boxed();
+    filter_by_cursor(query, cursor)
+        .limit(per_page + 1)
+        .load(conn)
+}
+
+/// Fails with `NotFound` if the baby changed since it was read.
 pub fn update_baby(update: Baby) -> Result<Baby, Error> {
     let conn = &mut establish_connection();
-    diesel::update(babies::table.find(update.id()))
-        .set((
-            babies::name.eq(update.name()),
-            babies::birthdate.eq(update.birthdate()),
-        ))
-        .get_result(conn)
+    let _timer = query_timer!();
+    diesel::update(
+        babies::table
+            .find(update.id())
+            .filter(babies::version.eq(update.version())),
+    )
+    .set((
+        babies::name.eq(update.name()),
+        babies::birthdate.eq(update.birthdate()),
+        babies::version.eq(update.version() + 1),
+    ))
+    .get_result(conn)
 }
 
-pub fn delete_baby_from_db(baby: i32) -> Result<usize, Error> {
+/// Fails with `NotFound` if the baby changed since it was read.
+pub fn delete_baby_from_db(baby: &Baby) -> Result<usize, Error> {
     let conn = &mut establish_connection();
-    diesel::delete(babies::table.find(baby)).execute(conn)
+    let _timer = query_timer!();
+    let deleted = diesel::delete(
+        babies::table
+            .find(baby.id())
+            .filter(babies::version.eq(baby.version())),
+    )
+    .execute(conn)?;
+    match deleted {
+        0 => Err(Error::NotFound),
+        _ => Ok(deleted),
+    }
 }
 
 pub fn update_baby_belongs_to(baby: i32, new_user: i32) -> Result<usize, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     diesel::update(babies::table.find(baby))
-        .set(babies::belongs_to.eq(new_user))
+        .set((
+            babies::belongs_to.eq(new_user),
+            babies::version.eq(babies::version + 1),
+        ))
         .execute(conn)
 }
 
 pub fn select_baby_from_unique_id(unique_id: Uuid) -> Result<i32, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     babies::table
         .filter(babies::unique_id.eq(unique_id))
         .select(babies::id)
         .first(conn)
 }
 
+pub fn select_babies_from_unique_ids(unique_ids: Vec<Uuid>) -> Result<Vec<Baby>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    babies::table
+        .filter(babies::unique_id.eq_any(unique_ids))
+        .order(babies::name.asc())
+        .load(conn)
+}
+
 pub fn select_babies_from_user_id(
     user_id: i32,
     pagination: Pagination,
 ) -> Result<(Vec<Baby>, i64), Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     let babies_id: Vec<i32> = users_babies::table
         .filter(users_babies::user_id.eq(user_id))
         .select(users_babies::baby_id)
         .load::<i32>(conn)?;
     babies::table
         .filter(babies::id.eq_any(babies_id))
+        .order((babies::added_on.asc(), babies::unique_id.asc()))
         .paginate(pagination.page())
         .per_page(pagination.per_page())
         .load_and_count_pages(conn)
 }
+
+pub fn select_babies_from_user_id_by_cursor(
+    user_id: i32,
+    cursor: &Cursor,
+    per_page: i64,
+) -> Result<Vec<Baby>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let babies_id: Vec<i32> = users_babies::table
+        .filter(users_babies::user_id.eq(user_id))
+        .select(users_babies::baby_id)
+        .load::<i32>(conn)?;
+    let query = babies::table
+        .filter(babies::id.eq_any(babies_id))
+        .into_boxed();
+    filter_by_cursor(query, cursor)
+        .limit(per_page + 1)
+        .load(conn)
+}
+
+fn filter_by_cursor<'a>(
+    query: babies::BoxedQuery<'a, Pg>,
+    cursor: &Cursor,
+) -> babies::BoxedQuery<'a, Pg> {
+    let (added_on, id) = (cursor.timestamp(), cursor.id());
+    if cursor.is_forward() {
+        query
+            .filter(
+                babies::added_on
+                    .gt(added_on)
+                    .or(babies::added_on.eq(added_on).and(babies::unique_id.gt(id))),
+            )
+            .order((babies::added_on.asc(), babies::unique_id.asc()))
+    } else {
+        query
+            .filter(
+                babies::added_on
+                    .lt(added_on)
+                    .or(babies::added_on.eq(added_on).and(babies::unique_id.lt(id))),
+            )
+            .order((babies::added_on.desc(), babies::unique_id.desc()))
+    }
+}
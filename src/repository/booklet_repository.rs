@@ -0,0 +1,109 @@
+use chrono::NaiveDate;
+use diesel::{prelude::*, result::Error};
+use uuid::Uuid;
+
+use crate::{
+    data::query_dto::Pagination,
+    model::booklet_model::{
+        BookletEntry, BookletKind, InsertableBookletEntry, InsertableScheduleItem, ScheduleItem,
+    },
+    schema::{booklet_entries, health_schedule},
+};
+
+use super::paginator::Paginate;
+use crate::connection::connection_psql::establish_connection;
+use crate::utils::metrics::query_timer;
+
+pub fn insert_new_booklet_entry(new_entry: InsertableBookletEntry) -> Result<BookletEntry, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::insert_into(booklet_entries::table)
+        .values(new_entry)
+        .get_result(conn)
+}
+
+pub fn select_booklet_entry_by_id(record: Uuid) -> Result<BookletEntry, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    booklet_entries::table
+        .filter(booklet_entries::public_id.eq(record))
+        .first::<BookletEntry>(conn)
+}
+
+pub fn select_booklet_entries_from_baby(
+    baby: i32,
+    kind: BookletKind,
+    pagination: Pagination,
+) -> Result<(Vec<BookletEntry>, i64), Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    booklet_entries::table
+        .filter(booklet_entries::baby_id.eq(baby))
+        .filter(booklet_entries::kind.eq(kind.get()))
+        .order(booklet_entries::date.asc())
+        .paginate(pagination.page())
+        .per_page(pagination.per_page())
+        .load_and_count_pages(conn)
+}
+
+/// Date each reference schedule item was recorded for a baby.
+pub fn select_scheduled_entry_dates(baby: i32) -> Result<Vec<(Option<i32>, NaiveDate)>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    booklet_entries::table
+        .filter(booklet_entries::baby_id.eq(baby))
+        .filter(booklet_entries::schedule_id.is_not_null())
+        .select((booklet_entries::schedule_id, booklet_entries::date))
+        .load(conn)
+}
+
+pub fn update_booklet_entry(entry: BookletEntry) -> Result<BookletEntry, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::update(booklet_entries::table.find(entry.id()))
+        .set((
+            booklet_entries::name.eq(entry.name()),
+            booklet_entries::date.eq(entry.date()),
+            booklet_entries::notes.eq(entry.notes()),
+        ))
+        .get_result(conn)
+}
+
+pub fn delete_booklet_entry(record: i32) -> Result<usize, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::delete(booklet_entries::table.find(record)).execute(conn)
+}
+
+pub fn select_schedule() -> Result<Vec<ScheduleItem>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    health_schedule::table
+        .order((
+            health_schedule::age_months.asc(),
+            health_schedule::kind.asc(),
+        ))
+        .load::<ScheduleItem>(conn)
+}
+
+pub fn select_schedule_item_by_id(record: i32) -> Result<ScheduleItem, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    health_schedule::table
+        .find(record)
+        .first::<ScheduleItem>(conn)
+}
+
+pub fn insert_schedule_item(new_item: InsertableScheduleItem) -> Result<ScheduleItem, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::insert_into(health_schedule::table)
+        .values(new_item)
+        .get_result(conn)
+}
+
+pub fn delete_schedule_item(record: i32) -> Result<usize, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::delete(health_schedule::table.find(record)).execute(conn)
+}
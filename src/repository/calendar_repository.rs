@@ -0,0 +1,61 @@
+use diesel::prelude::*;
+use diesel::result::Error;
+use uuid::Uuid;
+
+use crate::{
+    connection::connection_psql::establish_connection,
+    model::calendar_model::{CalendarToken, InsertableCalendarToken},
+    schema::{calendar_tokens, users_babies},
+    utils::metrics::query_timer,
+};
+
+/// Replace any previous token for the same baby and user, old feed urls stop working.
+pub fn insert_calendar_token(new_token: InsertableCalendarToken) -> Result<CalendarToken, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    conn.transaction(|conn| {
+        diesel::delete(
+            calendar_tokens::table
+                .filter(calendar_tokens::baby_id.eq(new_token.baby_id()))
+                .filter(calendar_tokens::user_id.eq(new_token.user_id())),
+        )
+        .execute(conn)?;
+        diesel::insert_into(calendar_tokens::table)
+            .values(new_token)
+            .get_result(conn)
+    })
+}
+
+/// Token is only valid while user and baby are still associated.
+///
+/// Raw SQL:
+/// ```sql
+/// SELECT calendar_tokens.baby_id FROM calendar_tokens
+/// INNER JOIN users_babies ON users_babies.baby_id = calendar_tokens.baby_id
+///     AND users_babies.user_id = calendar_tokens.user_id
+/// WHERE calendar_tokens.token = ${token};
+/// ```
+pub fn select_baby_from_calendar_token(token: Uuid) -> Result<i32, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    calendar_tokens::table
+        .inner_join(
+            users_babies::table.on(users_babies::baby_id
+                .eq(calendar_tokens::baby_id)
+                .and(users_babies::user_id.eq(calendar_tokens::user_id))),
+        )
+        .filter(calendar_tokens::token.eq(token))
+        .select(calendar_tokens::baby_id)
+        .first(conn)
+}
+
+pub fn delete_calendar_token(baby: i32, user: i32) -> Result<usize, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::delete(
+        calendar_tokens::table
+            .filter(calendar_tokens::baby_id.eq(baby))
+            .filter(calendar_tokens::user_id.eq(user)),
+    )
+    .execute(conn)
+}
@@ -1,21 +1,31 @@
 use chrono::{NaiveDate, NaiveDateTime};
-use diesel::{prelude::*, result::Error};
+use diesel::{dsl::IntervalDsl, pg::Pg, prelude::*, result::Error};
+use uuid::Uuid;
 
 use crate::{
-    data::query_dto::Pagination,
-    model::dream_model::{Dream, InsertableDream},
+    data::{
+        common_structure::DataType,
+        dream_dto::DreamFilterDto,
+        query_dto::{Pagination, Sort},
+    },
+    model::{
+        dream_model::{Dream, InsertableDream},
+        sync_model::InsertableDeletedRecord,
+    },
     schema::dreams,
-    utils::datetime::now,
+    utils::{cursor::Cursor, datetime::now},
 };
 
-use super::paginator::Paginate;
+use super::{paginator::Paginate, sync_repository::insert_deleted_record};
 use crate::connection::connection_psql::establish_connection;
+use crate::utils::metrics::query_timer;
 
 pub fn insert_new_dream<T>(new_dream: T) -> Result<Dream, Error>
 where
     T: Into<InsertableDream>,
 {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     diesel::insert_into(dreams::table)
         .values(new_dream.into())
         .get_result(conn)
@@ -23,12 +33,12 @@ where
 
 pub fn select_all_dreams_from_baby(
     baby: i32,
+    filter: &DreamFilterDto,
     pagination: Pagination,
 ) -> Result<(Vec<Dream>, i64), Error> {
     let conn = &mut establish_connection();
-    dreams::table
-        .filter(dreams::baby_id.eq(baby))
-        .order(dreams::from_date.asc())
+    let _timer = query_timer!();
+    sorted_dreams(filtered_dreams(baby, filter), filter.sort())
         .paginate(pagination.page())
         .per_page(pagination.per_page())
         .load_and_count_pages(conn)
@@ -38,6 +48,7 @@ pub fn select_all_dreams_from_baby(
 /// in descending to get the higher one.
 pub fn select_last_dream(baby: i32) -> Result<Dream, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     dreams::table
         .filter(dreams::baby_id.eq(baby))
         .filter(dreams::to_date.is_null())
@@ -47,9 +58,15 @@ pub fn select_last_dream(baby: i32) -> Result<Dream, Error> {
 
 pub fn update_last_dream(dream: InsertableDream) -> Result<Dream, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     let last_dream = select_last_dream(dream.baby_id());
     diesel::update(dreams::table.filter(dreams::id.eq(last_dream.unwrap().id())))
-        .set(dreams::to_date.eq(dream.to_date()))
+        .set((
+            dreams::to_date.eq(dream.to_date()),
+            dreams::updated_at.eq(now()),
+            dreams::edited_at.eq(now()),
+            dreams::version.eq(dreams::version + 1),
+        ))
         .get_result(conn)
 }
 
@@ -60,6 +77,7 @@ pub fn select_dreams_for_summary(
     to: NaiveDate,
 ) -> Result<Vec<Dream>, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     let from_timestamp = from.and_hms_opt(0, 0, 1).unwrap();
     let to_timestamp = to.and_hms_opt(23, 59, 59).unwrap();
     dreams::table
@@ -69,51 +87,182 @@ pub fn select_dreams_for_summary(
         .load::<Dream>(conn)
 }
 
-pub fn select_dream_by_id(id: i32) -> Result<Dream, Error> {
+pub fn select_dream_by_id(id: Uuid) -> Result<Dream, Error> {
     let conn = &mut establish_connection();
-    dreams::table.find(id).first(conn)
+    let _timer = query_timer!();
+    dreams::table.filter(dreams::public_id.eq(id)).first(conn)
 }
 
+/// Fails with `NotFound` if the dream changed since it was read.
 pub fn update_dream(dream: Dream) -> Result<Dream, Error> {
     let conn = &mut establish_connection();
-    diesel::update(dreams::table.find(dream.id()))
-        .set((
-            dreams::from_date.eq(dream.from_date()),
-            dreams::to_date.eq(dream.to_date()),
-        ))
+    let _timer = query_timer!();
+    diesel::update(
+        dreams::table
+            .find(dream.id())
+            .filter(dreams::version.eq(dream.version())),
+    )
+    .set((
+        dreams::from_date.eq(dream.from_date()),
+        dreams::to_date.eq(dream.to_date()),
+        dreams::updated_at.eq(dream.updated_at()),
+        dreams::edited_at.eq(dream.edited_at()),
+        dreams::version.eq(dream.version() + 1),
+    ))
+    .get_result(conn)
+}
+
+/// Delete a dream leaving a tombstone for syncing clients.
+///
+/// Fails with `NotFound` if the dream changed since it was read.
+pub fn delete_dream(dream: &Dream) -> Result<usize, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    conn.transaction(|conn| {
+        let deleted: Option<Dream> = diesel::delete(
+            dreams::table
+                .find(dream.id())
+                .filter(dreams::version.eq(dream.version())),
+        )
         .get_result(conn)
+        .optional()?;
+        match deleted {
+            Some(dream) => insert_deleted_record(
+                conn,
+                InsertableDeletedRecord::new(
+                    dream.baby_id(),
+                    DataType::Dream,
+                    dream.public_id(),
+                    dream.client_id(),
+                    now(),
+                ),
+            ),
+            None => Err(Error::NotFound),
+        }
+    })
 }
 
-pub fn delete_dream(record_id: i32) -> Result<usize, Error> {
+pub fn select_dream_by_client_id(baby: i32, client_id: Uuid) -> Result<Option<Dream>, Error> {
     let conn = &mut establish_connection();
-    diesel::delete(dreams::table.find(record_id)).execute(conn)
+    let _timer = query_timer!();
+    dreams::table
+        .filter(dreams::baby_id.eq(baby))
+        .filter(dreams::client_id.eq(client_id))
+        .first(conn)
+        .optional()
+}
+
+/// Dreams created or updated after the cursor, every dream without one.
+pub fn select_dreams_updated_since(
+    baby: i32,
+    since: Option<NaiveDateTime>,
+) -> Result<Vec<Dream>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let mut query = dreams::table.filter(dreams::baby_id.eq(baby)).into_boxed();
+    if let Some(since) = since {
+        query = query.filter(dreams::updated_at.gt(since));
+    }
+    query.order(dreams::updated_at.asc()).load(conn)
 }
 
 pub fn select_dreams_with_pagination(
     baby_id: i32,
+    filter: &DreamFilterDto,
     pagination: Pagination,
     from: NaiveDate,
     to: NaiveDate,
 ) -> Result<(Vec<Dream>, i64), Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     let from_timestamp = from.and_hms_opt(0, 0, 1).unwrap();
     let to_timestamp = to.and_hms_opt(23, 59, 59).unwrap();
-    dreams::table
-        .filter(dreams::baby_id.eq(baby_id))
+    let query = filtered_dreams(baby_id, filter)
         .filter(dreams::to_date.le(to_timestamp))
-        .filter(dreams::to_date.ge(from_timestamp))
-        .order(dreams::from_date.asc())
+        .filter(dreams::to_date.ge(from_timestamp));
+    sorted_dreams(query, filter.sort())
         .paginate(pagination.page())
         .per_page(pagination.per_page())
         .load_and_count_pages(conn)
 }
 
+/// Dreams next to `cursor`, nearest first. Loads one more than `per_page` to tell if there are
+/// more. `descending` lists are newest first, `sort=-date`.
+pub fn select_dreams_by_cursor(
+    baby_id: i32,
+    range: Option<(NaiveDate, NaiveDate)>,
+    filter: &DreamFilterDto,
+    cursor: &Cursor,
+    descending: bool,
+    per_page: i64,
+) -> Result<Vec<Dream>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let mut query = filtered_dreams(baby_id, filter);
+    if let Some((from, to)) = range {
+        query = query
+            .filter(dreams::to_date.le(to.and_hms_opt(23, 59, 59).unwrap()))
+            .filter(dreams::to_date.ge(from.and_hms_opt(0, 0, 1).unwrap()));
+    }
+    let (date, id) = (cursor.timestamp(), cursor.id());
+    // Following a cursor means later dates, unless the list is newest first.
+    query = if cursor.is_forward() != descending {
+        query
+            .filter(
+                dreams::from_date
+                    .gt(date)
+                    .or(dreams::from_date.eq(date).and(dreams::public_id.gt(id))),
+            )
+            .order((dreams::from_date.asc(), dreams::public_id.asc()))
+    } else {
+        query
+            .filter(
+                dreams::from_date
+                    .lt(date)
+                    .or(dreams::from_date.eq(date).and(dreams::public_id.lt(id))),
+            )
+            .order((dreams::from_date.desc(), dreams::public_id.desc()))
+    };
+    query.limit(per_page + 1).load(conn)
+}
+
+/// Duration is measured up to `to_date`, so dreams still going on have none.
+fn filtered_dreams<'a>(baby: i32, filter: &DreamFilterDto) -> dreams::BoxedQuery<'a, Pg> {
+    let mut query = dreams::table.filter(dreams::baby_id.eq(baby)).into_boxed();
+    if let Some(minutes) = filter.duration_gte() {
+        query =
+            query.filter(dreams::to_date.ge((dreams::from_date + minutes.minutes()).nullable()));
+    }
+    if let Some(minutes) = filter.duration_lte() {
+        query =
+            query.filter(dreams::to_date.le((dreams::from_date + minutes.minutes()).nullable()));
+    }
+    query = match filter.open() {
+        Some(true) => query.filter(dreams::to_date.is_null()),
+        Some(false) => query.filter(dreams::to_date.is_not_null()),
+        None => query,
+    };
+    query
+}
+
+/// Ties are broken by id, so pages don't overlap.
+fn sorted_dreams<'a>(
+    query: dreams::BoxedQuery<'a, Pg>,
+    sort: Option<&Sort>,
+) -> dreams::BoxedQuery<'a, Pg> {
+    match sort.map(Sort::descending) {
+        Some(true) => query.order((dreams::from_date.desc(), dreams::public_id.desc())),
+        _ => query.order((dreams::from_date.asc(), dreams::public_id.asc())),
+    }
+}
+
 /*
 Get first Option<date> and last Option<date> for a baby id, if both records are null, default
 to today date.
  */
 pub fn select_date_first_and_last_dream(baby: i32) -> Result<(NaiveDate, NaiveDate), Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     let start: Option<NaiveDateTime> = dreams::table
         .filter(dreams::baby_id.eq(baby))
         .select(dreams::to_date)
@@ -128,3 +277,37 @@ pub fn select_date_first_and_last_dream(baby: i32) -> Result<(NaiveDate, NaiveDa
         .first(conn)?;
     Ok((start.unwrap_or(now()).date(), stop.unwrap_or(now()).date()))
 }
+
+/// Dreams overlapping the range, like one starting the night before `from`. Open dreams are
+/// included once they have started.
+pub fn select_dreams_by_date_range(
+    baby: i32,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<Dream>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let from_timestamp = from.and_hms_opt(0, 0, 0).unwrap();
+    let to_timestamp = to.and_hms_opt(23, 59, 59).unwrap();
+    dreams::table
+        .filter(dreams::baby_id.eq(baby))
+        .filter(dreams::from_date.le(to_timestamp))
+        .filter(
+            dreams::to_date
+                .is_null()
+                .or(dreams::to_date.ge(from_timestamp)),
+        )
+        .order(dreams::from_date.asc())
+        .load::<Dream>(conn)
+}
+
+/// Most recent dream for every baby in one query, ongoing ones have no `to_date`.
+pub fn select_last_dreams(babies: Vec<i32>) -> Result<Vec<Dream>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    dreams::table
+        .filter(dreams::baby_id.eq_any(babies))
+        .distinct_on(dreams::baby_id)
+        .order((dreams::baby_id, dreams::from_date.desc()))
+        .load(conn)
+}
@@ -0,0 +1,48 @@
+use std::sync::{Mutex, PoisonError};
+
+use redis::{
+    aio::{MultiplexedConnection, PubSub},
+    RedisError,
+};
+
+use crate::connection::connection_redis::poll;
+
+/// Shared by every publish, dropped after an error so the next one connects again.
+static PUBLISHER: Mutex<Option<MultiplexedConnection>> = Mutex::new(None);
+
+pub async fn publish_event(channel: &str, payload: &str) -> Result<(), RedisError> {
+    let mut conn = publisher().await?;
+    let published = redis::cmd("PUBLISH")
+        .arg(channel)
+        .arg(payload)
+        .query_async(&mut conn)
+        .await;
+    if published.is_err() {
+        PUBLISHER
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .take();
+    }
+    published
+}
+
+async fn publisher() -> Result<MultiplexedConnection, RedisError> {
+    let shared = PUBLISHER
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .clone();
+    if let Some(conn) = shared {
+        return Ok(conn);
+    }
+    let conn = poll().await.get_multiplexed_tokio_connection().await?;
+    *PUBLISHER.lock().unwrap_or_else(PoisonError::into_inner) = Some(conn.clone());
+    Ok(conn)
+}
+
+/// Dedicated connection, it stays subscribed until the client drops the stream.
+pub async fn subscribe_to_channel(channel: &str) -> Result<PubSub, RedisError> {
+    let conn = poll().await.get_async_connection().await?;
+    let mut pubsub = conn.into_pubsub();
+    pubsub.subscribe(channel).await?;
+    Ok(pubsub)
+}
@@ -0,0 +1,21 @@
+use std::time::Duration;
+
+use diesel::{sql_query, RunQueryDsl};
+use redis::RedisError;
+
+use crate::connection::{connection_psql::try_establish_connection_within, connection_redis::poll};
+
+/// Unlike other queries it doesn't panic when the database is unreachable.
+///
+/// Connecting gives up after `limit`, so a hung database doesn't pile up blocking threads.
+pub fn ping_database(limit: Duration) -> anyhow::Result<()> {
+    let connect_timeout = limit.as_secs_f64().ceil() as u64;
+    let conn = &mut try_establish_connection_within(connect_timeout.max(1))?;
+    sql_query("SELECT 1").execute(conn)?;
+    Ok(())
+}
+
+pub async fn ping_redis() -> Result<(), RedisError> {
+    let mut conn = poll().await.get_async_connection().await?;
+    redis::cmd("PING").query_async(&mut conn).await
+}
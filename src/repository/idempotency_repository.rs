@@ -0,0 +1,44 @@
+use redis::RedisError;
+
+use crate::connection::connection_redis::poll;
+
+/// Store the value only if the key is free, true when it was stored.
+pub async fn insert_idempotency_key(
+    key: &str,
+    value: &str,
+    duration_in_seconds: usize,
+) -> Result<bool, RedisError> {
+    let mut conn = poll().await.get_async_connection().await?;
+    let stored: Option<String> = redis::cmd("SET")
+        .arg(key)
+        .arg(value)
+        .arg("NX")
+        .arg("EX")
+        .arg(duration_in_seconds)
+        .query_async(&mut conn)
+        .await?;
+    Ok(stored.is_some())
+}
+
+pub async fn select_idempotency_key(key: &str) -> Result<Option<String>, RedisError> {
+    let mut conn = poll().await.get_async_connection().await?;
+    redis::cmd("GET").arg(key).query_async(&mut conn).await
+}
+
+pub async fn update_idempotency_key(
+    key: &str,
+    value: &str,
+    duration_in_seconds: usize,
+) -> Result<(), RedisError> {
+    let mut conn = poll().await.get_async_connection().await?;
+    redis::pipe()
+        .set(key, value)
+        .expire(key, duration_in_seconds)
+        .query_async(&mut conn)
+        .await
+}
+
+pub async fn delete_idempotency_key(key: &str) -> Result<(), RedisError> {
+    let mut conn = poll().await.get_async_connection().await?;
+    redis::cmd("DEL").arg(key).query_async(&mut conn).await
+}
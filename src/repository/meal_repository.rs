@@ -1,37 +1,79 @@
 use chrono::{NaiveDate, NaiveDateTime};
-use diesel::{prelude::*, result::Error};
+use diesel::{dsl::IntervalDsl, pg::Pg, prelude::*, result::Error};
+use uuid::Uuid;
 // use diesel_::RunQueryDsl;
 
 use crate::{
-    data::query_dto::Pagination,
-    model::meals_model::{InsertableMeal, Meal},
-    schema::meals,
+    data::{
+        common_structure::DataType,
+        meal_dto::MealFilterDto,
+        query_dto::{Pagination, Sort},
+    },
+    model::{
+        meals_model::{InsertableMeal, Meal},
+        sync_model::InsertableDeletedRecord,
+    },
+    schema::{meals, milk_bags},
+    utils::{cursor::Cursor, datetime::now},
 };
 
-use super::paginator::Paginate;
+use super::{
+    milk_bag_repository::select_milk_bag_public_ids, paginator::Paginate,
+    sync_repository::insert_deleted_record,
+};
 use crate::connection::connection_psql::establish_connection;
+use crate::utils::metrics::query_timer;
 
 pub fn insert_new_meal<T>(new_meal: T) -> Result<Meal, Error>
 where
     T: Into<InsertableMeal>,
 {
     let conn = &mut establish_connection();
-    diesel::insert_into(meals::table)
+    let _timer = query_timer!();
+    let meal = diesel::insert_into(meals::table)
         .values(new_meal.into())
-        .get_result(conn)
+        .get_result(conn)?;
+    with_milk_bag(conn, meal)
+}
+
+/// Bottle feed from a milk bag, quantity is subtracted from the bag in the same transaction.
+///
+/// Fails with `NotFound` if the bag doesn't have enough milk left.
+pub fn insert_new_meal_from_bag(new_meal: InsertableMeal) -> Result<Meal, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let bag = new_meal.milk_bag_id().unwrap_or_default();
+    let quantity = new_meal.quantity().unwrap_or_default();
+    conn.transaction(|conn| {
+        let updated = diesel::update(
+            milk_bags::table
+                .filter(milk_bags::id.eq(bag))
+                .filter(milk_bags::remaining.ge(quantity)),
+        )
+        .set(milk_bags::remaining.eq(milk_bags::remaining - quantity))
+        .execute(conn)?;
+        if updated.eq(&0) {
+            return Err(Error::NotFound);
+        }
+        let meal = diesel::insert_into(meals::table)
+            .values(new_meal)
+            .get_result(conn)?;
+        with_milk_bag(conn, meal)
+    })
 }
 
 pub fn select_all_meals_from_baby(
     baby: i32,
+    filter: &MealFilterDto,
     pagination: Pagination,
 ) -> Result<(Vec<Meal>, i64), Error> {
     let conn = &mut establish_connection();
-    meals::table
-        .filter(meals::baby_id.eq(baby))
-        .order(meals::date.asc())
+    let _timer = query_timer!();
+    let (meals, pages) = sorted_meals(filtered_meals(baby, filter), filter.sort())
         .paginate(pagination.page())
         .per_page(pagination.per_page())
-        .load_and_count_pages(conn)
+        .load_and_count_pages(conn)?;
+    Ok((with_milk_bags(conn, meals)?, pages))
 }
 
 pub fn select_meals_by_date_range(
@@ -40,58 +82,257 @@ pub fn select_meals_by_date_range(
     to_date: NaiveDate,
 ) -> Result<Vec<Meal>, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     let from = from_date.and_hms_opt(0, 0, 1).unwrap();
     let to = to_date.and_hms_opt(23, 59, 59).unwrap();
-    meals::table
+    let meals = meals::table
         .filter(meals::baby_id.eq(baby))
         .filter(meals::date.ge(from))
         .filter(meals::date.le(to))
         .order(meals::date.asc())
-        .load::<Meal>(conn)
+        .load::<Meal>(conn)?;
+    with_milk_bags(conn, meals)
 }
 
-pub fn select_meal_by_id(record: i32) -> Result<Meal, Error> {
+pub fn select_meal_by_id(record: Uuid) -> Result<Meal, Error> {
     let conn = &mut establish_connection();
-    meals::table.find(record).first::<Meal>(conn)
+    let _timer = query_timer!();
+    let meal = meals::table
+        .filter(meals::public_id.eq(record))
+        .first::<Meal>(conn)?;
+    with_milk_bag(conn, meal)
 }
 
+/// Fails with `NotFound` if the meal changed since it was read.
 pub fn update_meal(meal: Meal) -> Result<Meal, Error> {
     let conn = &mut establish_connection();
-    diesel::update(meals::table.find(meal.id()))
-        .set((
-            meals::date.eq(meal.date()),
-            meals::quantity.eq(meal.quantity()),
-            meals::to_time.eq(meal.to_time()),
-        ))
-        .get_result(conn)
+    let _timer = query_timer!();
+    let meal = update_meal_version(conn, &meal)?;
+    with_milk_bag(conn, meal)
+}
+
+/// Bottle feed whose quantity changed from `previous_quantity`, the difference is taken from or
+/// given back to the bag in the same transaction.
+///
+/// Fails with `NotFound` if the meal changed since it was read or the bag doesn't have enough
+/// milk left.
+pub fn update_meal_from_bag(meal: Meal, previous_quantity: i16) -> Result<Meal, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let bag = meal.milk_bag_id().unwrap_or_default();
+    let taken = meal.formatted_quantity() - previous_quantity;
+    conn.transaction(|conn| {
+        let updated = update_meal_version(conn, &meal)?;
+        if taken.ne(&0) {
+            let bags = diesel::update(
+                milk_bags::table
+                    .filter(milk_bags::id.eq(bag))
+                    .filter(milk_bags::remaining.ge(taken)),
+            )
+            .set(milk_bags::remaining.eq(milk_bags::remaining - taken))
+            .execute(conn)?;
+            if bags.eq(&0) {
+                return Err(Error::NotFound);
+            }
+        }
+        with_milk_bag(conn, updated)
+    })
+}
+
+/// Fails with `NotFound` if the meal changed since it was read.
+fn update_meal_version(conn: &mut PgConnection, meal: &Meal) -> Result<Meal, Error> {
+    diesel::update(
+        meals::table
+            .find(meal.id())
+            .filter(meals::version.eq(meal.version())),
+    )
+    .set((
+        meals::date.eq(meal.date()),
+        meals::quantity.eq(meal.quantity()),
+        meals::to_time.eq(meal.to_time()),
+        meals::updated_at.eq(meal.updated_at()),
+        meals::edited_at.eq(meal.edited_at()),
+        meals::version.eq(meal.version() + 1),
+    ))
+    .get_result(conn)
+}
+
+/// Delete a meal leaving a tombstone for syncing clients.
+///
+/// Fails with `NotFound` if the meal changed since it was read.
+pub fn delete_meal(meal: &Meal) -> Result<usize, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    conn.transaction(|conn| delete_meal_with_tombstone(conn, meal))
 }
 
-pub fn delete_meal(record: i32) -> Result<usize, Error> {
+/// Delete a bottle feed and give its quantity back to the bag.
+///
+/// Fails with `NotFound` if the meal changed since it was read.
+pub fn delete_meal_restoring_bag(meal: &Meal) -> Result<usize, Error> {
     let conn = &mut establish_connection();
-    diesel::delete(meals::table.find(record)).execute(conn)
+    let _timer = query_timer!();
+    conn.transaction(|conn| {
+        let deleted = delete_meal_with_tombstone(conn, meal)?;
+        if let Some(bag) = meal.milk_bag_id() {
+            diesel::update(milk_bags::table.find(bag))
+                .set(milk_bags::remaining.eq(milk_bags::remaining + meal.formatted_quantity()))
+                .execute(conn)?;
+        }
+        Ok(deleted)
+    })
+}
+
+fn delete_meal_with_tombstone(conn: &mut PgConnection, meal: &Meal) -> Result<usize, Error> {
+    let deleted: Option<Meal> = diesel::delete(
+        meals::table
+            .find(meal.id())
+            .filter(meals::version.eq(meal.version())),
+    )
+    .get_result(conn)
+    .optional()?;
+    match deleted {
+        Some(meal) => insert_deleted_record(
+            conn,
+            InsertableDeletedRecord::new(
+                meal.baby_id(),
+                DataType::Meal,
+                meal.public_id(),
+                meal.client_id(),
+                now(),
+            ),
+        ),
+        None => Err(Error::NotFound),
+    }
+}
+
+pub fn select_meal_by_client_id(baby: i32, client_id: Uuid) -> Result<Option<Meal>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let meal = meals::table
+        .filter(meals::baby_id.eq(baby))
+        .filter(meals::client_id.eq(client_id))
+        .first(conn)
+        .optional()?;
+    meal.map(|meal| with_milk_bag(conn, meal)).transpose()
+}
+
+/// Meals created or updated after the cursor, every meal without one.
+pub fn select_meals_updated_since(
+    baby: i32,
+    since: Option<NaiveDateTime>,
+) -> Result<Vec<Meal>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let mut query = meals::table.filter(meals::baby_id.eq(baby)).into_boxed();
+    if let Some(since) = since {
+        query = query.filter(meals::updated_at.gt(since));
+    }
+    let meals = query.order(meals::updated_at.asc()).load(conn)?;
+    with_milk_bags(conn, meals)
 }
 
 pub fn select_meals_with_pagination(
     baby_id: i32,
     from_date: NaiveDate,
     to_date: NaiveDate,
+    filter: &MealFilterDto,
     pagination: Pagination,
 ) -> Result<(Vec<Meal>, i64), Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     let from = from_date.and_hms_opt(0, 0, 1).unwrap();
     let to = to_date.and_hms_opt(23, 59, 59).unwrap();
-    meals::table
-        .filter(meals::baby_id.eq(baby_id))
+    let query = filtered_meals(baby_id, filter)
         .filter(meals::date.ge(from))
-        .filter(meals::date.le(to))
-        .order(meals::date.asc())
+        .filter(meals::date.le(to));
+    let (meals, pages) = sorted_meals(query, filter.sort())
         .paginate(pagination.page())
         .per_page(pagination.per_page())
-        .load_and_count_pages(conn)
+        .load_and_count_pages(conn)?;
+    Ok((with_milk_bags(conn, meals)?, pages))
+}
+
+/// Meals next to `cursor`, nearest first. Loads one more than `per_page` to tell if there are
+/// more. `descending` lists are newest first, `sort=-date`.
+pub fn select_meals_by_cursor(
+    baby_id: i32,
+    range: Option<(NaiveDate, NaiveDate)>,
+    filter: &MealFilterDto,
+    cursor: &Cursor,
+    descending: bool,
+    per_page: i64,
+) -> Result<Vec<Meal>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let mut query = filtered_meals(baby_id, filter);
+    if let Some((from_date, to_date)) = range {
+        query = query
+            .filter(meals::date.ge(from_date.and_hms_opt(0, 0, 1).unwrap()))
+            .filter(meals::date.le(to_date.and_hms_opt(23, 59, 59).unwrap()));
+    }
+    let (date, id) = (cursor.timestamp(), cursor.id());
+    // Following a cursor means later dates, unless the list is newest first.
+    query = if cursor.is_forward() != descending {
+        query
+            .filter(
+                meals::date
+                    .gt(date)
+                    .or(meals::date.eq(date).and(meals::public_id.gt(id))),
+            )
+            .order((meals::date.asc(), meals::public_id.asc()))
+    } else {
+        query
+            .filter(
+                meals::date
+                    .lt(date)
+                    .or(meals::date.eq(date).and(meals::public_id.lt(id))),
+            )
+            .order((meals::date.desc(), meals::public_id.desc()))
+    };
+    let meals = query.limit(per_page + 1).load(conn)?;
+    with_milk_bags(conn, meals)
+}
+
+/// Duration is measured up to `to_time`, so running feedings have none.
+fn filtered_meals<'a>(baby: i32, filter: &MealFilterDto) -> meals::BoxedQuery<'a, Pg> {
+    let mut query = meals::table.filter(meals::baby_id.eq(baby)).into_boxed();
+    if let Some(quantity) = filter.quantity_gte() {
+        query = query.filter(meals::quantity.ge(quantity));
+    }
+    if let Some(quantity) = filter.quantity_lte() {
+        query = query.filter(meals::quantity.le(quantity));
+    }
+    if let Some(minutes) = filter.duration_gte() {
+        query = query.filter(meals::to_time.ge((meals::date + minutes.minutes()).nullable()));
+    }
+    if let Some(minutes) = filter.duration_lte() {
+        query = query.filter(meals::to_time.le((meals::date + minutes.minutes()).nullable()));
+    }
+    query = match filter.open() {
+        Some(true) => query.filter(meals::to_time.is_null()),
+        Some(false) => query.filter(meals::to_time.is_not_null()),
+        None => query,
+    };
+    query
+}
+
+/// Ties are broken by id, so pages don't overlap.
+fn sorted_meals<'a>(
+    query: meals::BoxedQuery<'a, Pg>,
+    sort: Option<&Sort>,
+) -> meals::BoxedQuery<'a, Pg> {
+    match sort.map(|sort| (sort.field(), sort.descending())) {
+        Some(("quantity", false)) => query.order((meals::quantity.asc(), meals::id.asc())),
+        Some(("quantity", true)) => query.order((meals::quantity.desc(), meals::id.desc())),
+        Some((_, true)) => query.order((meals::date.desc(), meals::public_id.desc())),
+        _ => query.order((meals::date.asc(), meals::public_id.asc())),
+    }
 }
 
 pub fn select_date_first_and_last_meal(baby: i32) -> Result<(NaiveDate, NaiveDate), Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     let start: NaiveDateTime = meals::table
         .filter(meals::baby_id.eq(baby))
         .select(meals::date)
@@ -104,3 +345,32 @@ pub fn select_date_first_and_last_meal(baby: i32) -> Result<(NaiveDate, NaiveDat
         .first(conn)?;
     Ok((start.date(), stop.date()))
 }
+
+/// Most recent meal for every baby in one query.
+pub fn select_last_meals(babies: Vec<i32>) -> Result<Vec<Meal>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let meals = meals::table
+        .filter(meals::baby_id.eq_any(babies))
+        .distinct_on(meals::baby_id)
+        .order((meals::baby_id, meals::date.desc()))
+        .load(conn)?;
+    with_milk_bags(conn, meals)
+}
+
+/// Fill in the public id of the bags bottle feeds came from.
+fn with_milk_bags(conn: &mut PgConnection, mut meals: Vec<Meal>) -> Result<Vec<Meal>, Error> {
+    let bags: Vec<i32> = meals.iter().filter_map(Meal::milk_bag_id).collect();
+    if bags.is_empty() {
+        return Ok(meals);
+    }
+    let public_ids = select_milk_bag_public_ids(conn, bags)?;
+    meals
+        .iter_mut()
+        .for_each(|meal| meal.resolve_milk_bag(&public_ids));
+    Ok(meals)
+}
+
+fn with_milk_bag(conn: &mut PgConnection, meal: Meal) -> Result<Meal, Error> {
+    with_milk_bags(conn, vec![meal]).map(|mut meals| meals.remove(0))
+}
@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDate, NaiveDateTime};
+use diesel::{dsl::max, prelude::*, result::Error};
+use uuid::Uuid;
+
+use crate::{
+    data::query_dto::Pagination,
+    model::medication_model::{
+        InsertableMedication, InsertableMedicationDose, Medication, MedicationDose,
+    },
+    schema::{medication_doses, medications},
+};
+
+use super::paginator::Paginate;
+use crate::connection::connection_psql::establish_connection;
+use crate::utils::metrics::query_timer;
+
+pub fn insert_new_medication(new_medication: InsertableMedication) -> Result<Medication, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::insert_into(medications::table)
+        .values(new_medication)
+        .get_result(conn)
+}
+
+pub fn select_medication_by_id(record: Uuid) -> Result<Medication, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    medications::table
+        .filter(medications::public_id.eq(record))
+        .first::<Medication>(conn)
+}
+
+pub fn select_medications_from_baby(
+    baby: i32,
+    pagination: Pagination,
+) -> Result<(Vec<Medication>, i64), Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    medications::table
+        .filter(medications::baby_id.eq(baby))
+        .order(medications::name.asc())
+        .paginate(pagination.page())
+        .per_page(pagination.per_page())
+        .load_and_count_pages(conn)
+}
+
+/// Last dose of some medications, whichever baby they belong to.
+pub fn select_last_doses(
+    medication_ids: Vec<i32>,
+) -> Result<Vec<(Medication, Option<NaiveDateTime>)>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let medications: Vec<Medication> = medications::table
+        .filter(medications::id.eq_any(medication_ids.to_vec()))
+        .load(conn)?;
+    let last_doses: Vec<(i32, Option<NaiveDateTime>)> = medication_doses::table
+        .filter(medication_doses::medication_id.eq_any(medication_ids))
+        .group_by(medication_doses::medication_id)
+        .select((medication_doses::medication_id, max(medication_doses::date)))
+        .load(conn)?;
+    Ok(medications
+        .into_iter()
+        .map(|medication| {
+            let last_dose = last_doses
+                .iter()
+                .find(|(id, _)| id.eq(&medication.id()))
+                .and_then(|(_, date)| *date);
+            (medication, last_dose)
+        })
+        .collect())
+}
+
+/// Every medication with the date of its last dose given up to a moment.
+pub fn select_medications_with_last_dose(
+    baby: i32,
+    until: NaiveDateTime,
+) -> Result<Vec<(Medication, Option<NaiveDateTime>)>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let medications: Vec<Medication> = medications::table
+        .filter(medications::baby_id.eq(baby))
+        .order(medications::name.asc())
+        .load(conn)?;
+    let last_doses: Vec<(i32, Option<NaiveDateTime>)> = medication_doses::table
+        .inner_join(medications::table)
+        .filter(medications::baby_id.eq(baby))
+        .filter(medication_doses::date.le(until))
+        .group_by(medication_doses::medication_id)
+        .select((medication_doses::medication_id, max(medication_doses::date)))
+        .load(conn)?;
+    Ok(medications
+        .into_iter()
+        .map(|medication| {
+            let last_dose = last_doses
+                .iter()
+                .find(|(id, _)| id.eq(&medication.id()))
+                .and_then(|(_, date)| *date);
+            (medication, last_dose)
+        })
+        .collect())
+}
+
+pub fn update_medication(medication: Medication) -> Result<Medication, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::update(medications::table.find(medication.id()))
+        .set((
+            medications::name.eq(medication.name()),
+            medications::dose.eq(medication.dose()),
+            medications::unit.eq(medication.unit()),
+            medications::interval_minutes.eq(medication.interval_minutes()),
+            medications::daily_time.eq(medication.daily_time()),
+        ))
+        .get_result(conn)
+}
+
+pub fn delete_medication(record: i32) -> Result<usize, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::delete(medications::table.find(record)).execute(conn)
+}
+
+/// Dose checked against its surrounding doses with the medication locked, doses of the same
+/// medication sent at once are checked one after the other.
+///
+/// Returns `Ok(Err(date))` if the dose isn't allowed, with the earliest date one is.
+pub fn insert_new_dose(
+    new_dose: InsertableMedicationDose,
+) -> Result<Result<MedicationDose, NaiveDateTime>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    conn.transaction(|conn| {
+        let medication = medications::table
+            .find(new_dose.medication_id())
+            .for_update()
+            .first::<Medication>(conn)?;
+        let (previous, following) =
+            surrounding_doses(conn, new_dose.medication_id(), new_dose.date())?;
+        if let Err(next_dose) = medication.check_dose(new_dose.date(), previous, following) {
+            return Ok(Err(next_dose));
+        }
+        let mut dose: MedicationDose = diesel::insert_into(medication_doses::table)
+            .values(new_dose)
+            .get_result(conn)?;
+        dose.resolve_medication(&HashMap::from([(medication.id(), medication.public_id())]));
+        Ok(Ok(dose))
+    })
+}
+
+pub fn select_dose_by_id(record: Uuid) -> Result<MedicationDose, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let dose = medication_doses::table
+        .filter(medication_doses::public_id.eq(record))
+        .first::<MedicationDose>(conn)?;
+    with_medications(conn, vec![dose]).map(|mut doses| doses.remove(0))
+}
+
+/// Closest doses given before (or at) and after a date.
+fn surrounding_doses(
+    conn: &mut PgConnection,
+    medication: i32,
+    date: NaiveDateTime,
+) -> Result<(Option<NaiveDateTime>, Option<NaiveDateTime>), Error> {
+    let previous = medication_doses::table
+        .filter(medication_doses::medication_id.eq(medication))
+        .filter(medication_doses::date.le(date))
+        .select(medication_doses::date)
+        .order(medication_doses::date.desc())
+        .first::<NaiveDateTime>(conn)
+        .optional()?;
+    let following = medication_doses::table
+        .filter(medication_doses::medication_id.eq(medication))
+        .filter(medication_doses::date.gt(date))
+        .select(medication_doses::date)
+        .order(medication_doses::date.asc())
+        .first::<NaiveDateTime>(conn)
+        .optional()?;
+    Ok((previous, following))
+}
+
+pub fn select_doses_with_pagination(
+    medication: i32,
+    from_date: NaiveDate,
+    to_date: NaiveDate,
+    pagination: Pagination,
+) -> Result<(Vec<MedicationDose>, i64), Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let from = from_date.and_hms_opt(0, 0, 0).unwrap();
+    let to = to_date.and_hms_opt(23, 59, 59).unwrap();
+    let (doses, pages) = medication_doses::table
+        .filter(medication_doses::medication_id.eq(medication))
+        .filter(medication_doses::date.ge(from))
+        .filter(medication_doses::date.le(to))
+        .order(medication_doses::date.asc())
+        .paginate(pagination.page())
+        .per_page(pagination.per_page())
+        .load_and_count_pages(conn)?;
+    Ok((with_medications(conn, doses)?, pages))
+}
+
+pub fn delete_dose(record: i32) -> Result<usize, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::delete(medication_doses::table.find(record)).execute(conn)
+}
+
+/// Public ids of `medications`, keyed by id.
+pub(super) fn select_medication_public_ids(
+    conn: &mut PgConnection,
+    medications: Vec<i32>,
+) -> Result<HashMap<i32, Uuid>, Error> {
+    medications::table
+        .filter(medications::id.eq_any(medications))
+        .select((medications::id, medications::public_id))
+        .load::<(i32, Uuid)>(conn)
+        .map(|rows| rows.into_iter().collect())
+}
+
+/// Fill in the public id of the medications doses were given of.
+fn with_medications(
+    conn: &mut PgConnection,
+    mut doses: Vec<MedicationDose>,
+) -> Result<Vec<MedicationDose>, Error> {
+    let medications: Vec<i32> = doses.iter().map(MedicationDose::medication_id).collect();
+    if medications.is_empty() {
+        return Ok(doses);
+    }
+    let public_ids = select_medication_public_ids(conn, medications)?;
+    doses
+        .iter_mut()
+        .for_each(|dose| dose.resolve_medication(&public_ids));
+    Ok(doses)
+}
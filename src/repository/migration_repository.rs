@@ -0,0 +1,112 @@
+use anyhow::anyhow;
+use diesel::{
+    migration::MigrationSource,
+    pg::Pg,
+    sql_query,
+    sql_types::{BigInt, Bool, Text},
+    PgConnection, QueryableByName, RunQueryDsl,
+};
+use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
+
+use crate::connection::connection_psql::try_establish_connection;
+
+/// Every file in `migrations/`, compiled into the binary.
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+/// Arbitrary key, the same for every replica.
+const MIGRATION_LOCK_KEY: i64 = 7_326_211;
+/// Run by the old compose init script on an empty database, without any history.
+const BASELINE_VERSION: &str = "20230525174758";
+
+pub struct MigrationStatus {
+    pub name: String,
+    pub applied: bool,
+}
+
+#[derive(QueryableByName)]
+struct SchemaState {
+    #[diesel(sql_type = Bool)]
+    has_tables: bool,
+    #[diesel(sql_type = Bool)]
+    has_history: bool,
+}
+
+/// Applied versions, empty when the schema is up to date.
+pub fn run_pending_migrations() -> anyhow::Result<Vec<String>> {
+    with_migration_lock(apply_migrations)
+}
+
+/// Baseline a schema created without migration history, then apply pending migrations.
+pub fn apply_migrations(conn: &mut PgConnection) -> anyhow::Result<Vec<String>> {
+    baseline_untracked_schema(conn)?;
+    let applied = conn
+        .run_pending_migrations(MIGRATIONS)
+        .map_err(|error| anyhow!(error))?;
+    Ok(applied.iter().map(ToString::to_string).collect())
+}
+
+/// Tables without history come from the init script, the first migration is marked as applied.
+fn baseline_untracked_schema(conn: &mut PgConnection) -> anyhow::Result<()> {
+    let state: SchemaState = sql_query(
+        "SELECT to_regclass('users') IS NOT NULL AS has_tables, \
+         to_regclass('__diesel_schema_migrations') IS NOT NULL AS has_history",
+    )
+    .get_result(conn)?;
+    if !state.has_tables || state.has_history {
+        return Ok(());
+    }
+    // Same table diesel creates on its first run.
+    sql_query(
+        "CREATE TABLE IF NOT EXISTS __diesel_schema_migrations (\
+         version VARCHAR(50) PRIMARY KEY NOT NULL, \
+         run_on TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP)",
+    )
+    .execute(conn)?;
+    sql_query(
+        "INSERT INTO __diesel_schema_migrations (version) VALUES ($1) ON CONFLICT DO NOTHING",
+    )
+    .bind::<Text, _>(BASELINE_VERSION)
+    .execute(conn)?;
+    Ok(())
+}
+
+/// Reverted version.
+pub fn revert_last_migration() -> anyhow::Result<String> {
+    with_migration_lock(|conn| {
+        let reverted = conn
+            .revert_last_migration(MIGRATIONS)
+            .map_err(|error| anyhow!(error))?;
+        Ok(reverted.to_string())
+    })
+}
+
+/// Embedded migrations in order, with whether they were applied to the database.
+pub fn select_migration_status() -> anyhow::Result<Vec<MigrationStatus>> {
+    let conn = &mut try_establish_connection()?;
+    let applied = conn.applied_migrations().map_err(|error| anyhow!(error))?;
+    let migrations =
+        MigrationSource::<Pg>::migrations(&MIGRATIONS).map_err(|error| anyhow!(error))?;
+    Ok(migrations
+        .iter()
+        .map(|migration| MigrationStatus {
+            name: migration.name().to_string(),
+            applied: applied.contains(&migration.name().version()),
+        })
+        .collect())
+}
+
+/// Session advisory lock, so replicas starting together run migrations one after another.
+fn with_migration_lock<T, F>(action: F) -> anyhow::Result<T>
+where
+    F: FnOnce(&mut PgConnection) -> anyhow::Result<T>,
+{
+    let conn = &mut try_establish_connection()?;
+    sql_query("SELECT pg_advisory_lock($1)")
+        .bind::<BigInt, _>(MIGRATION_LOCK_KEY)
+        .execute(conn)?;
+    let result = action(conn);
+    sql_query("SELECT pg_advisory_unlock($1)")
+        .bind::<BigInt, _>(MIGRATION_LOCK_KEY)
+        .execute(conn)?;
+    result
+}
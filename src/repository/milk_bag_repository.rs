@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use diesel::{prelude::*, result::Error};
+use uuid::Uuid;
+
+use crate::{
+    data::query_dto::Pagination,
+    model::pumping_model::{InsertableMilkBag, MilkBag},
+    schema::milk_bags,
+};
+
+use super::{paginator::Paginate, pumping_repository::select_pumping_public_ids};
+use crate::connection::connection_psql::establish_connection;
+use crate::utils::metrics::query_timer;
+
+pub fn insert_new_milk_bag(new_bag: InsertableMilkBag) -> Result<MilkBag, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let bag = diesel::insert_into(milk_bags::table)
+        .values(new_bag)
+        .get_result(conn)?;
+    with_pumping(conn, bag)
+}
+
+pub fn select_milk_bag_by_id(record: Uuid) -> Result<MilkBag, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let bag = milk_bags::table
+        .filter(milk_bags::public_id.eq(record))
+        .first::<MilkBag>(conn)?;
+    with_pumping(conn, bag)
+}
+
+/// Bags sorted by expiry, first to expire goes first.
+///
+/// When `available_at` is set, empty and expired bags are left out.
+pub fn select_milk_bags_from_baby(
+    baby: i32,
+    available_at: Option<NaiveDateTime>,
+    pagination: Pagination,
+) -> Result<(Vec<MilkBag>, i64), Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let mut query = milk_bags::table
+        .filter(milk_bags::baby_id.eq(baby))
+        .into_boxed();
+    if let Some(now) = available_at {
+        query = query
+            .filter(milk_bags::remaining.gt(0))
+            .filter(milk_bags::expires_at.gt(now));
+    }
+    let (bags, pages) = query
+        .order(milk_bags::expires_at.asc())
+        .paginate(pagination.page())
+        .per_page(pagination.per_page())
+        .load_and_count_pages(conn)?;
+    Ok((with_pumpings(conn, bags)?, pages))
+}
+
+/// Every bag with milk left, expired ones included.
+pub fn select_stocked_milk_bags(baby: i32) -> Result<Vec<MilkBag>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let bags = milk_bags::table
+        .filter(milk_bags::baby_id.eq(baby))
+        .filter(milk_bags::remaining.gt(0))
+        .order(milk_bags::expires_at.asc())
+        .load::<MilkBag>(conn)?;
+    with_pumpings(conn, bags)
+}
+
+pub fn update_milk_bag(bag: MilkBag) -> Result<MilkBag, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let bag = diesel::update(milk_bags::table.find(bag.id()))
+        .set((
+            milk_bags::volume.eq(bag.volume()),
+            milk_bags::remaining.eq(bag.remaining()),
+            milk_bags::pumped_at.eq(bag.pumped_at()),
+            milk_bags::location.eq(bag.formatted_location()),
+            milk_bags::expires_at.eq(bag.expires_at()),
+        ))
+        .get_result(conn)?;
+    with_pumping(conn, bag)
+}
+
+/// Public ids of `bags`, keyed by id.
+pub(super) fn select_milk_bag_public_ids(
+    conn: &mut PgConnection,
+    bags: Vec<i32>,
+) -> Result<HashMap<i32, Uuid>, Error> {
+    milk_bags::table
+        .filter(milk_bags::id.eq_any(bags))
+        .select((milk_bags::id, milk_bags::public_id))
+        .load::<(i32, Uuid)>(conn)
+        .map(|rows| rows.into_iter().collect())
+}
+
+pub fn delete_milk_bag(record: i32) -> Result<usize, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::delete(milk_bags::table.find(record)).execute(conn)
+}
+
+/// Fill in the public id of the sessions bags were pumped in.
+fn with_pumpings(conn: &mut PgConnection, mut bags: Vec<MilkBag>) -> Result<Vec<MilkBag>, Error> {
+    let sessions: Vec<i32> = bags.iter().filter_map(MilkBag::pumping_id).collect();
+    if sessions.is_empty() {
+        return Ok(bags);
+    }
+    let public_ids = select_pumping_public_ids(conn, sessions)?;
+    bags.iter_mut()
+        .for_each(|bag| bag.resolve_pumping(&public_ids));
+    Ok(bags)
+}
+
+fn with_pumping(conn: &mut PgConnection, bag: MilkBag) -> Result<MilkBag, Error> {
+    with_pumpings(conn, vec![bag]).map(|mut bags| bags.remove(0))
+}
@@ -1,10 +1,24 @@
 pub mod admin_repository;
 pub mod association_repository;
 pub mod baby_repository;
+pub mod booklet_repository;
+pub mod calendar_repository;
 pub mod dream_repository;
+pub mod event_repository;
+pub mod idempotency_repository;
 pub mod meal_repository;
+pub mod medication_repository;
+pub mod milk_bag_repository;
+pub mod note_repository;
+pub mod notification_repository;
 pub mod paginator;
+pub mod pumping_repository;
+pub mod reminder_repository;
 pub mod session_repository;
+pub mod sync_repository;
 pub mod user_repository;
+pub mod webhook_repository;
 pub mod weight_repository;
 pub mod role_repository;
+pub mod health_repository;
+pub mod migration_repository;
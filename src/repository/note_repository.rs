@@ -0,0 +1,125 @@
+use chrono::NaiveDate;
+use diesel::{
+    dsl::sql,
+    pg::Pg,
+    prelude::*,
+    result::Error,
+    sql_types::{Bool, Text},
+};
+use uuid::Uuid;
+
+use crate::{
+    data::{note_dto::NoteFilterDto, query_dto::Pagination},
+    model::note_model::{InsertableNote, Note},
+    schema::notes,
+};
+
+use super::paginator::Paginate;
+use crate::connection::connection_psql::establish_connection;
+use crate::utils::metrics::query_timer;
+
+pub fn insert_new_note(new_note: InsertableNote) -> Result<Note, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::insert_into(notes::table)
+        .values(new_note)
+        .get_result(conn)
+}
+
+pub fn select_note_by_id(record: Uuid) -> Result<Note, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    notes::table
+        .filter(notes::public_id.eq(record))
+        .first::<Note>(conn)
+}
+
+pub fn update_note(note: Note) -> Result<Note, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::update(notes::table.find(note.id()))
+        .set((
+            notes::category.eq(note.category()),
+            notes::date.eq(note.date()),
+            notes::value.eq(note.value()),
+            notes::unit.eq(note.unit()),
+            notes::description.eq(note.description()),
+        ))
+        .get_result(conn)
+}
+
+pub fn delete_note(record: i32) -> Result<usize, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::delete(notes::table.find(record)).execute(conn)
+}
+
+pub fn select_all_notes_from_baby(
+    baby: i32,
+    filter: NoteFilterDto,
+    pagination: Pagination,
+) -> Result<(Vec<Note>, i64), Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    filtered_notes(baby, filter)
+        .order(notes::date.asc())
+        .paginate(pagination.page())
+        .per_page(pagination.per_page())
+        .load_and_count_pages(conn)
+}
+
+pub fn select_notes_with_pagination(
+    baby: i32,
+    from_date: NaiveDate,
+    to_date: NaiveDate,
+    filter: NoteFilterDto,
+    pagination: Pagination,
+) -> Result<(Vec<Note>, i64), Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let from = from_date.and_hms_opt(0, 0, 1).unwrap();
+    let to = to_date.and_hms_opt(23, 59, 59).unwrap();
+    filtered_notes(baby, filter)
+        .filter(notes::date.ge(from))
+        .filter(notes::date.le(to))
+        .order(notes::date.asc())
+        .paginate(pagination.page())
+        .per_page(pagination.per_page())
+        .load_and_count_pages(conn)
+}
+
+/// Every note in the period, oldest first.
+pub fn select_notes_by_date_range(
+    baby: i32,
+    from_date: NaiveDate,
+    to_date: NaiveDate,
+) -> Result<Vec<Note>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let from = from_date.and_hms_opt(0, 0, 0).unwrap();
+    let to = to_date.and_hms_opt(23, 59, 59).unwrap();
+    notes::table
+        .filter(notes::baby_id.eq(baby))
+        .filter(notes::date.ge(from))
+        .filter(notes::date.le(to))
+        .order(notes::date.asc())
+        .load::<Note>(conn)
+}
+
+/// Search expression must match `idx_notes_search` so the index can be used.
+fn filtered_notes<'a>(baby: i32, filter: NoteFilterDto) -> notes::BoxedQuery<'a, Pg> {
+    let mut query = notes::table.filter(notes::baby_id.eq(baby)).into_boxed();
+    if let Some(category) = filter.category() {
+        query = query.filter(notes::category.eq(category));
+    }
+    if let Some(search) = filter.search() {
+        query = query.filter(
+            sql::<Bool>(
+                "to_tsvector('simple', category || ' ' || coalesce(description, '')) @@ plainto_tsquery('simple', ",
+            )
+            .bind::<Text, _>(search)
+            .sql(")"),
+        );
+    }
+    query
+}
@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::result::Error;
+use uuid::Uuid;
+
+use crate::{
+    connection::connection_psql::establish_connection,
+    data::query_dto::Pagination,
+    model::notification_model::{InsertableNotification, Notification},
+    schema::{babies, notifications},
+    utils::metrics::query_timer,
+};
+
+use super::{paginator::Paginate, reminder_repository::select_reminder_rule_public_ids};
+
+pub fn insert_notification(notification: InsertableNotification) -> Result<Notification, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let mut notification = diesel::insert_into(notifications::table)
+        .values(notification)
+        .get_result(conn)?;
+    resolve_rules(conn, vec![&mut notification])?;
+    Ok(notification)
+}
+
+pub fn select_notification_by_id(record: Uuid) -> Result<(Notification, Uuid), Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let mut notification: (Notification, Uuid) = notifications::table
+        .inner_join(babies::table)
+        .filter(notifications::public_id.eq(record))
+        .select((notifications::all_columns, babies::unique_id))
+        .first(conn)?;
+    resolve_rules(conn, vec![&mut notification.0])?;
+    Ok(notification)
+}
+
+/// Notifications of a user with their baby's unique id, newest first.
+pub fn select_notifications_from_user(
+    user: i32,
+    only_unread: bool,
+    pagination: Pagination,
+) -> Result<(Vec<(Notification, Uuid)>, i64), Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let mut query = notifications::table
+        .inner_join(babies::table)
+        .filter(notifications::user_id.eq(user))
+        .select((notifications::all_columns, babies::unique_id))
+        .into_boxed();
+    if only_unread {
+        query = query.filter(notifications::read_at.is_null());
+    }
+    let (mut notifications, pages): (Vec<(Notification, Uuid)>, i64) = query
+        .order(notifications::created_at.desc())
+        .paginate(pagination.page())
+        .per_page(pagination.per_page())
+        .load_and_count_pages(conn)?;
+    resolve_rules(
+        conn,
+        notifications
+            .iter_mut()
+            .map(|(notification, _)| notification)
+            .collect(),
+    )?;
+    Ok((notifications, pages))
+}
+
+pub fn update_notification_read_at(
+    record: i32,
+    read_at: Option<NaiveDateTime>,
+) -> Result<usize, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::update(notifications::table.find(record))
+        .set(notifications::read_at.eq(read_at))
+        .execute(conn)
+}
+
+pub fn update_all_notifications_read(user: i32, read_at: NaiveDateTime) -> Result<usize, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::update(
+        notifications::table
+            .filter(notifications::user_id.eq(user))
+            .filter(notifications::read_at.is_null()),
+    )
+    .set(notifications::read_at.eq(read_at))
+    .execute(conn)
+}
+
+pub fn delete_notification(record: i32) -> Result<usize, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::delete(notifications::table.find(record)).execute(conn)
+}
+
+/// Fill in the public id of the rules that raised the notifications.
+fn resolve_rules(
+    conn: &mut PgConnection,
+    mut notifications: Vec<&mut Notification>,
+) -> Result<(), Error> {
+    let rules: Vec<i32> = notifications
+        .iter()
+        .filter_map(|notification| notification.rule_id())
+        .collect();
+    if rules.is_empty() {
+        return Ok(());
+    }
+    let public_ids = select_reminder_rule_public_ids(conn, rules)?;
+    notifications
+        .iter_mut()
+        .for_each(|notification| notification.resolve_rule(&public_ids));
+    Ok(())
+}
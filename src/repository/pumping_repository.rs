@@ -0,0 +1,118 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+use diesel::{prelude::*, result::Error};
+use uuid::Uuid;
+
+use crate::{
+    data::query_dto::Pagination,
+    model::pumping_model::{InsertableMilkBag, InsertablePumping, MilkBag, Pumping},
+    schema::{milk_bags, pumping_sessions},
+};
+
+use super::paginator::Paginate;
+use crate::connection::connection_psql::establish_connection;
+use crate::utils::metrics::query_timer;
+
+pub fn insert_new_pumping(new_pumping: InsertablePumping) -> Result<Pumping, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::insert_into(pumping_sessions::table)
+        .values(new_pumping)
+        .get_result(conn)
+}
+
+/// Store the pumped milk in a new bag, bag is linked to its session.
+pub fn insert_new_pumping_with_bag<F>(
+    new_pumping: InsertablePumping,
+    new_bag: F,
+) -> Result<(Pumping, MilkBag), Error>
+where
+    F: FnOnce(&Pumping) -> InsertableMilkBag,
+{
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    conn.transaction(|conn| {
+        let pumping: Pumping = diesel::insert_into(pumping_sessions::table)
+            .values(new_pumping)
+            .get_result(conn)?;
+        let mut bag: MilkBag = diesel::insert_into(milk_bags::table)
+            .values(new_bag(&pumping))
+            .get_result(conn)?;
+        bag.resolve_pumping(&HashMap::from([(pumping.id(), pumping.public_id())]));
+        Ok((pumping, bag))
+    })
+}
+
+pub fn select_pumping_by_id(record: Uuid) -> Result<Pumping, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    pumping_sessions::table
+        .filter(pumping_sessions::public_id.eq(record))
+        .first::<Pumping>(conn)
+}
+
+pub fn select_all_pumpings_from_baby(
+    baby: i32,
+    pagination: Pagination,
+) -> Result<(Vec<Pumping>, i64), Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    pumping_sessions::table
+        .filter(pumping_sessions::baby_id.eq(baby))
+        .order(pumping_sessions::date.asc())
+        .paginate(pagination.page())
+        .per_page(pagination.per_page())
+        .load_and_count_pages(conn)
+}
+
+pub fn select_pumpings_with_pagination(
+    baby: i32,
+    from_date: NaiveDate,
+    to_date: NaiveDate,
+    pagination: Pagination,
+) -> Result<(Vec<Pumping>, i64), Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let from = from_date.and_hms_opt(0, 0, 1).unwrap();
+    let to = to_date.and_hms_opt(23, 59, 59).unwrap();
+    pumping_sessions::table
+        .filter(pumping_sessions::baby_id.eq(baby))
+        .filter(pumping_sessions::date.ge(from))
+        .filter(pumping_sessions::date.le(to))
+        .order(pumping_sessions::date.asc())
+        .paginate(pagination.page())
+        .per_page(pagination.per_page())
+        .load_and_count_pages(conn)
+}
+
+pub fn update_pumping(pumping: Pumping) -> Result<Pumping, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::update(pumping_sessions::table.find(pumping.id()))
+        .set((
+            pumping_sessions::date.eq(pumping.date()),
+            pumping_sessions::side.eq(pumping.side()),
+            pumping_sessions::duration.eq(pumping.duration()),
+            pumping_sessions::volume.eq(pumping.volume()),
+        ))
+        .get_result(conn)
+}
+
+/// Public ids of `sessions`, keyed by id.
+pub(super) fn select_pumping_public_ids(
+    conn: &mut PgConnection,
+    sessions: Vec<i32>,
+) -> Result<HashMap<i32, Uuid>, Error> {
+    pumping_sessions::table
+        .filter(pumping_sessions::id.eq_any(sessions))
+        .select((pumping_sessions::id, pumping_sessions::public_id))
+        .load::<(i32, Uuid)>(conn)
+        .map(|rows| rows.into_iter().collect())
+}
+
+pub fn delete_pumping(record: i32) -> Result<usize, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::delete(pumping_sessions::table.find(record)).execute(conn)
+}
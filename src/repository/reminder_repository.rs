@@ -0,0 +1,143 @@
+use std::collections::HashMap;
+
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::result::Error;
+use uuid::Uuid;
+
+use crate::{
+    connection::connection_psql::establish_connection,
+    data::query_dto::Pagination,
+    model::reminder_model::{InsertableReminderRule, ReminderRule},
+    schema::{babies, reminder_rules, users_babies},
+    utils::metrics::query_timer,
+};
+
+use super::{medication_repository::select_medication_public_ids, paginator::Paginate};
+
+pub fn insert_new_reminder_rule(new_rule: InsertableReminderRule) -> Result<ReminderRule, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let rule = diesel::insert_into(reminder_rules::table)
+        .values(new_rule)
+        .get_result(conn)?;
+    with_medication(conn, rule)
+}
+
+pub fn select_reminder_rule_by_id(record: Uuid) -> Result<ReminderRule, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let rule = reminder_rules::table
+        .filter(reminder_rules::public_id.eq(record))
+        .first(conn)?;
+    with_medication(conn, rule)
+}
+
+/// Rules created by a user for a baby.
+pub fn select_reminder_rules_from_baby(
+    baby: i32,
+    user: i32,
+    pagination: Pagination,
+) -> Result<(Vec<ReminderRule>, i64), Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let (rules, pages) = reminder_rules::table
+        .filter(reminder_rules::baby_id.eq(baby))
+        .filter(reminder_rules::user_id.eq(user))
+        .order(reminder_rules::created_at.asc())
+        .paginate(pagination.page())
+        .per_page(pagination.per_page())
+        .load_and_count_pages(conn)?;
+    Ok((with_medications(conn, rules)?, pages))
+}
+
+/// Active rules with the baby's name, only while user and baby are still associated.
+pub fn select_active_reminder_rules() -> Result<Vec<(ReminderRule, String)>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    reminder_rules::table
+        .inner_join(babies::table)
+        .inner_join(
+            users_babies::table.on(users_babies::baby_id
+                .eq(reminder_rules::baby_id)
+                .and(users_babies::user_id.eq(reminder_rules::user_id))),
+        )
+        .filter(reminder_rules::active.eq(true))
+        .select((reminder_rules::all_columns, babies::name))
+        .load(conn)
+}
+
+pub fn update_reminder_rule(rule: ReminderRule) -> Result<ReminderRule, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let rule = diesel::update(reminder_rules::table.find(rule.id()))
+        .set((
+            reminder_rules::threshold_minutes.eq(rule.threshold_minutes()),
+            reminder_rules::due_time.eq(rule.due_time()),
+            reminder_rules::channels.eq(rule.formatted_channels()),
+            reminder_rules::active.eq(rule.active()),
+        ))
+        .get_result(conn)?;
+    with_medication(conn, rule)
+}
+
+/// Store when a rule fired, only if nobody else did it since it was read.
+///
+/// Returns false when another scheduler already fired it.
+pub fn mark_reminder_rule_triggered(
+    rule: i32,
+    previous: Option<NaiveDateTime>,
+    triggered_at: NaiveDateTime,
+) -> Result<bool, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let updated = diesel::update(
+        reminder_rules::table
+            .filter(reminder_rules::id.eq(rule))
+            .filter(reminder_rules::last_triggered_at.is_not_distinct_from(previous)),
+    )
+    .set(reminder_rules::last_triggered_at.eq(triggered_at))
+    .execute(conn)?;
+    Ok(updated.gt(&0))
+}
+
+pub fn delete_reminder_rule(record: i32) -> Result<usize, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::delete(reminder_rules::table.find(record)).execute(conn)
+}
+
+/// Public ids of `rules`, keyed by id.
+pub(super) fn select_reminder_rule_public_ids(
+    conn: &mut PgConnection,
+    rules: Vec<i32>,
+) -> Result<HashMap<i32, Uuid>, Error> {
+    reminder_rules::table
+        .filter(reminder_rules::id.eq_any(rules))
+        .select((reminder_rules::id, reminder_rules::public_id))
+        .load::<(i32, Uuid)>(conn)
+        .map(|rows| rows.into_iter().collect())
+}
+
+/// Fill in the public id of the medications rules remind of.
+fn with_medications(
+    conn: &mut PgConnection,
+    mut rules: Vec<ReminderRule>,
+) -> Result<Vec<ReminderRule>, Error> {
+    let medications: Vec<i32> = rules
+        .iter()
+        .filter_map(ReminderRule::medication_id)
+        .collect();
+    if medications.is_empty() {
+        return Ok(rules);
+    }
+    let public_ids = select_medication_public_ids(conn, medications)?;
+    rules
+        .iter_mut()
+        .for_each(|rule| rule.resolve_medication(&public_ids));
+    Ok(rules)
+}
+
+fn with_medication(conn: &mut PgConnection, rule: ReminderRule) -> Result<ReminderRule, Error> {
+    with_medications(conn, vec![rule]).map(|mut rules| rules.remove(0))
+}
@@ -7,10 +7,12 @@ use crate::{
     connection::connection_psql::establish_connection,
     model::role_model::Role,
     schema::{roles, users_roles},
+    utils::metrics::query_timer,
 };
 
 pub fn select_roles_names_from_user(user_id: i32) -> Result<Vec<String>, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     let roles_id: Vec<i16> = users_roles::table
         .filter(users_roles::user_id.eq(user_id))
         .select(users_roles::rol_id)
@@ -25,6 +27,7 @@ pub fn select_roles_names_from_user(user_id: i32) -> Result<Vec<String>, Error>
 pub fn select_roles_id_from_user(user_id: i32) -> Result<HashSet<i16>, Error> {
     let mut roles: HashSet<i16> = HashSet::new();
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     users_roles::table
         .filter(users_roles::user_id.eq(user_id))
         .select(users_roles::rol_id)
@@ -38,7 +41,22 @@ pub fn select_roles_id_from_user(user_id: i32) -> Result<HashSet<i16>, Error> {
 
 pub fn select_role_from_role_name(rol_name: &str) -> Result<Role, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     roles::table
         .filter(roles::name.eq(rol_name))
         .get_result(conn)
 }
+
+/// Roles already present are left untouched.
+pub fn insert_missing_roles(new_roles: &[(i16, &str)]) -> Result<usize, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let values: Vec<_> = new_roles
+        .iter()
+        .map(|(id, name)| (roles::id.eq(id), roles::name.eq(name)))
+        .collect();
+    diesel::insert_into(roles::table)
+        .values(values)
+        .on_conflict_do_nothing()
+        .execute(conn)
+}
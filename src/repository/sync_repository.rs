@@ -0,0 +1,35 @@
+use chrono::NaiveDateTime;
+use diesel::{pg::PgConnection, prelude::*, result::Error};
+
+use crate::{
+    model::sync_model::{DeletedRecord, InsertableDeletedRecord},
+    schema::deleted_records,
+};
+
+use crate::connection::connection_psql::establish_connection;
+use crate::utils::metrics::query_timer;
+
+/// Runs inside the transaction that deletes the record.
+pub(super) fn insert_deleted_record(
+    conn: &mut PgConnection,
+    record: InsertableDeletedRecord,
+) -> Result<usize, Error> {
+    diesel::insert_into(deleted_records::table)
+        .values(record)
+        .execute(conn)
+}
+
+pub fn select_deleted_records_since(
+    baby: i32,
+    since: Option<NaiveDateTime>,
+) -> Result<Vec<DeletedRecord>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let mut query = deleted_records::table
+        .filter(deleted_records::baby_id.eq(baby))
+        .into_boxed();
+    if let Some(since) = since {
+        query = query.filter(deleted_records::deleted_at.gt(since));
+    }
+    query.order(deleted_records::deleted_at.asc()).load(conn)
+}
@@ -5,6 +5,7 @@ use crate::{
         user_model::{InsertableUser, User},
     },
     schema::{babies, users, users_babies, users_roles},
+    utils::cursor::Cursor,
 };
 use chrono::NaiveDateTime;
 use diesel::prelude::*;
@@ -13,18 +14,51 @@ use uuid::Uuid;
 
 use super::paginator::Paginate;
 use crate::connection::connection_psql::establish_connection;
+use crate::utils::metrics::query_timer;
 
 ///
 /// Get all users from database.
 pub fn select_all_users(pagination: Pagination) -> Result<(Vec<User>, i64), Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     users::table
         .select(users::all_columns)
+        .order((users::created_at.asc(), users::id.asc()))
         .paginate(pagination.page())
         .per_page(pagination.per_page())
         .load_and_count_pages(conn)
 }
 
+/// Users next to `cursor`, nearest first. Loads one more than `per_page` to tell if there are
+/// more.
+pub fn select_users_by_cursor(
+    cursor: &Cursor<i32>,
+    per_page: i64,
+) -> Result<Vec<User>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let (created_at, id) = (cursor.timestamp(), cursor.id());
+    let query = users::table.select(users::all_columns).into_boxed();
+    let query = if cursor.is_forward() {
+        query
+            .filter(
+                users::created_at
+                    .gt(created_at)
+                    .or(users::created_at.eq(created_at).and(users::id.gt(id))),
+            )
+            .order((users::created_at.asc(), users::id.asc()))
+    } else {
+        query
+            .filter(
+                users::created_at
+                    .lt(created_at)
+                    .or(users::created_at.eq(created_at).and(users::id.lt(id))),
+            )
+            .order((users::created_at.desc(), users::id.desc()))
+    };
+    query.limit(per_page + 1).load(conn)
+}
+
 /// Raw SQL:
 ///
 /// ```sql
@@ -32,6 +66,7 @@ pub fn select_all_users(pagination: Pagination) -> Result<(Vec<User>, i64), Erro
 /// ```
 pub fn select_user_by_username<T: Into<String>>(username: T) -> Result<User, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     users::table
         .filter(users::username.eq(username.into()))
         .first(conn)
@@ -39,32 +74,40 @@ pub fn select_user_by_username<T: Into<String>>(username: T) -> Result<User, Err
 
 pub fn select_user_by_id(user_id: i32) -> Result<User, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     users::table.find(user_id).first(conn)
 }
 
-pub fn insert_new_user<T: Into<InsertableUser>>(new_user: T, rol: i16) -> Result<User, Error> {
+/// User and its roles are inserted together, a failed role leaves no user behind.
+pub fn insert_new_user<T: Into<InsertableUser>>(new_user: T, roles: &[i16]) -> Result<User, Error> {
     let conn = &mut establish_connection();
-    // Create user entry in db.
-    let user: Result<User, Error> = diesel::insert_into(users::table)
-        .values(new_user.into())
-        .returning(User::as_returning())
-        .get_result(conn);
-    let binding = match user {
-        Ok(ref value) => value.id(),
-        Err(e) => return Err(e),
-    };
-    // Associate user and rol.
-    diesel::insert_into(users_roles::table)
-        .values((
-            &users_roles::rol_id.eq(rol),
-            &users_roles::user_id.eq(binding),
-        ))
-        .execute(conn)?;
-    user
+    let _timer = query_timer!();
+    conn.transaction(|conn| {
+        // Create user entry in db.
+        let user: User = diesel::insert_into(users::table)
+            .values(new_user.into())
+            .returning(User::as_returning())
+            .get_result(conn)?;
+        // Associate user and roles.
+        let user_roles: Vec<_> = roles
+            .iter()
+            .map(|rol| {
+                (
+                    users_roles::rol_id.eq(*rol),
+                    users_roles::user_id.eq(user.id()),
+                )
+            })
+            .collect();
+        diesel::insert_into(users_roles::table)
+            .values(user_roles)
+            .execute(conn)?;
+        Ok(user)
+    })
 }
 
 pub fn update_user(profile: User) -> Result<User, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     diesel::update(users::table.find(profile.id()))
         .set((
             users::name.eq(profile.name()),
@@ -81,18 +124,34 @@ pub fn update_active_for_user(
     time: NaiveDateTime,
 ) -> Result<usize, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     diesel::update(users::table.find(user))
         .set((users::active.eq(active), users::updated_at.eq(time)))
         .execute(conn)
 }
 
+/// `password` must be hashed already.
+pub fn update_password_for_user(
+    user: i32,
+    password: String,
+    time: NaiveDateTime,
+) -> Result<usize, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::update(users::table.find(user))
+        .set((users::password.eq(password), users::updated_at.eq(time)))
+        .execute(conn)
+}
+
 pub fn delete_user(user: i32) -> Result<usize, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     diesel::delete(users::table.find(user)).execute(conn)
 }
 
 pub fn delete_all_users(older_than: NaiveDateTime) -> Result<usize, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     diesel::delete(
         users::table
             .filter(users::active.eq(false))
@@ -108,6 +167,7 @@ pub fn delete_all_users(older_than: NaiveDateTime) -> Result<usize, Error> {
 /// ```
 pub fn select_id_from_username(username: &str) -> Result<i32, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     users::table
         .filter(users::username.eq(username))
         .select(users::id)
@@ -121,6 +181,7 @@ pub fn select_id_from_username(username: &str) -> Result<i32, Error> {
 /// ```
 pub fn select_babies_for_user_id(user: i32) -> Result<Vec<BabyInfo>, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     let babies_id: Vec<i32> = users_babies::table
         .filter(users_babies::user_id.eq(user))
         .select(users_babies::baby_id)
@@ -140,6 +201,7 @@ pub fn select_babies_for_user_id(user: i32) -> Result<Vec<BabyInfo>, Error> {
 
 pub fn select_user_from_username(username: &str) -> Result<usize, Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     users::table
         .filter(users::username.eq(username))
         .execute(conn)
@@ -0,0 +1,157 @@
+use chrono::NaiveDateTime;
+use diesel::prelude::*;
+use diesel::result::Error;
+use uuid::Uuid;
+
+use crate::{
+    connection::connection_psql::establish_connection,
+    data::query_dto::Pagination,
+    model::webhook_model::{
+        DeliveryStatus, InsertableWebhook, InsertableWebhookDelivery, Webhook, WebhookDelivery,
+    },
+    schema::{users_babies, webhook_deliveries, webhooks},
+    utils::metrics::query_timer,
+};
+
+use super::paginator::Paginate;
+
+pub fn insert_new_webhook(new_webhook: InsertableWebhook) -> Result<Webhook, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::insert_into(webhooks::table)
+        .values(new_webhook)
+        .get_result(conn)
+}
+
+pub fn select_webhook_by_id(record: Uuid) -> Result<Webhook, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    webhooks::table
+        .filter(webhooks::public_id.eq(record))
+        .first(conn)
+}
+
+/// Webhooks registered by a user for a baby.
+pub fn select_webhooks_from_baby(
+    baby: i32,
+    user: i32,
+    pagination: Pagination,
+) -> Result<(Vec<Webhook>, i64), Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    webhooks::table
+        .filter(webhooks::baby_id.eq(baby))
+        .filter(webhooks::user_id.eq(user))
+        .order(webhooks::created_at.asc())
+        .paginate(pagination.page())
+        .per_page(pagination.per_page())
+        .load_and_count_pages(conn)
+}
+
+/// Active webhooks of a baby whose owner still has access to it, only the ones registered by
+/// `user` when given.
+pub fn select_active_webhooks(baby: i32, user: Option<i32>) -> Result<Vec<Webhook>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let mut query = webhooks::table
+        .inner_join(
+            users_babies::table.on(users_babies::baby_id
+                .eq(webhooks::baby_id)
+                .and(users_babies::user_id.eq(webhooks::user_id))),
+        )
+        .filter(webhooks::baby_id.eq(baby))
+        .filter(webhooks::active.eq(true))
+        .select(webhooks::all_columns)
+        .into_boxed();
+    if let Some(user) = user {
+        query = query.filter(webhooks::user_id.eq(user));
+    }
+    query.load(conn)
+}
+
+pub fn update_webhook(webhook: Webhook) -> Result<Webhook, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::update(webhooks::table.find(webhook.id()))
+        .set((
+            webhooks::url.eq(webhook.url()),
+            webhooks::active.eq(webhook.active()),
+        ))
+        .get_result(conn)
+}
+
+/// Delivery logs go with it.
+pub fn delete_webhook(record: i32) -> Result<usize, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::delete(webhooks::table.find(record)).execute(conn)
+}
+
+pub fn insert_webhook_deliveries(
+    deliveries: Vec<InsertableWebhookDelivery>,
+) -> Result<usize, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::insert_into(webhook_deliveries::table)
+        .values(deliveries)
+        .execute(conn)
+}
+
+/// Lock pending deliveries that are due and push their next attempt to `lease_until`.
+///
+/// Other replicas skip locked rows, so every delivery is only sent by one of them.
+pub fn claim_due_deliveries(
+    now: NaiveDateTime,
+    lease_until: NaiveDateTime,
+    limit: i64,
+) -> Result<Vec<(WebhookDelivery, Webhook)>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    conn.transaction(|conn| {
+        let due: Vec<(WebhookDelivery, Webhook)> = webhook_deliveries::table
+            .inner_join(webhooks::table)
+            .filter(webhook_deliveries::status.eq(DeliveryStatus::Pending.get()))
+            .filter(webhook_deliveries::next_attempt_at.le(now))
+            .filter(webhooks::active.eq(true))
+            .order(webhook_deliveries::next_attempt_at.asc())
+            .limit(limit)
+            .for_update()
+            .skip_locked()
+            .load(conn)?;
+        let claimed: Vec<i32> = due.iter().map(|(delivery, _)| delivery.id()).collect();
+        diesel::update(webhook_deliveries::table.filter(webhook_deliveries::id.eq_any(claimed)))
+            .set(webhook_deliveries::next_attempt_at.eq(lease_until))
+            .execute(conn)?;
+        Ok(due)
+    })
+}
+
+pub fn update_webhook_delivery(delivery: WebhookDelivery) -> Result<usize, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    diesel::update(webhook_deliveries::table.find(delivery.id()))
+        .set((
+            webhook_deliveries::status.eq(delivery.formatted_status()),
+            webhook_deliveries::attempts.eq(delivery.attempts()),
+            webhook_deliveries::next_attempt_at.eq(delivery.next_attempt_at()),
+            webhook_deliveries::response_code.eq(delivery.response_code()),
+            webhook_deliveries::error.eq(delivery.error()),
+            webhook_deliveries::delivered_at.eq(delivery.delivered_at()),
+        ))
+        .execute(conn)
+}
+
+/// Delivery log, newest first.
+pub fn select_deliveries_from_webhook(
+    webhook: i32,
+    pagination: Pagination,
+) -> Result<(Vec<WebhookDelivery>, i64), Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    webhook_deliveries::table
+        .filter(webhook_deliveries::webhook_id.eq(webhook))
+        .order(webhook_deliveries::created_at.desc())
+        .paginate(pagination.page())
+        .per_page(pagination.per_page())
+        .load_and_count_pages(conn)
+}
@@ -1,20 +1,27 @@
 use chrono::NaiveDate;
-use diesel::{prelude::*, result::Error};
+use diesel::{pg::Pg, prelude::*, result::Error};
+use uuid::Uuid;
 
 use crate::{
-    data::query_dto::Pagination,
+    data::{
+        query_dto::{Pagination, Sort},
+        weight_dto::WeightFilterDto,
+    },
     model::weight_model::{InsertableWeight, Weight},
     schema::weights,
+    utils::cursor::Cursor,
 };
 
 use super::paginator::Paginate;
 use crate::connection::connection_psql::establish_connection;
+use crate::utils::metrics::query_timer;
 
 pub fn insert_new_weight<T>(new_measure: T) -> Result<Weight, Error>
 where
     T: Into<InsertableWeight>,
 {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
     diesel::insert_into(weights::table)
         .values(new_measure.into())
         .get_result(conn)
@@ -22,50 +29,161 @@ where
 
 pub fn select_all_weights_from_baby(
     baby: i32,
+    filter: &WeightFilterDto,
     pagination: Pagination,
 ) -> Result<(Vec<Weight>, i64), Error> {
     let conn = &mut establish_connection();
-    weights::table
-        .filter(weights::baby_id.eq(baby))
-        .order(weights::date.asc())
+    let _timer = query_timer!();
+    sorted_weights(filtered_weights(baby, filter), filter.sort())
         .paginate(pagination.page())
         .per_page(pagination.per_page())
         .load_and_count_pages(conn)
 }
 
+/// Fails with `NotFound` if the measure changed since it was read.
 pub fn update_weight(measure: Weight) -> Result<Weight, Error> {
     let conn = &mut establish_connection();
-    diesel::update(weights::table.find(measure.id()))
-        .set((
-            weights::date.eq(measure.date()),
-            weights::value.eq(measure.value()),
-        ))
-        .get_result(conn)
+    let _timer = query_timer!();
+    diesel::update(
+        weights::table
+            .find(measure.id())
+            .filter(weights::version.eq(measure.version())),
+    )
+    .set((
+        weights::date.eq(measure.date()),
+        weights::value.eq(measure.value()),
+        weights::version.eq(measure.version() + 1),
+    ))
+    .get_result(conn)
 }
 
-pub fn select_weight_by_id(id: i32) -> Result<Weight, Error> {
+pub fn select_weight_by_id(id: Uuid) -> Result<Weight, Error> {
     let conn = &mut establish_connection();
-    weights::table.find(id).first(conn)
+    let _timer = query_timer!();
+    weights::table.filter(weights::public_id.eq(id)).first(conn)
+}
+
+/// Every measure, oldest first.
+pub fn select_weights_from_baby(baby: i32) -> Result<Vec<Weight>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    weights::table
+        .filter(weights::baby_id.eq(baby))
+        .order((weights::date.asc(), weights::public_id.asc()))
+        .load(conn)
 }
 
-pub fn delete_weight(record: i32) -> Result<usize, Error> {
+/// Fails with `NotFound` if the measure changed since it was read.
+pub fn delete_weight(measure: &Weight) -> Result<usize, Error> {
     let conn = &mut establish_connection();
-    diesel::delete(weights::table.find(record)).execute(conn)
+    let _timer = query_timer!();
+    let deleted = diesel::delete(
+        weights::table
+            .find(measure.id())
+            .filter(weights::version.eq(measure.version())),
+    )
+    .execute(conn)?;
+    match deleted {
+        0 => Err(Error::NotFound),
+        _ => Ok(deleted),
+    }
 }
 
 pub fn select_weights_with_pagination(
     baby_id: i32,
     from: NaiveDate,
     to: NaiveDate,
+    filter: &WeightFilterDto,
     pagination: Pagination,
 ) -> Result<(Vec<Weight>, i64), Error> {
     let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let query = filtered_weights(baby_id, filter)
+        .filter(weights::date.ge(from))
+        .filter(weights::date.le(to));
+    sorted_weights(query, filter.sort())
+        .paginate(pagination.page())
+        .per_page(pagination.per_page())
+        .load_and_count_pages(conn)
+}
+
+/// Measures next to `cursor`, nearest first. Loads one more than `per_page` to tell if there
+/// are more. `descending` lists are newest first, `sort=-date`.
+pub fn select_weights_by_cursor(
+    baby_id: i32,
+    range: Option<(NaiveDate, NaiveDate)>,
+    filter: &WeightFilterDto,
+    cursor: &Cursor,
+    descending: bool,
+    per_page: i64,
+) -> Result<Vec<Weight>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
+    let mut query = filtered_weights(baby_id, filter);
+    if let Some((from, to)) = range {
+        query = query
+            .filter(weights::date.ge(from))
+            .filter(weights::date.le(to));
+    }
+    let (date, id) = (cursor.timestamp().date(), cursor.id());
+    // Following a cursor means later dates, unless the list is newest first.
+    query = if cursor.is_forward() != descending {
+        query
+            .filter(
+                weights::date
+                    .gt(date)
+                    .or(weights::date.eq(date).and(weights::public_id.gt(id))),
+            )
+            .order((weights::date.asc(), weights::public_id.asc()))
+    } else {
+        query
+            .filter(
+                weights::date
+                    .lt(date)
+                    .or(weights::date.eq(date).and(weights::public_id.lt(id))),
+            )
+            .order((weights::date.desc(), weights::public_id.desc()))
+    };
+    query.limit(per_page + 1).load(conn)
+}
+
+fn filtered_weights<'a>(baby: i32, filter: &WeightFilterDto) -> weights::BoxedQuery<'a, Pg> {
+    let mut query = weights::table
+        .filter(weights::baby_id.eq(baby))
+        .into_boxed();
+    if let Some(value) = filter.value_gte() {
+        query = query.filter(weights::value.ge(value));
+    }
+    if let Some(value) = filter.value_lte() {
+        query = query.filter(weights::value.le(value));
+    }
+    query
+}
+
+/// Ties are broken by id, so pages don't overlap.
+fn sorted_weights<'a>(
+    query: weights::BoxedQuery<'a, Pg>,
+    sort: Option<&Sort>,
+) -> weights::BoxedQuery<'a, Pg> {
+    match sort.map(|sort| (sort.field(), sort.descending())) {
+        Some(("value", false)) => query.order((weights::value.asc(), weights::id.asc())),
+        Some(("value", true)) => query.order((weights::value.desc(), weights::id.desc())),
+        Some((_, true)) => query.order((weights::date.desc(), weights::public_id.desc())),
+        _ => query.order((weights::date.asc(), weights::public_id.asc())),
+    }
+}
+
+pub fn select_weights_by_date_range(
+    baby_id: i32,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<Weight>, Error> {
+    let conn = &mut establish_connection();
+    let _timer = query_timer!();
     weights::table
         .filter(weights::baby_id.eq(baby_id))
         .filter(weights::date.ge(from))
         .filter(weights::date.le(to))
         .order(weights::date.asc())
-        .paginate(pagination.page())
-        .per_page(pagination.per_page())
-        .load_and_count_pages(conn)
+        .load::<Weight>(conn)
 }
@@ -5,7 +5,7 @@ use diesel::result::Error;
 use hyper::StatusCode;
 use redis::RedisError;
 use serde::Serialize;
-use serde_json::json;
+use utoipa::ToSchema;
 
 #[derive(Debug)]
 pub enum ApiError {
@@ -20,14 +20,89 @@ pub enum ApiError {
     PageNotFound,
     LoginRequired,
     DatesUnordered,
+    /// Minimum interval between doses is not met, holds next allowed date.
+    DoseNotAllowed(String),
+    /// First request with the same idempotency key is still running.
+    IdempotencyKeyInUse,
+    /// Idempotency key was sent before with another method or path.
+    IdempotencyKeyReused,
+    /// Record changed since the version sent in `If-Match`.
+    PreconditionFailed,
+    /// `If-Match` is required to change a versioned record in v2.
+    PreconditionRequired,
     CastError(String),
+    /// Values of the request that failed validation, one entry per value.
+    Invalid(Vec<FieldError>),
     DBError(Error),
     Redis(RedisError),
     Generic500Error(String),
     Generic400Error(String),
 }
 
+/// Invalid value of a request, `code` tells why and `source` where it was sent.
+#[derive(Debug)]
+pub struct FieldError {
+    code: &'static str,
+    source: ErrorSource,
+    detail: String,
+}
+
+impl FieldError {
+    /// Member of the json body, `path` is like `meals/0/client_id`.
+    pub fn pointer(path: &str, code: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            code,
+            source: ErrorSource::Pointer(format!("/{path}")),
+            detail: detail.into(),
+        }
+    }
+
+    pub fn parameter(name: &str, code: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            code,
+            source: ErrorSource::Parameter(name.to_string()),
+            detail: detail.into(),
+        }
+    }
+}
+
+/// JSON:API `source` of an error, a JSON pointer into the body or a query parameter.
+#[derive(Serialize, Debug, PartialEq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ErrorSource {
+    Pointer(String),
+    Parameter(String),
+}
+
 impl ApiError {
+    /// Stable code clients can match on, `detail` is only meant for people.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ApiError::EmptyBody => "empty_body",
+            ApiError::IncorrectPassword => "incorrect_password",
+            ApiError::Forbidden => "forbidden",
+            ApiError::DuplicateUser => "duplicate_user",
+            ApiError::NoUser => "user_not_found",
+            ApiError::NoRecordFound => "record_not_found",
+            ApiError::NoActiveUser => "inactive_user",
+            ApiError::PageNotFound => "page_not_found",
+            ApiError::LoginRequired => "login_required",
+            ApiError::DatesUnordered => "dates_unordered",
+            ApiError::DoseNotAllowed(_) => "dose_not_allowed",
+            ApiError::IdempotencyKeyInUse => "idempotency_key_in_use",
+            ApiError::IdempotencyKeyReused => "idempotency_key_reused",
+            ApiError::PreconditionFailed => "precondition_failed",
+            ApiError::PreconditionRequired => "precondition_required",
+            ApiError::CastError(_) => "invalid_format",
+            ApiError::Invalid(_) => "invalid",
+            ApiError::Generic400Error(_) => "bad_request",
+            ApiError::DBError(_) => "database_error",
+            ApiError::Redis(_) => "session_store_error",
+            ApiError::Generic500Error(_) => "internal_error",
+        }
+    }
+
+    /// Status and detail sent to clients, internal errors don't say what failed.
     pub fn get_error<'a>(&self) -> (StatusCode, String) {
         match self {
             // 40X Error
@@ -57,45 +132,211 @@ impl ApiError {
                 StatusCode::BAD_REQUEST,
                 String::from("Target date must be higher."),
             ),
+            ApiError::DoseNotAllowed(next_dose) => (
+                StatusCode::CONFLICT,
+                format!(
+                    "Minimum interval between doses not met, next dose allowed from {next_dose}."
+                ),
+            ),
+            ApiError::IdempotencyKeyInUse => (
+                StatusCode::CONFLICT,
+                String::from("A request with this idempotency key is still in progress."),
+            ),
+            ApiError::IdempotencyKeyReused => (
+                StatusCode::UNPROCESSABLE_ENTITY,
+                String::from("Idempotency key was already used for a different request."),
+            ),
+            ApiError::PreconditionFailed => (
+                StatusCode::PRECONDITION_FAILED,
+                String::from("Record was modified, get its last version and try again."),
+            ),
+            ApiError::PreconditionRequired => (
+                StatusCode::PRECONDITION_REQUIRED,
+                String::from("Send the version of the record in If-Match."),
+            ),
             ApiError::CastError(msg) => (StatusCode::BAD_REQUEST, format!("Casting error: {msg}")),
+            ApiError::Invalid(fields) => (
+                StatusCode::BAD_REQUEST,
+                fields
+                    .iter()
+                    .map(|field| field.detail.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            ),
             // 50X Error
-            ApiError::DBError(error) => (StatusCode::INTERNAL_SERVER_ERROR, error.to_string()),
+            ApiError::DBError(_) => (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                String::from("Database error."),
+            ),
             ApiError::Generic500Error(msg) => {
                 (StatusCode::INTERNAL_SERVER_ERROR, String::from(msg))
             }
-            ApiError::Redis(error) => (
+            ApiError::Redis(_) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Redis error: {error}"),
+                String::from("Session store error."),
             ),
         }
     }
 }
 
+/// Body is [ErrorBodyV2], [ErrorBody] travels in the extensions for `/api/v1`.
 impl IntoResponse for ApiError {
     fn into_response(self) -> axum::response::Response {
-        let (status_code, msg) = self.get_error();
-        let error = ErrorField {
-            code: status_code.as_u16(),
-            detail: &msg,
-            title: &status_code.canonical_reason().unwrap(),
-        };
-        let body = Json(json!({"errors": error}));
+        let (status_code, detail) = self.get_error();
+        let legacy = ErrorBody::new(status_code, detail);
+        let mut response = (status_code, Json(ErrorBodyV2::from(self))).into_response();
+        response.extensions_mut().insert(legacy);
+        response
+    }
+}
+
+/// Body of error responses in `/api/v1`, one error with the status as `code`.
+#[derive(Serialize, Clone, ToSchema)]
+pub struct ErrorBody {
+    #[schema(inline)]
+    errors: ErrorFieldV1,
+}
+
+impl ErrorBody {
+    pub fn new(status_code: StatusCode, detail: String) -> Self {
+        ErrorBody {
+            errors: ErrorFieldV1 {
+                code: status_code.as_u16(),
+                title: status_code.canonical_reason().unwrap_or_default(),
+                detail,
+            },
+        }
+    }
 
-        (status_code, body).into_response()
+    pub fn detail(&self) -> &str {
+        &self.errors.detail
     }
 }
 
-#[derive(Serialize)]
-struct ErrorField<'a> {
+#[derive(Serialize, Clone, ToSchema)]
+struct ErrorFieldV1 {
     code: u16,
-    title: &'a str,
-    detail: &'a str,
+    title: &'static str,
+    detail: String,
+}
+
+/// Body of error responses in `/api/v2`, one entry per invalid value.
+#[derive(Serialize, ToSchema)]
+pub struct ErrorBodyV2 {
+    #[schema(inline)]
+    errors: Vec<ErrorField>,
+}
+
+impl From<ApiError> for ErrorBodyV2 {
+    fn from(error: ApiError) -> Self {
+        let (status_code, detail) = error.get_error();
+        let status = status_code.as_u16();
+        let title = status_code.canonical_reason().unwrap_or_default();
+        let errors = match error {
+            ApiError::Invalid(fields) => fields
+                .into_iter()
+                .map(|field| ErrorField {
+                    status,
+                    code: field.code,
+                    title,
+                    detail: field.detail,
+                    source: Some(field.source),
+                })
+                .collect(),
+            error => vec![ErrorField {
+                status,
+                code: error.code(),
+                title,
+                detail,
+                source: None,
+            }],
+        };
+        ErrorBodyV2 { errors }
+    }
 }
 
+#[derive(Serialize, ToSchema)]
+struct ErrorField {
+    status: u16,
+    code: &'static str,
+    title: &'static str,
+    detail: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<ErrorSource>,
+}
+
+/// Logs get the cause of internal errors, which is never sent to clients.
 impl Display for ApiError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let (status_code, msg) = self.get_error();
-        let readable_msg = format!("{status_code}: {msg}");
-        write!(f, "{}", readable_msg)
+        match self {
+            ApiError::DBError(error) => write!(f, "{status_code}: {msg} {error}"),
+            ApiError::Redis(error) => write!(f, "{status_code}: {msg} {error}"),
+            _ => write!(f, "{status_code}: {msg}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_error {
+    use serde_json::{json, to_value};
+
+    use super::*;
+
+    #[test]
+    fn test_field_errors() {
+        let error = ApiError::Invalid(vec![
+            FieldError::pointer("name", "required", "Medication name is required."),
+            FieldError::parameter("sort", "not_allowed", "Can't sort by volume."),
+        ]);
+        assert_eq!(error.get_error().0, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            to_value(ErrorBodyV2::from(error)).unwrap(),
+            json!({"errors": [
+                {
+                    "status": 400,
+                    "code": "required",
+                    "title": "Bad Request",
+                    "detail": "Medication name is required.",
+                    "source": {"pointer": "/name"}
+                },
+                {
+                    "status": 400,
+                    "code": "not_allowed",
+                    "title": "Bad Request",
+                    "detail": "Can't sort by volume.",
+                    "source": {"parameter": "sort"}
+                }
+            ]})
+        );
+    }
+
+    #[test]
+    fn test_database_error_hidden() {
+        let error = ApiError::DBError(Error::RollbackTransaction);
+        let cause = Error::RollbackTransaction.to_string();
+        assert!(error.to_string().ends_with(&cause));
+        let body = to_value(ErrorBodyV2::from(error)).unwrap();
+        assert_eq!(body["errors"][0]["code"], "database_error");
+        assert_eq!(body["errors"][0]["detail"], "Database error.");
+        assert!(body["errors"][0].get("source").is_none());
+    }
+
+    #[test]
+    fn test_error_body_v1() {
+        let response = ApiError::Invalid(vec![
+            FieldError::pointer("name", "required", "Name is required."),
+            FieldError::pointer("dose", "out_of_range", "Dose must be higher than 0."),
+        ])
+        .into_response();
+        let legacy = response.extensions().get::<ErrorBody>().unwrap();
+        assert_eq!(
+            to_value(legacy).unwrap(),
+            json!({"errors": {
+                "code": 400,
+                "title": "Bad Request",
+                "detail": "Name is required. Dose must be higher than 0."
+            }})
+        );
     }
 }
@@ -1,8 +1,28 @@
 use axum::{response::IntoResponse, Json};
 
-use hyper::StatusCode;
+use hyper::{header, StatusCode};
 use serde::Serialize;
-use serde_json::json;
+use serde_json::{json, Value};
+use utoipa::ToSchema;
+
+use crate::{
+    data::{
+        common_structure::{
+            AdminBabyDto, AdminUserDto, BabyDto, BookletEntryDto, CalendarDto, DashboardDto,
+            DashboardDtoV2, DoseDto, DoseDtoV2, DreamDto, DreamDtoV2, MealDto, MealDtoV2,
+            MedicationDto, MedicationStatusDto, MedicationStatusDtoV2, MilkBagDto, MilkBagDtoV2,
+            MilkStashDto, NoteDto, NoteDtoV2, NotificationDto, NotificationDtoV2, PumpingDto,
+            PumpingDtoV2, ReminderDto, ReminderDtoV2, RoleDto, ScheduleItemDto, ScheduleStatusDto,
+            SessionDto, UserDto, WebhookDeliveryDto, WebhookDeliveryDtoV2, WebhookDto,
+            WebhookDtoV2, WeightDto,
+        },
+        dream_dto::{DreamSummaryDto, DreamSummaryDtoV2},
+        meal_dto::{MealSummaryDto, MealSummaryDtoV2},
+        sync_dto::{SyncData, SyncDataV2},
+    },
+    repository::admin_repository::StatsDB,
+    utils::etag::format_etag,
+};
 
 /// Return a factory set message.
 #[derive(Debug)]
@@ -10,6 +30,7 @@ pub enum MsgResponse {
     UpdateRecord,
     DeleteRecord,
     DeleteXRecords(usize),
+    NewXRecords(usize),
     ActiveStatusUpdate,
     LogoutUser,
 }
@@ -24,6 +45,9 @@ impl MsgResponse {
             MsgResponse::DeleteXRecords(number) => {
                 (StatusCode::OK, format!("{number} records deleted."))
             }
+            MsgResponse::NewXRecords(number) => {
+                (StatusCode::CREATED, format!("{number} records created."))
+            }
         }
     }
 }
@@ -36,13 +60,20 @@ impl IntoResponse for MsgResponse {
             detail: &msg,
             title: status_code.canonical_reason().unwrap(),
         };
-        let body = Json(json!({"message": message}));
+        let body = Json(MessageBody { message });
 
         (status_code, body).into_response()
     }
 }
 
-#[derive(Serialize)]
+/// Body of a [MsgResponse].
+#[derive(Serialize, ToSchema)]
+pub struct MessageBody<'a> {
+    #[schema(inline)]
+    message: Message<'a>,
+}
+
+#[derive(Serialize, ToSchema)]
 struct Message<'a> {
     status: u16,
     title: &'a str,
@@ -57,6 +88,7 @@ where
 {
     pub data: T,
     pager: PageInfo,
+    fields: Option<Vec<String>>,
     pub status_code: StatusCode,
 }
 
@@ -69,25 +101,66 @@ where
         Self {
             data,
             pager,
+            fields: None,
             status_code: StatusCode::OK,
         }
     }
+
+    /// Page reached through a cursor, there are no page numbers to show.
+    pub fn from_cursors(data: T, next_cursor: Option<String>, prev_cursor: Option<String>) -> Self {
+        let pager = PageInfo {
+            current: None,
+            first: None,
+            prev: None,
+            next: None,
+            last: None,
+            next_cursor,
+            prev_cursor,
+        };
+        Self {
+            data,
+            pager,
+            fields: None,
+            status_code: StatusCode::OK,
+        }
+    }
+
+    /// Link neighbour pages by cursor too, so clients can move to keyset paging.
+    pub fn with_cursors(self, next_cursor: Option<String>, prev_cursor: Option<String>) -> Self {
+        let pager = PageInfo {
+            next_cursor,
+            prev_cursor,
+            ..self.pager
+        };
+        Self { pager, ..self }
+    }
+
+    /// Sparse fieldset, only these attributes are sent for every record.
+    pub fn with_fields(self, fields: Option<Vec<String>>) -> Self {
+        Self { fields, ..self }
+    }
 }
 
-#[derive(Serialize, Debug)]
+/// Page numbers are missing for pages reached by cursor.
+#[derive(Serialize, Debug, ToSchema)]
 pub struct PageInfo {
-    current: i64,
-    first: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    current: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    first: Option<i64>,
     prev: Option<i64>,
     next: Option<i64>,
-    last: i64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last: Option<i64>,
+    next_cursor: Option<String>,
+    prev_cursor: Option<String>,
 }
 
 impl PageInfo {
     pub fn new(current: i64, total_pages: i64) -> Self {
         Self {
-            current,
-            first: 1,
+            current: Some(current),
+            first: Some(1),
             prev: if current.gt(&1) {
                 Some(current - 1)
             } else {
@@ -98,7 +171,9 @@ impl PageInfo {
             } else {
                 None
             },
-            last: if total_pages.ge(&1) { total_pages } else { 1 },
+            last: Some(if total_pages.ge(&1) { total_pages } else { 1 }),
+            next_cursor: None,
+            prev_cursor: None,
         }
     }
 }
@@ -108,12 +183,67 @@ where
     T: Serialize,
 {
     fn into_response(self) -> axum::response::Response {
-        let body = Json(json!({"data": self.data, "page_info": self.pager}));
+        let mut data = json!(self.data);
+        if let Some(fields) = self.fields {
+            retain_attributes(&mut data, &fields);
+        }
+        let body = Json(PageBody {
+            data,
+            page_info: self.pager,
+        });
 
         (self.status_code, body).into_response()
     }
 }
 
+/// Body of a [PagedResponse], `data` holds the list of records.
+#[derive(Serialize, ToSchema)]
+#[aliases(
+    BabyPage = PageBody<Vec<BabyDto>>,
+    AdminBabyPage = PageBody<Vec<AdminBabyDto>>,
+    AdminUserPage = PageBody<Vec<AdminUserDto>>,
+    MealPage = PageBody<Vec<MealDto>>,
+    MealPageV2 = PageBody<Vec<MealDtoV2>>,
+    MealSummaryPage = PageBody<Vec<MealSummaryDto>>,
+    MealSummaryPageV2 = PageBody<Vec<MealSummaryDtoV2>>,
+    DreamPage = PageBody<Vec<DreamDto>>,
+    DreamPageV2 = PageBody<Vec<DreamDtoV2>>,
+    DreamSummaryPage = PageBody<Vec<DreamSummaryDto>>,
+    DreamSummaryPageV2 = PageBody<Vec<DreamSummaryDtoV2>>,
+    WeightPage = PageBody<Vec<WeightDto>>,
+    NotePage = PageBody<Vec<NoteDto>>,
+    NotePageV2 = PageBody<Vec<NoteDtoV2>>,
+    MedicationPage = PageBody<Vec<MedicationDto>>,
+    DosePage = PageBody<Vec<DoseDto>>,
+    DosePageV2 = PageBody<Vec<DoseDtoV2>>,
+    BookletEntryPage = PageBody<Vec<BookletEntryDto>>,
+    PumpingPage = PageBody<Vec<PumpingDto>>,
+    PumpingPageV2 = PageBody<Vec<PumpingDtoV2>>,
+    MilkBagPage = PageBody<Vec<MilkBagDto>>,
+    MilkBagPageV2 = PageBody<Vec<MilkBagDtoV2>>,
+    ReminderPage = PageBody<Vec<ReminderDto>>,
+    ReminderPageV2 = PageBody<Vec<ReminderDtoV2>>,
+    NotificationPage = PageBody<Vec<NotificationDto>>,
+    NotificationPageV2 = PageBody<Vec<NotificationDtoV2>>,
+    WebhookPage = PageBody<Vec<WebhookDto>>,
+    WebhookPageV2 = PageBody<Vec<WebhookDtoV2>>,
+    WebhookDeliveryPage = PageBody<Vec<WebhookDeliveryDto>>,
+    WebhookDeliveryPageV2 = PageBody<Vec<WebhookDeliveryDtoV2>>,
+)]
+pub struct PageBody<T> {
+    data: T,
+    page_info: PageInfo,
+}
+
+fn retain_attributes(data: &mut Value, fields: &[String]) {
+    let records = data.as_array_mut().into_iter().flatten();
+    for attributes in records.filter_map(|record| record.get_mut("attributes")) {
+        if let Some(attributes) = attributes.as_object_mut() {
+            attributes.retain(|key, _| fields.contains(key));
+        }
+    }
+}
+
 #[derive(Debug)]
 /// Return a single record.
 pub struct RecordResponse<T>
@@ -122,6 +252,7 @@ where
 {
     pub data: T,
     pub status_code: StatusCode,
+    etag: Option<String>,
 }
 
 impl<T> RecordResponse<T>
@@ -135,6 +266,7 @@ where
         Self {
             data,
             status_code: StatusCode::OK,
+            etag: None,
         }
     }
 
@@ -145,6 +277,15 @@ where
         Self {
             data,
             status_code: StatusCode::CREATED,
+            etag: None,
+        }
+    }
+
+    /// Send record version as `ETag` header.
+    pub fn with_version(self, version: i32) -> Self {
+        Self {
+            etag: Some(format_etag(version)),
+            ..self
         }
     }
 }
@@ -154,8 +295,111 @@ where
     T: Serialize,
 {
     fn into_response(self) -> axum::response::Response {
-        let body = Json(json!({ "data": self.data }));
+        let body = Json(RecordBody { data: self.data });
 
-        (self.status_code, body).into_response()
+        match self.etag {
+            Some(etag) => (self.status_code, [(header::ETAG, etag)], body).into_response(),
+            None => (self.status_code, body).into_response(),
+        }
+    }
+}
+
+/// Body of a [RecordResponse].
+#[derive(Serialize, ToSchema)]
+#[aliases(
+    SessionRecord = RecordBody<SessionDto>,
+    UserRecord = RecordBody<UserDto>,
+    BabyRecord = RecordBody<BabyDto>,
+    DashboardRecord = RecordBody<Vec<DashboardDto>>,
+    DashboardRecordV2 = RecordBody<Vec<DashboardDtoV2>>,
+    MealRecord = RecordBody<MealDto>,
+    MealRecordV2 = RecordBody<MealDtoV2>,
+    DreamRecord = RecordBody<DreamDto>,
+    DreamRecordV2 = RecordBody<DreamDtoV2>,
+    WeightRecord = RecordBody<WeightDto>,
+    NoteRecord = RecordBody<NoteDto>,
+    NoteRecordV2 = RecordBody<NoteDtoV2>,
+    MedicationRecord = RecordBody<MedicationDto>,
+    MedicationStatusRecord = RecordBody<Vec<MedicationStatusDto>>,
+    MedicationStatusRecordV2 = RecordBody<Vec<MedicationStatusDtoV2>>,
+    DoseRecord = RecordBody<DoseDto>,
+    DoseRecordV2 = RecordBody<DoseDtoV2>,
+    BookletEntryRecord = RecordBody<BookletEntryDto>,
+    BookletStatusRecord = RecordBody<Vec<ScheduleStatusDto>>,
+    ScheduleItemRecord = RecordBody<ScheduleItemDto>,
+    ScheduleRecord = RecordBody<Vec<ScheduleItemDto>>,
+    PumpingRecord = RecordBody<PumpingDto>,
+    PumpingRecordV2 = RecordBody<PumpingDtoV2>,
+    MilkBagRecord = RecordBody<MilkBagDto>,
+    MilkBagRecordV2 = RecordBody<MilkBagDtoV2>,
+    MilkStashRecord = RecordBody<MilkStashDto>,
+    CalendarRecord = RecordBody<CalendarDto>,
+    ReminderRecord = RecordBody<ReminderDto>,
+    ReminderRecordV2 = RecordBody<ReminderDtoV2>,
+    NotificationRecord = RecordBody<NotificationDto>,
+    NotificationRecordV2 = RecordBody<NotificationDtoV2>,
+    WebhookRecord = RecordBody<WebhookDto>,
+    WebhookRecordV2 = RecordBody<WebhookDtoV2>,
+    SyncRecord = RecordBody<SyncData>,
+    SyncRecordV2 = RecordBody<SyncDataV2>,
+    RolesRecord = RecordBody<Vec<RoleDto>>,
+    StatsRecord = RecordBody<StatsDB<'static>>,
+)]
+pub struct RecordBody<T> {
+    data: T,
+}
+
+/// Return a rendered document, like a report, instead of json data.
+#[derive(Debug)]
+pub struct FileResponse {
+    pub content: Vec<u8>,
+    content_type: &'static str,
+    filename: Option<String>,
+    pub status_code: StatusCode,
+}
+
+impl FileResponse {
+    /// Html document, shown in the browser.
+    pub fn html(content: String) -> Self {
+        Self {
+            content: content.into_bytes(),
+            content_type: "text/html; charset=utf-8",
+            filename: None,
+            status_code: StatusCode::OK,
+        }
+    }
+
+    /// iCalendar feed, for calendar subscriptions.
+    pub fn calendar(content: String) -> Self {
+        Self {
+            content: content.into_bytes(),
+            content_type: "text/calendar; charset=utf-8",
+            filename: None,
+            status_code: StatusCode::OK,
+        }
+    }
+
+    /// Pdf document, downloaded as `filename`.
+    pub fn pdf(content: Vec<u8>, filename: String) -> Self {
+        Self {
+            content,
+            content_type: "application/pdf",
+            filename: Some(filename),
+            status_code: StatusCode::OK,
+        }
+    }
+}
+
+impl IntoResponse for FileResponse {
+    fn into_response(self) -> axum::response::Response {
+        let disposition = match self.filename {
+            Some(filename) => format!("attachment; filename=\"{filename}\""),
+            None => String::from("inline"),
+        };
+        let headers = [
+            (header::CONTENT_TYPE, self.content_type.to_string()),
+            (header::CONTENT_DISPOSITION, disposition),
+        ];
+        (self.status_code, headers, self.content).into_response()
     }
 }
@@ -8,6 +8,41 @@ diesel::table! {
         birthdate -> Date,
         belongs_to -> Int4,
         added_on -> Timestamp,
+        version -> Int4,
+    }
+}
+
+diesel::table! {
+    booklet_entries (id) {
+        id -> Int4,
+        baby_id -> Int4,
+        schedule_id -> Nullable<Int4>,
+        kind -> Varchar,
+        name -> Varchar,
+        date -> Date,
+        notes -> Nullable<Text>,
+        public_id -> Uuid,
+    }
+}
+
+diesel::table! {
+    calendar_tokens (id) {
+        id -> Int4,
+        baby_id -> Int4,
+        user_id -> Int4,
+        token -> Uuid,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    deleted_records (id) {
+        id -> Int4,
+        baby_id -> Int4,
+        record_type -> Varchar,
+        record_id -> Uuid,
+        client_id -> Nullable<Uuid>,
+        deleted_at -> Timestamp,
     }
 }
 
@@ -17,6 +52,21 @@ diesel::table! {
         baby_id -> Int4,
         from_date -> Timestamp,
         to_date -> Nullable<Timestamp>,
+        client_id -> Nullable<Uuid>,
+        updated_at -> Timestamp,
+        version -> Int4,
+        public_id -> Uuid,
+        edited_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    health_schedule (id) {
+        id -> Int4,
+        kind -> Varchar,
+        name -> Varchar,
+        age_months -> Int2,
+        tolerance_months -> Int2,
     }
 }
 
@@ -27,6 +77,106 @@ diesel::table! {
         date -> Timestamp,
         quantity -> Nullable<Int2>,
         to_time -> Nullable<Timestamp>,
+        milk_bag_id -> Nullable<Int4>,
+        client_id -> Nullable<Uuid>,
+        updated_at -> Timestamp,
+        version -> Int4,
+        public_id -> Uuid,
+        edited_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    medication_doses (id) {
+        id -> Int4,
+        medication_id -> Int4,
+        date -> Timestamp,
+        dose -> Float4,
+        public_id -> Uuid,
+    }
+}
+
+diesel::table! {
+    medications (id) {
+        id -> Int4,
+        baby_id -> Int4,
+        name -> Varchar,
+        dose -> Float4,
+        unit -> Varchar,
+        interval_minutes -> Nullable<Int4>,
+        daily_time -> Nullable<Time>,
+        created_at -> Timestamp,
+        public_id -> Uuid,
+    }
+}
+
+diesel::table! {
+    milk_bags (id) {
+        id -> Int4,
+        baby_id -> Int4,
+        pumping_id -> Nullable<Int4>,
+        volume -> Int2,
+        remaining -> Int2,
+        pumped_at -> Timestamp,
+        location -> Varchar,
+        expires_at -> Timestamp,
+        public_id -> Uuid,
+    }
+}
+
+diesel::table! {
+    notes (id) {
+        id -> Int4,
+        baby_id -> Int4,
+        category -> Varchar,
+        date -> Timestamp,
+        value -> Nullable<Float4>,
+        unit -> Nullable<Varchar>,
+        description -> Nullable<Text>,
+        public_id -> Uuid,
+    }
+}
+
+diesel::table! {
+    notifications (id) {
+        id -> Int4,
+        user_id -> Int4,
+        baby_id -> Int4,
+        rule_id -> Nullable<Int4>,
+        title -> Varchar,
+        message -> Text,
+        created_at -> Timestamp,
+        read_at -> Nullable<Timestamp>,
+        public_id -> Uuid,
+    }
+}
+
+diesel::table! {
+    pumping_sessions (id) {
+        id -> Int4,
+        baby_id -> Int4,
+        date -> Timestamp,
+        side -> Varchar,
+        duration -> Int2,
+        volume -> Int2,
+        public_id -> Uuid,
+    }
+}
+
+diesel::table! {
+    reminder_rules (id) {
+        id -> Int4,
+        baby_id -> Int4,
+        user_id -> Int4,
+        kind -> Varchar,
+        threshold_minutes -> Nullable<Int4>,
+        medication_id -> Nullable<Int4>,
+        due_time -> Nullable<Time>,
+        channels -> Varchar,
+        active -> Bool,
+        last_triggered_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        public_id -> Uuid,
     }
 }
 
@@ -67,31 +217,97 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    webhook_deliveries (id) {
+        id -> Int4,
+        webhook_id -> Int4,
+        event -> Varchar,
+        payload -> Text,
+        status -> Varchar,
+        attempts -> Int2,
+        next_attempt_at -> Timestamp,
+        response_code -> Nullable<Int2>,
+        error -> Nullable<Text>,
+        created_at -> Timestamp,
+        delivered_at -> Nullable<Timestamp>,
+        public_id -> Uuid,
+    }
+}
+
+diesel::table! {
+    webhooks (id) {
+        id -> Int4,
+        baby_id -> Int4,
+        user_id -> Int4,
+        url -> Text,
+        secret -> Varchar,
+        active -> Bool,
+        created_at -> Timestamp,
+        public_id -> Uuid,
+    }
+}
+
 diesel::table! {
     weights (id) {
         id -> Int4,
         baby_id -> Int4,
         date -> Date,
         value -> Float4,
+        version -> Int4,
+        public_id -> Uuid,
     }
 }
 
 diesel::joinable!(babies -> users (belongs_to));
+diesel::joinable!(booklet_entries -> babies (baby_id));
+diesel::joinable!(booklet_entries -> health_schedule (schedule_id));
+diesel::joinable!(calendar_tokens -> babies (baby_id));
+diesel::joinable!(calendar_tokens -> users (user_id));
+diesel::joinable!(deleted_records -> babies (baby_id));
 diesel::joinable!(dreams -> babies (baby_id));
 diesel::joinable!(meals -> babies (baby_id));
+diesel::joinable!(meals -> milk_bags (milk_bag_id));
+diesel::joinable!(medication_doses -> medications (medication_id));
+diesel::joinable!(medications -> babies (baby_id));
+diesel::joinable!(milk_bags -> babies (baby_id));
+diesel::joinable!(milk_bags -> pumping_sessions (pumping_id));
+diesel::joinable!(notes -> babies (baby_id));
+diesel::joinable!(notifications -> babies (baby_id));
+diesel::joinable!(notifications -> reminder_rules (rule_id));
+diesel::joinable!(notifications -> users (user_id));
+diesel::joinable!(pumping_sessions -> babies (baby_id));
+diesel::joinable!(reminder_rules -> babies (baby_id));
+diesel::joinable!(reminder_rules -> medications (medication_id));
+diesel::joinable!(reminder_rules -> users (user_id));
 diesel::joinable!(users_babies -> babies (baby_id));
 diesel::joinable!(users_babies -> users (user_id));
 diesel::joinable!(users_roles -> roles (rol_id));
 diesel::joinable!(users_roles -> users (user_id));
+diesel::joinable!(webhook_deliveries -> webhooks (webhook_id));
+diesel::joinable!(webhooks -> babies (baby_id));
+diesel::joinable!(webhooks -> users (user_id));
 diesel::joinable!(weights -> babies (baby_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     babies,
+    booklet_entries,
+    calendar_tokens,
+    deleted_records,
     dreams,
+    health_schedule,
     meals,
+    medication_doses,
+    medications,
+    milk_bags,
+    notes,
+    notifications,
+    pumping_sessions,
+    reminder_rules,
     roles,
     users,
     users_babies,
     users_roles,
+    webhook_deliveries,
+    webhooks,
     weights,
 );
@@ -1,6 +1,12 @@
 use crate::{
-    data::common_structure::RoleDto,
-    repository::admin_repository::{select_stats_from_tables, select_roles, StatsDB},
+    data::{admin_dto::BabyExportData, common_structure::RoleDto},
+    repository::{
+        admin_repository::{select_roles, select_stats_from_tables, StatsDB},
+        baby_repository::select_baby_by_id,
+        dream_repository::select_dreams_updated_since,
+        meal_repository::select_meals_updated_since,
+        weight_repository::select_weights_from_baby,
+    },
     response::{error::ApiError, response::RecordResponse},
 };
 
@@ -19,3 +25,20 @@ pub async fn get_roles_service() -> Result<RecordResponse<Vec<RoleDto>>, ApiErro
     let response = RecordResponse::new(data);
     Ok(response)
 }
+
+/// Baby with all its meals, dreams and weights.
+pub async fn get_baby_export_service(
+    baby_id: i32,
+) -> Result<RecordResponse<BabyExportData>, ApiError> {
+    let baby = select_baby_by_id(baby_id)?;
+    let meals = select_meals_updated_since(baby_id, None)?;
+    let dreams = select_dreams_updated_since(baby_id, None)?;
+    let weights = select_weights_from_baby(baby_id)?;
+    let data = BabyExportData {
+        baby: baby.into(),
+        meals: meals.into_iter().map(|meal| meal.into()).collect(),
+        dreams: dreams.into_iter().map(|dream| dream.into()).collect(),
+        weights: weights.into_iter().map(|weight| weight.into()).collect(),
+    };
+    Ok(RecordResponse::new(data))
+}
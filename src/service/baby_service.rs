@@ -8,15 +8,25 @@ use crate::{
     repository::{
         association_repository::{delete_baby_association, insert_baby_to_user},
         baby_repository::{
-            delete_baby_from_db, insert_new_baby, select_babies, select_babies_from_user_id,
-            select_baby_by_id, update_baby, update_baby_belongs_to,
+            delete_baby_from_db, insert_new_baby, select_babies, select_babies_by_cursor,
+            select_babies_from_user_id, select_babies_from_user_id_by_cursor, select_baby_by_id,
+            update_baby, update_baby_belongs_to,
         },
     },
     response::{
         error::ApiError,
         response::{MsgResponse, PagedResponse, RecordResponse},
     },
-    utils::datetime::{convert_to_date, today},
+    utils::{
+        cursor::CursorScope,
+        datetime::{convert_to_date, today},
+        etag::IfMatch,
+        metrics::record_created,
+    },
+};
+
+use super::util_service::{
+    assert_version_matches, cursor_page, invalid_date, numbered_page, stale_record,
 };
 
 pub async fn post_new_baby_service<T>(
@@ -31,36 +41,46 @@ where
         return Err(ApiError::EmptyBody);
     }
     let birthdate = match new_baby.birthdate {
-        Some(day) => convert_to_date(&day)?,
+        Some(day) => convert_to_date(&day).map_err(|_| invalid_date("birthdate"))?,
         None => today(),
     };
     let insert_baby = InsertableBaby::new(new_baby.name.unwrap(), birthdate, user);
     let baby: BabyDto = insert_new_baby(insert_baby, user)?.into();
+    record_created("babies");
     Ok(RecordResponse::new_entry(baby.into()))
 }
 
 pub async fn get_baby_by_id_service(baby_id: i32) -> Result<RecordResponse<BabyDto>, ApiError> {
     let baby = select_baby_by_id(baby_id)?;
-    Ok(RecordResponse::new(baby.into()))
+    let version = baby.version();
+    Ok(RecordResponse::new(baby.into()).with_version(version))
 }
 
 pub async fn get_all_babies_service(
     pagination: Pagination,
 ) -> Result<PagedResponse<Vec<AdminBabyDto>>, ApiError> {
+    let scope = CursorScope::default();
+    if let Some(cursor) = pagination.cursor(&scope)? {
+        let per_page = pagination.per_page();
+        let babies = select_babies_by_cursor(&cursor, per_page)?;
+        return Ok(cursor_page(babies, &cursor, per_page));
+    }
     let current = pagination.page();
     let (babies, total_pages) = select_babies(pagination)?;
-    let babies: Vec<AdminBabyDto> = babies.into_iter().map(|baby| baby.into()).collect();
-    let response = PagedResponse::new(babies, current, total_pages);
-    Ok(response)
+    Ok(numbered_page(babies, current, total_pages, &scope))
 }
 
+/// Stale `If-Match` or a concurrent update fail with 412, a required `If-Match` missing with 428.
 pub async fn patch_baby_service(
     baby_id: i32,
     update: InputBabyDto,
+    if_match: IfMatch,
 ) -> Result<RecordResponse<BabyDto>, ApiError> {
     let baby = select_baby_by_id(baby_id)?;
-    let updated_baby = update_baby(baby.update_baby(update))?;
-    let response = RecordResponse::new(updated_baby.into());
+    assert_version_matches(&if_match, baby.version())?;
+    let updated_baby = update_baby(baby.update_baby(update)).map_err(stale_record)?;
+    let version = updated_baby.version();
+    let response = RecordResponse::new(updated_baby.into()).with_version(version);
     Ok(response)
 }
 
@@ -68,12 +88,18 @@ pub async fn patch_baby_service(
 If baby belongs to current user, delete everything from said baby, if not, delete only the
 association between user and baby.
 */
-pub async fn delete_baby_service(baby_id: i32, user: i32) -> Result<MsgResponse, ApiError> {
+pub async fn delete_baby_service(
+    baby_id: i32,
+    user: i32,
+    if_match: IfMatch,
+) -> Result<MsgResponse, ApiError> {
     let baby = select_baby_by_id(baby_id)?;
+    assert_version_matches(&if_match, baby.version())?;
     match baby.belongs_to().eq(&user) {
-        true => delete_baby_from_db(baby_id)?,
-        false => delete_baby_association(baby_id, user)?,
-    };
+        true => delete_baby_from_db(&baby),
+        false => delete_baby_association(baby_id, user, baby.version()),
+    }
+    .map_err(stale_record)?;
     Ok(MsgResponse::DeleteRecord)
 }
 
@@ -81,11 +107,15 @@ pub async fn get_babies_for_user_service(
     user_id: i32,
     pagination: Pagination,
 ) -> Result<PagedResponse<Vec<BabyDto>>, ApiError> {
+    let scope = CursorScope::default();
+    if let Some(cursor) = pagination.cursor(&scope)? {
+        let per_page = pagination.per_page();
+        let babies = select_babies_from_user_id_by_cursor(user_id, &cursor, per_page)?;
+        return Ok(cursor_page(babies, &cursor, per_page));
+    }
     let current = pagination.page();
     let (babies, total_pages) = select_babies_from_user_id(user_id, pagination)?;
-    let babies: Vec<BabyDto> = babies.into_iter().map(|baby| baby.into()).collect();
-    let response = PagedResponse::new(babies, current, total_pages);
-    Ok(response)
+    Ok(numbered_page(babies, current, total_pages, &scope))
 }
 
 /// Change ownership from one user to another.
@@ -106,4 +136,4 @@ pub async fn post_share_baby_with_user_service(
         Ok(_) => Ok(MsgResponse::UpdateRecord),
         Err(error) => Err(ApiError::DBError(error)),
     }
-}
\ No newline at end of file
+}
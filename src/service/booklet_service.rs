@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+
+use uuid::Uuid;
+
+use crate::{
+    data::{
+        booklet_dto::{InputBookletEntryDto, InputScheduleItemDto},
+        common_structure::{BookletEntryDto, ScheduleItemDto, ScheduleStatusDto},
+        query_dto::Pagination,
+    },
+    model::booklet_model::{
+        BookletEntry, BookletKind, InsertableBookletEntry, InsertableScheduleItem,
+    },
+    repository::{
+        baby_repository::select_baby_by_id,
+        booklet_repository::{
+            delete_booklet_entry, delete_schedule_item, insert_new_booklet_entry,
+            insert_schedule_item, select_booklet_entries_from_baby, select_booklet_entry_by_id,
+            select_schedule, select_schedule_item_by_id, select_scheduled_entry_dates,
+            update_booklet_entry,
+        },
+    },
+    response::{
+        error::{ApiError, FieldError},
+        response::{MsgResponse, PagedResponse, RecordResponse},
+    },
+    utils::datetime::{convert_to_date, today},
+};
+
+use super::util_service::{assert_record_belongs_to_parent, invalid_date};
+
+/// Record a milestone or vaccination, name defaults to the reference schedule one.
+pub async fn post_booklet_entry_service(
+    new_entry: InputBookletEntryDto,
+    kind: BookletKind,
+    baby_id: i32,
+) -> Result<RecordResponse<BookletEntryDto>, ApiError> {
+    let scheduled_name = match new_entry.schedule_id {
+        Some(schedule_id) => {
+            let item = select_schedule_item_by_id(schedule_id)?;
+            if BookletKind::parse(&item.kind()).ne(&Some(kind)) {
+                return Err(FieldError::pointer(
+                    "schedule_id",
+                    "invalid",
+                    format!("Schedule item is not a {}.", kind.get()),
+                )
+                .into());
+            }
+            Some(item.name())
+        }
+        None => None,
+    };
+    let name = new_entry
+        .name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .or(scheduled_name)
+        .ok_or(FieldError::pointer("name", "required", "Name is required."))?;
+    let date = match new_entry.date {
+        Some(date) => convert_to_date(&date).map_err(|_| invalid_date("date"))?,
+        None => today(),
+    };
+    let entry = InsertableBookletEntry::new(
+        baby_id,
+        new_entry.schedule_id,
+        kind,
+        name,
+        date,
+        new_entry.notes,
+    );
+    let insert_data: BookletEntry = insert_new_booklet_entry(entry)?;
+    Ok(RecordResponse::new_entry(insert_data.into()))
+}
+
+pub async fn patch_booklet_entry_service(
+    entry: InputBookletEntryDto,
+    kind: BookletKind,
+    record: Uuid,
+    baby_id: i32,
+) -> Result<RecordResponse<BookletEntryDto>, ApiError> {
+    let entry_record = find_booklet_entry(record, kind, baby_id)?;
+    let new_entry = update_booklet_entry(entry_record.update_entry(entry))?;
+    Ok(RecordResponse::new(new_entry.into()))
+}
+
+pub async fn get_booklet_entries_service(
+    kind: BookletKind,
+    baby_id: i32,
+    pagination: Pagination,
+) -> Result<PagedResponse<Vec<BookletEntryDto>>, ApiError> {
+    let current = pagination.page();
+    let (entries, total_pages) = select_booklet_entries_from_baby(baby_id, kind, pagination)?;
+    let entries: Vec<BookletEntryDto> = entries.into_iter().map(|entry| entry.into()).collect();
+    Ok(PagedResponse::new(entries, current, total_pages))
+}
+
+pub async fn get_booklet_entry_id_service(
+    kind: BookletKind,
+    record: Uuid,
+    baby_id: i32,
+) -> Result<RecordResponse<BookletEntryDto>, ApiError> {
+    let entry = find_booklet_entry(record, kind, baby_id)?;
+    Ok(RecordResponse::new(entry.into()))
+}
+
+pub async fn delete_booklet_entry_service(
+    kind: BookletKind,
+    record: Uuid,
+    baby_id: i32,
+) -> Result<MsgResponse, ApiError> {
+    let entry = find_booklet_entry(record, kind, baby_id)?;
+    delete_booklet_entry(entry.id())?;
+    Ok(MsgResponse::DeleteRecord)
+}
+
+/// Reference schedule compared with baby's age and recorded entries.
+pub async fn get_booklet_status_service(
+    baby_id: i32,
+) -> Result<RecordResponse<Vec<ScheduleStatusDto>>, ApiError> {
+    let birthdate = select_baby_by_id(baby_id)?.birthdate();
+    let done: HashMap<i32, _> = select_scheduled_entry_dates(baby_id)?
+        .into_iter()
+        .filter_map(|(schedule_id, date)| schedule_id.map(|id| (id, date)))
+        .collect();
+    let today = today();
+    let status: Vec<ScheduleStatusDto> = select_schedule()?
+        .into_iter()
+        .map(|item| {
+            let done_date = done.get(&item.id()).copied();
+            item.status(birthdate, today, done_date).into()
+        })
+        .collect();
+    Ok(RecordResponse::new(status))
+}
+
+pub async fn get_schedule_service() -> Result<RecordResponse<Vec<ScheduleItemDto>>, ApiError> {
+    let schedule: Vec<ScheduleItemDto> = select_schedule()?
+        .into_iter()
+        .map(|item| item.into())
+        .collect();
+    Ok(RecordResponse::new(schedule))
+}
+
+pub async fn post_schedule_item_service(
+    new_item: InputScheduleItemDto,
+) -> Result<RecordResponse<ScheduleItemDto>, ApiError> {
+    let kind = BookletKind::parse(&new_item.kind).ok_or(FieldError::pointer(
+        "kind",
+        "invalid",
+        "Kind must be milestone or vaccination.",
+    ))?;
+    let mut errors = Vec::new();
+    if new_item.name.trim().is_empty() {
+        errors.push(FieldError::pointer("name", "required", "Name is required."));
+    }
+    if new_item.age_months.lt(&0) {
+        errors.push(FieldError::pointer(
+            "age_months",
+            "out_of_range",
+            "Age must be positive.",
+        ));
+    }
+    if !errors.is_empty() {
+        return Err(ApiError::Invalid(errors));
+    }
+    let item = InsertableScheduleItem::new(
+        kind,
+        new_item.name.trim().to_string(),
+        new_item.age_months,
+        new_item.tolerance_months.unwrap_or(1).max(0),
+    );
+    let insert_data = insert_schedule_item(item)?;
+    Ok(RecordResponse::new_entry(insert_data.into()))
+}
+
+pub async fn delete_schedule_item_service(record: i32) -> Result<MsgResponse, ApiError> {
+    match delete_schedule_item(record)? {
+        0 => Err(ApiError::NoRecordFound),
+        _ => Ok(MsgResponse::DeleteRecord),
+    }
+}
+
+fn find_booklet_entry(
+    record: Uuid,
+    kind: BookletKind,
+    baby_id: i32,
+) -> Result<BookletEntry, ApiError> {
+    let entry = select_booklet_entry_by_id(record)?;
+    assert_record_belongs_to_parent(entry.baby_id(), baby_id)?;
+    if entry.kind().ne(&Some(kind)) {
+        return Err(ApiError::NoRecordFound);
+    }
+    Ok(entry)
+}
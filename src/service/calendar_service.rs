@@ -0,0 +1,78 @@
+use chrono::{Days, NaiveDate};
+use uuid::Uuid;
+
+use crate::{
+    data::common_structure::CalendarDto,
+    model::calendar_model::{CalendarEvent, InsertableCalendarToken},
+    repository::{
+        baby_repository::select_baby_by_id,
+        calendar_repository::{
+            delete_calendar_token, insert_calendar_token, select_baby_from_calendar_token,
+        },
+        dream_repository::select_dreams_by_date_range,
+        meal_repository::select_meals_by_date_range,
+    },
+    response::{
+        error::{ApiError, FieldError},
+        response::{FileResponse, MsgResponse, RecordResponse},
+    },
+    utils::{calendar::render_calendar, datetime::today},
+};
+
+/// Create a new feed token, any previous token for this baby and user is revoked.
+pub async fn post_calendar_token_service(
+    baby_id: i32,
+    user_id: i32,
+) -> Result<RecordResponse<CalendarDto>, ApiError> {
+    let token = insert_calendar_token(InsertableCalendarToken::new(baby_id, user_id))?;
+    Ok(RecordResponse::new_entry(token.into()))
+}
+
+pub async fn delete_calendar_token_service(
+    baby_id: i32,
+    user_id: i32,
+) -> Result<MsgResponse, ApiError> {
+    match delete_calendar_token(baby_id, user_id)? {
+        0 => Err(ApiError::NoRecordFound),
+        _ => Ok(MsgResponse::DeleteRecord),
+    }
+}
+
+/// Check feed token and return the baby it belongs to.
+pub fn check_calendar_token(token: &str) -> Result<i32, ApiError> {
+    let unique_token = Uuid::parse_str(token)
+        .map_err(|_| FieldError::parameter("token", "invalid_format", "Token must be a UUID."))?;
+    match select_baby_from_calendar_token(unique_token) {
+        Ok(baby_id) => Ok(baby_id),
+        Err(_) => Err(ApiError::Forbidden),
+    }
+}
+
+pub async fn get_calendar_feed_range_service(
+    baby_id: i32,
+    from_date: NaiveDate,
+    to_date: NaiveDate,
+) -> Result<FileResponse, ApiError> {
+    let baby = select_baby_by_id(baby_id)?;
+    let mut events: Vec<CalendarEvent> = select_meals_by_date_range(baby_id, from_date, to_date)?
+        .into_iter()
+        .map(|meal| meal.into())
+        .collect();
+    select_dreams_by_date_range(baby_id, from_date, to_date)?
+        .into_iter()
+        .for_each(|dream| events.push(dream.into()));
+    events.sort_by_key(|event| event.start());
+    Ok(FileResponse::calendar(render_calendar(
+        &baby.name(),
+        &events,
+    )))
+}
+
+pub async fn get_calendar_feed_last_days_service(
+    baby_id: i32,
+    last_days: u32,
+) -> Result<FileResponse, ApiError> {
+    let today = today();
+    let from_date = today.checked_sub_days(Days::new(last_days.into())).unwrap();
+    get_calendar_feed_range_service(baby_id, from_date, today).await
+}
@@ -0,0 +1,32 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    model::dashboard_model::BabyDashboard,
+    repository::{
+        baby_repository::select_babies_from_unique_ids, dream_repository::select_last_dreams,
+        meal_repository::select_last_meals,
+    },
+    response::{error::ApiError, response::RecordResponse},
+    utils::datetime::now,
+};
+
+/// Latest meal and dream of every baby in the household.
+///
+/// Three queries are made whatever the number of babies.
+pub async fn get_household_dashboard_service<D>(
+    babies: Vec<Uuid>,
+) -> Result<RecordResponse<Vec<D>>, ApiError>
+where
+    D: From<BabyDashboard> + Serialize,
+{
+    let babies = select_babies_from_unique_ids(babies)?;
+    let babies_id: Vec<i32> = babies.iter().map(|baby| baby.id()).collect();
+    let meals = select_last_meals(babies_id.to_vec())?;
+    let dreams = select_last_dreams(babies_id)?;
+    let dashboard: Vec<D> = BabyDashboard::from_batch(babies, meals, dreams, now())
+        .into_iter()
+        .map(|baby| baby.into())
+        .collect();
+    Ok(RecordResponse::new(dashboard))
+}
@@ -1,114 +1,192 @@
 use chrono::{Days, NaiveDate};
+use serde::Serialize;
+use uuid::Uuid;
 
 use crate::{
-    data::{common_structure::DreamDto, dream_dto::InputDreamDto, query_dto::Pagination},
-    model::dream_model::{Dream, InsertableDream},
+    data::{
+        common_structure::{DataType, DreamDto},
+        dream_dto::{DreamFilterDto, InputDreamDto},
+        query_dto::{ListQuery, Pagination},
+    },
+    model::{
+        dream_model::{Dream, InsertableDream},
+        event_model::EventAction,
+    },
     repository::dream_repository::{
-        delete_dream, insert_new_dream, select_all_dreams_from_baby, select_dream_by_id,
-        select_dreams_with_pagination, update_dream, update_last_dream,
+        delete_dream, insert_new_dream, select_all_dreams_from_baby, select_dream_by_client_id,
+        select_dream_by_id, select_dreams_by_cursor, select_dreams_with_pagination, update_dream,
+        update_last_dream,
     },
     response::{
         error::ApiError,
         response::{MsgResponse, PagedResponse, RecordResponse},
     },
-    utils::datetime::today,
+    utils::{datetime::today, etag::IfMatch, metrics::record_created},
 };
 
-use super::util_service::{assert_record_belongs_to_parent, cast_to_date_from};
+use super::{
+    event_service::{publish_deleted_event, publish_record_event},
+    util_service::{
+        assert_record_belongs_to_parent, assert_version_matches, cast_to_date_from,
+        cursor_descending, cursor_page, sorted_page, stale_record,
+    },
+};
 
-pub async fn post_dream_service(
+/// `D` is the dream of the API version, events are always sent as [DreamDto].
+pub async fn post_dream_service<D>(
     new_dream: InputDreamDto,
     baby_id: i32,
-) -> Result<RecordResponse<DreamDto>, ApiError> {
+) -> Result<RecordResponse<D>, ApiError>
+where
+    D: From<Dream> + Serialize,
+{
+    if let Some(client_id) = new_dream.client_id {
+        if let Some(dream) = select_dream_by_client_id(baby_id, client_id)? {
+            return Ok(RecordResponse::new(dream.into()));
+        }
+    }
     let dream: InsertableDream;
-    let entry: Dream = if new_dream.from_date.is_some() {
+    // Without a start date the ongoing dream is finished.
+    let (entry, action): (Dream, EventAction) = if new_dream.from_date.is_some() {
         dream = create_new_dream_entry(new_dream, baby_id).await?;
         let entry: Dream = insert_new_dream(dream)?;
-        entry
+        record_created("dreams");
+        (entry, EventAction::Created)
     } else {
         dream = create_new_dream_entry(new_dream, baby_id).await?;
         let entry: Dream = update_last_dream(dream)?;
-        entry
+        (entry, EventAction::Updated)
     };
-    let response: RecordResponse<DreamDto> = RecordResponse::new_entry(entry.into());
+    let event: DreamDto = entry.clone().into();
+    publish_record_event(baby_id, action, &event).await;
+    let response: RecordResponse<D> = RecordResponse::new_entry(entry.into());
     Ok(response)
 }
 
-async fn create_new_dream_entry(
+pub(super) async fn create_new_dream_entry(
     new_dream: InputDreamDto,
     baby_id: i32,
 ) -> Result<InsertableDream, ApiError> {
-    let to_date_binding = cast_to_date_from(new_dream.to_date)?;
-    let from_date_binding = cast_to_date_from(new_dream.from_date)?;
-    let dream = InsertableDream::new(baby_id, from_date_binding, to_date_binding);
+    let to_date_binding = cast_to_date_from(new_dream.to_date, "to_date")?;
+    let from_date_binding = cast_to_date_from(new_dream.from_date, "from_date")?;
+    let dream = InsertableDream::new(
+        baby_id,
+        from_date_binding,
+        to_date_binding,
+        new_dream.client_id,
+    );
     Ok(dream)
 }
 
-pub async fn patch_dream_service(
+/// Stale `If-Match` or a concurrent update fail with 412, a required `If-Match` missing with 428.
+pub async fn patch_dream_service<D>(
     dream: InputDreamDto,
-    record: i32,
+    record: Uuid,
     baby_id: i32,
-) -> Result<RecordResponse<DreamDto>, ApiError> {
+    if_match: IfMatch,
+) -> Result<RecordResponse<D>, ApiError>
+where
+    D: From<Dream> + Serialize,
+{
     let dream_record = select_dream_by_id(record)?;
     assert_record_belongs_to_parent(dream_record.baby_id(), baby_id)?;
+    assert_version_matches(&if_match, dream_record.version())?;
 
-    let dream: Dream = update_dream(dream_record.update_dream(dream))?;
-    let response: RecordResponse<DreamDto> = RecordResponse::new(dream.into());
+    let updated = update_dream(dream_record.update_dream(dream)).map_err(stale_record)?;
+    let version = updated.version();
+    let event: DreamDto = updated.clone().into();
+    publish_record_event(baby_id, EventAction::Updated, &event).await;
+    let response: RecordResponse<D> = RecordResponse::new(updated.into()).with_version(version);
     Ok(response)
 }
 
-pub async fn get_dreams_by_range_date_service(
+pub async fn get_dreams_by_range_date_service<D>(
     baby_id: i32,
     from_date: NaiveDate,
     to_date: NaiveDate,
+    list: ListQuery,
     pagination: Pagination,
-) -> Result<PagedResponse<Vec<DreamDto>>, ApiError> {
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<Dream> + Serialize,
+{
+    let filter = DreamFilterDto::try_from(&list)?;
+    let fields = list.fields(DataType::Dream.get());
+    let range = Some((from_date, to_date));
+    let scope = list.scope(range);
+    if let Some(cursor) = pagination.cursor(&scope)? {
+        let descending = cursor_descending(filter.sort())?;
+        let per_page = pagination.per_page();
+        let dreams =
+            select_dreams_by_cursor(baby_id, range, &filter, &cursor, descending, per_page)?;
+        return Ok(cursor_page(dreams, &cursor, per_page).with_fields(fields));
+    }
     let current = pagination.page();
     let (dreams, total_pages) =
-        select_dreams_with_pagination(baby_id, pagination, from_date, to_date)?;
-    let dreams: Vec<DreamDto> = into_dreams_dto(dreams)?;
-    let response = PagedResponse::new(dreams, current, total_pages);
-    Ok(response)
+        select_dreams_with_pagination(baby_id, &filter, pagination, from_date, to_date)?;
+    Ok(sorted_page(dreams, current, total_pages, filter.sort(), &scope).with_fields(fields))
 }
 
-pub async fn get_dreams_by_last_days_service(
+pub async fn get_dreams_by_last_days_service<D>(
     baby_id: i32,
     last_days: u32,
+    list: ListQuery,
     pagination: Pagination,
-) -> Result<PagedResponse<Vec<DreamDto>>, ApiError> {
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<Dream> + Serialize,
+{
     let today = today();
     let from_date = today.checked_sub_days(Days::new(last_days.into())).unwrap();
-    get_dreams_by_range_date_service(baby_id, from_date, today, pagination).await
+    get_dreams_by_range_date_service(baby_id, from_date, today, list, pagination).await
 }
 
-fn into_dreams_dto(dreams: Vec<Dream>) -> Result<Vec<DreamDto>, ApiError> {
-    Ok(dreams.into_iter().map(|dream| dream.into()).collect())
-}
-
-pub async fn delete_dream_service(record: i32, baby_id: i32) -> Result<MsgResponse, ApiError> {
+pub async fn delete_dream_service(
+    record: Uuid,
+    baby_id: i32,
+    if_match: IfMatch,
+) -> Result<MsgResponse, ApiError> {
     let old_dream = select_dream_by_id(record)?;
     assert_record_belongs_to_parent(old_dream.baby_id(), baby_id)?;
-    delete_dream(record)?;
+    assert_version_matches(&if_match, old_dream.version())?;
+    delete_dream(&old_dream).map_err(stale_record)?;
+    publish_deleted_event(baby_id, DataType::Dream, record).await;
     Ok(MsgResponse::DeleteRecord)
 }
 
-pub async fn get_dreams_all_service(
+pub async fn get_dreams_all_service<D>(
     baby_id: i32,
+    list: ListQuery,
     pagination: Pagination,
-) -> Result<PagedResponse<Vec<DreamDto>>, ApiError> {
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<Dream> + Serialize,
+{
+    let filter = DreamFilterDto::try_from(&list)?;
+    let fields = list.fields(DataType::Dream.get());
+    let scope = list.scope(None);
+    if let Some(cursor) = pagination.cursor(&scope)? {
+        let descending = cursor_descending(filter.sort())?;
+        let per_page = pagination.per_page();
+        let dreams =
+            select_dreams_by_cursor(baby_id, None, &filter, &cursor, descending, per_page)?;
+        return Ok(cursor_page(dreams, &cursor, per_page).with_fields(fields));
+    }
     let current = pagination.page();
-    let (dreams, total_pages) = select_all_dreams_from_baby(baby_id, pagination)?;
-    let dreams: Vec<DreamDto> = into_dreams_dto(dreams)?;
-    let response = PagedResponse::new(dreams, current, total_pages);
-    Ok(response)
+    let (dreams, total_pages) = select_all_dreams_from_baby(baby_id, &filter, pagination)?;
+    Ok(sorted_page(dreams, current, total_pages, filter.sort(), &scope).with_fields(fields))
 }
 
-pub async fn get_dream_id_service(
-    dream_id: i32,
+pub async fn get_dream_id_service<D>(
+    dream_id: Uuid,
     baby_id: i32,
-) -> Result<RecordResponse<DreamDto>, ApiError> {
+) -> Result<RecordResponse<D>, ApiError>
+where
+    D: From<Dream> + Serialize,
+{
     let dream: Dream = select_dream_by_id(dream_id)?;
     assert_record_belongs_to_parent(dream.baby_id(), baby_id)?;
-    let response: RecordResponse<DreamDto> = RecordResponse::new(dream.into());
+    let version = dream.version();
+    let response: RecordResponse<D> = RecordResponse::new(dream.into()).with_version(version);
     Ok(response)
 }
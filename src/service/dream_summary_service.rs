@@ -1,7 +1,8 @@
 use chrono::{Days, NaiveDate};
+use serde::Serialize;
 
 use crate::{
-    data::{dream_dto::DreamSummaryDto, query_dto::Pagination},
+    data::query_dto::Pagination,
     model::{dream_model::Dream, summary_model::DreamSummary},
     repository::dream_repository::{select_dreams_for_summary, select_date_first_and_last_dream},
     response::{
@@ -13,12 +14,15 @@ use crate::{
 
 use super::util_service::{paginate_over_dates, round_total_pages};
 
-pub async fn get_dreams_summary_range_service(
+pub async fn get_dreams_summary_range_service<D>(
     baby_id: i32,
     from_date: NaiveDate,
     to_date: NaiveDate,
     pagination: Pagination,
-) -> Result<PagedResponse<Vec<DreamSummaryDto>>, ApiError> {
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<DreamSummary> + Serialize,
+{
     let current = pagination.page();
     let total_pages = round_total_pages(from_date, to_date, pagination.per_page());
     let (start, stop) = paginate_over_dates(pagination, from_date, to_date);
@@ -28,7 +32,7 @@ pub async fn get_dreams_summary_range_service(
 }
 
 /// Need to add plus one day to look for certain date.
-async fn fetch_dream_summary_range(
+pub(crate) async fn fetch_dream_summary_range(
     baby_id: i32,
     from_date: NaiveDate,
     to_date: NaiveDate,
@@ -52,11 +56,14 @@ async fn fetch_dream_summary_range(
     Ok(summary_vec)
 }
 
-pub async fn get_dreams_summary_last_days_service(
+pub async fn get_dreams_summary_last_days_service<D>(
     baby_id: i32,
     last_days: u32,
     pagination: Pagination,
-) -> Result<PagedResponse<Vec<DreamSummaryDto>>, ApiError> {
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<DreamSummary> + Serialize,
+{
     let today = today();
     let from_date = today
         .checked_sub_days(Days::new(last_days.try_into().unwrap()))
@@ -64,14 +71,17 @@ pub async fn get_dreams_summary_last_days_service(
     get_dreams_summary_range_service(baby_id, from_date, today, pagination).await
 }
 
-fn into_summary_dto(summaries: Vec<DreamSummary>) -> Vec<DreamSummaryDto> {
+fn into_summary_dto<D: From<DreamSummary>>(summaries: Vec<DreamSummary>) -> Vec<D> {
     summaries.into_iter().map(|item| item.into()).collect()
 }
 
-pub async fn get_dreams_summary_all_service(
+pub async fn get_dreams_summary_all_service<D>(
     baby_id: i32,
     pagination: Pagination,
-) -> Result<PagedResponse<Vec<DreamSummaryDto>>, ApiError> {
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<DreamSummary> + Serialize,
+{
     let current = pagination.page();
     let (raw_start, raw_stop) = select_date_first_and_last_dream(baby_id)?;
     let total_pages = round_total_pages(raw_start, raw_stop, pagination.per_page());
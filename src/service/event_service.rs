@@ -0,0 +1,96 @@
+use std::{convert::Infallible, time::Duration};
+
+use axum::response::sse::{Event, KeepAlive, Sse};
+use futures_util::{Stream, StreamExt};
+use serde::Serialize;
+use tokio::{task::spawn_blocking, time::interval};
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    configuration::constant::GlobalCte,
+    data::{
+        common_structure::{BasicDataStruct, DataType},
+        event_dto::EventData,
+    },
+    model::event_model::EventAction,
+    repository::{
+        association_repository::select_user_has_baby,
+        event_repository::{publish_event, subscribe_to_channel},
+    },
+    response::error::ApiError,
+};
+
+use super::webhook_service::enqueue_webhook_deliveries;
+
+/// Send a record change to every caregiver listening, on any replica, and queue it for webhooks.
+///
+/// Records are already saved, a failed notification is only logged.
+pub async fn publish_record_event<T>(baby_id: i32, action: EventAction, record: &T)
+where
+    T: Serialize,
+{
+    let event = EventData {
+        event: action.get(),
+        data: record,
+    };
+    let payload = match serde_json::to_string(&event) {
+        Ok(payload) => payload,
+        Err(err) => {
+            error!("Can't serialize event: {err}");
+            return;
+        }
+    };
+    if let Err(err) = publish_event(&baby_channel(baby_id), &payload).await {
+        error!("Can't publish event: {err}");
+    }
+    if let Err(err) = enqueue_webhook_deliveries(baby_id, action.get(), &payload) {
+        error!("Can't queue webhook deliveries: {err:?}");
+    }
+}
+
+/// Deleted records are sent without attributes.
+pub async fn publish_deleted_event(baby_id: i32, data_type: DataType, record: Uuid) {
+    let deleted = BasicDataStruct::new(record, data_type, ());
+    publish_record_event(baby_id, EventAction::Deleted, &deleted).await
+}
+
+/// Stream ends once the user no longer has access to the baby.
+pub async fn get_baby_events_service(
+    baby_id: i32,
+    user_id: i32,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let pubsub = subscribe_to_channel(&baby_channel(baby_id)).await?;
+    let stream = pubsub
+        .into_on_message()
+        .filter_map(|message| async move {
+            match message.get_payload::<String>() {
+                Ok(payload) => Some(Ok(Event::default().data(payload))),
+                Err(_) => None,
+            }
+        })
+        .take_until(access_revoked(user_id, baby_id));
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+/// Completes when the baby is no longer shared with the user, checked every
+/// `limits.events_access_check_seconds`. Database errors keep the stream open.
+async fn access_revoked(user_id: i32, baby_id: i32) {
+    let period = Duration::from_secs(GlobalCte::EventsAccessCheckSeconds.get().into());
+    let mut checks = interval(period);
+    // First tick is immediate, access was just checked.
+    checks.tick().await;
+    loop {
+        checks.tick().await;
+        match spawn_blocking(move || select_user_has_baby(user_id, baby_id)).await {
+            Ok(Ok(true)) => (),
+            Ok(Ok(false)) => return,
+            Ok(Err(err)) => error!("Events: {err}"),
+            Err(err) => error!("Events: {err}"),
+        }
+    }
+}
+
+fn baby_channel(baby_id: i32) -> String {
+    format!("baby:{baby_id}:events")
+}
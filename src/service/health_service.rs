@@ -0,0 +1,74 @@
+use std::{
+    fmt::Display,
+    future::Future,
+    time::{Duration, Instant},
+};
+
+use hyper::StatusCode;
+use tokio::{task::spawn_blocking, time::timeout};
+use tracing::error;
+
+use crate::{
+    configuration::constant::GlobalCte,
+    data::health_dto::{DependencyDto, HealthDto, HealthStatus, ReadinessDto},
+    repository::health_repository::{ping_database, ping_redis},
+    response::response::RecordResponse,
+};
+
+/// The process is up and serving requests, dependencies aren't checked.
+pub async fn get_liveness_service() -> RecordResponse<HealthDto> {
+    RecordResponse::new(HealthDto {
+        status: HealthStatus::Up,
+    })
+}
+
+/// Both dependencies are checked at once, any of them down answers 503.
+pub async fn get_readiness_service() -> RecordResponse<ReadinessDto> {
+    let limit = Duration::from_millis(GlobalCte::HealthCheckTimeoutMillis.get().into());
+    let (postgres, redis) = tokio::join!(
+        check_dependency(
+            "PostgreSQL",
+            async move { spawn_blocking(move || ping_database(limit)).await? },
+            limit,
+        ),
+        check_dependency("Redis", ping_redis(), limit),
+    );
+    let status = if postgres.status.eq(&HealthStatus::Up) && redis.status.eq(&HealthStatus::Up) {
+        HealthStatus::Up
+    } else {
+        HealthStatus::Down
+    };
+    let mut response = RecordResponse::new(ReadinessDto {
+        status,
+        postgres,
+        redis,
+    });
+    if status.eq(&HealthStatus::Down) {
+        response.status_code = StatusCode::SERVICE_UNAVAILABLE;
+    }
+    response
+}
+
+/// Errors are only logged, they may carry hosts or credentials.
+async fn check_dependency<F, E>(name: &str, ping: F, limit: Duration) -> DependencyDto
+where
+    F: Future<Output = Result<(), E>>,
+    E: Display,
+{
+    let start = Instant::now();
+    let result = timeout(limit, ping).await;
+    let latency_ms = start.elapsed().as_millis();
+    let (status, error) = match result {
+        Ok(Ok(())) => (HealthStatus::Up, None),
+        Ok(Err(error)) => {
+            error!("{name} readiness check failed: {error}");
+            (HealthStatus::Down, Some(String::from("Unavailable.")))
+        }
+        Err(_) => (HealthStatus::Down, Some(String::from("Timed out."))),
+    };
+    DependencyDto {
+        status,
+        latency_ms,
+        error,
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{
+    configuration::constant::GlobalCte,
+    model::idempotency_model::IdempotentResponse,
+    repository::idempotency_repository::{
+        delete_idempotency_key, insert_idempotency_key, select_idempotency_key,
+        update_idempotency_key,
+    },
+    response::error::ApiError,
+};
+
+/// Lock the key for a new request, or get the response to replay.
+///
+/// Keys are scoped by user so two accounts can't read each other's responses, anonymous
+/// requests are never stored.
+pub async fn begin_idempotent_request(
+    user_id: i64,
+    key: &str,
+    fingerprint: &str,
+) -> Result<Option<IdempotentResponse>, ApiError> {
+    let redis_key = idempotency_key(user_id, key);
+    let pending = to_json(&IdempotentResponse::pending(fingerprint))?;
+    let lock_seconds = GlobalCte::IdempotencyLockSeconds.get().try_into()?;
+    if insert_idempotency_key(&redis_key, &pending, lock_seconds).await? {
+        return Ok(None);
+    }
+    let stored: IdempotentResponse = match select_idempotency_key(&redis_key).await? {
+        Some(value) => serde_json::from_str(&value)
+            .map_err(|err| ApiError::Generic500Error(err.to_string()))?,
+        // Expired between both calls, the lock is not retried.
+        None => return Err(ApiError::IdempotencyKeyInUse),
+    };
+    if !stored.matches(fingerprint) {
+        return Err(ApiError::IdempotencyKeyReused);
+    }
+    if stored.is_pending() {
+        return Err(ApiError::IdempotencyKeyInUse);
+    }
+    Ok(Some(stored))
+}
+
+/// Keep the response for replays, server errors free the key so the client can retry.
+pub async fn finish_idempotent_request(
+    user_id: i64,
+    key: &str,
+    fingerprint: &str,
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: &str,
+    error_detail: Option<String>,
+) -> Result<(), ApiError> {
+    let redis_key = idempotency_key(user_id, key);
+    if status.ge(&500) {
+        return Ok(delete_idempotency_key(&redis_key).await?);
+    }
+    let completed = to_json(&IdempotentResponse::completed(
+        fingerprint,
+        status,
+        headers,
+        body,
+        error_detail,
+    ))?;
+    let key_seconds = GlobalCte::IdempotencyKeySeconds.get().try_into()?;
+    Ok(update_idempotency_key(&redis_key, &completed, key_seconds).await?)
+}
+
+fn idempotency_key(user_id: i64, key: &str) -> String {
+    format!("idempotency:{user_id}:{key}")
+}
+
+fn to_json(response: &IdempotentResponse) -> Result<String, ApiError> {
+    serde_json::to_string(response).map_err(|err| ApiError::Generic500Error(err.to_string()))
+}
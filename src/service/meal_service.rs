@@ -1,103 +1,281 @@
-use chrono::{Days, NaiveDate};
+use chrono::{Days, NaiveDate, NaiveDateTime};
+use serde::Serialize;
+use uuid::Uuid;
 
 use crate::{
-    data::{common_structure::MealDto, meal_dto::InputMealDto, query_dto::Pagination},
-    model::meals_model::{InsertableMeal, Meal},
-    repository::meal_repository::{
-        delete_meal, insert_new_meal, select_all_meals_from_baby, select_meal_by_id,
-        select_meals_with_pagination, update_meal,
+    data::{
+        common_structure::{DataType, MealDto},
+        meal_dto::{InputMealDto, MealFilterDto},
+        query_dto::{ListQuery, Pagination},
+    },
+    model::{
+        event_model::EventAction,
+        meals_model::{InsertableMeal, Meal},
+    },
+    repository::{
+        meal_repository::{
+            delete_meal, delete_meal_restoring_bag, insert_new_meal, insert_new_meal_from_bag,
+            select_all_meals_from_baby, select_meal_by_client_id, select_meal_by_id,
+            select_meals_by_cursor, select_meals_with_pagination, update_meal,
+            update_meal_from_bag,
+        },
+        milk_bag_repository::select_milk_bag_by_id,
     },
     response::{
-        error::ApiError,
+        error::{ApiError, FieldError},
         response::{MsgResponse, PagedResponse, RecordResponse},
     },
-    utils::datetime::{now, today},
+    utils::{
+        datetime::{now, today},
+        etag::IfMatch,
+        metrics::record_created,
+    },
+};
+
+use super::{
+    event_service::{publish_deleted_event, publish_record_event},
+    util_service::{
+        assert_record_belongs_to_parent, assert_version_matches, cast_to_date_from,
+        cursor_descending, cursor_page, sorted_page, stale_record,
+    },
 };
 
-use super::util_service::{assert_record_belongs_to_parent, cast_to_date_from};
+/// `D` is the meal of the API version, events are always sent as [MealDto].
+pub async fn post_meal_service<D>(
+    new_meal: InputMealDto,
+    baby_id: i32,
+) -> Result<RecordResponse<D>, ApiError>
+where
+    D: From<Meal> + Serialize,
+{
+    if let Some(client_id) = new_meal.client_id {
+        if let Some(meal) = select_meal_by_client_id(baby_id, client_id)? {
+            return Ok(RecordResponse::new(meal.into()));
+        }
+    }
+    let meal = create_meal(new_meal, baby_id, now())?;
+    let event: MealDto = meal.clone().into();
+    publish_record_event(baby_id, EventAction::Created, &event).await;
+    let response: RecordResponse<D> = RecordResponse::new_entry(meal.into());
+    Ok(response)
+}
 
-pub async fn post_meal_service(
+/// `edited_at` is when the meal was recorded, on the client for offline changes.
+pub(super) fn create_meal(
     new_meal: InputMealDto,
     baby_id: i32,
-) -> Result<RecordResponse<MealDto>, ApiError> {
-    let timestamp = cast_to_date_from(new_meal.date)?;
-    let timestamp_to_time = cast_to_date_from(new_meal.to_time)?;
+    edited_at: NaiveDateTime,
+) -> Result<Meal, ApiError> {
+    let timestamp = cast_to_date_from(new_meal.date, "date")?;
+    let timestamp_to_time = cast_to_date_from(new_meal.to_time, "to_time")?;
+    let milk_bag_id = match new_meal.milk_bag_id {
+        Some(bag) => Some(check_milk_bag(bag, baby_id, new_meal.quantity)?),
+        None => None,
+    };
     let meal = InsertableMeal::new(
         baby_id,
         timestamp.unwrap_or(now()),
         new_meal.quantity,
         timestamp_to_time,
-    );
-    let insert_data: Meal = insert_new_meal(meal)?;
-    let response: RecordResponse<MealDto> = RecordResponse::new_entry(insert_data.into());
-    Ok(response)
+        milk_bag_id,
+        new_meal.client_id,
+    )
+    .with_edited_at(edited_at);
+    let insert_data: Meal = match milk_bag_id {
+        Some(_) => insert_new_meal_from_bag(meal)?,
+        None => insert_new_meal(meal)?,
+    };
+    record_created("meals");
+    Ok(insert_data)
 }
 
-pub async fn patch_meal_service(
+/// Stale `If-Match` or a concurrent update fail with 412, a required `If-Match` missing with 428.
+pub async fn patch_meal_service<D>(
     meal: InputMealDto,
-    record: i32,
+    record: Uuid,
     baby_id: i32,
-) -> Result<RecordResponse<MealDto>, ApiError> {
+    if_match: IfMatch,
+) -> Result<RecordResponse<D>, ApiError>
+where
+    D: From<Meal> + Serialize,
+{
     let meal_record = select_meal_by_id(record)?;
     assert_record_belongs_to_parent(meal_record.baby_id(), baby_id)?;
-    let new_meal: Meal = update_meal(meal_record.update_meal(meal))?;
-    let response: RecordResponse<MealDto> = RecordResponse::new(new_meal.into());
+    assert_version_matches(&if_match, meal_record.version())?;
+    let updated = save_meal_changes(&meal_record, meal_record.update_meal(meal))?;
+    let version = updated.version();
+    let event: MealDto = updated.clone().into();
+    publish_record_event(baby_id, EventAction::Updated, &event).await;
+    let response: RecordResponse<D> = RecordResponse::new(updated.into()).with_version(version);
     Ok(response)
 }
 
-pub async fn get_meals_by_range_service(
+/// Bottle feeds take the quantity difference from their bag or give it back, a meal changed
+/// since it was read fails with 412.
+pub(super) fn save_meal_changes(meal: &Meal, changes: Meal) -> Result<Meal, ApiError> {
+    let updated = match meal.milk_bag_id() {
+        Some(_) => {
+            check_milk_bag_change(meal, &changes)?;
+            update_meal_from_bag(changes, meal.formatted_quantity())
+        }
+        None => update_meal(changes),
+    };
+    updated.map_err(stale_record)
+}
+
+pub async fn get_meals_by_range_service<D>(
     baby_id: i32,
     from_date: NaiveDate,
     to_date: NaiveDate,
+    list: ListQuery,
     pagination: Pagination,
-) -> Result<PagedResponse<Vec<MealDto>>, ApiError> {
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<Meal> + Serialize,
+{
+    let filter = MealFilterDto::try_from(&list)?;
+    let fields = list.fields(DataType::Meal.get());
+    let range = Some((from_date, to_date));
+    let scope = list.scope(range);
+    if let Some(cursor) = pagination.cursor(&scope)? {
+        let descending = cursor_descending(filter.sort())?;
+        let per_page = pagination.per_page();
+        let meals = select_meals_by_cursor(baby_id, range, &filter, &cursor, descending, per_page)?;
+        return Ok(cursor_page(meals, &cursor, per_page).with_fields(fields));
+    }
     let current = pagination.page();
     let (meals, total_pages) =
-        select_meals_with_pagination(baby_id, from_date, to_date, pagination)?;
-    let dreams: Vec<MealDto> = into_meals_dto(meals)?;
-    let response = PagedResponse::new(dreams, current, total_pages);
-    Ok(response)
+        select_meals_with_pagination(baby_id, from_date, to_date, &filter, pagination)?;
+    Ok(sorted_page(meals, current, total_pages, filter.sort(), &scope).with_fields(fields))
 }
 
-pub async fn get_meals_by_last_days_service(
+pub async fn get_meals_by_last_days_service<D>(
     baby_id: i32,
     last_days: u32,
+    list: ListQuery,
     pagination: Pagination,
-) -> Result<PagedResponse<Vec<MealDto>>, ApiError> {
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<Meal> + Serialize,
+{
     let from_date = today()
         .checked_sub_days(Days::new(last_days.into()))
         .unwrap();
-    get_meals_by_range_service(baby_id, from_date, today(), pagination).await
-}
-
-fn into_meals_dto(meals: Vec<Meal>) -> Result<Vec<MealDto>, ApiError> {
-    Ok(meals.into_iter().map(|dream| dream.into()).collect())
+    get_meals_by_range_service(baby_id, from_date, today(), list, pagination).await
 }
 
-pub async fn delete_meal_service(record: i32, baby_id: i32) -> Result<MsgResponse, ApiError> {
+pub async fn delete_meal_service(
+    record: Uuid,
+    baby_id: i32,
+    if_match: IfMatch,
+) -> Result<MsgResponse, ApiError> {
     let meal_to_delete = select_meal_by_id(record)?;
     assert_record_belongs_to_parent(meal_to_delete.baby_id(), baby_id)?;
-    delete_meal(record)?;
+    assert_version_matches(&if_match, meal_to_delete.version())?;
+    remove_meal(&meal_to_delete)?;
+    publish_deleted_event(baby_id, DataType::Meal, record).await;
     Ok(MsgResponse::DeleteRecord)
 }
 
-pub async fn get_meals_all_service(
+/// Bottle feeds give their milk back to the bag, a meal changed since it was read fails with 412.
+pub(super) fn remove_meal(meal: &Meal) -> Result<usize, ApiError> {
+    let deleted = match meal.milk_bag_id() {
+        Some(_) => delete_meal_restoring_bag(meal),
+        None => delete_meal(meal),
+    };
+    deleted.map_err(stale_record)
+}
+
+pub async fn get_meals_all_service<D>(
     baby_id: i32,
+    list: ListQuery,
     pagination: Pagination,
-) -> Result<PagedResponse<Vec<MealDto>>, ApiError> {
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<Meal> + Serialize,
+{
+    let filter = MealFilterDto::try_from(&list)?;
+    let fields = list.fields(DataType::Meal.get());
+    let scope = list.scope(None);
+    if let Some(cursor) = pagination.cursor(&scope)? {
+        let descending = cursor_descending(filter.sort())?;
+        let per_page = pagination.per_page();
+        let meals = select_meals_by_cursor(baby_id, None, &filter, &cursor, descending, per_page)?;
+        return Ok(cursor_page(meals, &cursor, per_page).with_fields(fields));
+    }
     let current = pagination.page();
-    let (meals, total_pages) = select_all_meals_from_baby(baby_id, pagination)?;
-    let meals = into_meals_dto(meals)?;
-    let response = PagedResponse::new(meals, current, total_pages);
-    Ok(response)
+    let (meals, total_pages) = select_all_meals_from_baby(baby_id, &filter, pagination)?;
+    Ok(sorted_page(meals, current, total_pages, filter.sort(), &scope).with_fields(fields))
 }
 
-pub async fn get_meal_id_service(
-    meal_id: i32,
+pub async fn get_meal_id_service<D>(
+    meal_id: Uuid,
     baby_id: i32,
-) -> Result<RecordResponse<MealDto>, ApiError> {
+) -> Result<RecordResponse<D>, ApiError>
+where
+    D: From<Meal> + Serialize,
+{
     let meal = select_meal_by_id(meal_id)?;
     assert_record_belongs_to_parent(meal.baby_id(), baby_id)?;
-    let response: RecordResponse<MealDto> = RecordResponse::new(meal.into());
+    let version = meal.version();
+    let response: RecordResponse<D> = RecordResponse::new(meal.into()).with_version(version);
     Ok(response)
 }
+
+/// Bag must belong to the baby, not be expired and hold enough milk for the feed.
+///
+/// Returns the id meals reference the bag by.
+fn check_milk_bag(bag: Uuid, baby_id: i32, quantity: Option<i16>) -> Result<i32, ApiError> {
+    let milk_bag = select_milk_bag_by_id(bag)?;
+    assert_record_belongs_to_parent(milk_bag.baby_id(), baby_id)?;
+    let quantity = match quantity {
+        Some(value) if value.gt(&0) => value,
+        _ => {
+            return Err(FieldError::pointer(
+                "quantity",
+                "required",
+                "Quantity is required to take milk from a bag.",
+            )
+            .into())
+        }
+    };
+    if milk_bag.expired(now()) {
+        return Err(FieldError::pointer("milk_bag_id", "expired", "Milk bag is expired.").into());
+    }
+    if milk_bag.remaining().lt(&quantity) {
+        return Err(FieldError::pointer(
+            "quantity",
+            "out_of_range",
+            format!("Not enough milk in bag, {} ml left.", milk_bag.remaining()),
+        )
+        .into());
+    }
+    Ok(milk_bag.id())
+}
+
+/// A bottle feed keeps a quantity, the bag must hold any extra milk taken.
+fn check_milk_bag_change(meal: &Meal, changes: &Meal) -> Result<(), ApiError> {
+    let quantity = changes.formatted_quantity();
+    if quantity.le(&0) {
+        return Err(FieldError::pointer(
+            "quantity",
+            "required",
+            "Quantity is required to take milk from a bag.",
+        )
+        .into());
+    }
+    let taken = quantity - meal.formatted_quantity();
+    if taken.le(&0) {
+        return Ok(());
+    }
+    let milk_bag = select_milk_bag_by_id(meal.milk_bag_public_id().unwrap_or_default())?;
+    if milk_bag.remaining().lt(&taken) {
+        return Err(FieldError::pointer(
+            "quantity",
+            "out_of_range",
+            format!("Not enough milk in bag, {} ml left.", milk_bag.remaining()),
+        )
+        .into());
+    }
+    Ok(())
+}
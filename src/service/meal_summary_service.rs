@@ -1,7 +1,8 @@
 use chrono::{Days, NaiveDate};
+use serde::Serialize;
 
 use crate::{
-    data::{meal_dto::MealSummaryDto, query_dto::Pagination},
+    data::query_dto::Pagination,
     model::{meals_model::Meal, summary_model::MealSummary},
     repository::meal_repository::{select_meals_by_date_range, select_date_first_and_last_meal},
     response::{error::ApiError, response::PagedResponse},
@@ -10,12 +11,15 @@ use crate::{
 
 use super::util_service::{paginate_over_dates, round_total_pages};
 
-pub async fn get_meals_summary_range_service(
+pub async fn get_meals_summary_range_service<D>(
     baby_id: i32,
     from_date: NaiveDate,
     to_date: NaiveDate,
     pagination: Pagination,
-) -> Result<PagedResponse<Vec<MealSummaryDto>>, ApiError> {
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<MealSummary> + Serialize,
+{
     let current = pagination.page();
     let total_pages = round_total_pages(from_date, to_date, pagination.per_page());
     let (start, stop) = paginate_over_dates(pagination, from_date, to_date);
@@ -25,7 +29,7 @@ pub async fn get_meals_summary_range_service(
 }
 
 /// Need to add plus one day to look for certain date.
-async fn fetch_meal_summary_range(
+pub(crate) async fn fetch_meal_summary_range(
     baby_id: i32,
     from_date: NaiveDate,
     to_date: NaiveDate,
@@ -48,11 +52,14 @@ async fn fetch_meal_summary_range(
     Ok(summary_vec)
 }
 
-pub async fn get_meals_summary_last_days_service(
+pub async fn get_meals_summary_last_days_service<D>(
     baby_id: i32,
     last_days: u32,
     pagination: Pagination,
-) -> Result<PagedResponse<Vec<MealSummaryDto>>, ApiError> {
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<MealSummary> + Serialize,
+{
     let today = today();
     let from_date = today
         .checked_sub_days(Days::new(last_days.try_into().unwrap()))
@@ -60,14 +67,17 @@ pub async fn get_meals_summary_last_days_service(
     get_meals_summary_range_service(baby_id, from_date, today, pagination).await
 }
 
-fn into_summary_dto(summaries: Vec<MealSummary>) -> Vec<MealSummaryDto> {
+fn into_summary_dto<D: From<MealSummary>>(summaries: Vec<MealSummary>) -> Vec<D> {
     summaries.into_iter().map(|item| item.into()).collect()
 }
 
-pub async fn get_meals_summary_all_service(
+pub async fn get_meals_summary_all_service<D>(
     baby_id: i32,
     pagination: Pagination,
-) -> Result<PagedResponse<Vec<MealSummaryDto>>, ApiError> {
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<MealSummary> + Serialize,
+{
     let current = pagination.page();
     let (raw_start, raw_stop) = select_date_first_and_last_meal(baby_id)?;
     let total_pages = round_total_pages(raw_start, raw_stop, pagination.per_page());
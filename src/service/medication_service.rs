@@ -0,0 +1,228 @@
+use chrono::{Days, NaiveDate};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    data::{
+        common_structure::MedicationDto,
+        medication_dto::{InputDoseDto, InputMedicationDto},
+        query_dto::Pagination,
+    },
+    model::medication_model::{
+        InsertableMedication, InsertableMedicationDose, Medication, MedicationDose,
+        MedicationStatus,
+    },
+    repository::medication_repository::{
+        delete_dose, delete_medication, insert_new_dose, insert_new_medication, select_dose_by_id,
+        select_doses_with_pagination, select_medication_by_id, select_medications_from_baby,
+        select_medications_with_last_dose, update_medication,
+    },
+    response::{
+        error::{ApiError, FieldError},
+        response::{MsgResponse, PagedResponse, RecordResponse},
+    },
+    utils::datetime::{convert_to_time, format_date_time, now, today},
+};
+
+use super::util_service::{assert_record_belongs_to_parent, cast_to_date_from};
+
+/// Every invalid field is reported at once.
+pub async fn post_medication_service(
+    new_medication: InputMedicationDto,
+    baby_id: i32,
+) -> Result<RecordResponse<MedicationDto>, ApiError> {
+    let mut errors = Vec::new();
+    let name = new_medication
+        .name
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty());
+    if name.is_none() {
+        errors.push(FieldError::pointer(
+            "name",
+            "required",
+            "Medication name is required.",
+        ));
+    }
+    let dose = new_medication.dose.filter(|dose| dose.gt(&0.0));
+    if dose.is_none() {
+        errors.push(FieldError::pointer(
+            "dose",
+            "out_of_range",
+            "Dose must be higher than 0.",
+        ));
+    }
+    let daily_time = match new_medication.daily_time.as_deref().map(convert_to_time) {
+        Some(Ok(time)) => Some(time),
+        Some(Err(_)) => {
+            errors.push(FieldError::pointer(
+                "daily_time",
+                "invalid_format",
+                "Daily time must be HH:MM.",
+            ));
+            None
+        }
+        None => None,
+    };
+    let (name, dose) = match (name, dose) {
+        (Some(name), Some(dose)) if errors.is_empty() => (name, dose),
+        _ => return Err(ApiError::Invalid(errors)),
+    };
+    let medication = InsertableMedication::new(
+        baby_id,
+        name,
+        dose,
+        new_medication.unit.unwrap_or_default().trim().to_string(),
+        new_medication.interval_minutes,
+        daily_time,
+        now(),
+    );
+    if !medication.has_schedule() {
+        return Err(missing_schedule());
+    }
+    let insert_data: Medication = insert_new_medication(medication)?;
+    Ok(RecordResponse::new_entry(insert_data.into()))
+}
+
+pub async fn patch_medication_service(
+    medication: InputMedicationDto,
+    record: Uuid,
+    baby_id: i32,
+) -> Result<RecordResponse<MedicationDto>, ApiError> {
+    let medication_record = find_medication(record, baby_id)?;
+    let updated = medication_record.update_medication(medication);
+    if !updated.has_schedule() {
+        return Err(missing_schedule());
+    }
+    let new_medication: Medication = update_medication(updated)?;
+    Ok(RecordResponse::new(new_medication.into()))
+}
+
+pub async fn get_medications_service(
+    baby_id: i32,
+    pagination: Pagination,
+) -> Result<PagedResponse<Vec<MedicationDto>>, ApiError> {
+    let current = pagination.page();
+    let (medications, total_pages) = select_medications_from_baby(baby_id, pagination)?;
+    let medications: Vec<MedicationDto> = medications
+        .into_iter()
+        .map(|medication| medication.into())
+        .collect();
+    Ok(PagedResponse::new(medications, current, total_pages))
+}
+
+pub async fn get_medication_id_service(
+    record: Uuid,
+    baby_id: i32,
+) -> Result<RecordResponse<MedicationDto>, ApiError> {
+    let medication = find_medication(record, baby_id)?;
+    Ok(RecordResponse::new(medication.into()))
+}
+
+pub async fn delete_medication_service(
+    record: Uuid,
+    baby_id: i32,
+) -> Result<MsgResponse, ApiError> {
+    let medication = find_medication(record, baby_id)?;
+    delete_medication(medication.id())?;
+    Ok(MsgResponse::DeleteRecord)
+}
+
+/// Next dose for every medication, with due and overdue flags.
+pub async fn get_medications_due_service<S>(
+    baby_id: i32,
+) -> Result<RecordResponse<Vec<S>>, ApiError>
+where
+    S: From<MedicationStatus> + Serialize,
+{
+    let current = now();
+    let status: Vec<S> = select_medications_with_last_dose(baby_id, current)?
+        .into_iter()
+        .map(|(medication, last_dose)| medication.status(last_dose, current).into())
+        .collect();
+    Ok(RecordResponse::new(status))
+}
+
+/// Log a dose, refused if it breaks the minimum interval with doses around it.
+pub async fn post_dose_service<D>(
+    new_dose: InputDoseDto,
+    record: Uuid,
+    baby_id: i32,
+) -> Result<RecordResponse<D>, ApiError>
+where
+    D: From<MedicationDose> + Serialize,
+{
+    let medication = find_medication(record, baby_id)?;
+    let date = cast_to_date_from(new_dose.date, "date")?.unwrap_or(now());
+    let dose = InsertableMedicationDose::new(
+        medication.id(),
+        date,
+        new_dose
+            .dose
+            .filter(|dose| dose.gt(&0.0))
+            .unwrap_or(medication.dose()),
+    );
+    match insert_new_dose(dose)? {
+        Ok(insert_data) => Ok(RecordResponse::new_entry(insert_data.into())),
+        Err(next_dose) => Err(ApiError::DoseNotAllowed(format_date_time(next_dose))),
+    }
+}
+
+pub async fn get_doses_by_range_service<D>(
+    record: Uuid,
+    baby_id: i32,
+    from_date: NaiveDate,
+    to_date: NaiveDate,
+    pagination: Pagination,
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<MedicationDose> + Serialize,
+{
+    let medication = find_medication(record, baby_id)?;
+    let current = pagination.page();
+    let (doses, total_pages) =
+        select_doses_with_pagination(medication.id(), from_date, to_date, pagination)?;
+    let doses: Vec<D> = doses.into_iter().map(|dose| dose.into()).collect();
+    Ok(PagedResponse::new(doses, current, total_pages))
+}
+
+pub async fn get_doses_by_last_days_service<D>(
+    record: Uuid,
+    baby_id: i32,
+    last_days: u32,
+    pagination: Pagination,
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<MedicationDose> + Serialize,
+{
+    let from_date = today()
+        .checked_sub_days(Days::new(last_days.into()))
+        .unwrap();
+    get_doses_by_range_service(record, baby_id, from_date, today(), pagination).await
+}
+
+pub async fn delete_dose_service(
+    record: Uuid,
+    dose: Uuid,
+    baby_id: i32,
+) -> Result<MsgResponse, ApiError> {
+    let medication = find_medication(record, baby_id)?;
+    let dose_to_delete = select_dose_by_id(dose)?;
+    assert_record_belongs_to_parent(dose_to_delete.medication_id(), medication.id())?;
+    delete_dose(dose_to_delete.id())?;
+    Ok(MsgResponse::DeleteRecord)
+}
+
+fn find_medication(record: Uuid, baby_id: i32) -> Result<Medication, ApiError> {
+    let medication = select_medication_by_id(record)?;
+    assert_record_belongs_to_parent(medication.baby_id(), baby_id)?;
+    Ok(medication)
+}
+
+fn missing_schedule() -> ApiError {
+    FieldError::pointer(
+        "schedule",
+        "required",
+        "Medication needs an interval or a daily time.",
+    )
+    .into()
+}
@@ -0,0 +1,40 @@
+use std::{
+    sync::{Mutex, PoisonError},
+    time::{Duration, Instant},
+};
+
+use metrics::gauge;
+use tokio::task::spawn_blocking;
+use tracing::error;
+
+use crate::{repository::admin_repository::count_active_timers, utils::metrics::render_metrics};
+
+/// Open timers are counted again once this old, scrapes in between reuse the gauge.
+const ACTIVE_TIMERS_TTL: Duration = Duration::from_secs(30);
+
+static ACTIVE_TIMERS_READ_AT: Mutex<Option<Instant>> = Mutex::new(None);
+
+/// Created records are counters kept by each replica, only open timers need the database.
+/// A failing database still returns the other metrics.
+pub async fn get_metrics_service() -> String {
+    if active_timers_due() {
+        match spawn_blocking(count_active_timers).await {
+            Ok(Ok(timers)) => gauge!("nighty_night_active_timers", timers as f64),
+            Ok(Err(err)) => error!("Metrics: {err}"),
+            Err(err) => error!("Metrics: {err}"),
+        }
+    }
+    render_metrics()
+}
+
+/// Marks the gauge as read when it's due, so concurrent scrapes count it only once.
+fn active_timers_due() -> bool {
+    let mut read_at = ACTIVE_TIMERS_READ_AT
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    let due = read_at.map_or(true, |at| at.elapsed().ge(&ACTIVE_TIMERS_TTL));
+    if due {
+        *read_at = Some(Instant::now());
+    }
+    due
+}
@@ -0,0 +1,112 @@
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    data::{common_structure::MilkStashDto, pumping_dto::InputMilkBagDto, query_dto::Pagination},
+    model::pumping_model::{InsertableMilkBag, MilkBag, MilkStash, StorageLocation},
+    repository::{
+        baby_repository::select_baby_by_id,
+        milk_bag_repository::{
+            delete_milk_bag, insert_new_milk_bag, select_milk_bag_by_id,
+            select_milk_bags_from_baby, select_stocked_milk_bags, update_milk_bag,
+        },
+    },
+    response::{
+        error::{ApiError, FieldError},
+        response::{MsgResponse, PagedResponse, RecordResponse},
+    },
+    utils::datetime::now,
+};
+
+use super::{
+    pumping_service::invalid_location,
+    util_service::{assert_record_belongs_to_parent, cast_to_date_from},
+};
+
+pub async fn post_milk_bag_service<B>(
+    new_bag: InputMilkBagDto,
+    baby_id: i32,
+) -> Result<RecordResponse<B>, ApiError>
+where
+    B: From<MilkBag> + Serialize,
+{
+    let volume = new_bag
+        .volume
+        .filter(|volume| volume.gt(&0))
+        .ok_or(FieldError::pointer(
+            "volume",
+            "out_of_range",
+            "Volume must be higher than 0.",
+        ))?;
+    let location = match new_bag.location {
+        Some(location) => StorageLocation::parse(&location).ok_or(invalid_location("location"))?,
+        None => StorageLocation::Fridge,
+    };
+    let pumped_at = cast_to_date_from(new_bag.pumped_at, "pumped_at")?.unwrap_or(now());
+    let expires_at = cast_to_date_from(new_bag.expires_at, "expires_at")?;
+    let bag = InsertableMilkBag::new(baby_id, None, volume, pumped_at, location, expires_at);
+    let insert_data: MilkBag = insert_new_milk_bag(bag)?;
+    Ok(RecordResponse::new_entry(insert_data.into()))
+}
+
+pub async fn patch_milk_bag_service<B>(
+    bag: InputMilkBagDto,
+    record: Uuid,
+    baby_id: i32,
+) -> Result<RecordResponse<B>, ApiError>
+where
+    B: From<MilkBag> + Serialize,
+{
+    let bag_record = find_milk_bag(record, baby_id)?;
+    let new_bag = update_milk_bag(bag_record.update_bag(bag, now()))?;
+    Ok(RecordResponse::new(new_bag.into()))
+}
+
+pub async fn get_milk_bags_service<B>(
+    baby_id: i32,
+    available: bool,
+    pagination: Pagination,
+) -> Result<PagedResponse<Vec<B>>, ApiError>
+where
+    B: From<MilkBag> + Serialize,
+{
+    let current = pagination.page();
+    let available_at = if available { Some(now()) } else { None };
+    let (bags, total_pages) = select_milk_bags_from_baby(baby_id, available_at, pagination)?;
+    let bags: Vec<B> = bags.into_iter().map(|bag| bag.into()).collect();
+    Ok(PagedResponse::new(bags, current, total_pages))
+}
+
+pub async fn get_milk_bag_id_service<B>(
+    record: Uuid,
+    baby_id: i32,
+) -> Result<RecordResponse<B>, ApiError>
+where
+    B: From<MilkBag> + Serialize,
+{
+    let bag = find_milk_bag(record, baby_id)?;
+    Ok(RecordResponse::new(bag.into()))
+}
+
+/// Meals taken from this bag are kept, they lose the link to it.
+pub async fn delete_milk_bag_service(record: Uuid, baby_id: i32) -> Result<MsgResponse, ApiError> {
+    let bag = find_milk_bag(record, baby_id)?;
+    delete_milk_bag(bag.id())?;
+    Ok(MsgResponse::DeleteRecord)
+}
+
+/// Milk left by location, expiring and expired volumes are reported apart.
+pub async fn get_milk_stash_service(
+    baby_id: i32,
+) -> Result<RecordResponse<MilkStashDto>, ApiError> {
+    let baby = select_baby_by_id(baby_id)?;
+    let bags = select_stocked_milk_bags(baby_id)?;
+    let stash = MilkStash::new(baby.unique_id(), bags, now());
+    Ok(RecordResponse::new(stash.into()))
+}
+
+fn find_milk_bag(record: Uuid, baby_id: i32) -> Result<MilkBag, ApiError> {
+    let bag = select_milk_bag_by_id(record)?;
+    assert_record_belongs_to_parent(bag.baby_id(), baby_id)?;
+    Ok(bag)
+}
@@ -1,11 +1,27 @@
 pub mod admin_service;
 pub mod baby_service;
+pub mod booklet_service;
+pub mod calendar_service;
+pub mod dashboard_service;
 pub mod dream_service;
 pub mod dream_summary_service;
+pub mod event_service;
+pub mod health_service;
+pub mod idempotency_service;
 pub mod meal_service;
 pub mod meal_summary_service;
+pub mod medication_service;
+pub mod metrics_service;
+pub mod milk_bag_service;
+pub mod note_service;
+pub mod notification_service;
+pub mod pumping_service;
+pub mod reminder_service;
+pub mod report_service;
 pub mod role_service;
 pub mod session_service;
+pub mod sync_service;
 pub mod user_service;
 pub mod util_service;
+pub mod webhook_service;
 pub mod weight_service;
@@ -0,0 +1,143 @@
+use chrono::{Days, NaiveDate};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    data::{
+        note_dto::{InputNoteDto, NoteFilterDto},
+        query_dto::Pagination,
+    },
+    model::note_model::{InsertableNote, Note},
+    repository::note_repository::{
+        delete_note, insert_new_note, select_all_notes_from_baby, select_note_by_id,
+        select_notes_by_date_range, select_notes_with_pagination, update_note,
+    },
+    response::{
+        error::ApiError,
+        response::{MsgResponse, PagedResponse, RecordResponse},
+    },
+    utils::datetime::{now, today},
+};
+
+use super::util_service::{assert_record_belongs_to_parent, cast_to_date_from};
+
+pub async fn post_note_service<D>(
+    new_note: InputNoteDto,
+    baby_id: i32,
+) -> Result<RecordResponse<D>, ApiError>
+where
+    D: From<Note> + Serialize,
+{
+    let timestamp = cast_to_date_from(new_note.date, "date")?;
+    let note = InsertableNote::new(
+        baby_id,
+        new_note.category,
+        timestamp.unwrap_or(now()),
+        new_note.value,
+        new_note.unit,
+        new_note.description,
+    );
+    if note.is_empty() {
+        return Err(ApiError::EmptyBody);
+    }
+    let insert_data: Note = insert_new_note(note)?;
+    Ok(RecordResponse::new_entry(insert_data.into()))
+}
+
+pub async fn patch_note_service<D>(
+    note: InputNoteDto,
+    record: Uuid,
+    baby_id: i32,
+) -> Result<RecordResponse<D>, ApiError>
+where
+    D: From<Note> + Serialize,
+{
+    let note_record = select_note_by_id(record)?;
+    assert_record_belongs_to_parent(note_record.baby_id(), baby_id)?;
+    let new_note: Note = update_note(note_record.update_note(note))?;
+    Ok(RecordResponse::new(new_note.into()))
+}
+
+pub async fn get_notes_by_range_service<D>(
+    baby_id: i32,
+    from_date: NaiveDate,
+    to_date: NaiveDate,
+    filter: NoteFilterDto,
+    pagination: Pagination,
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<Note> + Serialize,
+{
+    let current = pagination.page();
+    let (notes, total_pages) =
+        select_notes_with_pagination(baby_id, from_date, to_date, filter, pagination)?;
+    Ok(PagedResponse::new(
+        into_notes_dto(notes),
+        current,
+        total_pages,
+    ))
+}
+
+pub async fn get_notes_by_last_days_service<D>(
+    baby_id: i32,
+    last_days: u32,
+    filter: NoteFilterDto,
+    pagination: Pagination,
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<Note> + Serialize,
+{
+    let from_date = today()
+        .checked_sub_days(Days::new(last_days.into()))
+        .unwrap();
+    get_notes_by_range_service(baby_id, from_date, today(), filter, pagination).await
+}
+
+pub(crate) fn fetch_notes_range(
+    baby_id: i32,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<Note>, ApiError> {
+    let notes = select_notes_by_date_range(baby_id, from, to)?;
+    Ok(notes)
+}
+
+pub async fn get_notes_all_service<D>(
+    baby_id: i32,
+    filter: NoteFilterDto,
+    pagination: Pagination,
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<Note> + Serialize,
+{
+    let current = pagination.page();
+    let (notes, total_pages) = select_all_notes_from_baby(baby_id, filter, pagination)?;
+    Ok(PagedResponse::new(
+        into_notes_dto(notes),
+        current,
+        total_pages,
+    ))
+}
+
+pub async fn get_note_id_service<D>(
+    note_id: Uuid,
+    baby_id: i32,
+) -> Result<RecordResponse<D>, ApiError>
+where
+    D: From<Note> + Serialize,
+{
+    let note = select_note_by_id(note_id)?;
+    assert_record_belongs_to_parent(note.baby_id(), baby_id)?;
+    Ok(RecordResponse::new(note.into()))
+}
+
+pub async fn delete_note_service(record: Uuid, baby_id: i32) -> Result<MsgResponse, ApiError> {
+    let note_to_delete = select_note_by_id(record)?;
+    assert_record_belongs_to_parent(note_to_delete.baby_id(), baby_id)?;
+    delete_note(note_to_delete.id())?;
+    Ok(MsgResponse::DeleteRecord)
+}
+
+fn into_notes_dto<D: From<Note>>(notes: Vec<Note>) -> Vec<D> {
+    notes.into_iter().map(|note| note.into()).collect()
+}
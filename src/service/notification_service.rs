@@ -0,0 +1,150 @@
+use serde::Serialize;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    data::{
+        event_dto::EventData, notification_dto::InputNotificationDto, query_dto::Pagination,
+        reminder_dto::ReminderAlertData,
+    },
+    model::{
+        notification_model::{InsertableNotification, Notification},
+        reminder_model::{ReminderChannel, ReminderRule},
+    },
+    repository::{
+        notification_repository::{
+            delete_notification, insert_notification, select_notification_by_id,
+            select_notifications_from_user, update_all_notifications_read,
+            update_notification_read_at,
+        },
+        user_repository::select_user_by_id,
+    },
+    response::{
+        error::ApiError,
+        response::{MsgResponse, PagedResponse, RecordResponse},
+    },
+    utils::{datetime::now, mailer::Mailer},
+};
+
+use super::{
+    util_service::assert_record_belongs_to_parent, webhook_service::enqueue_user_webhook_deliveries,
+};
+
+pub async fn get_notifications_service<D>(
+    user_id: i32,
+    only_unread: bool,
+    pagination: Pagination,
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<(Notification, Uuid)> + Serialize,
+{
+    let current = pagination.page();
+    let (notifications, total_pages) =
+        select_notifications_from_user(user_id, only_unread, pagination)?;
+    let notifications: Vec<D> = notifications
+        .into_iter()
+        .map(|notification| notification.into())
+        .collect();
+    Ok(PagedResponse::new(notifications, current, total_pages))
+}
+
+pub async fn patch_notification_service<D>(
+    notification: InputNotificationDto,
+    record: Uuid,
+    user_id: i32,
+) -> Result<RecordResponse<D>, ApiError>
+where
+    D: From<(Notification, Uuid)> + Serialize,
+{
+    let read = notification.read.ok_or(ApiError::EmptyBody)?;
+    let (old_notification, _) = select_notification_by_id(record)?;
+    assert_record_belongs_to_parent(old_notification.user_id(), user_id)?;
+    let read_at = match read {
+        true => Some(old_notification.read_at().unwrap_or(now())),
+        false => None,
+    };
+    update_notification_read_at(old_notification.id(), read_at)?;
+    let notification = select_notification_by_id(record)?;
+    Ok(RecordResponse::new(notification.into()))
+}
+
+pub async fn post_read_all_notifications_service(user_id: i32) -> Result<MsgResponse, ApiError> {
+    update_all_notifications_read(user_id, now())?;
+    Ok(MsgResponse::UpdateRecord)
+}
+
+pub async fn delete_notification_service(
+    record: Uuid,
+    user_id: i32,
+) -> Result<MsgResponse, ApiError> {
+    let (notification, _) = select_notification_by_id(record)?;
+    assert_record_belongs_to_parent(notification.user_id(), user_id)?;
+    delete_notification(notification.id())?;
+    Ok(MsgResponse::DeleteRecord)
+}
+
+/// Send a fired reminder through every channel of its rule, only to the user that created it.
+///
+/// A failing channel is only logged so the others still get the message.
+pub async fn notify_reminder(
+    rule: &ReminderRule,
+    baby_name: &str,
+    message: &str,
+    mailer: &dyn Mailer,
+) {
+    let title = format!("Reminder for {baby_name}");
+    for channel in rule.channels() {
+        match channel {
+            ReminderChannel::App => {
+                let notification = InsertableNotification::new(
+                    rule.user_id(),
+                    rule.baby_id(),
+                    Some(rule.id()),
+                    &title,
+                    message,
+                    now(),
+                );
+                if let Err(err) = insert_notification(notification) {
+                    error!("Can't save notification: {err}");
+                }
+            }
+            ReminderChannel::Webhook => {
+                let alert = ReminderAlertData {
+                    title: title.to_string(),
+                    message: message.to_string(),
+                };
+                let event = EventData {
+                    event: "reminder",
+                    data: &alert,
+                };
+                let result = serde_json::to_string(&event)
+                    .map_err(|err| err.to_string())
+                    .and_then(|payload| {
+                        enqueue_user_webhook_deliveries(
+                            rule.baby_id(),
+                            rule.user_id(),
+                            event.event,
+                            &payload,
+                        )
+                        .map_err(|err| format!("{err:?}"))
+                    });
+                if let Err(err) = result {
+                    error!("Can't queue reminder webhook: {err}");
+                }
+            }
+            ReminderChannel::Email => {
+                let email = select_user_by_id(rule.user_id())
+                    .ok()
+                    .and_then(|user| user.email());
+                match email {
+                    Some(email) => {
+                        if let Err(err) = mailer.send(&email, &title, message).await {
+                            error!("Can't send reminder email: {err}");
+                        }
+                    }
+                    None => error!("User {} has no email for reminders", rule.user_id()),
+                }
+            }
+        }
+    }
+}
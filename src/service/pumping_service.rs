@@ -0,0 +1,172 @@
+use chrono::{Days, NaiveDate};
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    data::{pumping_dto::InputPumpingDto, query_dto::Pagination},
+    model::pumping_model::{
+        InsertableMilkBag, InsertablePumping, Pumping, PumpingSide, StorageLocation,
+    },
+    repository::pumping_repository::{
+        delete_pumping, insert_new_pumping, insert_new_pumping_with_bag,
+        select_all_pumpings_from_baby, select_pumping_by_id, select_pumpings_with_pagination,
+        update_pumping,
+    },
+    response::{
+        error::{ApiError, FieldError},
+        response::{MsgResponse, PagedResponse, RecordResponse},
+    },
+    utils::datetime::{now, today},
+};
+
+use super::util_service::{assert_record_belongs_to_parent, cast_to_date_from};
+
+/// Pumped milk can be stored right away in a new bag.
+pub async fn post_pumping_service<D>(
+    new_pumping: InputPumpingDto,
+    baby_id: i32,
+) -> Result<RecordResponse<D>, ApiError>
+where
+    D: From<Pumping> + Serialize,
+{
+    let date = cast_to_date_from(new_pumping.date, "date")?.unwrap_or(now());
+    let side = match new_pumping.side {
+        Some(side) => PumpingSide::parse(&side).ok_or(invalid_side())?,
+        None => PumpingSide::Both,
+    };
+    let volume = new_pumping.volume.unwrap_or_default();
+    let pumping = InsertablePumping::new(
+        baby_id,
+        date,
+        side,
+        new_pumping.duration.unwrap_or_default(),
+        volume,
+    );
+    let insert_data: Pumping = match new_pumping.store {
+        Some(location) => {
+            let location = StorageLocation::parse(&location).ok_or(invalid_location("store"))?;
+            if volume.le(&0) {
+                return Err(FieldError::pointer(
+                    "volume",
+                    "required",
+                    "Volume is required to store milk.",
+                )
+                .into());
+            }
+            let (pumping, _) = insert_new_pumping_with_bag(pumping, |pumping| {
+                InsertableMilkBag::new(
+                    baby_id,
+                    Some(pumping.id()),
+                    volume,
+                    date,
+                    location,
+                    None,
+                )
+            })?;
+            pumping
+        }
+        None => insert_new_pumping(pumping)?,
+    };
+    Ok(RecordResponse::new_entry(insert_data.into()))
+}
+
+pub async fn patch_pumping_service<D>(
+    pumping: InputPumpingDto,
+    record: Uuid,
+    baby_id: i32,
+) -> Result<RecordResponse<D>, ApiError>
+where
+    D: From<Pumping> + Serialize,
+{
+    let pumping_record = select_pumping_by_id(record)?;
+    assert_record_belongs_to_parent(pumping_record.baby_id(), baby_id)?;
+    let new_pumping = update_pumping(pumping_record.update_pumping(pumping))?;
+    Ok(RecordResponse::new(new_pumping.into()))
+}
+
+pub async fn get_pumpings_by_range_service<D>(
+    baby_id: i32,
+    from_date: NaiveDate,
+    to_date: NaiveDate,
+    pagination: Pagination,
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<Pumping> + Serialize,
+{
+    let current = pagination.page();
+    let (pumpings, total_pages) =
+        select_pumpings_with_pagination(baby_id, from_date, to_date, pagination)?;
+    Ok(PagedResponse::new(
+        into_pumpings_dto(pumpings),
+        current,
+        total_pages,
+    ))
+}
+
+pub async fn get_pumpings_by_last_days_service<D>(
+    baby_id: i32,
+    last_days: u32,
+    pagination: Pagination,
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<Pumping> + Serialize,
+{
+    let from_date = today()
+        .checked_sub_days(Days::new(last_days.into()))
+        .unwrap();
+    get_pumpings_by_range_service(baby_id, from_date, today(), pagination).await
+}
+
+pub async fn get_pumpings_all_service<D>(
+    baby_id: i32,
+    pagination: Pagination,
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<Pumping> + Serialize,
+{
+    let current = pagination.page();
+    let (pumpings, total_pages) = select_all_pumpings_from_baby(baby_id, pagination)?;
+    Ok(PagedResponse::new(
+        into_pumpings_dto(pumpings),
+        current,
+        total_pages,
+    ))
+}
+
+pub async fn get_pumping_id_service<D>(
+    record: Uuid,
+    baby_id: i32,
+) -> Result<RecordResponse<D>, ApiError>
+where
+    D: From<Pumping> + Serialize,
+{
+    let pumping = select_pumping_by_id(record)?;
+    assert_record_belongs_to_parent(pumping.baby_id(), baby_id)?;
+    Ok(RecordResponse::new(pumping.into()))
+}
+
+/// Bags stored from this session are kept.
+pub async fn delete_pumping_service(record: Uuid, baby_id: i32) -> Result<MsgResponse, ApiError> {
+    let pumping = select_pumping_by_id(record)?;
+    assert_record_belongs_to_parent(pumping.baby_id(), baby_id)?;
+    delete_pumping(pumping.id())?;
+    Ok(MsgResponse::DeleteRecord)
+}
+
+fn into_pumpings_dto<D: From<Pumping>>(pumpings: Vec<Pumping>) -> Vec<D> {
+    pumpings.into_iter().map(|pumping| pumping.into()).collect()
+}
+
+fn invalid_side() -> ApiError {
+    FieldError::pointer("side", "invalid", "Side must be left, right or both.").into()
+}
+
+/// `field` is `store` for pumping sessions and `location` for bags.
+pub(crate) fn invalid_location(field: &str) -> ApiError {
+    FieldError::pointer(
+        field,
+        "invalid",
+        "Location must be room, fridge or freezer.",
+    )
+    .into()
+}
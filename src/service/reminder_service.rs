@@ -0,0 +1,250 @@
+use std::{sync::Arc, time::Duration};
+
+use serde::Serialize;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    configuration::constant::GlobalCte,
+    data::{query_dto::Pagination, reminder_dto::InputReminderDto},
+    model::reminder_model::{
+        InsertableReminderRule, ReminderChannel, ReminderKind, ReminderRule, RuleContext,
+    },
+    repository::{
+        dream_repository::select_last_dreams,
+        meal_repository::select_last_meals,
+        medication_repository::{select_last_doses, select_medication_by_id},
+        reminder_repository::{
+            delete_reminder_rule, insert_new_reminder_rule, mark_reminder_rule_triggered,
+            select_active_reminder_rules, select_reminder_rule_by_id,
+            select_reminder_rules_from_baby, update_reminder_rule,
+        },
+    },
+    response::{
+        error::{ApiError, FieldError},
+        response::{MsgResponse, PagedResponse, RecordResponse},
+    },
+    utils::{
+        datetime::{convert_to_time, now},
+        mailer::Mailer,
+    },
+};
+
+use super::{notification_service::notify_reminder, util_service::assert_record_belongs_to_parent};
+
+/// Feed and sleep rules need `threshold_minutes`, medication rules `medication_id` and `due_time`.
+pub async fn post_reminder_service<D>(
+    new_rule: InputReminderDto,
+    baby_id: i32,
+    user_id: i32,
+) -> Result<RecordResponse<D>, ApiError>
+where
+    D: From<ReminderRule> + Serialize,
+{
+    let kind = match new_rule.kind.as_deref().map(ReminderKind::parse) {
+        Some(Some(kind)) => kind,
+        Some(None) => {
+            return Err(FieldError::pointer(
+                "kind",
+                "invalid",
+                "Kind must be no_feed, long_sleep or medication.",
+            )
+            .into())
+        }
+        None => return Err(ApiError::EmptyBody),
+    };
+    let channels = match new_rule.channels.as_deref() {
+        Some(channels) => ReminderChannel::parse_list(channels).ok_or_else(invalid_channels)?,
+        None => vec![ReminderChannel::App],
+    };
+    let rule = match kind {
+        ReminderKind::NoFeed | ReminderKind::LongSleep => {
+            let threshold = new_rule.threshold_minutes.filter(|minutes| minutes.gt(&0));
+            if threshold.is_none() {
+                return Err(FieldError::pointer(
+                    "threshold_minutes",
+                    "required",
+                    "Threshold in minutes is required.",
+                )
+                .into());
+            }
+            InsertableReminderRule::new(baby_id, user_id, kind, threshold, None, None, channels)
+        }
+        ReminderKind::Medication => {
+            let (medication_id, due_time) = match (new_rule.medication_id, new_rule.due_time) {
+                (Some(medication_id), Some(due_time)) => match convert_to_time(&due_time) {
+                    Ok(due_time) => (medication_id, due_time),
+                    Err(_) => {
+                        return Err(FieldError::pointer(
+                            "due_time",
+                            "invalid_format",
+                            "Due time must be HH:MM.",
+                        )
+                        .into())
+                    }
+                },
+                (medication_id, due_time) => {
+                    let mut errors = Vec::new();
+                    if medication_id.is_none() {
+                        errors.push(FieldError::pointer(
+                            "medication_id",
+                            "required",
+                            "Medication is required.",
+                        ));
+                    }
+                    if due_time.is_none() {
+                        errors.push(FieldError::pointer(
+                            "due_time",
+                            "required",
+                            "Due time is required.",
+                        ));
+                    }
+                    return Err(ApiError::Invalid(errors));
+                }
+            };
+            let medication = select_medication_by_id(medication_id)?;
+            assert_record_belongs_to_parent(medication.baby_id(), baby_id)?;
+            InsertableReminderRule::new(
+                baby_id,
+                user_id,
+                kind,
+                None,
+                Some(medication.id()),
+                Some(due_time),
+                channels,
+            )
+        }
+    };
+    let rule = insert_new_reminder_rule(rule)?;
+    Ok(RecordResponse::new_entry(rule.into()))
+}
+
+pub async fn patch_reminder_service<D>(
+    rule: InputReminderDto,
+    record: Uuid,
+    baby_id: i32,
+    user_id: i32,
+) -> Result<RecordResponse<D>, ApiError>
+where
+    D: From<ReminderRule> + Serialize,
+{
+    if let Some(channels) = rule.channels.as_deref() {
+        ReminderChannel::parse_list(channels).ok_or_else(invalid_channels)?;
+    }
+    let old_rule = find_reminder_rule(record, baby_id, user_id)?;
+    let updated = update_reminder_rule(old_rule.update_rule(rule))?;
+    Ok(RecordResponse::new(updated.into()))
+}
+
+pub async fn get_reminders_service<D>(
+    baby_id: i32,
+    user_id: i32,
+    pagination: Pagination,
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<ReminderRule> + Serialize,
+{
+    let current = pagination.page();
+    let (rules, total_pages) = select_reminder_rules_from_baby(baby_id, user_id, pagination)?;
+    let rules: Vec<D> = rules.into_iter().map(|rule| rule.into()).collect();
+    Ok(PagedResponse::new(rules, current, total_pages))
+}
+
+pub async fn get_reminder_id_service<D>(
+    record: Uuid,
+    baby_id: i32,
+    user_id: i32,
+) -> Result<RecordResponse<D>, ApiError>
+where
+    D: From<ReminderRule> + Serialize,
+{
+    let rule = find_reminder_rule(record, baby_id, user_id)?;
+    Ok(RecordResponse::new(rule.into()))
+}
+
+pub async fn delete_reminder_service(
+    record: Uuid,
+    baby_id: i32,
+    user_id: i32,
+) -> Result<MsgResponse, ApiError> {
+    let rule = find_reminder_rule(record, baby_id, user_id)?;
+    delete_reminder_rule(rule.id())?;
+    Ok(MsgResponse::DeleteRecord)
+}
+
+/// Check every active rule against latest activity and notify those that fire.
+///
+/// Latest meals, dreams and doses are loaded in batch for all rules.
+pub async fn evaluate_reminder_rules(mailer: &dyn Mailer) -> Result<usize, ApiError> {
+    let rules = select_active_reminder_rules()?;
+    if rules.is_empty() {
+        return Ok(0);
+    }
+    let mut babies: Vec<i32> = rules.iter().map(|(rule, _)| rule.baby_id()).collect();
+    babies.sort_unstable();
+    babies.dedup();
+    let medication_ids: Vec<i32> = rules
+        .iter()
+        .filter_map(|(rule, _)| rule.medication_id())
+        .collect();
+    let meals = select_last_meals(babies.clone())?;
+    let dreams = select_last_dreams(babies)?;
+    let medications = select_last_doses(medication_ids)?;
+    let now = now();
+    let mut fired = 0;
+    for (rule, baby_name) in rules {
+        let medication = medications
+            .iter()
+            .find(|(medication, _)| rule.medication_id().eq(&Some(medication.id())));
+        let context = RuleContext {
+            last_meal: meals
+                .iter()
+                .find(|meal| meal.baby_id().eq(&rule.baby_id()))
+                .map(|meal| meal.date()),
+            ongoing_dream: dreams
+                .iter()
+                .find(|dream| dream.baby_id().eq(&rule.baby_id()) && dream.to_date().is_none())
+                .map(|dream| dream.from_date()),
+            medication_name: medication.map(|(medication, _)| medication.name()),
+            last_dose: medication.and_then(|(_, last_dose)| *last_dose),
+        };
+        let message = match rule.evaluate(&context, now) {
+            Some(message) => message,
+            None => continue,
+        };
+        if mark_reminder_rule_triggered(rule.id(), rule.last_triggered_at(), now)? {
+            notify_reminder(&rule, &baby_name, &message, mailer).await;
+            fired += 1;
+        }
+    }
+    Ok(fired)
+}
+
+/// Background loop evaluating reminder rules, started with the server.
+pub async fn run_reminder_scheduler(mailer: Arc<dyn Mailer>) {
+    let period = Duration::from_secs(GlobalCte::ReminderPollSeconds.get().into());
+    let mut interval = tokio::time::interval(period);
+    loop {
+        interval.tick().await;
+        if let Err(error) = evaluate_reminder_rules(mailer.as_ref()).await {
+            error!("Reminder evaluation failed: {error:?}");
+        }
+    }
+}
+
+/// Rules are private to the user that created them.
+fn find_reminder_rule(record: Uuid, baby_id: i32, user_id: i32) -> Result<ReminderRule, ApiError> {
+    let rule = select_reminder_rule_by_id(record)?;
+    assert_record_belongs_to_parent(rule.baby_id(), baby_id)?;
+    assert_record_belongs_to_parent(rule.user_id(), user_id)?;
+    Ok(rule)
+}
+
+fn invalid_channels() -> ApiError {
+    FieldError::pointer(
+        "channels",
+        "invalid",
+        "Channels must be app, webhook or email.",
+    )
+    .into()
+}
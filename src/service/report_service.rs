@@ -0,0 +1,72 @@
+use chrono::{Days, NaiveDate};
+
+use crate::{
+    data::report_dto::{ReportData, ReportFormat},
+    model::report_model::Report,
+    repository::baby_repository::select_baby_by_id,
+    response::{error::ApiError, response::FileResponse},
+    utils::{datetime::today, report::render_html},
+};
+
+use super::{
+    dream_summary_service::fetch_dream_summary_range,
+    meal_summary_service::fetch_meal_summary_range, note_service::fetch_notes_range,
+    weight_service::fetch_weights_range,
+};
+
+pub async fn get_report_range_service(
+    baby_id: i32,
+    from_date: NaiveDate,
+    to_date: NaiveDate,
+    format: ReportFormat,
+) -> Result<FileResponse, ApiError> {
+    let report = fetch_report(baby_id, from_date, to_date).await?;
+    render_report(report.into(), format)
+}
+
+pub async fn get_report_last_days_service(
+    baby_id: i32,
+    last_days: u32,
+    format: ReportFormat,
+) -> Result<FileResponse, ApiError> {
+    let today = today();
+    let from_date = today.checked_sub_days(Days::new(last_days.into())).unwrap();
+    get_report_range_service(baby_id, from_date, today, format).await
+}
+
+/// Gather baby info, growth, daily summaries and notes for a period.
+pub(crate) async fn fetch_report(
+    baby_id: i32,
+    from_date: NaiveDate,
+    to_date: NaiveDate,
+) -> Result<Report, ApiError> {
+    let baby = select_baby_by_id(baby_id)?;
+    let weights = fetch_weights_range(baby_id, from_date, to_date)?;
+    let meals = fetch_meal_summary_range(baby_id, from_date, to_date).await?;
+    let dreams = fetch_dream_summary_range(baby_id, from_date, to_date).await?;
+    let notes = fetch_notes_range(baby_id, from_date, to_date)?;
+    Ok(Report::new(
+        baby, from_date, to_date, weights, meals, dreams, notes,
+    ))
+}
+
+fn render_report(report: ReportData, format: ReportFormat) -> Result<FileResponse, ApiError> {
+    match format {
+        ReportFormat::Html => Ok(FileResponse::html(render_html(&report))),
+        ReportFormat::Pdf => render_pdf_report(report),
+    }
+}
+
+#[cfg(feature = "pdf")]
+fn render_pdf_report(report: ReportData) -> Result<FileResponse, ApiError> {
+    let filename = format!("report_{}_{}.pdf", report.from, report.to);
+    let content = crate::utils::report::render_pdf(&report)?;
+    Ok(FileResponse::pdf(content, filename))
+}
+
+#[cfg(not(feature = "pdf"))]
+fn render_pdf_report(_report: ReportData) -> Result<FileResponse, ApiError> {
+    Err(ApiError::Generic400Error(
+        "Pdf reports are not enabled.".into(),
+    ))
+}
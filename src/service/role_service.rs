@@ -1,5 +1,9 @@
 use crate::{
-    model::role_model::Rol, repository::{role_repository::select_role_from_role_name, association_repository::{insert_rol_to_user, delete_rol_to_user}},
+    model::role_model::Rol,
+    repository::{
+        association_repository::{delete_rol_to_user, insert_rol_to_user},
+        role_repository::{insert_missing_roles, select_role_from_role_name},
+    },
     response::{error::ApiError, response::MsgResponse},
 };
 
@@ -17,6 +21,17 @@ pub async fn add_rol_to_user_service(user: i32, rol: Rol) -> Result<MsgResponse,
     }
 }
 
+/// Roles created by the first migration, with their fixed ids.
+pub async fn seed_roles_service() -> Result<MsgResponse, ApiError> {
+    let roles: [(i16, &str); 3] = [
+        (Rol::Admin.into(), "admin"),
+        (Rol::User.into(), "user"),
+        (Rol::Anonymous.into(), "anonymous"),
+    ];
+    let rows = insert_missing_roles(&roles)?;
+    Ok(MsgResponse::NewXRecords(rows))
+}
+
 pub async fn delete_rol_to_user_service(user: i32, rol: Rol) -> Result<MsgResponse, ApiError> {
     match delete_rol_to_user(user, rol.into()) {
         Ok(_) => Ok(MsgResponse::DeleteRecord),
@@ -24,7 +39,6 @@ pub async fn delete_rol_to_user_service(user: i32, rol: Rol) -> Result<MsgRespon
     }
 }
 
-
 #[cfg(test)]
 mod test_role_service {
     use std::path::Path;
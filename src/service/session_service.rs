@@ -1,9 +1,10 @@
 use axum_session::SessionRedisPool;
 use axum_session_auth::AuthSession;
+use tracing::{field, Span};
 use uuid::Uuid;
 
 use crate::{
-    configuration::settings::Setting,
+    configuration::config::config,
     data::{session_dto::CurrentUserDto, common_structure::SessionDto},
     mapping::rol_mapper::translate_roles,
     model::{role_model::Rol, session_model::CurrentUser, user_model::User},
@@ -17,7 +18,7 @@ use crate::{
             select_babies_for_user_id, select_user_by_id,
         }, role_repository::select_roles_id_from_user,
     },
-    response::{error::ApiError, response::RecordResponse},
+    response::{error::{ApiError, FieldError}, response::RecordResponse},
 };
 
 pub async fn login_session<T>(
@@ -53,10 +54,7 @@ pub async fn save_user_session(
     let key = user_redis_key(user.id());
     let session_duration = match duration {
         Some(value) => value,
-        None => Setting::SessionDuration
-            .get()
-            .parse::<usize>()
-            .unwrap_or(600),
+        None => config().session_duration,
     };
     insert_user_session(&key, (*user).clone().into(), session_duration).await?;
     Ok(())
@@ -108,6 +106,16 @@ pub async fn create_current_user(current_user: User) -> Result<CurrentUser, ApiE
     Ok(user_session)
 }
 
+/// Drop the cached user, the next request loads it from the database again.
+pub async fn clear_user_session<T>(id: T) -> Result<(), ApiError>
+where
+    T: Into<i64>,
+{
+    let key = user_redis_key(id.into());
+    delete_user_session(&key).await?;
+    Ok(())
+}
+
 fn user_redis_key(id: i64) -> String {
     format!("user_{}", id)
 }
@@ -145,7 +153,10 @@ pub fn check_user_permissions(
     auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
     baby_unique_id: &str,
 ) -> Result<i32, ApiError> {
-    let unique_id = Uuid::parse_str(baby_unique_id)?;
+    let unique_id = Uuid::parse_str(baby_unique_id).map_err(|_| {
+        FieldError::parameter("baby_id", "invalid_format", "Baby id must be a UUID.")
+    })?;
+    Span::current().record("baby_id", field::display(unique_id));
     if auth.is_anonymous() {
         return Err(ApiError::LoginRequired);
     } else if has_baby(auth, unique_id) {
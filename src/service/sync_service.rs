@@ -0,0 +1,187 @@
+use chrono::NaiveDateTime;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{
+    data::{
+        common_structure::{DataType, DreamDto, MealDto},
+        dream_dto::InputDreamDto,
+        meal_dto::InputMealDto,
+        sync_dto::{InputSyncDto, SyncChangeDto},
+    },
+    model::{
+        event_model::EventAction,
+        sync_model::{parse_cursor, server_wins, SyncChanges},
+    },
+    repository::{
+        dream_repository::{
+            delete_dream, insert_new_dream, select_dream_by_client_id, select_dreams_updated_since,
+            update_dream,
+        },
+        meal_repository::{select_meal_by_client_id, select_meals_updated_since},
+        sync_repository::select_deleted_records_since,
+    },
+    response::{
+        error::{ApiError, FieldError},
+        response::RecordResponse,
+    },
+    utils::{
+        datetime::{convert_to_date_time, now},
+        metrics::record_created,
+    },
+};
+
+use super::{
+    dream_service::create_new_dream_entry,
+    event_service::{publish_deleted_event, publish_record_event},
+    meal_service::{create_meal, remove_meal, save_meal_changes},
+    util_service::stale_record,
+};
+
+/// Apply offline changes and return what changed on the server since the cursor.
+///
+/// Changes are matched by `client_id`, so a batch that fails halfway can be sent again.
+pub async fn post_sync_service<S>(
+    changes: InputSyncDto,
+    baby_id: i32,
+) -> Result<RecordResponse<S>, ApiError>
+where
+    S: From<SyncChanges> + Serialize,
+{
+    let since = match changes.cursor.as_deref() {
+        Some(cursor) => Some(parse_cursor(cursor).map_err(|_| {
+            FieldError::pointer("cursor", "invalid_format", "Cursor is not valid.")
+        })?),
+        None => None,
+    };
+    let mut errors = Vec::new();
+    let meals = validate_changes("meals", changes.meals, |meal| meal.client_id, &mut errors);
+    let dreams = validate_changes(
+        "dreams",
+        changes.dreams,
+        |dream| dream.client_id,
+        &mut errors,
+    );
+    if !errors.is_empty() {
+        return Err(ApiError::Invalid(errors));
+    }
+    let mut conflicts: Vec<Uuid> = Vec::new();
+    for (client_id, edited_at, change) in meals {
+        if !apply_meal_change(change, client_id, edited_at, baby_id).await? {
+            conflicts.push(client_id);
+        }
+    }
+    for (client_id, edited_at, change) in dreams {
+        if !apply_dream_change(change, client_id, edited_at, baby_id).await? {
+            conflicts.push(client_id);
+        }
+    }
+    let changes = SyncChanges::new(
+        now(),
+        select_meals_updated_since(baby_id, since)?,
+        select_dreams_updated_since(baby_id, since)?,
+        select_deleted_records_since(baby_id, since)?,
+        conflicts,
+    );
+    Ok(RecordResponse::new(changes.into()))
+}
+
+/// Whole batch is checked before anything is written, every invalid change is reported.
+fn validate_changes<T, F>(
+    member: &str,
+    changes: Vec<SyncChangeDto<T>>,
+    client_id: F,
+    errors: &mut Vec<FieldError>,
+) -> Vec<(Uuid, NaiveDateTime, SyncChangeDto<T>)>
+where
+    F: Fn(&T) -> Option<Uuid>,
+{
+    let mut valid = Vec::new();
+    for (index, change) in changes.into_iter().enumerate() {
+        let id = client_id(&change.record);
+        if id.is_none() {
+            errors.push(FieldError::pointer(
+                &format!("{member}/{index}/client_id"),
+                "required",
+                "Every change needs a client_id.",
+            ));
+        }
+        let edited_at = convert_to_date_time(&change.updated_at).ok();
+        if edited_at.is_none() {
+            errors.push(FieldError::pointer(
+                &format!("{member}/{index}/updated_at"),
+                "invalid_format",
+                "Date must be YYYY-mm-dd HH:MM or RFC 3339.",
+            ));
+        }
+        if let (Some(id), Some(edited_at)) = (id, edited_at) {
+            valid.push((id, edited_at, change));
+        }
+    }
+    valid
+}
+
+/// False when the server copy is newer and the change is discarded.
+async fn apply_meal_change(
+    change: SyncChangeDto<InputMealDto>,
+    client_id: Uuid,
+    edited_at: NaiveDateTime,
+    baby_id: i32,
+) -> Result<bool, ApiError> {
+    match select_meal_by_client_id(baby_id, client_id)? {
+        Some(meal) if server_wins(meal.edited_at(), edited_at) => return Ok(false),
+        Some(meal) if change.deleted => {
+            let record = meal.public_id();
+            remove_meal(&meal)?;
+            publish_deleted_event(baby_id, DataType::Meal, record).await;
+        }
+        Some(meal) => {
+            let changes = meal.update_meal(change.record).with_edited_at(edited_at);
+            let meal: MealDto = save_meal_changes(&meal, changes)?.into();
+            publish_record_event(baby_id, EventAction::Updated, &meal).await;
+        }
+        None if change.deleted => (),
+        None => {
+            let meal: MealDto = create_meal(change.record, baby_id, edited_at)?.into();
+            publish_record_event(baby_id, EventAction::Created, &meal).await;
+        }
+    }
+    Ok(true)
+}
+
+async fn apply_dream_change(
+    change: SyncChangeDto<InputDreamDto>,
+    client_id: Uuid,
+    edited_at: NaiveDateTime,
+    baby_id: i32,
+) -> Result<bool, ApiError> {
+    match select_dream_by_client_id(baby_id, client_id)? {
+        Some(dream) if server_wins(dream.edited_at(), edited_at) => return Ok(false),
+        Some(dream) if change.deleted => {
+            delete_dream(&dream).map_err(stale_record)?;
+            publish_deleted_event(baby_id, DataType::Dream, dream.public_id()).await;
+        }
+        Some(dream) => {
+            let dream: DreamDto =
+                update_dream(dream.update_dream(change.record).with_edited_at(edited_at))
+                    .map_err(stale_record)?
+                    .into();
+            publish_record_event(baby_id, EventAction::Updated, &dream).await;
+        }
+        None if change.deleted => (),
+        None if change.record.from_date.is_none() => {
+            return Err(ApiError::Generic400Error(
+                "New dreams need a from_date.".into(),
+            ))
+        }
+        None => {
+            let dream = create_new_dream_entry(change.record, baby_id)
+                .await?
+                .with_edited_at(edited_at);
+            let dream: DreamDto = insert_new_dream(dream)?.into();
+            record_created("dreams");
+            publish_record_event(baby_id, EventAction::Created, &dream).await;
+        }
+    }
+    Ok(true)
+}
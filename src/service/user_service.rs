@@ -12,39 +12,59 @@ use crate::{
     repository::user_repository::{
         delete_all_users, delete_user, insert_new_user, select_all_users, select_id_from_username,
         select_user_by_id, select_user_by_username, select_user_from_username,
-        update_active_for_user, update_user,
+        select_users_by_cursor, update_active_for_user, update_password_for_user, update_user,
     },
     response::{
-        error::ApiError,
+        error::{ApiError, FieldError},
         response::{MsgResponse, PagedResponse, RecordResponse},
     },
+    security::security::hash_password,
     utils::{
+        cursor::CursorScope,
         datetime::now,
+        metrics::record_created,
         validator::{valid_password, validate_fields},
     },
 };
 
-use super::session_service::{create_current_user, save_user_session};
+use super::{
+    session_service::{clear_user_session, create_current_user, save_user_session},
+    util_service::{cursor_page, numbered_page},
+};
 
 pub async fn post_new_user_service(
     new_user: NewUserDto,
 ) -> Result<(RecordResponse<SessionDto>, i32), ApiError> {
     validate_new_user_information(&new_user)?;
-    let user = match insert_new_user(new_user, Rol::User.into()) {
+    let user = match insert_new_user(new_user, &[Rol::User.into()]) {
         Ok(user) => user,
         Err(_) => return Err(ApiError::DuplicateUser),
     };
+    record_created("users");
     let id_binding = user.id();
     let new_user = cache_user_in_session(user).await?;
     Ok((RecordResponse::new_entry(new_user), id_binding))
 }
 
+/// Admin users also hold the user role, like the seeded `admin`.
+pub async fn post_new_admin_service(
+    new_user: NewUserDto,
+) -> Result<RecordResponse<UserDto>, ApiError> {
+    validate_new_user_information(&new_user)?;
+    let user = match insert_new_user(new_user, &[Rol::Admin.into(), Rol::User.into()]) {
+        Ok(user) => user,
+        Err(_) => return Err(ApiError::DuplicateUser),
+    };
+    record_created("users");
+    Ok(RecordResponse::new_entry(user.into()))
+}
+
 pub fn validate_new_user_information(new_user: &NewUserDto) -> Result<(), ApiError> {
     if validate_fields(&new_user.data()) {
         return Err(ApiError::EmptyBody);
     }
     if valid_password(&new_user.password) {
-        return Err(ApiError::Generic400Error("Password too short.".into()));
+        return Err(password_too_short());
     }
     if exist_username_in_database(&new_user.username)? {
         return Err(ApiError::DuplicateUser);
@@ -61,11 +81,15 @@ fn exist_username_in_database(username: &str) -> Result<bool, ApiError> {
 pub async fn get_all_users_service(
     pagination: Pagination,
 ) -> Result<PagedResponse<Vec<AdminUserDto>>, ApiError> {
+    let scope = CursorScope::default();
+    if let Some(cursor) = pagination.cursor(&scope)? {
+        let per_page = pagination.per_page();
+        let users = select_users_by_cursor(&cursor, per_page)?;
+        return Ok(cursor_page(users, &cursor, per_page));
+    }
     let current = pagination.page();
     let (users, total_pages) = select_all_users(pagination)?;
-    let users: Vec<AdminUserDto> = users.into_iter().map(|user| user.into()).collect();
-    let response = PagedResponse::new(users, current, total_pages);
-    Ok(response)
+    Ok(numbered_page(users, current, total_pages, &scope))
 }
 
 pub async fn post_find_user_service(
@@ -124,6 +148,19 @@ pub async fn patch_user_service(
     Ok(response)
 }
 
+pub async fn patch_password_service(
+    username: &str,
+    password: &str,
+) -> Result<MsgResponse, ApiError> {
+    if valid_password(password) {
+        return Err(password_too_short());
+    }
+    let user_id = get_user_id_from_username(username).await?;
+    update_password_for_user(user_id, hash_password(password), now())?;
+    clear_user_session(user_id).await?;
+    Ok(MsgResponse::UpdateRecord)
+}
+
 pub async fn delete_active_user_service(
     user_id: i32,
     active: bool,
@@ -177,3 +214,7 @@ pub async fn get_user_id_from_username(username: &str) -> Result<i32, ApiError>
 pub fn delete_session_user_service() -> Result<MsgResponse, ApiError> {
     Ok(MsgResponse::LogoutUser)
 }
+
+fn password_too_short() -> ApiError {
+    FieldError::pointer("password", "too_short", "Password too short.").into()
+}
@@ -1,23 +1,52 @@
 use chrono::{Days, NaiveDate, NaiveDateTime};
+use diesel::result::Error;
+
+use serde::Serialize;
 
 use crate::{
-    data::query_dto::Pagination, response::error::ApiError, utils::datetime::convert_to_date_time,
+    data::query_dto::{Pagination, Sort},
+    response::{
+        error::{ApiError, FieldError},
+        response::PagedResponse,
+    },
+    utils::{
+        cursor::{Cursor, CursorScope, Keyset},
+        datetime::convert_to_date_time,
+        etag::IfMatch,
+    },
 };
 
 pub fn not_found() -> ApiError {
     ApiError::PageNotFound
 }
 
-pub fn cast_to_date_from(date: Option<String>) -> Result<Option<NaiveDateTime>, ApiError> {
+/// `field` is the body member holding the date, reported when it can't be parsed.
+pub fn cast_to_date_from(
+    date: Option<String>,
+    field: &str,
+) -> Result<Option<NaiveDateTime>, ApiError> {
     match date {
         Some(d) => match convert_to_date_time(&d) {
             Ok(date) => Ok(Some(date)),
-            Err(e) => Err(e.into()),
+            Err(_) => Err(invalid_date_time(field)),
         },
         None => Ok(None),
     }
 }
 
+pub fn invalid_date(field: &str) -> ApiError {
+    FieldError::pointer(field, "invalid_format", "Date must be YYYY-mm-dd.").into()
+}
+
+pub fn invalid_date_time(field: &str) -> ApiError {
+    FieldError::pointer(
+        field,
+        "invalid_format",
+        "Date must be YYYY-mm-dd HH:MM or RFC 3339.",
+    )
+    .into()
+}
+
 /// Checks if records' baby is the same as the one you want to perform the modification on.
 pub fn assert_record_belongs_to_parent(record_baby: i32, baby_id: i32) -> Result<(), ApiError> {
     if record_baby.ne(&baby_id) {
@@ -27,6 +56,27 @@ pub fn assert_record_belongs_to_parent(record_baby: i32, baby_id: i32) -> Result
     }
 }
 
+/// Client copy must be the current version of the record.
+///
+/// Writes must then filter on `version`, so a change in between fails with [stale_record].
+pub fn assert_version_matches(if_match: &IfMatch, version: i32) -> Result<(), ApiError> {
+    if if_match.is_missing() {
+        Err(ApiError::PreconditionRequired)
+    } else if if_match.matches(version) {
+        Ok(())
+    } else {
+        Err(ApiError::PreconditionFailed)
+    }
+}
+
+/// Versioned updates find no row when another write got in first.
+pub fn stale_record(error: Error) -> ApiError {
+    match error {
+        Error::NotFound => ApiError::PreconditionFailed,
+        error => error.into(),
+    }
+}
+
 pub fn paginate_over_dates(
     pagination: Pagination,
     from: NaiveDate,
@@ -45,35 +95,153 @@ pub fn paginate_over_dates(
     (start_date, end_date)
 }
 
+/// Numbered page, neighbours are linked by cursor too so clients can move to keyset paging.
+pub fn numbered_page<T, D>(
+    records: Vec<T>,
+    current: i64,
+    total_pages: i64,
+    scope: &CursorScope,
+) -> PagedResponse<Vec<D>>
+where
+    T: Keyset + Into<D>,
+    D: Serialize,
+{
+    let next_cursor = records
+        .last()
+        .filter(|_| current.lt(&total_pages))
+        .map(|record| Cursor::after(record, scope).encode());
+    let prev_cursor = records
+        .first()
+        .filter(|_| current.gt(&1))
+        .map(|record| Cursor::before(record, scope).encode());
+    let data = records.into_iter().map(|record| record.into()).collect();
+    PagedResponse::new(data, current, total_pages).with_cursors(next_cursor, prev_cursor)
+}
+
+/// Cursors follow date order either way, records sorted any other way are only paged by number.
+fn in_cursor_order(sort: Option<&Sort>) -> bool {
+    sort.map_or(true, |sort| sort.field().eq("date"))
+}
+
+/// Whether cursor pages go newest first, `sort=-date`.
+pub fn cursor_descending(sort: Option<&Sort>) -> Result<bool, ApiError> {
+    if in_cursor_order(sort) {
+        Ok(sort.map_or(false, Sort::descending))
+    } else {
+        Err(FieldError::parameter(
+            "sort",
+            "not_allowed",
+            "Cursor pages are sorted by date, use page numbers to sort otherwise.",
+        )
+        .into())
+    }
+}
+
+/// Numbered page, linked by cursor while sorted by date.
+pub fn sorted_page<T, D>(
+    records: Vec<T>,
+    current: i64,
+    total_pages: i64,
+    sort: Option<&Sort>,
+    scope: &CursorScope,
+) -> PagedResponse<Vec<D>>
+where
+    T: Keyset + Into<D>,
+    D: Serialize,
+{
+    let response = numbered_page(records, current, total_pages, scope);
+    if in_cursor_order(sort) {
+        response
+    } else {
+        response.with_cursors(None, None)
+    }
+}
+
+/// Page from a keyset query, `records` come nearest to `cursor` first with one extra record
+/// when the list goes on.
+pub fn cursor_page<T, D>(
+    mut records: Vec<T>,
+    cursor: &Cursor<T::Id>,
+    per_page: i64,
+) -> PagedResponse<Vec<D>>
+where
+    T: Keyset + Into<D>,
+    D: Serialize,
+{
+    let per_page: usize = per_page.try_into().unwrap_or_default();
+    let more = records.len().gt(&per_page);
+    records.truncate(per_page);
+    if !cursor.is_forward() {
+        records.reverse();
+    }
+    // The page we came from is always there.
+    let (has_next, has_prev) = if cursor.is_forward() {
+        (more, true)
+    } else {
+        (true, more)
+    };
+    let next_cursor = records
+        .last()
+        .filter(|_| has_next)
+        .map(|record| Cursor::after(record, cursor.scope()).encode());
+    let prev_cursor = records
+        .first()
+        .filter(|_| has_prev)
+        .map(|record| Cursor::before(record, cursor.scope()).encode());
+    let data = records.into_iter().map(|record| record.into()).collect();
+    PagedResponse::from_cursors(data, next_cursor, prev_cursor)
+}
+
 pub fn round_total_pages(from_date: NaiveDate, to_date: NaiveDate, per_page: i64) -> i64 {
     ((to_date - from_date).num_days() as f64 / per_page as f64).ceil() as i64
 }
 
 #[cfg(test)]
 mod test_service {
+    use std::collections::HashMap;
+
+    use crate::data::query_dto::ListQuery;
+
     use super::*;
 
+    fn sorted(sort: &str) -> ListQuery {
+        ListQuery::from_params(HashMap::from([("sort".to_string(), sort.to_string())]))
+    }
+
+    #[test]
+    fn test_cursor_descending() {
+        assert!(!cursor_descending(None).unwrap());
+        assert!(!cursor_descending(sorted("date").sort()).unwrap());
+        assert!(cursor_descending(sorted("-date").sort()).unwrap());
+        assert!(cursor_descending(sorted("-quantity").sort()).is_err());
+    }
+
     #[test]
     fn test_pagination_dates() {
         let page_1 = Pagination {
             page: 1,
             per_page: Some(10),
+            cursor: None,
         };
         let page_2 = Pagination {
             page: 2,
             per_page: Some(10),
+            cursor: None,
         };
         let page_3 = Pagination {
             page: 3,
             per_page: Some(10),
+            cursor: None,
         };
         let page_4 = Pagination {
             page: 4,
             per_page: Some(10),
+            cursor: None,
         };
         let page_5 = Pagination {
             page: 5,
             per_page: Some(10),
+            cursor: None,
         };
         let from = NaiveDate::from_ymd_opt(2023, 06, 01).unwrap();
         let to = NaiveDate::from_ymd_opt(2023, 07, 05).unwrap();
@@ -113,4 +281,34 @@ mod test_service {
             )
         );
     }
+
+    struct Row(i32);
+
+    impl Keyset for Row {
+        type Id = i32;
+
+        fn keyset(&self) -> (NaiveDateTime, i32) {
+            let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+            (date.and_hms_opt(10, 0, 0).unwrap(), self.0)
+        }
+    }
+
+    impl From<Row> for i32 {
+        fn from(row: Row) -> Self {
+            row.0
+        }
+    }
+
+    #[test]
+    fn test_cursor_page() {
+        let scope = CursorScope::default();
+        let forward = Cursor::after(&Row(2), &scope);
+        let page: PagedResponse<Vec<i32>> = cursor_page(vec![Row(3), Row(4), Row(5)], &forward, 2);
+        assert_eq!(page.data, vec![3, 4]);
+        let backward = Cursor::before(&Row(5), &scope);
+        let page: PagedResponse<Vec<i32>> = cursor_page(vec![Row(4), Row(3), Row(2)], &backward, 2);
+        assert_eq!(page.data, vec![3, 4]);
+        let page: PagedResponse<Vec<i32>> = cursor_page(vec![Row(3)], &forward, 2);
+        assert_eq!(page.data, vec![3]);
+    }
 }
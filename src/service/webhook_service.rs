@@ -0,0 +1,231 @@
+use std::time::Duration;
+
+use futures_util::future::join_all;
+use serde::Serialize;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::{
+    configuration::constant::GlobalCte,
+    data::{query_dto::Pagination, webhook_dto::InputWebhookDto},
+    model::webhook_model::{
+        valid_url, InsertableWebhook, InsertableWebhookDelivery, Webhook, WebhookDelivery,
+    },
+    repository::webhook_repository::{
+        claim_due_deliveries, delete_webhook, insert_new_webhook, insert_webhook_deliveries,
+        select_active_webhooks, select_deliveries_from_webhook, select_webhook_by_id,
+        select_webhooks_from_baby, update_webhook, update_webhook_delivery,
+    },
+    response::{
+        error::{ApiError, FieldError},
+        response::{MsgResponse, PagedResponse, RecordResponse},
+    },
+    utils::{
+        datetime::now,
+        webhook::{check_destination, send_webhook, TIMEOUT_SECONDS},
+    },
+};
+
+use super::util_service::assert_record_belongs_to_parent;
+
+/// Deliveries claimed by a worker on every poll, they're sent concurrently.
+const DELIVERY_BATCH: i64 = 50;
+/// Seconds a claimed delivery is hidden from other workers while it's being sent.
+const DELIVERY_LEASE_SECONDS: i64 = 60;
+// A lease expiring mid-batch would let another worker send the same delivery.
+const _: () = assert!(DELIVERY_LEASE_SECONDS as u64 > 2 * TIMEOUT_SECONDS);
+
+/// Secret is only shown in this response, it signs every payload.
+pub async fn post_webhook_service<W>(
+    new_webhook: InputWebhookDto,
+    baby_id: i32,
+    user_id: i32,
+) -> Result<RecordResponse<W>, ApiError>
+where
+    W: From<(Webhook, Option<String>)> + Serialize,
+{
+    let url = match new_webhook.url {
+        Some(url) if valid_url(&url) => url,
+        Some(_) => return Err(invalid_url()),
+        None => return Err(ApiError::EmptyBody),
+    };
+    assert_public_destination(&url).await?;
+    let webhook = insert_new_webhook(InsertableWebhook::new(baby_id, user_id, url, now()))?;
+    let secret = webhook.secret();
+    Ok(RecordResponse::new_entry((webhook, Some(secret)).into()))
+}
+
+pub async fn patch_webhook_service<W>(
+    webhook: InputWebhookDto,
+    record: Uuid,
+    baby_id: i32,
+    user_id: i32,
+) -> Result<RecordResponse<W>, ApiError>
+where
+    W: From<Webhook> + Serialize,
+{
+    if let Some(url) = webhook.url.as_deref() {
+        if !valid_url(url) {
+            return Err(invalid_url());
+        }
+        assert_public_destination(url).await?;
+    }
+    let old_webhook = find_webhook(record, baby_id, user_id)?;
+    let updated = update_webhook(old_webhook.update_webhook(webhook))?;
+    Ok(RecordResponse::new(updated.into()))
+}
+
+pub async fn get_webhooks_service<W>(
+    baby_id: i32,
+    user_id: i32,
+    pagination: Pagination,
+) -> Result<PagedResponse<Vec<W>>, ApiError>
+where
+    W: From<Webhook> + Serialize,
+{
+    let current = pagination.page();
+    let (webhooks, total_pages) = select_webhooks_from_baby(baby_id, user_id, pagination)?;
+    let webhooks: Vec<W> = webhooks.into_iter().map(|webhook| webhook.into()).collect();
+    Ok(PagedResponse::new(webhooks, current, total_pages))
+}
+
+pub async fn get_webhook_id_service<W>(
+    record: Uuid,
+    baby_id: i32,
+    user_id: i32,
+) -> Result<RecordResponse<W>, ApiError>
+where
+    W: From<Webhook> + Serialize,
+{
+    let webhook = find_webhook(record, baby_id, user_id)?;
+    Ok(RecordResponse::new(webhook.into()))
+}
+
+pub async fn delete_webhook_service(
+    record: Uuid,
+    baby_id: i32,
+    user_id: i32,
+) -> Result<MsgResponse, ApiError> {
+    let webhook = find_webhook(record, baby_id, user_id)?;
+    delete_webhook(webhook.id())?;
+    Ok(MsgResponse::DeleteRecord)
+}
+
+pub async fn get_webhook_deliveries_service<D>(
+    record: Uuid,
+    baby_id: i32,
+    user_id: i32,
+    pagination: Pagination,
+) -> Result<PagedResponse<Vec<D>>, ApiError>
+where
+    D: From<WebhookDelivery> + Serialize,
+{
+    let webhook = find_webhook(record, baby_id, user_id)?;
+    let current = pagination.page();
+    let (deliveries, total_pages) = select_deliveries_from_webhook(webhook.id(), pagination)?;
+    let deliveries: Vec<D> = deliveries
+        .into_iter()
+        .map(|delivery| delivery.into())
+        .collect();
+    Ok(PagedResponse::new(deliveries, current, total_pages))
+}
+
+/// Queue a payload for every active webhook of a baby.
+pub fn enqueue_webhook_deliveries(
+    baby_id: i32,
+    event: &str,
+    payload: &str,
+) -> Result<usize, ApiError> {
+    enqueue_deliveries(select_active_webhooks(baby_id, None)?, event, payload)
+}
+
+/// Queue a payload only for the webhooks a user registered, for private messages like reminders.
+pub fn enqueue_user_webhook_deliveries(
+    baby_id: i32,
+    user_id: i32,
+    event: &str,
+    payload: &str,
+) -> Result<usize, ApiError> {
+    enqueue_deliveries(
+        select_active_webhooks(baby_id, Some(user_id))?,
+        event,
+        payload,
+    )
+}
+
+fn enqueue_deliveries(
+    webhooks: Vec<Webhook>,
+    event: &str,
+    payload: &str,
+) -> Result<usize, ApiError> {
+    let created_at = now();
+    let deliveries: Vec<InsertableWebhookDelivery> = webhooks
+        .iter()
+        .map(|webhook| InsertableWebhookDelivery::new(webhook.id(), event, payload, created_at))
+        .collect();
+    if deliveries.is_empty() {
+        return Ok(0);
+    }
+    Ok(insert_webhook_deliveries(deliveries)?)
+}
+
+/// Send every due delivery once, failed ones are rescheduled with backoff.
+///
+/// Sends run at the same time, so a batch takes one timeout at most and ends within its lease.
+pub async fn deliver_pending_webhooks() -> Result<usize, ApiError> {
+    let claimed_at = now();
+    let lease_until = claimed_at + chrono::Duration::seconds(DELIVERY_LEASE_SECONDS);
+    let due = claim_due_deliveries(claimed_at, lease_until, DELIVERY_BATCH)?;
+    let sent = due.len();
+    let outcomes = join_all(due.iter().map(|(delivery, webhook)| async move {
+        send_webhook(
+            &webhook.url(),
+            &webhook.secret(),
+            &delivery.event(),
+            delivery.public_id(),
+            &delivery.payload(),
+        )
+        .await
+    }))
+    .await;
+    for ((delivery, _), outcome) in due.iter().zip(outcomes) {
+        update_webhook_delivery(delivery.record_attempt(outcome, now()))?;
+    }
+    Ok(sent)
+}
+
+/// Background loop polling the delivery queue, started with the server.
+pub async fn run_webhook_worker() {
+    let period = Duration::from_secs(GlobalCte::WebhookPollSeconds.get().into());
+    let mut interval = tokio::time::interval(period);
+    loop {
+        interval.tick().await;
+        if let Err(error) = deliver_pending_webhooks().await {
+            error!("Webhook delivery failed: {error:?}");
+        }
+    }
+}
+
+/// Webhooks are private to the user that registered them.
+fn find_webhook(record: Uuid, baby_id: i32, user_id: i32) -> Result<Webhook, ApiError> {
+    let webhook = select_webhook_by_id(record)?;
+    assert_record_belongs_to_parent(webhook.baby_id(), baby_id)?;
+    assert_record_belongs_to_parent(webhook.user_id(), user_id)?;
+    Ok(webhook)
+}
+
+/// Urls reaching loopback, private or metadata addresses are refused, delivery checks again.
+async fn assert_public_destination(url: &str) -> Result<(), ApiError> {
+    check_destination(url).await.map_err(|_| {
+        FieldError::pointer(
+            "url",
+            "not_allowed",
+            "Url host must resolve to a public address.",
+        )
+        .into()
+    })
+}
+
+fn invalid_url() -> ApiError {
+    FieldError::pointer("url", "invalid", "Url must start with http:// or https://.").into()
+}
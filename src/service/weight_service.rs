@@ -1,99 +1,161 @@
 use chrono::{Days, NaiveDate};
+use uuid::Uuid;
 
 use crate::{
-    data::{common_structure::WeightDto, query_dto::Pagination, weight_dto::InputWeightDto},
-    model::weight_model::{InsertableWeight, Weight},
+    data::{
+        common_structure::{DataType, WeightDto},
+        query_dto::{ListQuery, Pagination},
+        weight_dto::{InputWeightDto, WeightFilterDto},
+    },
+    model::{
+        event_model::EventAction,
+        weight_model::{InsertableWeight, Weight},
+    },
     repository::weight_repository::{
         delete_weight, insert_new_weight, select_all_weights_from_baby, select_weight_by_id,
-        select_weights_with_pagination, update_weight,
+        select_weights_by_cursor, select_weights_by_date_range, select_weights_with_pagination,
+        update_weight,
     },
     response::{
         error::ApiError,
         response::PagedResponse,
         response::{MsgResponse, RecordResponse},
     },
-    utils::datetime::{convert_to_date, today},
+    utils::{
+        datetime::{convert_to_date, today},
+        etag::IfMatch,
+        metrics::record_created,
+    },
 };
 
-use super::util_service::assert_record_belongs_to_parent;
+use super::{
+    event_service::{publish_deleted_event, publish_record_event},
+    util_service::{
+        assert_record_belongs_to_parent, assert_version_matches, cursor_descending, cursor_page,
+        invalid_date, sorted_page, stale_record,
+    },
+};
 
 pub async fn post_weight_service(
     new_measure: InputWeightDto,
     baby_id: i32,
 ) -> Result<RecordResponse<WeightDto>, ApiError> {
     let date = match new_measure.date {
-        Some(day) => convert_to_date(&day)?,
+        Some(day) => convert_to_date(&day).map_err(|_| invalid_date("date"))?,
         None => today(),
     };
     let measure = InsertableWeight::new(baby_id, date, new_measure.value.unwrap_or_default());
-    let entry: Weight = insert_new_weight(measure)?;
-    let response: RecordResponse<WeightDto> = RecordResponse::new_entry(entry.into());
+    let entry: WeightDto = insert_new_weight(measure)?.into();
+    record_created("weights");
+    publish_record_event(baby_id, EventAction::Created, &entry).await;
+    let response: RecordResponse<WeightDto> = RecordResponse::new_entry(entry);
     Ok(response)
 }
 
 pub async fn get_weights_all_service(
     baby_id: i32,
+    list: ListQuery,
     pagination: Pagination,
 ) -> Result<PagedResponse<Vec<WeightDto>>, ApiError> {
+    let filter = WeightFilterDto::try_from(&list)?;
+    let fields = list.fields(DataType::Weight.get());
+    let scope = list.scope(None);
+    if let Some(cursor) = pagination.cursor(&scope)? {
+        let descending = cursor_descending(filter.sort())?;
+        let per_page = pagination.per_page();
+        let measures =
+            select_weights_by_cursor(baby_id, None, &filter, &cursor, descending, per_page)?;
+        return Ok(cursor_page(measures, &cursor, per_page).with_fields(fields));
+    }
     let current = pagination.page();
-    let (measures, total_pages) = select_all_weights_from_baby(baby_id, pagination)?;
-    let measures = into_weight_dto(measures)?;
-    let response = PagedResponse::new(measures, current, total_pages);
-    Ok(response)
+    let (measures, total_pages) = select_all_weights_from_baby(baby_id, &filter, pagination)?;
+    Ok(sorted_page(measures, current, total_pages, filter.sort(), &scope).with_fields(fields))
 }
 
 pub async fn get_weight_range_service(
     baby_id: i32,
     from: NaiveDate,
     to: NaiveDate,
+    list: ListQuery,
     pagination: Pagination,
 ) -> Result<PagedResponse<Vec<WeightDto>>, ApiError> {
+    let filter = WeightFilterDto::try_from(&list)?;
+    let fields = list.fields(DataType::Weight.get());
+    let range = Some((from, to));
+    let scope = list.scope(range);
+    if let Some(cursor) = pagination.cursor(&scope)? {
+        let descending = cursor_descending(filter.sort())?;
+        let per_page = pagination.per_page();
+        let measures =
+            select_weights_by_cursor(baby_id, range, &filter, &cursor, descending, per_page)?;
+        return Ok(cursor_page(measures, &cursor, per_page).with_fields(fields));
+    }
     let current = pagination.page();
-    let (measures, total_pages) = select_weights_with_pagination(baby_id, from, to, pagination)?;
-    let measures = into_weight_dto(measures)?;
-    let response = PagedResponse::new(measures, current, total_pages);
-    Ok(response)
+    let (measures, total_pages) =
+        select_weights_with_pagination(baby_id, from, to, &filter, pagination)?;
+    Ok(sorted_page(measures, current, total_pages, filter.sort(), &scope).with_fields(fields))
 }
 
 pub async fn get_weights_by_last_days(
     baby_id: i32,
     last_days: u32,
+    list: ListQuery,
     pagination: Pagination,
 ) -> Result<PagedResponse<Vec<WeightDto>>, ApiError> {
     let today = today();
     let from = today.checked_sub_days(Days::new(last_days.into())).unwrap();
-    get_weight_range_service(baby_id, from, today, pagination).await
+    get_weight_range_service(baby_id, from, today, list, pagination).await
 }
 
+/// Stale `If-Match` or a concurrent update fail with 412, a required `If-Match` missing with 428.
 pub async fn patch_weight_service(
     measure: InputWeightDto,
-    record: i32,
+    record: Uuid,
     baby_id: i32,
+    if_match: IfMatch,
 ) -> Result<RecordResponse<WeightDto>, ApiError> {
     let old_record = select_weight_by_id(record)?;
     assert_record_belongs_to_parent(old_record.baby_id(), baby_id)?;
-    let weight: Weight = update_weight(old_record.update_weight(measure))?;
-    let response: RecordResponse<WeightDto> = RecordResponse::new(weight.into());
+    assert_version_matches(&if_match, old_record.version())?;
+    let updated = update_weight(old_record.update_weight(measure)).map_err(stale_record)?;
+    let version = updated.version();
+    let weight: WeightDto = updated.into();
+    publish_record_event(baby_id, EventAction::Updated, &weight).await;
+    let response: RecordResponse<WeightDto> = RecordResponse::new(weight).with_version(version);
     Ok(response)
 }
 
-fn into_weight_dto(measures: Vec<Weight>) -> Result<Vec<WeightDto>, ApiError> {
-    Ok(measures.into_iter().map(|measure| measure.into()).collect())
+/// All measures in a range of dates, without pagination.
+pub(crate) fn fetch_weights_range(
+    baby_id: i32,
+    from: NaiveDate,
+    to: NaiveDate,
+) -> Result<Vec<Weight>, ApiError> {
+    let measures = select_weights_by_date_range(baby_id, from, to)?;
+    Ok(measures)
 }
 
-pub async fn delete_weight_service(record: i32, baby_id: i32) -> Result<MsgResponse, ApiError> {
+pub async fn delete_weight_service(
+    record: Uuid,
+    baby_id: i32,
+    if_match: IfMatch,
+) -> Result<MsgResponse, ApiError> {
     let delete_record = select_weight_by_id(record)?;
     assert_record_belongs_to_parent(delete_record.baby_id(), baby_id)?;
-    delete_weight(record)?;
+    assert_version_matches(&if_match, delete_record.version())?;
+    delete_weight(&delete_record).map_err(stale_record)?;
+    publish_deleted_event(baby_id, DataType::Weight, record).await;
     Ok(MsgResponse::DeleteRecord)
 }
 
 pub async fn get_weight_id_service(
-    weight_id: i32,
+    weight_id: Uuid,
     baby_id: i32,
 ) -> Result<RecordResponse<WeightDto>, ApiError> {
     let weight: Weight = select_weight_by_id(weight_id)?;
     assert_record_belongs_to_parent(weight.baby_id(), baby_id)?;
-    let response: RecordResponse<WeightDto> = RecordResponse::new(weight.into());
+    let version = weight.version();
+    let response: RecordResponse<WeightDto> =
+        RecordResponse::new(weight.into()).with_version(version);
     Ok(response)
 }
@@ -31,8 +31,13 @@ pub async fn set_anonymous_user() -> Result<(), ApiError> {
     if user_exists_in_session(id).await? {
         return Ok(());
     } else {
-        let anonymous_id: i32 = GlobalCte::DefaultAnonymousID.get().try_into().unwrap();
-        let user = read_user_from_db(anonymous_id).await?;
-        save_user_indefinitely(&user).await
+        refresh_anonymous_user().await
     }
 }
+
+/// Cache the anonymous user again, even if it's already in Redis.
+pub async fn refresh_anonymous_user() -> Result<(), ApiError> {
+    let anonymous_id: i32 = GlobalCte::DefaultAnonymousID.get().try_into().unwrap();
+    let user = read_user_from_db(anonymous_id).await?;
+    save_user_indefinitely(&user).await
+}
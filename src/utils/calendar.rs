@@ -0,0 +1,108 @@
+use chrono::{NaiveDateTime, Utc};
+
+use crate::model::calendar_model::CalendarEvent;
+
+const CALENDAR_TIMESTAMP: &str = "%Y%m%dT%H%M%S";
+
+/// Render an iCalendar (RFC 5545) document.
+///
+/// Times are floating, calendar clients show them as they were recorded.
+pub fn render_calendar(name: &str, events: &[CalendarEvent]) -> String {
+    let stamp = format!("{}Z", Utc::now().format(CALENDAR_TIMESTAMP));
+    let mut lines: Vec<String> = vec![
+        String::from("BEGIN:VCALENDAR"),
+        String::from("VERSION:2.0"),
+        String::from("PRODID:-//nighty_night//calendar feed//EN"),
+        String::from("CALSCALE:GREGORIAN"),
+        String::from("METHOD:PUBLISH"),
+        format!("X-WR-CALNAME:{}", escape_text(name)),
+    ];
+    for event in events {
+        lines.push(String::from("BEGIN:VEVENT"));
+        lines.push(format!("UID:{}", event.uid()));
+        lines.push(format!("DTSTAMP:{stamp}"));
+        lines.push(format!("DTSTART:{}", format_timestamp(event.start())));
+        lines.push(format!("DTEND:{}", format_timestamp(event.end())));
+        lines.push(format!("SUMMARY:{}", escape_text(&event.summary())));
+        if event.ongoing() {
+            lines.push(String::from("STATUS:TENTATIVE"));
+        }
+        lines.push(String::from("END:VEVENT"));
+    }
+    lines.push(String::from("END:VCALENDAR"));
+    lines
+        .into_iter()
+        .map(|line| fold_line(&line))
+        .collect::<Vec<String>>()
+        .join("\r\n")
+        + "\r\n"
+}
+
+fn format_timestamp(timestamp: NaiveDateTime) -> String {
+    timestamp.format(CALENDAR_TIMESTAMP).to_string()
+}
+
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// Lines longer than 75 octets must be split, continuation lines start with a space.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::new();
+    let mut length = 0;
+    for character in line.chars() {
+        let size = character.len_utf8();
+        if length + size > 75 {
+            folded.push_str("\r\n ");
+            length = 1;
+        }
+        folded.push(character);
+        length += size;
+    }
+    folded
+}
+
+#[cfg(test)]
+mod test_calendar {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape_text("a,b;c\\d"), "a\\,b\\;c\\\\d");
+    }
+
+    #[test]
+    fn test_fold_line() {
+        let short = "SUMMARY:Sleep";
+        assert_eq!(fold_line(short), short);
+        let long = "X".repeat(80);
+        let folded = fold_line(&long);
+        assert_eq!(folded, format!("{}\r\n {}", "X".repeat(75), "X".repeat(5)));
+    }
+
+    #[test]
+    fn test_render_event() {
+        let start = NaiveDate::from_ymd_opt(2023, 6, 1)
+            .unwrap()
+            .and_hms_opt(8, 30, 0)
+            .unwrap();
+        let end = start + chrono::Duration::minutes(45);
+        let event = CalendarEvent::new(
+            "dream-1@nighty_night".to_string(),
+            "Sleep".to_string(),
+            start,
+            Some(end),
+        );
+        let calendar = render_calendar("Baby", &[event]);
+        assert!(calendar.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(calendar.contains("DTSTART:20230601T083000\r\n"));
+        assert!(calendar.contains("DTEND:20230601T091500\r\n"));
+        assert!(calendar.contains("SUMMARY:Sleep\r\n"));
+        assert!(calendar.ends_with("END:VCALENDAR\r\n"));
+    }
+}
@@ -0,0 +1,217 @@
+use std::{fmt::Display, str::FromStr};
+
+use chrono::NaiveDateTime;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::response::error::{ApiError, FieldError};
+
+/// Records listed by keyset pagination, ordered by timestamp and then id.
+pub trait Keyset {
+    /// Id clients already see, cursors never carry an internal key.
+    type Id: Copy + Display + FromStr;
+
+    fn keyset(&self) -> (NaiveDateTime, Self::Id);
+}
+
+/// Filters, sort and dates of a list, a cursor only pages the list it was issued for.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CursorScope(String);
+
+impl CursorScope {
+    /// Digest of `parts`, without parts it is the default scope.
+    pub fn new<I, S>(parts: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut parts = parts.into_iter().peekable();
+        if parts.peek().is_none() {
+            return Self::default();
+        }
+        let mut hasher = Sha256::new();
+        for part in parts {
+            hasher.update(part.as_ref().as_bytes());
+            hasher.update([0]);
+        }
+        let digest = hasher.finalize();
+        Self(
+            digest[..8]
+                .iter()
+                .map(|byte| format!("{byte:02x}"))
+                .collect(),
+        )
+    }
+}
+
+/// Position in a keyset ordered list, sent to clients as an opaque string.
+#[derive(Debug, PartialEq)]
+pub struct Cursor<I = Uuid> {
+    forward: bool,
+    timestamp: NaiveDateTime,
+    id: I,
+    scope: CursorScope,
+}
+
+impl<I: Copy + Display + FromStr> Cursor<I> {
+    /// Records following `record`.
+    pub fn after<T: Keyset<Id = I>>(record: &T, scope: &CursorScope) -> Self {
+        let (timestamp, id) = record.keyset();
+        Self {
+            forward: true,
+            timestamp,
+            id,
+            scope: scope.clone(),
+        }
+    }
+
+    /// Records preceding `record`.
+    pub fn before<T: Keyset<Id = I>>(record: &T, scope: &CursorScope) -> Self {
+        let (timestamp, id) = record.keyset();
+        Self {
+            forward: false,
+            timestamp,
+            id,
+            scope: scope.clone(),
+        }
+    }
+
+    pub fn is_forward(&self) -> bool {
+        self.forward
+    }
+
+    pub fn timestamp(&self) -> NaiveDateTime {
+        self.timestamp
+    }
+
+    pub fn id(&self) -> I {
+        self.id
+    }
+
+    pub fn scope(&self) -> &CursorScope {
+        &self.scope
+    }
+
+    /// Hex encoded, so it can go in a query string as is.
+    pub fn encode(&self) -> String {
+        let direction = if self.forward { 'a' } else { 'b' };
+        let raw = format!(
+            "{direction}{}:{}:{}",
+            self.timestamp.timestamp_micros(),
+            self.id,
+            self.scope.0
+        );
+        raw.bytes().map(|byte| format!("{byte:02x}")).collect()
+    }
+
+    /// Fails if the cursor was issued for a list with other filters, sort or dates.
+    pub fn decode(value: &str, scope: &CursorScope) -> Result<Self, ApiError> {
+        let cursor = Self::parse(value)
+            .ok_or_else(|| FieldError::parameter("cursor", "invalid", "Invalid cursor."))?;
+        if cursor.scope.ne(scope) {
+            return Err(FieldError::parameter(
+                "cursor",
+                "not_allowed",
+                "Cursor belongs to a list with other filters, sort or dates.",
+            )
+            .into());
+        }
+        Ok(cursor)
+    }
+
+    fn parse(value: &str) -> Option<Self> {
+        if value.len() % 2 != 0 {
+            return None;
+        }
+        let bytes = (0..value.len())
+            .step_by(2)
+            .map(|index| u8::from_str_radix(value.get(index..index + 2)?, 16).ok())
+            .collect::<Option<Vec<u8>>>()?;
+        let raw = String::from_utf8(bytes).ok()?;
+        let forward = match raw.get(..1)? {
+            "a" => true,
+            "b" => false,
+            _ => return None,
+        };
+        let mut parts = raw.get(1..)?.splitn(3, ':');
+        let timestamp = NaiveDateTime::from_timestamp_micros(parts.next()?.parse().ok()?)?;
+        let id = parts.next()?.parse().ok()?;
+        let scope = CursorScope(parts.next()?.to_string());
+        Some(Self {
+            forward,
+            timestamp,
+            id,
+            scope,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test_cursor {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    struct Row(NaiveDateTime, Uuid);
+
+    impl Keyset for Row {
+        type Id = Uuid;
+
+        fn keyset(&self) -> (NaiveDateTime, Uuid) {
+            (self.0, self.1)
+        }
+    }
+
+    #[test]
+    fn test_cursor_round_trip() {
+        let timestamp = NaiveDate::from_ymd_opt(2024, 1, 15)
+            .unwrap()
+            .and_hms_micro_opt(10, 30, 5, 120)
+            .unwrap();
+        let row = Row(timestamp, Uuid::new_v4());
+        let scope = CursorScope::new(["quantity_gte=60"]);
+        let after = Cursor::after(&row, &scope);
+        let before = Cursor::before(&row, &scope);
+        assert_eq!(Cursor::decode(&after.encode(), &scope).unwrap(), after);
+        assert_eq!(Cursor::decode(&before.encode(), &scope).unwrap(), before);
+        assert!(after.is_forward());
+        assert!(!before.is_forward());
+        assert_ne!(after.encode(), before.encode());
+    }
+
+    #[test]
+    fn test_cursor_scope() {
+        let timestamp = NaiveDate::from_ymd_opt(2024, 1, 15)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let row = Row(timestamp, Uuid::new_v4());
+        let scope = CursorScope::new(["quantity_gte=60"]);
+        let cursor = Cursor::after(&row, &scope).encode();
+        assert!(Cursor::<Uuid>::decode(&cursor, &scope).is_ok());
+        assert!(Cursor::<Uuid>::decode(&cursor, &CursorScope::default()).is_err());
+        let other = CursorScope::new(["quantity_gte=90"]);
+        assert!(Cursor::<Uuid>::decode(&cursor, &other).is_err());
+        assert_ne!(CursorScope::new(["ab", "c"]), CursorScope::new(["a", "bc"]));
+        assert_eq!(CursorScope::new(Vec::<String>::new()), CursorScope::default());
+    }
+
+    #[test]
+    fn test_invalid_cursor() {
+        let scope = CursorScope::default();
+        let id = Uuid::new_v4();
+        let decode = |raw: &str| Cursor::<Uuid>::decode(&hex(raw), &scope);
+        assert!(Cursor::<Uuid>::decode("", &scope).is_err());
+        assert!(Cursor::<Uuid>::decode("abc", &scope).is_err());
+        assert!(Cursor::<Uuid>::decode("zz", &scope).is_err());
+        assert!(decode(&format!("c1:{id}:")).is_err());
+        assert!(decode("a1").is_err());
+        assert!(decode("a1:1:").is_err());
+        assert!(decode(&format!("a1:{id}")).is_err());
+        assert!(decode(&format!("a1:{id}:")).is_ok());
+    }
+
+    fn hex(raw: &str) -> String {
+        raw.bytes().map(|byte| format!("{byte:02x}")).collect()
+    }
+}
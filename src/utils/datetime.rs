@@ -1,4 +1,4 @@
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
 
 pub fn now() -> NaiveDateTime {
     Utc::now().naive_local()
@@ -8,8 +8,23 @@ pub fn today() -> NaiveDate {
     now().date()
 }
 
+/// Timestamps are stored without offset, in UTC like [now].
+pub fn as_utc(date_time: NaiveDateTime) -> DateTime<Utc> {
+    Utc.from_utc_datetime(&date_time)
+}
+
+/// RFC 3339 with any offset, or `YYYY-mm-dd HH:MM` already in UTC.
+///
+/// Either way the timestamp is kept in UTC, seconds included.
 pub fn convert_to_date_time(date_time: &str) -> Result<NaiveDateTime, chrono::ParseError> {
-    NaiveDateTime::parse_from_str(&date_time, "%Y-%m-%d %H:%M")
+    match DateTime::parse_from_rfc3339(date_time) {
+        Ok(date_time) => Ok(date_time.naive_utc()),
+        Err(_) => NaiveDateTime::parse_from_str(&date_time, "%Y-%m-%d %H:%M"),
+    }
+}
+
+pub fn convert_to_time(time: &str) -> Result<NaiveTime, chrono::ParseError> {
+    NaiveTime::parse_from_str(&time, "%H:%M")
 }
 
 pub fn convert_to_date(date: &str) -> Result<NaiveDate, chrono::ParseError> {
@@ -24,6 +39,10 @@ pub fn format_time(time: NaiveTime) -> String {
     time.format("%H:%M").to_string()
 }
 
+pub fn format_date_time(date_time: NaiveDateTime) -> String {
+    date_time.format("%Y-%m-%d %H:%M").to_string()
+}
+
 /// From date is a past date of the other date (future_date)
 pub fn date_time_is_lower_than_other_date(
     from_date: NaiveDateTime,
@@ -131,6 +150,24 @@ mod test_timestamp {
         );
     }
 
+    #[test]
+    fn test_parse_rfc_3339() {
+        let expected = NaiveDate::from_ymd_opt(2023, 3, 23)
+            .unwrap()
+            .and_hms_opt(21, 31, 15)
+            .unwrap();
+        assert_eq!(
+            convert_to_date_time("2023-03-23T21:31:15Z").unwrap(),
+            expected
+        );
+        assert_eq!(
+            convert_to_date_time("2023-03-23T23:31:15+02:00").unwrap(),
+            expected
+        );
+        assert!(convert_to_date_time("2023-03-23T23:31").is_err());
+        assert_eq!(as_utc(expected).to_rfc3339(), "2023-03-23T21:31:15+00:00");
+    }
+
     #[test]
     fn test_compare_dates() {
         assert!(date_time_is_lower_than_other_date(
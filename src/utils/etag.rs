@@ -0,0 +1,103 @@
+use std::convert::Infallible;
+
+use axum::{async_trait, extract::FromRequestParts, http::request::Parts};
+use hyper::header;
+
+use super::version::ApiVersion;
+
+/// Record version as sent in `ETag`.
+pub fn format_etag(version: i32) -> String {
+    format!("\"{version}\"")
+}
+
+/// `If-Match` request header, missing header or `*` matches any version.
+///
+/// v2 requires the header, writes without it are refused with 428.
+#[derive(Default)]
+pub struct IfMatch {
+    value: Option<String>,
+    required: bool,
+}
+
+impl IfMatch {
+    pub fn new(value: Option<String>) -> Self {
+        Self {
+            value,
+            required: false,
+        }
+    }
+
+    pub fn required(value: Option<String>) -> Self {
+        Self {
+            value,
+            required: true,
+        }
+    }
+
+    /// Required header wasn't sent.
+    pub fn is_missing(&self) -> bool {
+        self.required && self.value.is_none()
+    }
+
+    /// Weak tags are compared as strong ones, every representation of a version is the same.
+    pub fn matches(&self, version: i32) -> bool {
+        let value = match &self.value {
+            Some(value) => value,
+            None => return true,
+        };
+        let etag = format_etag(version);
+        value
+            .split(',')
+            .map(str::trim)
+            .any(|tag| tag.eq("*") || tag.trim_start_matches("W/").eq(&etag))
+    }
+}
+
+#[async_trait]
+impl<S> FromRequestParts<S> for IfMatch
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let value = parts
+            .headers
+            .get(header::IF_MATCH)
+            .map(|value| value.to_str().unwrap_or_default().to_string());
+        match parts.extensions.get::<ApiVersion>() {
+            Some(ApiVersion::V2) => Ok(Self::required(value)),
+            _ => Ok(Self::new(value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_etag {
+    use super::*;
+
+    #[test]
+    fn test_format_etag() {
+        assert_eq!(format_etag(3), "\"3\"");
+    }
+
+    #[test]
+    fn test_if_match() {
+        assert!(IfMatch::default().matches(2));
+        assert!(IfMatch::new(Some("*".into())).matches(2));
+        assert!(IfMatch::new(Some("\"2\"".into())).matches(2));
+        assert!(IfMatch::new(Some("W/\"2\"".into())).matches(2));
+        assert!(IfMatch::new(Some("\"1\", \"2\"".into())).matches(2));
+        assert!(!IfMatch::new(Some("\"1\"".into())).matches(2));
+        assert!(!IfMatch::new(Some("2".into())).matches(2));
+        assert!(!IfMatch::new(Some(String::new())).matches(2));
+    }
+
+    #[test]
+    fn test_if_match_required() {
+        assert!(IfMatch::required(None).is_missing());
+        assert!(!IfMatch::required(Some("\"2\"".into())).is_missing());
+        assert!(!IfMatch::new(None).is_missing());
+        assert!(!IfMatch::default().is_missing());
+    }
+}
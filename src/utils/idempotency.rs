@@ -0,0 +1,181 @@
+use axum::{
+    body::{boxed, Full},
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use axum_session::SessionRedisPool;
+use axum_session_auth::AuthSession;
+use hyper::StatusCode;
+use tracing::error;
+
+use crate::{
+    model::{idempotency_model::IdempotentResponse, session_model::CurrentUser},
+    response::error::{ApiError, ErrorBody},
+    service::idempotency_service::{begin_idempotent_request, finish_idempotent_request},
+};
+
+const IDEMPOTENCY_HEADER: &str = "idempotency-key";
+const REPLAYED_HEADER: &str = "idempotent-replayed";
+const MAX_KEY_LENGTH: usize = 255;
+/// Set again for every response, they aren't stored with the replay. Cookies belong to the
+/// session of the first request and are never replayed.
+const SKIPPED_HEADERS: [header::HeaderName; 5] = [
+    header::CONNECTION,
+    header::CONTENT_LENGTH,
+    header::DATE,
+    header::SET_COOKIE,
+    header::TRANSFER_ENCODING,
+];
+
+/// Requests of a signed in user that change data and carry an `Idempotency-Key` run once per
+/// key.
+///
+/// Later requests with the same key get the first response back. Anonymous requests share no
+/// user to scope the key by, they always run.
+pub async fn idempotent_request<B>(
+    auth: AuthSession<CurrentUser, i64, SessionRedisPool, redis::Client>,
+    request: Request<B>,
+    next: Next<B>,
+) -> Response {
+    if request.method().eq(&Method::GET) || auth.is_anonymous() {
+        return next.run(request).await;
+    }
+    let key = match request.headers().get(IDEMPOTENCY_HEADER) {
+        Some(value) => match value.to_str() {
+            Ok(key) if !key.is_empty() && key.len().le(&MAX_KEY_LENGTH) => key.to_string(),
+            _ => {
+                return ApiError::Generic400Error("Invalid idempotency key.".into()).into_response()
+            }
+        },
+        None => return next.run(request).await,
+    };
+    let fingerprint = format!("{} {}", request.method(), request.uri().path());
+    match begin_idempotent_request(auth.id, &key, &fingerprint).await {
+        Ok(Some(stored)) => return replay_response(stored),
+        Ok(None) => (),
+        Err(error) => return error.into_response(),
+    }
+    let (parts, body) = next.run(request).await.into_parts();
+    let bytes = match hyper::body::to_bytes(body).await {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            error!("Can't read response body: {err}");
+            return ApiError::Generic500Error("Can't read response.".into()).into_response();
+        }
+    };
+    let stored = finish_idempotent_request(
+        auth.id,
+        &key,
+        &fingerprint,
+        parts.status.as_u16(),
+        stored_headers(&parts.headers),
+        &String::from_utf8_lossy(&bytes),
+        parts
+            .extensions
+            .get::<ErrorBody>()
+            .map(|error| error.detail().to_string()),
+    )
+    .await;
+    if let Err(err) = stored {
+        error!("Can't store idempotent response: {err}");
+    }
+    Response::from_parts(parts, boxed(Full::from(bytes)))
+}
+
+fn stored_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .filter(|(name, _)| !SKIPPED_HEADERS.contains(name))
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect()
+}
+
+/// First response with its headers, like `Location` or `ETag`.
+///
+/// Errors get their [ErrorBody] back, `/api/v1` replays the same body as the first time.
+fn replay_response(stored: IdempotentResponse) -> Response {
+    let status = StatusCode::from_u16(stored.status()).unwrap_or(StatusCode::OK);
+    let mut response = (status, stored.body()).into_response();
+    if let Some(detail) = stored.error_detail() {
+        response
+            .extensions_mut()
+            .insert(ErrorBody::new(status, detail.to_string()));
+    }
+    let headers = response.headers_mut();
+    headers.insert(
+        header::CONTENT_TYPE,
+        HeaderValue::from_static("application/json"),
+    );
+    let mut replayed = HeaderMap::new();
+    for (name, value) in stored.headers() {
+        if SKIPPED_HEADERS
+            .iter()
+            .any(|skipped| skipped.as_str().eq(name))
+        {
+            continue;
+        }
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.as_bytes()),
+            HeaderValue::from_str(value),
+        ) {
+            replayed.append(name, value);
+        }
+    }
+    // Stored values replace the defaults, repeated names like `Link` are all kept.
+    headers.extend(replayed);
+    headers.insert(REPLAYED_HEADER, HeaderValue::from_static("true"));
+    response
+}
+
+#[cfg(test)]
+mod test_idempotency {
+    use super::*;
+
+    #[test]
+    fn test_stored_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ETAG, HeaderValue::from_static("\"1\""));
+        headers.insert(header::SET_COOKIE, HeaderValue::from_static("session=1"));
+        headers.insert(header::CONTENT_LENGTH, HeaderValue::from_static("2"));
+        assert_eq!(
+            stored_headers(&headers),
+            [("etag".to_string(), "\"1\"".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_replay_without_cookie() {
+        let headers = vec![
+            ("location".to_string(), "/api/baby/1".to_string()),
+            ("set-cookie".to_string(), "session=1".to_string()),
+        ];
+        let stored = IdempotentResponse::completed("POST /api/baby", 201, headers, "{}", None);
+        let response = replay_response(stored);
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.headers()[header::LOCATION], "/api/baby/1");
+        assert!(!response.headers().contains_key(header::SET_COOKIE));
+        assert!(response.extensions().get::<ErrorBody>().is_none());
+    }
+
+    #[test]
+    fn test_replay_v1_error() {
+        let error = ApiError::Generic400Error("Invalid baby.".into()).into_response();
+        let detail = error.extensions().get::<ErrorBody>().unwrap().detail();
+        let stored = IdempotentResponse::completed(
+            "POST /api/baby",
+            400,
+            Vec::new(),
+            "{}",
+            Some(detail.to_string()),
+        );
+        let response = replay_response(stored);
+        let body = response.extensions().get::<ErrorBody>().unwrap();
+        assert_eq!(
+            serde_json::to_value(body).unwrap(),
+            serde_json::json!({
+                "errors": {"code": 400, "title": "Bad Request", "detail": "Invalid baby."}
+            })
+        );
+    }
+}
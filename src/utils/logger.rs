@@ -1,21 +1,61 @@
-use tracing::Level;
-use tracing_subscriber::FmtSubscriber;
+use opentelemetry::{
+    global,
+    sdk::{trace, Resource},
+    trace::TraceError,
+    KeyValue,
+};
+use opentelemetry_otlp::WithExportConfig;
+use tracing::{info, Level};
+use tracing_subscriber::{filter::LevelFilter, fmt, layer::SubscriberExt};
 
-use crate::configuration::settings::Setting;
+use crate::configuration::config::config;
 
+/// Text or JSON lines, plus spans exported over OTLP when an endpoint is set.
 pub fn setup_logger() {
     let level = set_level();
-    let subscriber = FmtSubscriber::builder()
-        .with_target(false)
-        .with_max_level(level)
-        .finish();
+    let logger = &config().logger;
+    let json = logger.format.eq("json");
+    let tracer = logger.otlp_endpoint.as_ref().and_then(|endpoint| {
+        otlp_tracer(endpoint)
+            .map_err(|err| eprintln!("OpenTelemetry exporter disabled: {err}"))
+            .ok()
+    });
+    let subscriber = tracing_subscriber::registry()
+        .with(LevelFilter::from_level(level))
+        .with(tracer.map(|tracer| tracing_opentelemetry::layer().with_tracer(tracer)))
+        .with(json.then(|| fmt::layer().json().with_current_span(true)))
+        .with((!json).then(|| fmt::layer().with_target(false)));
     tracing::subscriber::set_global_default(subscriber).expect("setting default subscriber failed");
+    if let Some(endpoint) = &logger.otlp_endpoint {
+        info!("Exporting traces to {endpoint}");
+    }
+}
+
+/// Batch exporter over OTLP/HTTP, spans are sent to `{endpoint}/v1/traces`.
+pub fn otlp_tracer(endpoint: &str) -> Result<trace::Tracer, TraceError> {
+    opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .http()
+                .with_endpoint(endpoint),
+        )
+        .with_trace_config(
+            trace::config().with_resource(Resource::new(vec![KeyValue::new(
+                "service.name",
+                env!("CARGO_PKG_NAME"),
+            )])),
+        )
+        .install_batch(opentelemetry::runtime::Tokio)
+}
+
+/// Flush pending spans, call it once the server stops.
+pub fn shutdown_logger() {
+    global::shutdown_tracer_provider();
 }
 
 fn set_level() -> Level {
-    let binding = Setting::LoggerLevel.get();
-    let input = binding.as_str();
-    match input {
+    match config().logger.level.as_str() {
         "debug" => Level::DEBUG,
         "info" => Level::INFO,
         "error" => Level::ERROR,
@@ -23,4 +63,4 @@ fn set_level() -> Level {
         "trace" => Level::TRACE,
         _ => Level::DEBUG,
     }
-}
\ No newline at end of file
+}
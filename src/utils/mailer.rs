@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use axum::async_trait;
+use lettre::{
+    message::{header::ContentType, Mailbox},
+    transport::smtp::authentication::Credentials,
+    AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use tracing::info;
+
+use crate::configuration::config::MailConfig;
+
+/// Outgoing email, implement it to plug a real provider in.
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), String>;
+}
+
+/// Mailer without `mail.host`, it only writes to the log that an email was sent.
+///
+/// Addresses are masked and messages left out, logs must not hold personal data.
+pub struct LogMailer;
+
+#[async_trait]
+impl Mailer for LogMailer {
+    async fn send(&self, to: &str, _subject: &str, body: &str) -> Result<(), String> {
+        info!("Email to {}, {} characters", redact_email(to), body.len());
+        Ok(())
+    }
+}
+
+/// Plain text emails through the SMTP server in `[mail]`, upgraded with STARTTLS.
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    pub fn new(host: &str, config: &MailConfig) -> Result<Self, String> {
+        let from: Mailbox = config
+            .from
+            .parse()
+            .map_err(|error| format!("mail.from: {error}"))?;
+        let mut transport = AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(host)
+            .map_err(|error| format!("mail.host: {error}"))?
+            .port(config.port);
+        if !config.user.is_empty() {
+            transport = transport.credentials(Credentials::new(
+                config.user.to_string(),
+                config.password.to_string(),
+            ));
+        }
+        Ok(Self {
+            transport: transport.build(),
+            from,
+        })
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send(&self, to: &str, subject: &str, body: &str) -> Result<(), String> {
+        let to: Mailbox = to
+            .parse()
+            .map_err(|_| format!("Invalid address {}", redact_email(to)))?;
+        let message = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject(subject)
+            .header(ContentType::TEXT_PLAIN)
+            .body(body.to_string())
+            .map_err(|error| error.to_string())?;
+        self.transport
+            .send(message)
+            .await
+            .map(|_| ())
+            .map_err(|error| error.to_string())
+    }
+}
+
+/// SMTP mailer when `mail.host` is set, emails are only logged otherwise.
+pub fn mailer_from_config(config: &MailConfig) -> Result<Arc<dyn Mailer>, String> {
+    match &config.host {
+        Some(host) => Ok(Arc::new(SmtpMailer::new(host, config)?)),
+        None => Ok(Arc::new(LogMailer)),
+    }
+}
+
+/// First letter and domain are kept, `j***@example.com`.
+fn redact_email(email: &str) -> String {
+    match email.split_once('@') {
+        Some((name, domain)) => {
+            let first: String = name.chars().take(1).collect();
+            format!("{first}***@{domain}")
+        }
+        None => "***".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test_mailer {
+    use super::*;
+
+    #[test]
+    fn test_redact_email() {
+        assert_eq!(redact_email("jane.doe@example.com"), "j***@example.com");
+        assert_eq!(redact_email("@example.com"), "***@example.com");
+        assert_eq!(redact_email("not an email"), "***");
+    }
+
+    #[test]
+    fn test_smtp_mailer_from_config() {
+        let config = MailConfig {
+            host: Some("smtp.example.com".to_string()),
+            from: "Nighty Night <reminders@example.com>".to_string(),
+            ..Default::default()
+        };
+        assert!(mailer_from_config(&config).is_ok());
+        let invalid = MailConfig {
+            from: "reminders".to_string(),
+            ..config
+        };
+        assert!(mailer_from_config(&invalid)
+            .err()
+            .is_some_and(|error| error.starts_with("mail.from")));
+        assert!(mailer_from_config(&MailConfig::default()).is_ok());
+    }
+}
@@ -0,0 +1,140 @@
+use std::{sync::OnceLock, time::Instant};
+
+use axum::{extract::MatchedPath, middleware::Next, response::Response};
+use hyper::Request;
+use metrics::{histogram, increment_counter};
+use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+
+static PROMETHEUS: OnceLock<PrometheusHandle> = OnceLock::new();
+
+/// Seconds, shared by HTTP and database histograms.
+const LATENCY_BUCKETS: [f64; 12] = [
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// Install the global Prometheus recorder, later calls are ignored.
+pub fn setup_metrics() {
+    PROMETHEUS.get_or_init(|| {
+        PrometheusBuilder::new()
+            .set_buckets(&LATENCY_BUCKETS)
+            .expect("Latency buckets should not be empty")
+            .install_recorder()
+            .expect("Metrics recorder should be installed only once")
+    });
+}
+
+/// Text exposition format, empty until `setup_metrics` is called.
+pub fn render_metrics() -> String {
+    PROMETHEUS
+        .get()
+        .map(PrometheusHandle::render)
+        .unwrap_or_default()
+}
+
+/// Count and time every request by route template, method and status.
+pub async fn track_metrics<B>(request: Request<B>, next: Next<B>) -> Response {
+    let start = Instant::now();
+    let path = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map(|matched| matched.as_str().to_owned())
+        .unwrap_or_else(|| String::from("unmatched"));
+    let method = request.method().to_string();
+
+    let response = next.run(request).await;
+
+    let labels = [
+        ("method", method),
+        ("path", path),
+        ("status", response.status().as_u16().to_string()),
+    ];
+    increment_counter!("http_requests_total", labels.as_slice());
+    histogram!(
+        "http_request_duration_seconds",
+        start.elapsed().as_secs_f64(),
+        labels.as_slice()
+    );
+    response
+}
+
+/// Records how long a repository function held its connection once dropped.
+pub struct QueryTimer {
+    repository: &'static str,
+    function: &'static str,
+    start: Instant,
+}
+
+impl QueryTimer {
+    /// `path` is the full function path, e.g. `nighty_night::repository::meal_repository::insert_new_meal`.
+    pub fn new(path: &'static str) -> Self {
+        let (repository, function) = split_function_path(path);
+        Self {
+            repository,
+            function,
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Drop for QueryTimer {
+    fn drop(&mut self) {
+        histogram!(
+            "db_query_duration_seconds",
+            self.start.elapsed().as_secs_f64(),
+            "repository" => self.repository,
+            "function" => self.function
+        );
+    }
+}
+
+fn split_function_path(path: &'static str) -> (&'static str, &'static str) {
+    let mut segments = path.rsplit("::");
+    let function = segments.next().unwrap_or(path);
+    let repository = segments.next().unwrap_or("unknown");
+    (repository, function)
+}
+
+/// Time the enclosing repository function, labelled with its module and name.
+macro_rules! query_timer {
+    () => {{
+        fn here() {}
+        fn name_of<T>(_: T) -> &'static str {
+            std::any::type_name::<T>()
+        }
+        $crate::utils::metrics::QueryTimer::new(name_of(here).trim_end_matches("::here"))
+    }};
+}
+
+pub(crate) use query_timer;
+
+/// Session lookups served from Redis, `false` when the user had to be read from PostgreSQL.
+pub fn record_session_lookup(hit: bool) {
+    let result = if hit { "hit" } else { "miss" };
+    increment_counter!("session_cache_requests_total", "result" => result);
+}
+
+/// A record was created, `data_type` is its table, e.g. `meals`.
+pub fn record_created(data_type: &'static str) {
+    increment_counter!("nighty_night_records_created_total", "type" => data_type);
+}
+
+#[cfg(test)]
+mod test_metrics {
+    use super::*;
+
+    #[test]
+    fn test_split_function_path() {
+        assert_eq!(
+            split_function_path("nighty_night::repository::meal_repository::insert_new_meal"),
+            ("meal_repository", "insert_new_meal")
+        );
+        assert_eq!(split_function_path("insert"), ("unknown", "insert"));
+    }
+
+    #[test]
+    fn test_query_timer_labels() {
+        let timer = query_timer!();
+        assert_eq!(timer.repository, "test_metrics");
+        assert_eq!(timer.function, "test_query_timer_labels");
+    }
+}
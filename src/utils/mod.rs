@@ -2,3 +2,12 @@ pub mod logger;
 pub mod datetime;
 pub mod validator;
 pub mod app;
+pub mod report;
+pub mod calendar;
+pub mod webhook;
+pub mod mailer;
+pub mod idempotency;
+pub mod etag;
+pub mod cursor;
+pub mod metrics;
+pub mod version;
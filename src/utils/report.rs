@@ -0,0 +1,287 @@
+use crate::data::{note_dto::NoteData, report_dto::ReportData};
+
+const REPORT_STYLE: &str = "body{font-family:sans-serif;margin:2em;color:#222}\
+table{border-collapse:collapse;margin-bottom:1.5em}\
+th,td{border:1px solid #999;padding:4px 10px;text-align:left}\
+th{background:#eee}h1,h2{margin-bottom:0.3em}";
+
+/// Render a self contained html document, styles included, ready to be printed.
+pub fn render_html(report: &ReportData) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html><html><head><meta charset=\"utf-8\">");
+    html.push_str(&format!(
+        "<title>Report {}</title><style>{REPORT_STYLE}</style></head><body>",
+        escape_html(&report.baby.name)
+    ));
+    html.push_str(&format!("<h1>{}</h1>", escape_html(&report.baby.name)));
+    html.push_str(&format!(
+        "<p>Birthdate: {}<br>Period: {} to {}</p>",
+        report.baby.birthdate, report.from, report.to
+    ));
+
+    html.push_str("<h2>Averages</h2>");
+    html.push_str(&table(
+        &[
+            "Feedings per day",
+            "Formula per day (ml)",
+            "Nursing time per day",
+            "Sleep per day",
+            "Weight change (kg)",
+        ],
+        vec![vec![
+            report.averages.feedings_per_day.to_string(),
+            report.averages.formula_per_day.to_string(),
+            report.averages.nursing_time_per_day.to_string(),
+            report.averages.sleep_per_day.to_string(),
+            report.averages.weight_change.to_string(),
+        ]],
+    ));
+
+    html.push_str("<h2>Growth</h2>");
+    html.push_str(&table(
+        &["Date", "Weight (kg)"],
+        report
+            .weights
+            .iter()
+            .map(|weight| vec![weight.date.to_string(), weight.value.to_string()])
+            .collect(),
+    ));
+
+    html.push_str("<h2>Feeding</h2>");
+    html.push_str(&table(
+        &["Date", "Feedings", "Nursing time", "Formula (ml)"],
+        report
+            .meals
+            .iter()
+            .map(|meal| {
+                vec![
+                    meal.date.to_string(),
+                    meal.total_feedings.to_string(),
+                    meal.nursing_time.to_string(),
+                    meal.formula.to_string(),
+                ]
+            })
+            .collect(),
+    ));
+
+    html.push_str("<h2>Sleep</h2>");
+    html.push_str(&table(
+        &["Date", "Total sleep"],
+        report
+            .dreams
+            .iter()
+            .map(|dream| vec![dream.date.to_string(), dream.summary.to_string()])
+            .collect(),
+    ));
+
+    html.push_str("<h2>Notes</h2>");
+    html.push_str(&table(
+        &["Date", "Time", "Category", "Value", "Description"],
+        report
+            .notes
+            .iter()
+            .map(|note| {
+                vec![
+                    note.date.to_string(),
+                    note.time.to_string(),
+                    note.category.to_string(),
+                    note_value(note),
+                    note.description.clone().unwrap_or_default(),
+                ]
+            })
+            .collect(),
+    ));
+
+    html.push_str("</body></html>");
+    html
+}
+
+/// Value with its unit, empty when the note is only text.
+fn note_value(note: &NoteData) -> String {
+    match (note.value, &note.unit) {
+        (Some(value), Some(unit)) => format!("{value} {unit}"),
+        (Some(value), None) => value.to_string(),
+        (None, _) => String::new(),
+    }
+}
+
+fn table(headers: &[&str], rows: Vec<Vec<String>>) -> String {
+    if rows.is_empty() {
+        return String::from("<p>No records.</p>");
+    }
+    let mut table = String::from("<table><tr>");
+    headers
+        .iter()
+        .for_each(|header| table.push_str(&format!("<th>{header}</th>")));
+    table.push_str("</tr>");
+    for row in rows {
+        table.push_str("<tr>");
+        row.iter()
+            .for_each(|cell| table.push_str(&format!("<td>{}</td>", escape_html(cell))));
+        table.push_str("</tr>");
+    }
+    table.push_str("</table>");
+    table
+}
+
+/// Escape user input before it goes into an html document.
+pub fn escape_html(input: &str) -> String {
+    input
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+/// Plain text lines, used by the pdf renderer.
+#[cfg(feature = "pdf")]
+fn report_lines(report: &ReportData) -> Vec<String> {
+    let mut lines = vec![
+        format!("Report: {}", report.baby.name),
+        format!("Birthdate: {}", report.baby.birthdate),
+        format!("Period: {} to {}", report.from, report.to),
+        String::new(),
+        String::from("Averages"),
+        format!("Feedings per day: {}", report.averages.feedings_per_day),
+        format!("Formula per day (ml): {}", report.averages.formula_per_day),
+        format!(
+            "Nursing time per day: {}",
+            report.averages.nursing_time_per_day
+        ),
+        format!("Sleep per day: {}", report.averages.sleep_per_day),
+        format!("Weight change (kg): {}", report.averages.weight_change),
+        String::new(),
+        String::from("Growth"),
+    ];
+    report
+        .weights
+        .iter()
+        .for_each(|weight| lines.push(format!("{}    {} kg", weight.date, weight.value)));
+    lines.push(String::new());
+    lines.push(String::from("Feeding"));
+    report.meals.iter().for_each(|meal| {
+        lines.push(format!(
+            "{}    feedings: {}    nursing: {}    formula: {} ml",
+            meal.date, meal.total_feedings, meal.nursing_time, meal.formula
+        ))
+    });
+    lines.push(String::new());
+    lines.push(String::from("Sleep"));
+    report
+        .dreams
+        .iter()
+        .for_each(|dream| lines.push(format!("{}    {}", dream.date, dream.summary)));
+    lines.push(String::new());
+    lines.push(String::from("Notes"));
+    report.notes.iter().for_each(|note| {
+        lines.push(format!(
+            "{} {}    {}    {}    {}",
+            note.date,
+            note.time,
+            note.category,
+            note_value(note),
+            note.description.as_deref().unwrap_or_default()
+        ))
+    });
+    lines
+}
+
+/// Render an A4 pdf document, one line of text per record.
+#[cfg(feature = "pdf")]
+pub fn render_pdf(report: &ReportData) -> Result<Vec<u8>, crate::response::error::ApiError> {
+    use printpdf::{BuiltinFont, Mm, PdfDocument};
+
+    use crate::response::error::ApiError;
+
+    const PAGE_WIDTH: f32 = 210.0;
+    const PAGE_HEIGHT: f32 = 297.0;
+    const MARGIN: f32 = 20.0;
+    const LINE_HEIGHT: f32 = 6.0;
+
+    let title = format!("Report {}", report.baby.name);
+    let (document, page, layer) =
+        PdfDocument::new(&title, Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+    let font = document
+        .add_builtin_font(BuiltinFont::Helvetica)
+        .map_err(|error| ApiError::Generic500Error(error.to_string()))?;
+    let mut current_layer = document.get_page(page).get_layer(layer);
+    let mut y = PAGE_HEIGHT - MARGIN;
+    for line in report_lines(report) {
+        if y < MARGIN {
+            let (new_page, new_layer) =
+                document.add_page(Mm(PAGE_WIDTH), Mm(PAGE_HEIGHT), "Layer 1");
+            current_layer = document.get_page(new_page).get_layer(new_layer);
+            y = PAGE_HEIGHT - MARGIN;
+        }
+        current_layer.use_text(line, 10.0, Mm(MARGIN), Mm(y), &font);
+        y -= LINE_HEIGHT;
+    }
+    document
+        .save_to_bytes()
+        .map_err(|error| ApiError::Generic500Error(error.to_string()))
+}
+
+#[cfg(test)]
+mod test_report {
+    use uuid::Uuid;
+
+    use crate::data::{baby_dto::BabyData, report_dto::ReportAveragesData};
+
+    use super::*;
+
+    #[test]
+    fn test_escape_html() {
+        assert_eq!(
+            escape_html("<b>Tom & \"Jerry\"</b>"),
+            "&lt;b&gt;Tom &amp; &quot;Jerry&quot;&lt;/b&gt;"
+        );
+    }
+
+    #[test]
+    fn test_notes_section() {
+        let note = NoteData {
+            date: "2023-06-01".to_string(),
+            time: "09:30".to_string(),
+            category: "temperature".to_string(),
+            value: Some(38.2),
+            unit: Some("°C".to_string()),
+            description: Some("After <b>vaccine</b>".to_string()),
+        };
+        let report = ReportData {
+            baby: BabyData {
+                unique_id: Uuid::new_v4(),
+                name: "Ada".to_string(),
+                birthdate: "2023-05-01".to_string(),
+            },
+            from: "2023-06-01".to_string(),
+            to: "2023-06-07".to_string(),
+            weights: vec![],
+            meals: vec![],
+            dreams: vec![],
+            notes: vec![note],
+            averages: ReportAveragesData {
+                feedings_per_day: 0.0,
+                formula_per_day: 0.0,
+                nursing_time_per_day: "00:00".to_string(),
+                sleep_per_day: "00:00".to_string(),
+                weight_change: 0.0,
+            },
+        };
+        let html = render_html(&report);
+        assert!(html.contains("<h2>Notes</h2>"));
+        assert!(html.contains(
+            "<tr><td>2023-06-01</td><td>09:30</td><td>temperature</td><td>38.2 °C</td>\
+            <td>After &lt;b&gt;vaccine&lt;/b&gt;</td></tr>"
+        ));
+    }
+
+    #[test]
+    fn test_empty_table() {
+        assert_eq!(table(&["Date"], vec![]), "<p>No records.</p>");
+        assert_eq!(
+            table(&["Date"], vec![vec!["2023-06-01".to_string()]]),
+            "<table><tr><th>Date</th></tr><tr><td>2023-06-01</td></tr></table>"
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use axum::{
+    http::{header, HeaderValue, Request},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+
+use crate::response::error::ErrorBody;
+
+/// Version of the API a router is built for, `/api` without version is v1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ApiVersion {
+    /// Dates, times and durations formatted for display.
+    V1,
+    /// RFC 3339 timestamps and durations in seconds.
+    V2,
+}
+
+impl ApiVersion {
+    pub fn prefix(self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "/api/v1",
+            ApiVersion::V2 => "/api/v2",
+        }
+    }
+
+    /// Version a request path belongs to, `None` outside `/api`.
+    pub fn from_path(path: &str) -> Option<Self> {
+        let rest = path.strip_prefix("/api")?;
+        if rest.eq("/v2") || rest.starts_with("/v2/") {
+            Some(ApiVersion::V2)
+        } else if rest.is_empty() || rest.starts_with('/') {
+            Some(ApiVersion::V1)
+        } else {
+            None
+        }
+    }
+}
+
+/// Mark v1 responses as deprecated and link the same path in v2.
+///
+/// Runs inside the nested router, the path has no `/api` prefix here.
+pub async fn deprecated_version<B>(request: Request<B>, next: Next<B>) -> Response {
+    let successor = successor_link(request.uri().path());
+    let mut response = next.run(request).await;
+    let headers = response.headers_mut();
+    headers.insert("deprecation", HeaderValue::from_static("true"));
+    if let Ok(link) = HeaderValue::from_str(&successor) {
+        headers.insert(header::LINK, link);
+    }
+    response
+}
+
+/// Errors of `/api` and `/api/v1` keep the body they had before v2.
+///
+/// Runs outside every router, errors of session, idempotency and 404 are included.
+pub async fn versioned_errors<B>(request: Request<B>, next: Next<B>) -> Response {
+    let legacy = ApiVersion::from_path(request.uri().path()) == Some(ApiVersion::V1);
+    let mut response = next.run(request).await;
+    let body = match response.extensions_mut().remove::<ErrorBody>() {
+        Some(body) if legacy => body,
+        _ => return response,
+    };
+    let (mut parts, _) = response.into_parts();
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, Json(body).into_response().into_body())
+}
+
+fn successor_link(path: &str) -> String {
+    format!(
+        "<{}{path}>; rel=\"successor-version\"",
+        ApiVersion::V2.prefix()
+    )
+}
+
+#[cfg(test)]
+mod test_version {
+    use super::*;
+
+    #[test]
+    fn test_successor_link() {
+        assert_eq!(
+            successor_link("/baby/1/meals"),
+            "</api/v2/baby/1/meals>; rel=\"successor-version\""
+        );
+    }
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(ApiVersion::from_path("/api/baby"), Some(ApiVersion::V1));
+        assert_eq!(ApiVersion::from_path("/api/v1/baby"), Some(ApiVersion::V1));
+        assert_eq!(ApiVersion::from_path("/api/v2/baby"), Some(ApiVersion::V2));
+        assert_eq!(ApiVersion::from_path("/api/v2"), Some(ApiVersion::V2));
+        assert_eq!(ApiVersion::from_path("/api/v2x/baby"), Some(ApiVersion::V1));
+        assert_eq!(ApiVersion::from_path("/apidocs"), None);
+        assert_eq!(ApiVersion::from_path("/health"), None);
+    }
+}
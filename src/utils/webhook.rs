@@ -0,0 +1,328 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::{Arc, OnceLock},
+    time::Duration,
+};
+
+use hmac::{Hmac, Mac};
+use reqwest::{
+    dns::{Addrs, Name, Resolve, Resolving},
+    redirect::Policy,
+    Client, Url,
+};
+use sha2::Sha256;
+use tokio::net::lookup_host;
+use uuid::Uuid;
+
+const SIGNATURE_HEADER: &str = "X-Nighty-Night-Signature";
+const EVENT_HEADER: &str = "X-Nighty-Night-Event";
+const DELIVERY_HEADER: &str = "X-Nighty-Night-Delivery";
+pub const TIMEOUT_SECONDS: u64 = 10;
+
+static CLIENT: OnceLock<Client> = OnceLock::new();
+
+/// HMAC-SHA256 of the payload, hex encoded and prefixed with the algorithm.
+pub fn sign_payload(secret: &str, payload: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(payload.as_bytes());
+    let signature: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    format!("sha256={signature}")
+}
+
+/// Post a signed json payload, only to hosts resolving to public addresses.
+///
+/// Returns response status code, or the error when there is no response in
+/// [`TIMEOUT_SECONDS`], name resolution included.
+pub async fn send_webhook(
+    url: &str,
+    secret: &str,
+    event: &str,
+    delivery: Uuid,
+    payload: &str,
+) -> Result<u16, String> {
+    let send = async {
+        check_destination(url).await?;
+        post_payload(client(), url, secret, event, delivery, payload).await
+    };
+    tokio::time::timeout(Duration::from_secs(TIMEOUT_SECONDS), send)
+        .await
+        .map_err(|_| format!("No response in {TIMEOUT_SECONDS} seconds"))?
+}
+
+/// Resolve the url host and fail when any of its addresses is not public.
+pub async fn check_destination(url: &str) -> Result<(), String> {
+    let url = Url::parse(url.trim()).map_err(|error| error.to_string())?;
+    let port = url.port_or_known_default().unwrap_or(80);
+    let host = url
+        .host_str()
+        .ok_or("Url has no host")?
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    let addresses: Vec<IpAddr> = match host.parse::<IpAddr>() {
+        Ok(ip) => vec![ip],
+        Err(_) => lookup_host((host, port))
+            .await
+            .map_err(|error| format!("{host} can't be resolved: {error}"))?
+            .map(|address| address.ip())
+            .collect(),
+    };
+    match addresses.iter().find(|ip| !is_public_ip(**ip)) {
+        Some(ip) => Err(format!("{url} resolves to non public address {ip}")),
+        None if addresses.is_empty() => Err(format!("{url} has no addresses")),
+        None => Ok(()),
+    }
+}
+
+/// Loopback, private, link-local (cloud metadata included) and reserved ranges are refused.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_ipv4(ip),
+            None => is_public_ipv6(ip),
+        },
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [first, second, ..] = ip.octets();
+    let shared = first == 100 && (second & 0xc0) == 64;
+    !(ip.is_unspecified()
+        || ip.is_loopback()
+        || ip.is_private()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        || shared
+        || first == 0
+        || first >= 240)
+}
+
+fn is_public_ipv6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    let unique_local = (first & 0xfe00) == 0xfc00;
+    let link_local = (first & 0xffc0) == 0xfe80;
+    !(ip.is_unspecified() || ip.is_loopback() || ip.is_multicast() || unique_local || link_local)
+}
+
+/// Resolver of the webhook client, pins names to the public addresses checked.
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addresses: Vec<SocketAddr> = lookup_host((name.as_str(), 0)).await?.collect();
+            if let Some(address) = addresses.iter().find(|address| !is_public_ip(address.ip())) {
+                let message = format!(
+                    "{} resolves to non public address {}",
+                    name.as_str(),
+                    address.ip()
+                );
+                return Err(message.into());
+            }
+            let addresses: Addrs = Box::new(addresses.into_iter());
+            Ok(addresses)
+        })
+    }
+}
+
+/// Client shared by every delivery, redirects are never followed.
+fn client() -> &'static Client {
+    CLIENT.get_or_init(|| {
+        Client::builder()
+            .timeout(Duration::from_secs(TIMEOUT_SECONDS))
+            .redirect(Policy::none())
+            .dns_resolver(Arc::new(PublicResolver))
+            .build()
+            .expect("Webhook client settings are static")
+    })
+}
+
+async fn post_payload(
+    client: &Client,
+    url: &str,
+    secret: &str,
+    event: &str,
+    delivery: Uuid,
+    payload: &str,
+) -> Result<u16, String> {
+    client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(SIGNATURE_HEADER, sign_payload(secret, payload))
+        .header(EVENT_HEADER, event)
+        .header(DELIVERY_HEADER, delivery.to_string())
+        .body(payload.to_string())
+        .send()
+        .await
+        .map(|response| response.status().as_u16())
+        .map_err(|error| error.to_string())
+}
+
+#[cfg(test)]
+mod test_webhook {
+    use std::{convert::Infallible, net::SocketAddr};
+
+    use hyper::{
+        service::{make_service_fn, service_fn},
+        Body, Request, Response, Server, StatusCode,
+    };
+
+    use super::*;
+
+    const SECRET: &str = "key";
+    const DELIVERY: Uuid = Uuid::from_u128(0x6c3d_1f0e_8a2b_4c5d_9e7f_0a1b_2c3d_4e5f);
+
+    /// Local stand-in answering 204 to valid signatures, 401 otherwise and 307 on /redirect.
+    ///
+    /// Deliveries other than [DELIVERY] get 400.
+    fn spawn_receiver() -> SocketAddr {
+        let make_service = make_service_fn(|_| async {
+            Ok::<_, Infallible>(service_fn(|request: Request<Body>| async move {
+                if request.uri().path().eq("/redirect") {
+                    return Ok::<_, Infallible>(
+                        Response::builder()
+                            .status(StatusCode::TEMPORARY_REDIRECT)
+                            .header(hyper::header::LOCATION, "/hook")
+                            .body(Body::empty())
+                            .unwrap(),
+                    );
+                }
+                let signature = request
+                    .headers()
+                    .get(SIGNATURE_HEADER)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string);
+                let delivery = request
+                    .headers()
+                    .get(DELIVERY_HEADER)
+                    .and_then(|value| value.to_str().ok())
+                    .map(str::to_string);
+                let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                let expected = sign_payload(SECRET, std::str::from_utf8(&body).unwrap());
+                let status = match (signature, delivery) {
+                    (_, delivery) if delivery.ne(&Some(DELIVERY.to_string())) => {
+                        StatusCode::BAD_REQUEST
+                    }
+                    (Some(value), _) if value.eq(&expected) => StatusCode::NO_CONTENT,
+                    _ => StatusCode::UNAUTHORIZED,
+                };
+                Ok::<_, Infallible>(
+                    Response::builder()
+                        .status(status)
+                        .body(Body::empty())
+                        .unwrap(),
+                )
+            }))
+        });
+        let server = Server::bind(&([127, 0, 0, 1], 0).into()).serve(make_service);
+        let address = server.local_addr();
+        tokio::spawn(server);
+        address
+    }
+
+    #[test]
+    fn test_sign_payload() {
+        assert_eq!(
+            sign_payload(SECRET, "The quick brown fox jumps over the lazy dog"),
+            "sha256=f7bc83f430538424b13298e6aa6fb143ef4d59a14946175997479dbc2d1a3cd8"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_post_payload() {
+        let url = format!("http://{}/hook", spawn_receiver());
+        let payload = r#"{"event":"created","data":{}}"#;
+        assert_eq!(
+            post_payload(client(), &url, SECRET, "created", DELIVERY, payload).await,
+            Ok(204)
+        );
+        assert_eq!(
+            post_payload(client(), &url, "other", "created", DELIVERY, payload).await,
+            Ok(401)
+        );
+        assert_eq!(
+            post_payload(client(), &url, SECRET, "created", Uuid::nil(), payload).await,
+            Ok(400)
+        );
+        assert!(post_payload(
+            client(),
+            "http://127.0.0.1:9/hook",
+            SECRET,
+            "created",
+            DELIVERY,
+            payload
+        )
+        .await
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_post_payload_not_following_redirects() {
+        let url = format!("http://{}/redirect", spawn_receiver());
+        assert_eq!(
+            post_payload(client(), &url, SECRET, "created", DELIVERY, "{}").await,
+            Ok(307)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_send_webhook_to_internal_host() {
+        let address = spawn_receiver();
+        for url in [
+            format!("http://{address}/hook"),
+            format!("http://localhost:{}/hook", address.port()),
+            "http://169.254.169.254/latest/meta-data".to_string(),
+            "http://[::ffff:10.0.0.1]/hook".to_string(),
+        ] {
+            assert!(send_webhook(&url, SECRET, "created", DELIVERY, "{}")
+                .await
+                .is_err());
+        }
+        let url = format!("http://localhost:{}/hook", address.port());
+        assert!(
+            post_payload(client(), &url, SECRET, "created", DELIVERY, "{}")
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_check_destination() {
+        assert!(check_destination("http://localhost/hook").await.is_err());
+        assert!(check_destination("http://10.1.2.3/hook").await.is_err());
+        assert!(check_destination("https://[::1]:8443/hook").await.is_err());
+        assert!(check_destination("https://8.8.8.8/hook").await.is_ok());
+    }
+
+    #[test]
+    fn test_is_public_ip() {
+        for ip in [
+            "127.0.0.1",
+            "10.0.0.1",
+            "172.16.5.4",
+            "192.168.1.10",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "255.255.255.255",
+            "::1",
+            "::",
+            "fd00:ec2::254",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{ip} must be refused");
+        }
+        for ip in ["8.8.8.8", "1.1.1.1", "2606:4700:4700::1111"] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{ip} must be allowed");
+        }
+    }
+}
@@ -165,4 +165,26 @@ pub mod assertions {
             msg, expected_value, array_length
         )
     }
+
+    /// Checks the status the error is sent with, not only that there is one.
+    pub fn assert_error_status<T>(
+        response: &Result<T, ApiError>,
+        msg: &str,
+        expected_status: StatusCode,
+    ) {
+        match response {
+            Err(error) => assert_eq!(
+                error.get_error().0,
+                expected_status,
+                "Test failed: {}. Expected: {} => Received: {}",
+                msg,
+                expected_status,
+                error
+            ),
+            Ok(_) => panic!(
+                "Test failed: {}. Expected: {} => Received a successful response",
+                msg, expected_status
+            ),
+        }
+    }
 }
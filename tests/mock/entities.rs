@@ -1,4 +1,4 @@
-use nighty_night::{service::{user_service::post_new_user_service, baby_service::post_new_baby_service}, data::user_dto::LoginDto};
+use nighty_night::{service::{user_service::post_new_user_service, baby_service::post_new_baby_service}, data::user_dto::LoginDto, repository::baby_repository::select_baby_from_unique_id};
 
 use crate::common::cte::DB_ERROR;
 
@@ -18,5 +18,5 @@ pub async fn create_new_user() -> (i32, LoginDto) {
 pub async fn create_new_baby(user_id: i32) -> i32 {
     let baby = generate_new_baby();
     let new_baby = post_new_baby_service(baby, user_id).await.expect(DB_ERROR);
-    new_baby.data.id
+    select_baby_from_unique_id(new_baby.data.id).expect(DB_ERROR)
 }
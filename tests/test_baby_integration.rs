@@ -2,6 +2,7 @@ use fake::{faker::name::en::FirstName, Fake};
 use hyper::StatusCode;
 use nighty_night::{
     data::{baby_dto::InputBabyDto, query_dto::Pagination},
+    repository::baby_repository::select_baby_from_unique_id,
     service::{
         baby_service::{
             delete_baby_service, get_babies_for_user_service, get_baby_by_id_service,
@@ -10,13 +11,14 @@ use nighty_night::{
         },
         user_service::delete_user_from_database,
     },
+    utils::etag::{format_etag, IfMatch},
 };
 
 use crate::{
     common::{
         assertions::{
             assert_compare_fields, assert_error_response, assert_len, assert_ok_message,
-            assert_ok_paginated, assert_ok_response, assert_error_message
+            assert_ok_paginated, assert_ok_response, assert_error_message, assert_error_status,
         },
         cte::{DB_ERROR, DELETE},
     },
@@ -50,7 +52,7 @@ async fn test_baby_flow() {
     );
 
     let baby = response_new_baby.unwrap();
-    let test_baby_id: i32 = baby.data.id;
+    let test_baby_id: i32 = select_baby_from_unique_id(baby.data.id).expect(DB_ERROR);
     let test_baby_uid = baby.data.attributes.unique_id;
 
     let response_get_baby_by_id = get_baby_by_id_service(test_baby_id).await;
@@ -73,7 +75,7 @@ async fn test_baby_flow() {
         "Unique id should match",
     );
 
-    let response_delete_baby = delete_baby_service(test_baby_id, user_id).await;
+    let response_delete_baby = delete_baby_service(test_baby_id, user_id, IfMatch::default()).await;
     assert_ok_message(
         &response_delete_baby,
         "Should delete baby from database",
@@ -109,7 +111,7 @@ async fn test_add_several_babies() {
     );
 
     for baby in babies {
-        delete_baby_service(baby, user_id).await.expect(DELETE);
+        delete_baby_service(baby, user_id, IfMatch::default()).await.expect(DELETE);
     }
 
     delete_user_from_database(user_id).expect(DB_ERROR);
@@ -127,7 +129,7 @@ async fn test_transfer_ownership() {
     );
 
     let baby = response_new_baby.unwrap();
-    let test_baby_id: i32 = baby.data.id;
+    let test_baby_id: i32 = select_baby_from_unique_id(baby.data.id).expect(DB_ERROR);
     let test_baby_uid = baby.data.attributes.unique_id;
 
     let (test_user_two, _credentials) = create_new_user().await;
@@ -173,7 +175,7 @@ async fn test_transfer_ownership() {
 
     assert_len(&updated_babies_count, 1, "Should maintain babies count");
 
-    let response_delete_baby = delete_baby_service(test_baby_id, user_id).await;
+    let response_delete_baby = delete_baby_service(test_baby_id, user_id, IfMatch::default()).await;
     assert_ok_message(
         &response_delete_baby,
         "Should delete baby from user",
@@ -193,7 +195,7 @@ async fn test_transfer_ownership() {
         StatusCode::OK,
     );
 
-    let response_delete_baby = delete_baby_service(test_baby_id, test_user_two).await;
+    let response_delete_baby = delete_baby_service(test_baby_id, test_user_two, IfMatch::default()).await;
     assert_ok_message(
         &response_delete_baby,
         "Should delete baby from database",
@@ -220,6 +222,7 @@ async fn test_update_baby_info() {
         .await
         .expect(DB_ERROR)
         .data;
+    let new_baby_id = select_baby_from_unique_id(new_baby.id).expect(DB_ERROR);
 
     let update_name = InputBabyDto {
         name: Some(FirstName().fake()),
@@ -227,7 +230,7 @@ async fn test_update_baby_info() {
     };
     let update_name_field = &update_name.name.to_owned().unwrap();
 
-    let response_patch_baby = patch_baby_service(new_baby.id, update_name).await;
+    let response_patch_baby = patch_baby_service(new_baby_id, update_name, IfMatch::default()).await;
     assert_ok_response(
         &response_patch_baby,
         "Should update baby information",
@@ -247,7 +250,7 @@ async fn test_update_baby_info() {
         birthdate: Some("abcd".to_string()),
     };
 
-    let bad_date = patch_baby_service(new_baby.id, bad_format_date)
+    let bad_date = patch_baby_service(new_baby_id, bad_format_date, IfMatch::default())
         .await
         .expect(DB_ERROR)
         .data;
@@ -264,7 +267,7 @@ async fn test_update_baby_info() {
     };
     let update_birthdate_field = &update_birthdate.birthdate.to_owned().unwrap();
 
-    let patched_date = patch_baby_service(new_baby.id, update_birthdate)
+    let patched_date = patch_baby_service(new_baby_id, update_birthdate, IfMatch::default())
         .await
         .expect(DB_ERROR)
         .data;
@@ -275,7 +278,7 @@ async fn test_update_baby_info() {
         "Date should be the same",
     );
 
-    delete_baby_service(new_baby.id, user_id).await.expect(DB_ERROR);
+    delete_baby_service(new_baby_id, user_id, IfMatch::default()).await.expect(DB_ERROR);
     delete_user_from_database(user_id).expect(DB_ERROR);
 
 }
@@ -290,4 +293,32 @@ async fn test_delete_on_cascade() {
     let response = get_baby_by_id_service(baby_id).await;
 
     assert_error_response(&response, "Baby should be deleted on cascade", StatusCode::NOT_FOUND);
-}
\ No newline at end of file
+}
+
+#[tokio::test]
+async fn test_baby_preconditions() {
+    let (user_id, _user_credentials) = create_new_user().await;
+    let baby_id = create_new_baby(user_id).await;
+    let version = |version: i32| IfMatch::required(Some(format_etag(version)));
+    let rename = || InputBabyDto {
+        name: Some(FirstName().fake()),
+        birthdate: None,
+    };
+
+    let response = patch_baby_service(baby_id, rename(), version(1)).await;
+    assert_ok_response(&response, "Should update the version read", StatusCode::OK);
+
+    let response = patch_baby_service(baby_id, rename(), version(1)).await;
+    assert_error_status(&response, "Stale patch should fail", StatusCode::PRECONDITION_FAILED);
+    let response = delete_baby_service(baby_id, user_id, version(1)).await;
+    assert_error_status(&response, "Stale delete should fail", StatusCode::PRECONDITION_FAILED);
+
+    let response = patch_baby_service(baby_id, rename(), IfMatch::required(None)).await;
+    assert_error_status(&response, "Patch needs If-Match", StatusCode::PRECONDITION_REQUIRED);
+    let response = delete_baby_service(baby_id, user_id, IfMatch::required(None)).await;
+    assert_error_status(&response, "Delete needs If-Match", StatusCode::PRECONDITION_REQUIRED);
+
+    let response = delete_baby_service(baby_id, user_id, version(2)).await;
+    assert_ok_message(&response, "Should delete the current version", StatusCode::OK);
+    delete_user_from_database(user_id).expect(DB_ERROR);
+}
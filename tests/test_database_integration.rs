@@ -1,8 +1,10 @@
 use diesel::pg::PgConnection;
 use diesel::prelude::*;
+use diesel::{connection::SimpleConnection, sql_query};
 use nighty_night::repository::admin_repository::select_stats_from_tables;
+use nighty_night::repository::migration_repository::apply_migrations;
 use nighty_night::{
-    configuration::settings::Setting,
+    configuration::config::{config, DatabaseConfig},
     connection::connection_redis::poll,
     data::session_dto::CurrentUserDto,
     repository::session_repository::{insert_user_session, select_user_session},
@@ -49,6 +51,43 @@ fn test_postgresql_database() {
     );
 }
 
+/// Schema created by the old compose init script, with no migration history.
+#[test]
+fn test_migrations_on_initialised_database() {
+    let name = format!("nn_baseline_{}", std::process::id());
+    let server = &mut PgConnection::establish(&config().database.url()).expect("PostgreSQL");
+    sql_query(format!("CREATE DATABASE {name}"))
+        .execute(server)
+        .expect("Should create a scratch database");
+    let url = DatabaseConfig {
+        name: name.to_string(),
+        ..config().database.clone()
+    }
+    .url();
+    {
+        let conn = &mut PgConnection::establish(&url).expect("Scratch database");
+        let create_tables = include_str!("../migrations/2023-05-25-174758_create_tables/up.sql");
+        conn.batch_execute(create_tables)
+            .expect("Init script should create the first tables");
+        conn.batch_execute(create_tables)
+            .expect("Seeds should be skipped when they exist");
+
+        let applied = apply_migrations(conn).expect("Should migrate an initialised database");
+        assert!(
+            !applied.contains(&"20230525174758".to_string()),
+            "Initial tables should be marked as applied, not created again"
+        );
+        assert!(!applied.is_empty(), "Later migrations should be applied");
+        assert!(
+            apply_migrations(conn).expect("Second run").is_empty(),
+            "Nothing should be pending after the first run"
+        );
+    }
+    sql_query(format!("DROP DATABASE {name}"))
+        .execute(server)
+        .expect("Should drop the scratch database");
+}
+
 async fn ping_redis<'a>() -> Result<String, RedisError> {
     let mut conn = match poll().await.get_connection() {
         Ok(server) => server,
@@ -58,7 +97,7 @@ async fn ping_redis<'a>() -> Result<String, RedisError> {
 }
 
 fn check_db_status<'a>() -> Result<String, String> {
-    let database_url = Setting::DatabaseUrl.get();
+    let database_url = config().database.url();
     match PgConnection::establish(&database_url) {
         Ok(_) => Ok("PostgreSQL ready.".to_string()),
         Err(error) => Err(format!("PostgreSQL: {error}")),
@@ -67,8 +106,8 @@ fn check_db_status<'a>() -> Result<String, String> {
 
 #[test]
 fn test_branch() {
-    assert_eq!(Setting::Branch.get(), "local");
-    assert_eq!(Setting::RedisHost.get(), "redis://127.0.0.1:6379/");
+    assert_eq!(config().branch, "local");
+    assert_eq!(config().redis.url(), "redis://127.0.0.1:6379/");
 }
 
 #[tokio::test]
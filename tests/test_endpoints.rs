@@ -2,7 +2,7 @@ pub mod common;
 
 use axum::http::StatusCode;
 use axum_test_helper::{TestClient, TestResponse};
-use nighty_night::app::{expand_router_layer, create_router};
+use nighty_night::{app::{expand_router_layer, create_router}, utils::metrics::setup_metrics};
 use serde_json::{json, Value};
 
 const SESSION: &'static str = "/api/auth/session";
@@ -50,6 +50,74 @@ async fn test_anonymous_call() {
     );
 }
 
+#[tokio::test]
+async fn test_health_probes() {
+    let router = expand_router_layer(create_router()).await;
+    let client = TestClient::new(router);
+
+    let live = client.get("/health/live").send().await;
+    assert_ok_status(&live, StatusCode::OK, HttpMethod::GET, "/health/live");
+    assert_eq!(live.json::<Value>().await, json!({"data": {"status": "up"}}));
+
+    let ready = client.get("/health/ready").send().await;
+    assert_ok_status(&ready, StatusCode::OK, HttpMethod::GET, "/health/ready");
+    let body = ready.json::<Value>().await;
+    assert_eq!(body["data"]["postgres"]["status"], "up");
+    assert_eq!(body["data"]["redis"]["status"], "up");
+}
+
+#[tokio::test]
+async fn test_request_id() {
+    let router = expand_router_layer(create_router()).await;
+    let client = TestClient::new(router);
+
+    let generated = client.get(SESSION).send().await;
+    assert!(generated.headers().contains_key("x-request-id"));
+
+    let propagated = client
+        .get(SESSION)
+        .header("x-request-id", "support-ticket-42")
+        .send()
+        .await;
+    assert_eq!(propagated.headers()["x-request-id"], "support-ticket-42");
+}
+
+#[tokio::test]
+async fn test_metrics() {
+    setup_metrics();
+    let router = expand_router_layer(create_router()).await;
+    let client = TestClient::new(router);
+
+    client.get(SESSION).send().await;
+    client.get("/api/missing").send().await;
+    let metrics = client.get("/metrics").send().await;
+    assert_ok_status(&metrics, StatusCode::OK, HttpMethod::GET, "/metrics");
+    let body = metrics.text().await;
+    assert!(body.contains("http_requests_total"));
+    assert!(body.contains("nighty_night_active_timers"));
+    assert!(body.contains("path=\"unmatched\""));
+}
+
+#[tokio::test]
+async fn test_api_versions() {
+    let router = expand_router_layer(create_router()).await;
+    let client = TestClient::new(router);
+
+    for path in [SESSION, "/api/v1/auth/session"] {
+        let deprecated = client.get(path).send().await;
+        assert_ok_status(&deprecated, StatusCode::OK, HttpMethod::GET, path);
+        assert_eq!(deprecated.headers()["deprecation"], "true");
+        assert_eq!(
+            deprecated.headers()["link"],
+            "</api/v2/auth/session>; rel=\"successor-version\""
+        );
+    }
+
+    let current = client.get("/api/v2/auth/session").send().await;
+    assert_ok_status(&current, StatusCode::OK, HttpMethod::GET, "/api/v2/auth/session");
+    assert!(!current.headers().contains_key("deprecation"));
+}
+
 fn assert_ok_status(
     received_status: &TestResponse,
     expected_code: StatusCode,
@@ -0,0 +1,68 @@
+use hyper::StatusCode;
+use nighty_night::{
+    data::{
+        common_structure::DoseDto,
+        medication_dto::{InputDoseDto, InputMedicationDto},
+    },
+    service::{
+        medication_service::{post_dose_service, post_medication_service},
+        user_service::delete_user_from_database,
+    },
+};
+
+use crate::{
+    common::{assertions::assert_error_status, cte::DB_ERROR},
+    mock::entities::{create_new_baby, create_new_user},
+};
+
+pub mod common;
+pub mod mock;
+
+#[ctor::ctor]
+fn init() {
+    common::initialiser::init()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_doses() {
+    let (user_id, _user_credentials) = create_new_user().await;
+    let baby_id = create_new_baby(user_id).await;
+    let medication = InputMedicationDto {
+        name: Some("Paracetamol".to_string()),
+        dose: Some(2.5),
+        unit: Some("ml".to_string()),
+        interval_minutes: Some(360),
+        daily_time: None,
+    };
+    let medication_id = post_medication_service(medication, baby_id)
+        .await
+        .expect(DB_ERROR)
+        .data
+        .id;
+
+    // Parents giving the same dose at once, only the first one is recorded.
+    let doses: Vec<_> = (0..4)
+        .map(|_| {
+            let dose = InputDoseDto {
+                date: Some("2024-01-01 10:00".to_string()),
+                dose: None,
+            };
+            tokio::spawn(post_dose_service::<DoseDto>(dose, medication_id, baby_id))
+        })
+        .collect();
+    let mut added = 0;
+    for dose in doses {
+        let response = dose.await.expect("Dose task should finish");
+        match response {
+            Ok(_) => added += 1,
+            Err(_) => assert_error_status(
+                &response,
+                "Dose within the interval should be rejected",
+                StatusCode::CONFLICT,
+            ),
+        }
+    }
+    assert_eq!(added, 1, "Only one of the doses should be added");
+
+    delete_user_from_database(user_id).expect(DB_ERROR);
+}
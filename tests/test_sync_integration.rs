@@ -0,0 +1,142 @@
+use nighty_night::{
+    data::{
+        common_structure::MilkBagDto,
+        meal_dto::InputMealDto,
+        pumping_dto::InputMilkBagDto,
+        sync_dto::{InputSyncDto, SyncChangeDto, SyncData},
+    },
+    service::{
+        milk_bag_service::{get_milk_bag_id_service, post_milk_bag_service},
+        sync_service::post_sync_service,
+        user_service::delete_user_from_database,
+    },
+};
+use uuid::Uuid;
+
+use crate::{
+    common::cte::DB_ERROR,
+    mock::entities::{create_new_baby, create_new_user},
+};
+
+pub mod common;
+pub mod mock;
+
+#[ctor::ctor]
+fn init() {
+    common::initialiser::init()
+}
+
+fn meal_change(client_id: Uuid, updated_at: &str, quantity: i16) -> InputSyncDto {
+    InputSyncDto {
+        cursor: None,
+        meals: vec![SyncChangeDto {
+            updated_at: updated_at.to_string(),
+            deleted: false,
+            record: InputMealDto {
+                date: Some("2024-01-01 10:00".to_string()),
+                quantity: Some(quantity),
+                to_time: None,
+                milk_bag_id: None,
+                client_id: Some(client_id),
+            },
+        }],
+        dreams: Vec::new(),
+    }
+}
+
+fn bag_meal_change(
+    client_id: Uuid,
+    updated_at: &str,
+    quantity: i16,
+    milk_bag_id: Uuid,
+) -> InputSyncDto {
+    let mut changes = meal_change(client_id, updated_at, quantity);
+    changes.meals[0].record.milk_bag_id = Some(milk_bag_id);
+    changes
+}
+
+async fn remaining(milk_bag_id: Uuid, baby_id: i32) -> i16 {
+    get_milk_bag_id_service::<MilkBagDto>(milk_bag_id, baby_id)
+        .await
+        .expect(DB_ERROR)
+        .data
+        .attributes
+        .remaining
+}
+
+async fn sync(changes: InputSyncDto, baby_id: i32) -> SyncData {
+    post_sync_service::<SyncData>(changes, baby_id)
+        .await
+        .expect(DB_ERROR)
+        .data
+}
+
+fn synced_quantity(data: &SyncData, client_id: Uuid) -> i16 {
+    data.meals
+        .iter()
+        .find(|meal| meal.attributes.client_id.eq(&Some(client_id)))
+        .expect("Synced meal should be returned")
+        .attributes
+        .quantity
+}
+
+#[tokio::test]
+async fn test_sync_out_of_order_batches() {
+    let (user_id, _user_credentials) = create_new_user().await;
+    let baby_id = create_new_baby(user_id).await;
+    let client_id = Uuid::new_v4();
+
+    let created = sync(meal_change(client_id, "2024-01-01 10:00", 90), baby_id).await;
+    assert!(created.conflicts.is_empty(), "New meal should be created");
+
+    // Edited offline after creation, applied although the server saved the meal later.
+    let edited = sync(meal_change(client_id, "2024-01-01 10:05", 120), baby_id).await;
+    assert!(edited.conflicts.is_empty(), "Newer edit should be applied");
+    assert_eq!(synced_quantity(&edited, client_id), 120);
+
+    // Batch from another device made before the last edit, arriving after it.
+    let replayed = sync(meal_change(client_id, "2024-01-01 10:02", 60), baby_id).await;
+    assert_eq!(replayed.conflicts, vec![client_id]);
+    assert_eq!(synced_quantity(&replayed, client_id), 120);
+
+    let same_time = sync(meal_change(client_id, "2024-01-01 10:05", 150), baby_id).await;
+    assert!(same_time.conflicts.is_empty(), "Client should win a tie");
+    assert_eq!(synced_quantity(&same_time, client_id), 150);
+
+    delete_user_from_database(user_id).expect(DB_ERROR);
+}
+
+#[tokio::test]
+async fn test_sync_bottle_feed_edit() {
+    let (user_id, _user_credentials) = create_new_user().await;
+    let baby_id = create_new_baby(user_id).await;
+    let new_bag = InputMilkBagDto {
+        volume: Some(200),
+        remaining: None,
+        pumped_at: None,
+        location: None,
+        expires_at: None,
+    };
+    let milk_bag_id = post_milk_bag_service::<MilkBagDto>(new_bag, baby_id)
+        .await
+        .expect(DB_ERROR)
+        .data
+        .id;
+    let client_id = Uuid::new_v4();
+
+    sync(
+        bag_meal_change(client_id, "2024-01-01 10:00", 50, milk_bag_id),
+        baby_id,
+    )
+    .await;
+    assert_eq!(remaining(milk_bag_id, baby_id).await, 150);
+
+    // Offline edits move the difference to or from the bag, like a patch.
+    let edited = sync(meal_change(client_id, "2024-01-01 10:05", 80), baby_id).await;
+    assert_eq!(synced_quantity(&edited, client_id), 80);
+    assert_eq!(remaining(milk_bag_id, baby_id).await, 120);
+    sync(meal_change(client_id, "2024-01-01 10:10", 30), baby_id).await;
+    assert_eq!(remaining(milk_bag_id, baby_id).await, 170);
+
+    delete_user_from_database(user_id).expect(DB_ERROR);
+}
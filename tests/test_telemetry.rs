@@ -0,0 +1,45 @@
+use std::{net::SocketAddr, time::Duration};
+
+use axum::{extract::State, http::HeaderMap, routing::post, Router};
+use nighty_night::utils::logger::{otlp_tracer, shutdown_logger};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tracing_subscriber::layer::SubscriberExt;
+
+/// Stand-in for an OTLP/HTTP collector, forwards the content type of each export.
+async fn collector(sender: UnboundedSender<String>) -> SocketAddr {
+    let app = Router::new()
+        .route(
+            "/v1/traces",
+            post(|State(sender): State<UnboundedSender<String>>, headers: HeaderMap| async move {
+                let content_type = headers["content-type"].to_str().unwrap().to_string();
+                sender.send(content_type).unwrap();
+            }),
+        )
+        .with_state(sender);
+    let server = axum::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(app.into_make_service());
+    let address = server.local_addr();
+    tokio::spawn(server);
+    address
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_spans_exported_to_collector() {
+    let (sender, mut receiver) = unbounded_channel();
+    let address = collector(sender).await;
+
+    let tracer = otlp_tracer(&format!("http://{address}")).unwrap();
+    let subscriber =
+        tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(tracer));
+    tracing::subscriber::with_default(subscriber, || {
+        tracing::info_span!("http_request", request_id = "test-request").in_scope(|| {
+            tracing::info!("inside request");
+        });
+    });
+    tokio::task::spawn_blocking(shutdown_logger).await.unwrap();
+
+    let content_type = tokio::time::timeout(Duration::from_secs(5), receiver.recv())
+        .await
+        .expect("Collector should receive spans")
+        .unwrap();
+    assert_eq!(content_type, "application/x-protobuf");
+}
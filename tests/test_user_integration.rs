@@ -4,7 +4,7 @@ pub mod mock;
 use fake::{faker::internet::en::Username, Fake};
 use hyper::StatusCode;
 use nighty_night::{
-    configuration::settings::Setting,
+    configuration::config::config,
     data::{
         common_structure::{SessionDto, UserDto},
         user_dto::NewUserDto,
@@ -38,8 +38,8 @@ fn init() {
 
 #[test]
 fn test_branch() {
-    assert_eq!(Setting::Branch.get(), "local");
-    assert_eq!(Setting::RedisHost.get(), "redis://127.0.0.1:6379/");
+    assert_eq!(config().branch, "local");
+    assert_eq!(config().redis.url(), "redis://127.0.0.1:6379/");
 }
 
 #[tokio::test]
@@ -1,9 +1,14 @@
-use crate::common::{assertions::assert_ok_response, cte::DB_ERROR};
+use crate::common::{
+    assertions::{assert_error_status, assert_ok_message, assert_ok_response},
+    cte::DB_ERROR,
+};
 use fake::{Fake, Faker};
 use hyper::StatusCode;
 use mock::generate_date;
 use nighty_night::service::user_service::delete_user_from_database;
-use nighty_night::service::weight_service::patch_weight_service;
+use nighty_night::service::weight_service::{delete_weight_service, patch_weight_service};
+use nighty_night::utils::etag::{format_etag, IfMatch};
+use uuid::Uuid;
 use nighty_night::{
     data::weight_dto::InputWeightDto,
     service::{baby_service::delete_baby_service, weight_service::post_weight_service},
@@ -34,7 +39,7 @@ async fn test_weight_flow() {
     let response_data = response_post_weight.expect(DB_ERROR).data;
     assert_count_decimals_is(response_data.attributes.value);
 
-    delete_baby_service(baby_id, user_id).await.expect(DB_ERROR);
+    delete_baby_service(baby_id, user_id, IfMatch::default()).await.expect(DB_ERROR);
     delete_user_from_database(user_id).expect(DB_ERROR);
 }
 
@@ -47,7 +52,7 @@ async fn patch_weight_value() {
         .await
         .expect(DB_ERROR)
         .data;
-    let weight_id: i32 = added_weight.id;
+    let weight_id: Uuid = added_weight.id;
     let one_decimal = InputWeightDto {
         date: None,
         value: Some(1.2),
@@ -55,7 +60,7 @@ async fn patch_weight_value() {
 
     let new_weight_value: &f32 = &one_decimal.value.unwrap();
 
-    let response_patch_weight = patch_weight_service(one_decimal, weight_id, baby_id).await;
+    let response_patch_weight = patch_weight_service(one_decimal, weight_id, baby_id, IfMatch::default()).await;
 
     assert_ok_response(
         &response_patch_weight,
@@ -85,14 +90,50 @@ async fn patch_weight_value() {
         value: Some(1.23),
     };
 
-    let data_two_decimals = patch_weight_service(two_decimals, weight_id, baby_id)
+    let data_two_decimals = patch_weight_service(two_decimals, weight_id, baby_id, IfMatch::default())
         .await
         .expect(DB_ERROR)
         .data;
 
     assert_count_decimals_is(data_two_decimals.attributes.value);
     
-    delete_baby_service(baby_id, user_id).await.expect(DB_ERROR);
+    delete_baby_service(baby_id, user_id, IfMatch::default()).await.expect(DB_ERROR);
+    delete_user_from_database(user_id).expect(DB_ERROR);
+}
+
+#[tokio::test]
+async fn test_weight_preconditions() {
+    let (user_id, _user_credentials) = create_new_user().await;
+    let baby_id = create_new_baby(user_id).await;
+    let weight_id = post_weight_service(generate_weight(), baby_id)
+        .await
+        .expect(DB_ERROR)
+        .data
+        .id;
+    let version = |version: i32| IfMatch::required(Some(format_etag(version)));
+    let new_value = || InputWeightDto {
+        date: None,
+        value: Some(4.2),
+    };
+
+    let response = patch_weight_service(new_value(), weight_id, baby_id, version(1)).await;
+    assert_ok_response(&response, "Should update the version read", StatusCode::OK);
+
+    let response = patch_weight_service(new_value(), weight_id, baby_id, version(1)).await;
+    assert_error_status(&response, "Stale patch should fail", StatusCode::PRECONDITION_FAILED);
+    let response = delete_weight_service(weight_id, baby_id, version(1)).await;
+    assert_error_status(&response, "Stale delete should fail", StatusCode::PRECONDITION_FAILED);
+
+    let response =
+        patch_weight_service(new_value(), weight_id, baby_id, IfMatch::required(None)).await;
+    assert_error_status(&response, "Patch needs If-Match", StatusCode::PRECONDITION_REQUIRED);
+    let response = delete_weight_service(weight_id, baby_id, IfMatch::required(None)).await;
+    assert_error_status(&response, "Delete needs If-Match", StatusCode::PRECONDITION_REQUIRED);
+
+    let response = delete_weight_service(weight_id, baby_id, version(2)).await;
+    assert_ok_message(&response, "Should delete the current version", StatusCode::OK);
+
+    delete_baby_service(baby_id, user_id, IfMatch::default()).await.expect(DB_ERROR);
     delete_user_from_database(user_id).expect(DB_ERROR);
 }
 